@@ -0,0 +1,516 @@
+use crate::camera::Camera;
+use crate::canvas::Canvas;
+use crate::color::Color;
+use crate::math::Quaternion;
+use crate::matrix::{Decomposition, Matrix4, MatrixError};
+use crate::tuple::Tuple;
+use crate::world::World;
+use std::fmt::Debug;
+
+pub mod tracks;
+
+pub use tracks::{CameraFieldOfView, CameraView, MaterialProperty, ObjectTransform, Turntable};
+
+/// Value that can be blended between two keyframes.
+pub trait Interpolate {
+    /// Blend from `self` at `t` = 0 to `other` at `t` = 1. Values of `t` outside of [0, 1] extrapolate.
+    fn interpolate(&self, other: &Self, t: f64) -> Self;
+}
+
+impl Interpolate for f64 {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        *self + (*other - *self) * t
+    }
+}
+
+impl Interpolate for Tuple {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for Matrix4 {
+    /// Matrices are blended element by element, which works well for translations and gentle scalings
+    /// but shrinks objects partway through large rotations; key the `Decomposition`s of the matrices
+    /// instead for those, such as with `ObjectTransform::decomposed`.
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        let mut blended = *self;
+        for row in 0..4 {
+            for column in 0..4 {
+                blended[(row, column)] = self[(row, column)].interpolate(&other[(row, column)], t);
+            }
+        }
+        blended
+    }
+}
+
+impl Interpolate for Quaternion {
+    /// Rotations are blended with `Quaternion::slerp`, turning at a constant rate the short way around.
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        self.slerp(other, t)
+    }
+}
+
+impl Interpolate for Decomposition {
+    /// Translations and scales are blended linearly and rotations with `Quaternion::slerp`, so unlike
+    /// blending matrices directly, objects keep their size while they turn.
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        Decomposition {
+            translation: self.translation.interpolate(&other.translation, t),
+            rotation: self.rotation.interpolate(&other.rotation, t),
+            scale: self.scale.interpolate(&other.scale, t),
+        }
+    }
+}
+
+/// How values change between two keyframes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Hold each keyframe's value until the next keyframe.
+    Step,
+    /// Change at a constant rate from one keyframe to the next.
+    #[default]
+    Linear,
+    /// Ease in and out of each keyframe, so motion starts and stops gently.
+    Smooth,
+    /// Follow a Catmull-Rom spline through the keyframes, so paths curve smoothly through every keyframe
+    /// instead of turning sharply at each one. Suited to camera fly-throughs.
+    CatmullRom,
+}
+
+/// Values keyed at moments in time, blended in between.
+#[derive(Debug, Clone, Default)]
+pub struct Keyframes<T> {
+    keys: Vec<(f64, T)>,
+    interpolation: Interpolation,
+}
+
+impl<T: Interpolate + Clone> Keyframes<T> {
+    /// Create an empty set of keyframes blended with `interpolation`.
+    pub fn new(interpolation: Interpolation) -> Self {
+        Self {
+            keys: Vec::new(),
+            interpolation,
+        }
+    }
+
+    /// Add a keyframe, replacing any keyframe at the same time, and return the keyframes.
+    pub fn key(mut self, time: f64, value: T) -> Self {
+        self.insert(time, value);
+        self
+    }
+
+    /// Add a keyframe, replacing any keyframe at the same time.
+    pub fn insert(&mut self, time: f64, value: T) {
+        match self
+            .keys
+            .binary_search_by(|(key_time, _)| key_time.total_cmp(&time))
+        {
+            Ok(index) => self.keys[index].1 = value,
+            Err(index) => self.keys.insert(index, (time, value)),
+        }
+    }
+
+    /// Get the interpolation used between keyframes.
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Get the number of keyframes.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Check whether there are no keyframes.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Compute the value at `time`. Before the first keyframe and after the last one, the value holds still.
+    /// Panics if there are no keyframes.
+    pub fn value_at(&self, time: f64) -> T {
+        assert!(!self.keys.is_empty(), "no keyframes to take a value from");
+        // Index of the first keyframe after `time`, so `time` lies between keyframes `next - 1` and `next`
+        let next = self.keys.partition_point(|(key_time, _)| *key_time <= time);
+        if next == 0 {
+            let (_, first) = &self.keys[0];
+            return first.clone();
+        }
+        let (start_time, start) = &self.keys[next - 1];
+        let Some((end_time, end)) = self.keys.get(next) else {
+            return start.clone();
+        };
+        let t = (time - start_time) / (end_time - start_time);
+        match self.interpolation {
+            Interpolation::Step => start.clone(),
+            Interpolation::Linear => start.interpolate(end, t),
+            Interpolation::Smooth => start.interpolate(end, t * t * (3.0 - 2.0 * t)),
+            Interpolation::CatmullRom => self.catmull_rom(next, time),
+        }
+    }
+
+    /// Evaluate the Catmull-Rom spline between keyframes `next - 1` and `next` at `time`.
+    ///
+    /// This uses the Barry-Goldman formulation, which only needs `interpolate` and takes the keyframe
+    /// times as the spline's knots, so unevenly spaced keyframes do not make the path overshoot.
+    /// The keyframes next to the first and last ones are mirrored to give the spline its missing neighbours.
+    fn catmull_rom(&self, next: usize, time: f64) -> T {
+        let (t1, p1) = &self.keys[next - 1];
+        let (t2, p2) = &self.keys[next];
+        // Mirror the neighbouring keyframe when there is no keyframe before the start or after the end
+        let (t0, p0) = match next.checked_sub(2) {
+            Some(previous) => self.keys[previous].clone(),
+            None => (t1 - (t2 - t1), p2.interpolate(p1, 2.0)),
+        };
+        let (t3, p3) = match self.keys.get(next + 1) {
+            Some(key) => key.clone(),
+            None => (t2 + (t2 - t1), p1.interpolate(p2, 2.0)),
+        };
+        let blend =
+            |a: &T, b: &T, start: f64, end: f64| a.interpolate(b, (time - start) / (end - start));
+
+        let a1 = blend(&p0, p1, t0, *t1);
+        let a2 = blend(p1, p2, *t1, *t2);
+        let a3 = blend(p2, &p3, *t2, t3);
+        let b1 = blend(&a1, &a2, t0, *t2);
+        let b2 = blend(&a2, &a3, *t1, t3);
+        blend(&b1, &b2, *t1, *t2)
+    }
+}
+
+/// Part of an animation that changes the world or the camera over time.
+pub trait Track: Debug {
+    /// Update the world or camera to how they look at `time`.
+    /// Returns an error if the track would give an object or the camera a transform that is not invertible.
+    fn apply(&self, world: &mut World, camera: &mut Camera, time: f64) -> Result<(), MatrixError>;
+}
+
+/// Collection of tracks animating a world and camera over a span of time.
+#[derive(Debug, Default)]
+pub struct Animation {
+    duration: f64,
+    tracks: Vec<Box<dyn Track>>,
+}
+
+impl Animation {
+    /// Create an animation without any tracks, lasting from time 0 to `duration`.
+    pub fn new(duration: f64) -> Self {
+        Self {
+            duration,
+            tracks: Vec::new(),
+        }
+    }
+
+    /// Get the time at which the animation ends.
+    pub fn duration(&self) -> f64 {
+        self.duration
+    }
+
+    /// Add a track to the animation.
+    pub fn add_track(&mut self, track: impl Track + 'static) {
+        self.tracks.push(Box::new(track));
+    }
+
+    /// Add a track to the animation and return the animation.
+    pub fn with_track(mut self, track: impl Track + 'static) -> Self {
+        self.add_track(track);
+        self
+    }
+
+    /// Update the world and camera to how they look at `time`, applying the tracks in the order they were added.
+    pub fn apply(
+        &self,
+        world: &mut World,
+        camera: &mut Camera,
+        time: f64,
+    ) -> Result<(), MatrixError> {
+        for track in &self.tracks {
+            track.apply(world, camera, time)?;
+        }
+        Ok(())
+    }
+
+    /// Get the time shown by frame `number` of an animation rendered in `frames` frames.
+    /// The first frame shows the start of the animation and the last one its end.
+    pub fn frame_time(&self, number: usize, frames: usize) -> f64 {
+        if frames <= 1 {
+            0.0
+        } else {
+            self.duration * number as f64 / (frames - 1) as f64
+        }
+    }
+}
+
+/// Image of a world at one moment of an animation.
+#[derive(Debug)]
+pub struct Frame {
+    /// Position of the frame in the sequence, starting at 0.
+    pub number: usize,
+    pub time: f64,
+    pub image: Canvas,
+}
+
+impl Frame {
+    /// Get a file name for the frame that sorts in sequence order, such as `shot-0007.ppm`.
+    pub fn file_name(&self, prefix: &str, extension: &str) -> String {
+        format!("{prefix}{:04}.{extension}", self.number)
+    }
+}
+
+/// Render `frames` evenly spaced frames of an animation, calling `on_frame` with each frame in order.
+///
+/// The world and camera are left as they look at the end of the animation.
+/// Returns an error if a track gives an object or the camera a transform that is not invertible.
+pub fn render_animation<F>(
+    world: &mut World,
+    camera: &mut Camera,
+    animation: &Animation,
+    frames: usize,
+    mut on_frame: F,
+) -> Result<(), MatrixError>
+where
+    F: FnMut(Frame),
+{
+    for number in 0..frames {
+        let time = animation.frame_time(number, frames);
+        animation.apply(world, camera, time)?;
+        on_frame(Frame {
+            number,
+            time,
+            image: camera.render(world),
+        });
+    }
+    Ok(())
+}
+
+/// Render `frames` frames of the camera spinning once around `pivot`, `radius` units away and raised
+/// `elevation` radians above the pivot's horizontal plane, calling `on_frame` with each frame in order.
+///
+/// The frames are spread evenly over a full turn without repeating the first one at the end, so the
+/// sequence loops seamlessly. The camera is left at the last frame's position.
+pub fn render_turntable<F>(
+    world: &mut World,
+    camera: &mut Camera,
+    pivot: Tuple,
+    radius: f64,
+    elevation: f64,
+    frames: usize,
+    on_frame: F,
+) -> Result<(), MatrixError>
+where
+    F: FnMut(Frame),
+{
+    let last_turn = frames.saturating_sub(1) as f64 / frames.max(1) as f64;
+    let animation = Animation::new(last_turn).with_track(Turntable::new(pivot, radius, elevation));
+    render_animation(world, camera, &animation, frames, on_frame)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::{rotation_y, scaling, translation, view_transform};
+    use crate::world::default_world;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn keyframes_value_at_linear() {
+        let keyframes = Keyframes::new(Interpolation::Linear)
+            .key(2.0, 10.0)
+            .key(0.0, 0.0)
+            .key(3.0, 40.0);
+        assert_eq!(keyframes.len(), 3);
+        let cases = [
+            (-1.0, 0.0),
+            (0.0, 0.0),
+            (1.0, 5.0),
+            (2.0, 10.0),
+            (2.5, 25.0),
+            (3.0, 40.0),
+            (9.0, 40.0),
+        ];
+        for (time, expected) in cases {
+            assert_approx_eq!(keyframes.value_at(time), expected, "{time}");
+        }
+    }
+
+    #[test]
+    fn keyframes_value_at_step_and_smooth() {
+        let step = Keyframes::new(Interpolation::Step)
+            .key(0.0, 0.0)
+            .key(1.0, 1.0);
+        assert_approx_eq!(step.value_at(0.9), 0.0);
+        assert_approx_eq!(step.value_at(1.0), 1.0);
+
+        let smooth = Keyframes::new(Interpolation::Smooth)
+            .key(0.0, 0.0)
+            .key(1.0, 1.0);
+        assert_approx_eq!(smooth.value_at(0.25), 0.15625);
+        assert_approx_eq!(smooth.value_at(0.5), 0.5);
+    }
+
+    #[test]
+    fn keyframes_value_at_quaternion() {
+        let axis = Tuple::new_vector(0.0, 1.0, 0.0);
+        let keyframes = Keyframes::new(Interpolation::Linear)
+            .key(0.0, Quaternion::IDENTITY)
+            .key(1.0, Quaternion::from_axis_angle(&axis, PI));
+        assert_approx_eq!(
+            keyframes.value_at(0.25),
+            Quaternion::from_axis_angle(&axis, FRAC_PI_4)
+        );
+    }
+
+    #[test]
+    fn keyframes_value_at_decomposition() {
+        let start = translation(0.0, 1.0, 0.0).decompose().unwrap();
+        let end = (translation(4.0, 1.0, 0.0) * rotation_y(PI) * scaling(3.0, 3.0, 3.0))
+            .decompose()
+            .unwrap();
+        let keyframes = Keyframes::new(Interpolation::Linear)
+            .key(0.0, start)
+            .key(1.0, end);
+        let expected = translation(2.0, 1.0, 0.0) * rotation_y(FRAC_PI_2) * scaling(2.0, 2.0, 2.0);
+        assert_approx_eq!(keyframes.value_at(0.5).to_matrix(), expected);
+    }
+
+    #[test]
+    fn keyframes_value_at_catmull_rom() {
+        let keyframes = Keyframes::new(Interpolation::CatmullRom)
+            .key(0.0, 0.0)
+            .key(1.0, 1.0)
+            .key(2.0, 0.0)
+            .key(3.0, 1.0);
+        // The spline passes through every keyframe
+        for (time, expected) in [(0.0, 0.0), (1.0, 1.0), (2.0, 0.0), (3.0, 1.0)] {
+            assert_approx_eq!(keyframes.value_at(time), expected);
+        }
+        // Evenly spaced keyframes give the uniform Catmull-Rom spline
+        let cases = [(1.25, 0.84375), (1.5, 0.5), (1.75, 0.15625), (0.9, 0.981)];
+        for (time, expected) in cases {
+            assert_approx_eq!(keyframes.value_at(time), expected, "{time}");
+        }
+        // Evenly spaced keyframes on a line stay on the line
+        let line = Keyframes::new(Interpolation::CatmullRom)
+            .key(0.0, Tuple::new_point(0.0, 0.0, 0.0))
+            .key(1.0, Tuple::new_point(1.0, 2.0, 0.0))
+            .key(2.0, Tuple::new_point(2.0, 4.0, 0.0));
+        assert!(line
+            .value_at(0.5)
+            .is_equal_to(&Tuple::new_point(0.5, 1.0, 0.0)));
+        assert!(line
+            .value_at(1.75)
+            .is_equal_to(&Tuple::new_point(1.75, 3.5, 0.0)));
+    }
+
+    #[test]
+    fn keyframes_insert_replaces() {
+        let mut keyframes = Keyframes::new(Interpolation::Linear).key(1.0, 1.0);
+        keyframes.insert(1.0, 2.0);
+        assert_eq!(keyframes.len(), 1);
+        assert_approx_eq!(keyframes.value_at(0.0), 2.0);
+    }
+
+    #[test]
+    fn interpolate_values() {
+        let color = Color::new(0.0, 0.5, 1.0).interpolate(&Color::new(1.0, 0.5, 0.0), 0.25);
+        assert!(color.is_equal_to(&Color::new(0.25, 0.5, 0.75)));
+
+        let point =
+            Tuple::new_point(0.0, 0.0, 0.0).interpolate(&Tuple::new_point(4.0, -4.0, 2.0), 0.5);
+        assert!(point.is_equal_to(&Tuple::new_point(2.0, -2.0, 1.0)));
+
+        let matrix = translation(0.0, 0.0, 0.0).interpolate(&translation(2.0, 4.0, 6.0), 0.5);
+        assert!(matrix.is_equal_to(&translation(1.0, 2.0, 3.0)));
+    }
+
+    #[test]
+    fn animation_frame_time() {
+        let animation = Animation::new(2.0);
+        let times: Vec<f64> = (0..5)
+            .map(|number| animation.frame_time(number, 5))
+            .collect();
+        assert_eq!(times, [0.0, 0.5, 1.0, 1.5, 2.0]);
+        assert_approx_eq!(animation.frame_time(0, 1), 0.0);
+    }
+
+    #[test]
+    fn frame_file_name() {
+        let frame = Frame {
+            number: 7,
+            time: 0.0,
+            image: Canvas::new(1, 1),
+        };
+        assert_eq!(frame.file_name("shot-", "ppm"), "shot-0007.ppm");
+    }
+
+    #[test]
+    fn render_animation_frames() {
+        let mut world = default_world();
+        world.objects.truncate(1);
+        let mut camera = Camera::new(5, 5, FRAC_PI_2);
+        camera
+            .set_transform(view_transform(
+                &Tuple::new_point(0.0, 0.0, -5.0),
+                &Tuple::new_point(0.0, 0.0, 0.0),
+                &Tuple::new_vector(0.0, 1.0, 0.0),
+            ))
+            .unwrap();
+        let animation = Animation::new(1.0).with_track(ObjectTransform::new(
+            0,
+            Keyframes::new(Interpolation::Linear)
+                .key(0.0, translation(0.0, 0.0, 0.0))
+                .key(1.0, translation(10.0, 0.0, 0.0)),
+        ));
+
+        let mut frames = Vec::new();
+        render_animation(&mut world, &mut camera, &animation, 3, |frame| {
+            frames.push(frame)
+        })
+        .unwrap();
+        let numbers: Vec<usize> = frames.iter().map(|frame| frame.number).collect();
+        assert_eq!(numbers, [0, 1, 2]);
+        let times: Vec<f64> = frames.iter().map(|frame| frame.time).collect();
+        assert_eq!(times, [0.0, 0.5, 1.0]);
+        assert!(world.objects[0]
+            .transform()
+            .is_equal_to(&translation(10.0, 0.0, 0.0)));
+
+        // The sphere slides out of the middle of the image
+        let black = Color::new(0.0, 0.0, 0.0);
+        assert!(!frames[0].image.pixel_at(2, 2).is_equal_to(&black));
+        assert!(frames[1].image.pixel_at(2, 2).is_equal_to(&black));
+        assert!(frames[2].image.pixel_at(2, 2).is_equal_to(&black));
+    }
+
+    #[test]
+    fn render_turntable_frames() {
+        let mut world = default_world();
+        let mut camera = Camera::new(5, 5, FRAC_PI_2);
+        let mut times = Vec::new();
+        render_turntable(
+            &mut world,
+            &mut camera,
+            Tuple::new_point(0.0, 0.0, 0.0),
+            5.0,
+            0.0,
+            4,
+            |frame| times.push(frame.time),
+        )
+        .unwrap();
+        // A quarter turn apart, stopping short of the starting position
+        assert_eq!(times, [0.0, 0.25, 0.5, 0.75]);
+        let expected = view_transform(
+            &Tuple::new_point(-5.0, 0.0, 0.0),
+            &Tuple::new_point(0.0, 0.0, 0.0),
+            &Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        assert!(camera.transform().is_equal_to(&expected));
+    }
+}
@@ -0,0 +1,319 @@
+use super::{Interpolate, Keyframes, Track};
+use crate::camera::Camera;
+use crate::light::Material;
+use crate::matrix::{Decomposition, Matrix4, MatrixError};
+use crate::transform::view_transform;
+use crate::tuple::Tuple;
+use crate::world::World;
+use std::f64::consts::TAU;
+use std::fmt::Debug;
+
+/// Track moving one of the world's objects by keying its transform.
+#[derive(Debug)]
+pub struct ObjectTransform {
+    object: usize,
+    keyframes: TransformKeyframes,
+}
+
+/// Transforms keyed by an `ObjectTransform`, either as matrices or as their decompositions.
+#[derive(Debug)]
+enum TransformKeyframes {
+    Matrices(Keyframes<Matrix4>),
+    Decompositions(Keyframes<Decomposition>),
+}
+
+impl ObjectTransform {
+    /// Animate the transform of the object at index `object` in the world's objects.
+    /// The matrices are blended element by element, so prefer `decomposed` for objects that turn far.
+    pub fn new(object: usize, keyframes: Keyframes<Matrix4>) -> Self {
+        Self {
+            object,
+            keyframes: TransformKeyframes::Matrices(keyframes),
+        }
+    }
+
+    /// Animate the transform of the object at index `object` by keying its translation, rotation and
+    /// scale, which keeps the object's size while it turns.
+    pub fn decomposed(object: usize, keyframes: Keyframes<Decomposition>) -> Self {
+        Self {
+            object,
+            keyframes: TransformKeyframes::Decompositions(keyframes),
+        }
+    }
+}
+
+impl Track for ObjectTransform {
+    /// Panics if the world has no object at the track's index.
+    fn apply(&self, world: &mut World, _camera: &mut Camera, time: f64) -> Result<(), MatrixError> {
+        let transform = match &self.keyframes {
+            TransformKeyframes::Matrices(keyframes) => keyframes.value_at(time),
+            TransformKeyframes::Decompositions(keyframes) => keyframes.value_at(time).to_matrix(),
+        };
+        world.objects[self.object].set_transform(transform)
+    }
+}
+
+/// Track changing one property of an object's material, such as its color or how reflective it is.
+#[derive(Debug)]
+pub struct MaterialProperty<T> {
+    object: usize,
+    property: fn(&mut Material) -> &mut T,
+    keyframes: Keyframes<T>,
+}
+
+impl<T: Interpolate> MaterialProperty<T> {
+    /// Animate the property of the material of the object at index `object` in the world's objects.
+    /// `property` selects the property, for example `|material| &mut material.reflective`.
+    pub fn new(
+        object: usize,
+        property: fn(&mut Material) -> &mut T,
+        keyframes: Keyframes<T>,
+    ) -> Self {
+        Self {
+            object,
+            property,
+            keyframes,
+        }
+    }
+}
+
+impl<T: Interpolate + Clone + Debug> Track for MaterialProperty<T> {
+    /// Panics if the world has no object at the track's index.
+    fn apply(&self, world: &mut World, _camera: &mut Camera, time: f64) -> Result<(), MatrixError> {
+        let object = &mut world.objects[self.object];
+        let mut material = object.material().clone();
+        *(self.property)(&mut material) = self.keyframes.value_at(time);
+        object.set_material(material);
+        Ok(())
+    }
+}
+
+/// Track moving the camera by keying where it is and the point it looks at.
+/// Keyframes using `Interpolation::CatmullRom` make smooth fly-throughs rather than point-to-point jumps.
+#[derive(Debug)]
+pub struct CameraView {
+    from: Keyframes<Tuple>,
+    to: Keyframes<Tuple>,
+    up: Tuple,
+}
+
+impl CameraView {
+    /// Animate the camera's position and the point it looks at, keeping `up` as its upward direction.
+    pub fn new(from: Keyframes<Tuple>, to: Keyframes<Tuple>, up: Tuple) -> Self {
+        Self { from, to, up }
+    }
+}
+
+impl Track for CameraView {
+    fn apply(&self, _world: &mut World, camera: &mut Camera, time: f64) -> Result<(), MatrixError> {
+        camera.set_transform(view_transform(
+            &self.from.value_at(time),
+            &self.to.value_at(time),
+            &self.up,
+        ))
+    }
+}
+
+/// Track zooming the camera by keying its field of view.
+#[derive(Debug)]
+pub struct CameraFieldOfView {
+    keyframes: Keyframes<f64>,
+}
+
+impl CameraFieldOfView {
+    /// Animate the camera's horizontal field of view, in radians.
+    pub fn new(keyframes: Keyframes<f64>) -> Self {
+        Self { keyframes }
+    }
+}
+
+impl Track for CameraFieldOfView {
+    fn apply(&self, _world: &mut World, camera: &mut Camera, time: f64) -> Result<(), MatrixError> {
+        camera.set_field_of_view(self.keyframes.value_at(time));
+        Ok(())
+    }
+}
+
+/// Track orbiting the camera around a pivot at a fixed distance and height, always looking at the pivot.
+///
+/// Time counts turns: at time 0 the camera is on the negative z side of the pivot, and each unit of time
+/// takes it once around the y axis, counterclockwise seen from above.
+#[derive(Debug)]
+pub struct Turntable {
+    pivot: Tuple,
+    radius: f64,
+    elevation: f64,
+}
+
+impl Turntable {
+    /// Orbit `radius` units away from `pivot`, raised `elevation` radians above its horizontal plane.
+    pub fn new(pivot: Tuple, radius: f64, elevation: f64) -> Self {
+        Self {
+            pivot,
+            radius,
+            elevation,
+        }
+    }
+
+    /// Get the position of the camera at `time`.
+    pub fn position_at(&self, time: f64) -> Tuple {
+        let angle = time * TAU;
+        let horizontal = self.radius * self.elevation.cos();
+        let offset = Tuple::new_vector(
+            horizontal * angle.sin(),
+            self.radius * self.elevation.sin(),
+            -horizontal * angle.cos(),
+        );
+        self.pivot + offset
+    }
+}
+
+impl Track for Turntable {
+    fn apply(&self, _world: &mut World, camera: &mut Camera, time: f64) -> Result<(), MatrixError> {
+        camera.set_transform(view_transform(
+            &self.position_at(time),
+            &self.pivot,
+            &Tuple::new_vector(0.0, 1.0, 0.0),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::Interpolation;
+    use crate::assert_approx_eq;
+    use crate::color::Color;
+    use crate::transform::{rotation_y, scaling};
+    use crate::world::default_world;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn material_property_apply() {
+        let mut world = default_world();
+        let mut camera = Camera::new(10, 10, FRAC_PI_2);
+        let reflective = MaterialProperty::new(
+            1,
+            |material| &mut material.reflective,
+            Keyframes::new(Interpolation::Linear)
+                .key(0.0, 0.0)
+                .key(1.0, 0.5),
+        );
+        let color = MaterialProperty::new(
+            1,
+            |material| &mut material.color,
+            Keyframes::new(Interpolation::Linear)
+                .key(0.0, Color::new(0.0, 0.0, 0.0))
+                .key(1.0, Color::new(1.0, 0.0, 0.0)),
+        );
+        reflective.apply(&mut world, &mut camera, 0.5).unwrap();
+        color.apply(&mut world, &mut camera, 0.5).unwrap();
+        let material = world.objects[1].material();
+        assert_approx_eq!(material.reflective, 0.25);
+        assert!(material.color.is_equal_to(&Color::new(0.5, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn camera_view_apply() {
+        let mut world = default_world();
+        let mut camera = Camera::new(10, 10, FRAC_PI_2);
+        let track = CameraView::new(
+            Keyframes::new(Interpolation::Linear)
+                .key(0.0, Tuple::new_point(0.0, 0.0, -4.0))
+                .key(1.0, Tuple::new_point(0.0, 0.0, -8.0)),
+            Keyframes::new(Interpolation::Linear).key(0.0, Tuple::new_point(0.0, 0.0, 0.0)),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        track.apply(&mut world, &mut camera, 0.5).unwrap();
+        let expected = view_transform(
+            &Tuple::new_point(0.0, 0.0, -6.0),
+            &Tuple::new_point(0.0, 0.0, 0.0),
+            &Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        assert!(camera.transform().is_equal_to(&expected));
+    }
+
+    #[test]
+    fn camera_field_of_view_apply() {
+        let mut world = default_world();
+        let mut camera = Camera::new(10, 10, FRAC_PI_2);
+        let track = CameraFieldOfView::new(
+            Keyframes::new(Interpolation::Linear)
+                .key(0.0, FRAC_PI_2)
+                .key(1.0, 0.0),
+        );
+        track.apply(&mut world, &mut camera, 0.5).unwrap();
+        assert_approx_eq!(camera.field_of_view(), FRAC_PI_4);
+    }
+
+    #[test]
+    fn object_transform_decomposed_apply() {
+        let mut world = default_world();
+        let mut camera = Camera::new(10, 10, FRAC_PI_2);
+        let key = |angle| {
+            (rotation_y(angle) * scaling(2.0, 2.0, 2.0))
+                .decompose()
+                .unwrap()
+        };
+        let track = ObjectTransform::decomposed(
+            0,
+            Keyframes::new(Interpolation::Linear)
+                .key(0.0, key(0.0))
+                .key(1.0, key(PI)),
+        );
+        track.apply(&mut world, &mut camera, 0.5).unwrap();
+        // Halfway through the half turn, the object is turned a quarter and still twice its size
+        let transform = *world.objects[0].transform();
+        let turned = transform * Tuple::new_vector(1.0, 0.0, 0.0);
+        assert_approx_eq!(turned.magnitude(), 2.0);
+        assert_approx_eq!(turned.x, 0.0);
+        assert_approx_eq!(
+            (transform * Tuple::new_vector(0.0, 1.0, 0.0)).magnitude(),
+            2.0
+        );
+    }
+
+    #[test]
+    fn object_transform_not_invertible() {
+        let mut world = default_world();
+        let mut camera = Camera::new(10, 10, FRAC_PI_2);
+        let track = ObjectTransform::new(
+            0,
+            Keyframes::new(Interpolation::Linear).key(0.0, scaling(0.0, 1.0, 1.0)),
+        );
+        assert!(track.apply(&mut world, &mut camera, 0.0).is_err());
+    }
+
+    #[test]
+    fn turntable_position_at() {
+        let turntable = Turntable::new(Tuple::new_point(1.0, 2.0, 3.0), 2.0, 0.0);
+        let cases = [
+            (0.0, Tuple::new_point(1.0, 2.0, 1.0)),
+            (0.25, Tuple::new_point(3.0, 2.0, 3.0)),
+            (0.5, Tuple::new_point(1.0, 2.0, 5.0)),
+            (1.0, Tuple::new_point(1.0, 2.0, 1.0)),
+        ];
+        for (time, expected) in cases {
+            assert!(turntable.position_at(time).is_equal_to(&expected), "{time}");
+        }
+
+        let raised = Turntable::new(Tuple::new_point(0.0, 0.0, 0.0), 2.0, FRAC_PI_2);
+        assert!(raised
+            .position_at(0.3)
+            .is_equal_to(&Tuple::new_point(0.0, 2.0, 0.0)));
+    }
+
+    #[test]
+    fn turntable_apply() {
+        let mut world = default_world();
+        let mut camera = Camera::new(10, 10, FRAC_PI_2);
+        let turntable = Turntable::new(Tuple::new_point(0.0, 0.0, 0.0), 5.0, 0.0);
+        turntable.apply(&mut world, &mut camera, 0.5).unwrap();
+        let expected = view_transform(
+            &Tuple::new_point(0.0, 0.0, 5.0),
+            &Tuple::new_point(0.0, 0.0, 0.0),
+            &Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        assert!(camera.transform().is_equal_to(&expected));
+    }
+}
@@ -0,0 +1,232 @@
+use super::Camera;
+use crate::color::Color;
+use crate::random::Rng;
+use crate::world::World;
+
+/// Strategy used to choose how many rays the camera casts through each pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AntiAliasing {
+    /// Cast a single ray through the center of each pixel.
+    #[default]
+    Off,
+    /// Cast `initial` x `initial` rays through each pixel, then keep quadrupling the number of rays while
+    /// the variance of the colors seen exceeds `threshold`, up to `maximum` x `maximum` rays. Flat areas
+    /// stay cheap while edges and fine detail get more rays. The camera's sampler spreads the rays over
+    /// the pixel.
+    Adaptive {
+        initial: usize,
+        maximum: usize,
+        threshold: f64,
+    },
+}
+
+impl AntiAliasing {
+    /// Create an adaptive strategy starting from 2x2 rays per pixel and refining edges up to 8x8 rays.
+    pub fn adaptive() -> Self {
+        Self::Adaptive {
+            initial: 2,
+            maximum: 8,
+            threshold: 1e-3,
+        }
+    }
+}
+
+impl Camera {
+    /// Compute the color of the pixel at column `px` and row `py`, casting as many rays as the camera's
+    /// anti-aliasing strategy and shutter require, scaled for the camera's exposure.
+    pub fn pixel_color(&self, world: &World, px: usize, py: usize) -> Color {
+        self.sampled_pixel_color(world, px, py) * self.exposure_scale()
+    }
+
+    /// Compute the color of a pixel like `pixel_color`, before any exposure.
+    fn sampled_pixel_color(&self, world: &World, px: usize, py: usize) -> Color {
+        match self.anti_aliasing {
+            AntiAliasing::Off if self.is_moving() => {
+                let mut rng = self.pixel_rng(px, py);
+                let samples: Vec<Color> = (0..self.shutter.samples.max(1))
+                    .map(|_| {
+                        let time = self.sample_time(&mut rng);
+                        let ray = self.ray_for_pixel_at(px, py, 0.5, 0.5, time);
+                        world.trace(&ray, self.seed)
+                    })
+                    .collect();
+                mean(&samples)
+            }
+            AntiAliasing::Off => world.trace(&self.ray_for_pixel(px, py), self.seed),
+            AntiAliasing::Adaptive {
+                initial,
+                maximum,
+                threshold,
+            } => {
+                self.adaptive_pixel_color(world, px, py, initial, maximum, threshold)
+                    .0
+            }
+        }
+    }
+
+    /// Create the random number generator for a pixel, drawing the stream of the camera's seed picked by
+    /// the pixel's position so renders are reproducible.
+    fn pixel_rng(&self, px: usize, py: usize) -> Rng {
+        Rng::with_stream(self.seed, (py * self.hsize + px) as u64)
+    }
+
+    /// Sample a pixel with successively finer grids of rays until the colors seen agree, returning the
+    /// average color and the number of rays cast.
+    fn adaptive_pixel_color(
+        &self,
+        world: &World,
+        px: usize,
+        py: usize,
+        initial: usize,
+        maximum: usize,
+        threshold: f64,
+    ) -> (Color, usize) {
+        let mut rng = self.pixel_rng(px, py);
+        let mut samples = Vec::new();
+        let mut grid = initial.max(1);
+        loop {
+            // The camera's sampler spreads grid x grid rays over the pixel
+            for (x_offset, y_offset) in self.sampler.sample_square(grid * grid, &mut rng) {
+                let time = self.sample_time(&mut rng);
+                let ray = self.ray_for_pixel_at(px, py, x_offset, y_offset, time);
+                samples.push(world.trace(&ray, self.seed));
+            }
+            if grid * 2 > maximum || variance(&samples) <= threshold {
+                break;
+            }
+            grid *= 2;
+        }
+        (mean(&samples), samples.len())
+    }
+}
+
+/// Compute the average of a set of colors.
+fn mean(colors: &[Color]) -> Color {
+    let sum = colors
+        .iter()
+        .fold(Color::default(), |sum, &color| sum + color);
+    sum * (1.0 / colors.len() as f64)
+}
+
+/// Compute the variance of a set of colors, taking the largest of the variances of the three channels.
+fn variance(colors: &[Color]) -> f64 {
+    let mean = mean(colors);
+    let mut channel_sums = [0.0; 3];
+    for color in colors {
+        let difference = *color - mean;
+        channel_sums[0] += difference.red * difference.red;
+        channel_sums[1] += difference.green * difference.green;
+        channel_sums[2] += difference.blue * difference.blue;
+    }
+    channel_sums
+        .iter()
+        .fold(0.0_f64, |largest, &sum| largest.max(sum))
+        / colors.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::light::{Material, PointLight};
+    use crate::random::{BlueNoiseSampler, HaltonSampler, JitteredSampler, Sampler};
+    use crate::shapes::{Shape, Sphere};
+    use crate::transform::{scaling, translation};
+    use crate::tuple::Tuple;
+    use crate::EPSILON;
+    use std::f64::consts::FRAC_PI_2;
+
+    /// Build a world with a flat white sphere in front of the camera, covering part of pixel (0, 0) of a 2x2 canvas.
+    fn edge_world() -> World {
+        let mut sphere = Sphere::new();
+        sphere
+            .set_transform(translation(0.0, 0.0, -5.0) * scaling(2.0, 2.0, 2.0))
+            .unwrap();
+        sphere.set_material(Material {
+            ambient: 1.0,
+            diffuse: 0.0,
+            specular: 0.0,
+            ..Material::default()
+        });
+        let mut world = World::new();
+        world.objects.push(Box::new(sphere));
+        world.light = Some(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        world
+    }
+
+    #[test]
+    fn anti_aliasing_default() {
+        let camera = Camera::new(2, 2, FRAC_PI_2);
+        assert_eq!(camera.anti_aliasing(), AntiAliasing::Off);
+    }
+
+    #[test]
+    fn camera_pixel_color_off() {
+        let world = edge_world();
+        let camera = Camera::new(2, 2, FRAC_PI_2);
+        // The center of pixel (0, 0) misses the sphere
+        assert!(camera
+            .pixel_color(&world, 0, 0)
+            .is_equal_to(&Color::default()));
+    }
+
+    #[test]
+    fn camera_adaptive_pixel_color_flat() {
+        let world = World::new();
+        let camera = Camera::new(2, 2, FRAC_PI_2);
+        let (color, samples) = camera.adaptive_pixel_color(&world, 1, 1, 2, 8, 1e-3);
+        assert!(color.is_equal_to(&Color::default()));
+        assert_eq!(samples, 4);
+    }
+
+    #[test]
+    fn camera_adaptive_pixel_color_edge() {
+        let world = edge_world();
+        let camera = Camera::new(2, 2, FRAC_PI_2);
+        let (color, samples) = camera.adaptive_pixel_color(&world, 0, 0, 2, 8, 1e-3);
+        // Every level of the grid is sampled: 2x2, 4x4, then 8x8 rays
+        assert_eq!(samples, 4 + 16 + 64);
+        assert!(color.red > EPSILON && color.red < 1.0 - EPSILON);
+        assert_approx_eq!(color.red, color.green);
+    }
+
+    #[test]
+    fn camera_pixel_color_adaptive() {
+        let world = edge_world();
+        let mut camera = Camera::new(2, 2, FRAC_PI_2);
+        camera.set_anti_aliasing(AntiAliasing::adaptive());
+        let color = camera.pixel_color(&world, 0, 0);
+        assert!(color.red > EPSILON && color.red < 1.0 - EPSILON);
+
+        // Rendering uses the same strategy
+        let image = camera.render(&world);
+        assert!(image.pixel_at(0, 0).is_equal_to(&color));
+    }
+
+    #[test]
+    fn camera_adaptive_pixel_color_samplers() {
+        let world = edge_world();
+        let mut camera = Camera::new(2, 2, FRAC_PI_2);
+        let (uniform, _) = camera.adaptive_pixel_color(&world, 0, 0, 8, 8, 1e-3);
+        let samplers: [Box<dyn Sampler>; 3] = [
+            Box::new(JitteredSampler),
+            Box::new(HaltonSampler),
+            Box::new(BlueNoiseSampler),
+        ];
+        let colors = samplers.map(|sampler| {
+            camera.sampler = sampler.into();
+            let (color, samples) = camera.adaptive_pixel_color(&world, 0, 0, 8, 8, 1e-3);
+            assert_eq!(samples, 64);
+            // Every sampler sees part of the edge, and is reproducible
+            assert!(color.red > EPSILON && color.red < 1.0 - EPSILON);
+            let (again, _) = camera.adaptive_pixel_color(&world, 0, 0, 8, 8, 1e-3);
+            assert!(again.is_equal_to(&color));
+            color
+        });
+        // The samplers cast rays through different points than the regular grid
+        assert!(colors.iter().any(|color| !color.is_equal_to(&uniform)));
+    }
+}
@@ -0,0 +1,146 @@
+use super::Camera;
+use crate::canvas::{Canvas, PostProcess};
+use crate::color::Color;
+use crate::world::World;
+
+/// Rendered image together with auxiliary images (arbitrary output variables) describing what each pixel
+/// sees, for compositing or for guiding a `Denoiser`.
+///
+/// The auxiliary images come from a single ray through the center of each pixel, and hold raw values
+/// rather than colors meant for display:
+/// - `depth` holds the distance from the camera to the surface in every channel, infinite where the ray
+///   hits nothing;
+/// - `normal` holds the surface normal in world space, facing the camera, with x, y and z in the red,
+///   green and blue channels;
+/// - `albedo` holds the color of the surface's material before any lighting.
+///
+/// Pixels seeing nothing have a black normal and albedo.
+#[derive(Debug)]
+pub struct Aovs {
+    pub beauty: Canvas,
+    pub depth: Canvas,
+    pub normal: Canvas,
+    pub albedo: Canvas,
+    object_ids: Vec<Option<usize>>,
+}
+
+impl Aovs {
+    /// Get the index in the world's `objects` of the object seen through the pixel at column `x` and row `y`,
+    /// or `None` if the pixel sees nothing. Shapes within a group share the group's index.
+    /// Panics if the pixel is outside of the images.
+    pub fn object_id(&self, x: usize, y: usize) -> Option<usize> {
+        assert!(
+            x < self.beauty.width() && y < self.beauty.height(),
+            "pixel ({x}, {y}) is outside of the images"
+        );
+        self.object_ids[y * self.beauty.width() + x]
+    }
+}
+
+impl Camera {
+    /// Render an image of the world like `render`, along with its auxiliary images.
+    /// The camera's post-processes only apply to the rendered image.
+    pub fn render_aovs(&self, world: &World) -> Aovs {
+        let mut aovs = Aovs {
+            beauty: Canvas::new(self.hsize, self.vsize),
+            depth: Canvas::new(self.hsize, self.vsize),
+            normal: Canvas::new(self.hsize, self.vsize),
+            albedo: Canvas::new(self.hsize, self.vsize),
+            object_ids: vec![None; self.hsize * self.vsize],
+        };
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                aovs.beauty.write_pixel(x, y, self.pixel_color(world, x, y));
+                let ray = self.ray_for_pixel(x, y);
+
+                // Intersect each object on its own to know which one is hit first
+                let nearest = world
+                    .objects
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(id, object)| {
+                        let intersections = object.intersect(&ray);
+                        let t = intersections.hit()?.t;
+                        Some((t, id, object.as_ref()))
+                    })
+                    .min_by(|(a, ..), (b, ..)| a.total_cmp(b));
+                let Some((_, id, object)) = nearest else {
+                    aovs.depth.write_pixel(
+                        x,
+                        y,
+                        Color::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+                    );
+                    continue;
+                };
+                let intersections = object.intersect(&ray);
+                let hit = intersections.hit().expect("the object was hit");
+                let comps =
+                    hit.prepare_computations_with_bias(&ray, &intersections, world.shadow_bias);
+                let distance = hit.t * ray.direction.magnitude();
+                let normal = &comps.normalv;
+                let material = comps.object.material();
+                aovs.depth
+                    .write_pixel(x, y, Color::new(distance, distance, distance));
+                aovs.normal
+                    .write_pixel(x, y, Color::new(normal.x, normal.y, normal.z));
+                aovs.albedo
+                    .write_pixel(x, y, material.color_at(comps.object, &comps.point));
+                aovs.object_ids[y * self.hsize + x] = Some(id);
+            }
+        }
+        self.post_processing.apply(&mut aovs.beauty);
+        aovs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::canvas::denoise::Denoiser;
+    use crate::transform::view_transform;
+    use crate::tuple::Tuple;
+    use crate::world::default_world;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn camera_render_aovs() {
+        let world = default_world();
+        let mut camera = Camera::new(11, 11, FRAC_PI_2);
+        camera
+            .set_transform(view_transform(
+                &Tuple::new_point(0.0, 0.0, -5.0),
+                &Tuple::new_point(0.0, 0.0, 0.0),
+                &Tuple::new_vector(0.0, 1.0, 0.0),
+            ))
+            .unwrap();
+        let aovs = camera.render_aovs(&world);
+        let image = camera.render(&world);
+        assert!(aovs
+            .beauty
+            .pixel_at(5, 5)
+            .is_equal_to(&image.pixel_at(5, 5)));
+
+        // The center pixel sees the front of the outer unit sphere, straight ahead
+        assert_approx_eq!(aovs.depth.pixel_at(5, 5).red, 4.0);
+        assert!(aovs
+            .normal
+            .pixel_at(5, 5)
+            .is_equal_to(&Color::new(0.0, 0.0, -1.0)));
+        assert!(aovs
+            .albedo
+            .pixel_at(5, 5)
+            .is_equal_to(&world.objects[0].material().color));
+        assert_eq!(aovs.object_id(5, 5), Some(0));
+
+        // The corner pixel sees nothing
+        assert!(aovs.depth.pixel_at(0, 0).red.is_infinite());
+        assert!(aovs.normal.pixel_at(0, 0).is_equal_to(&Color::default()));
+        assert!(aovs.albedo.pixel_at(0, 0).is_equal_to(&Color::default()));
+        assert_eq!(aovs.object_id(0, 0), None);
+
+        // The auxiliary images guide the denoiser
+        let denoised = Denoiser::default().denoise(&aovs.beauty, &aovs.normal, &aovs.depth);
+        assert!(denoised.pixel_at(0, 0).is_equal_to(&Color::default()));
+    }
+}
@@ -0,0 +1,141 @@
+use super::{Camera, Progress};
+use crate::canvas::{Canvas, PostProcess};
+use crate::world::World;
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Shared flag used to ask an in-flight render to stop.
+///
+/// Clones share the same flag, so one clone can be handed to the render while another, for example on
+/// a different thread, cancels it. The render checks the flag after each row of pixels.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a token that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every render holding a clone of this token to stop.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Check whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Error returned by a cancelled render, holding the rows of the image rendered before it stopped.
+#[derive(Debug)]
+pub struct Cancelled {
+    /// Image whose first `rows_completed` rows are rendered and whose other pixels are black.
+    pub partial: Canvas,
+    pub rows_completed: usize,
+}
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "render cancelled after {} of {} rows",
+            self.rows_completed,
+            self.partial.height()
+        )
+    }
+}
+
+impl Error for Cancelled {}
+
+impl Camera {
+    /// Render the world like `render_with_progress`, stopping early once `token` is cancelled.
+    /// Returns the partially rendered image in the error if the render was cancelled before it finished.
+    pub fn render_cancellable<F>(
+        &self,
+        world: &World,
+        token: &CancellationToken,
+        mut on_progress: F,
+    ) -> Result<Canvas, Cancelled>
+    where
+        F: FnMut(Progress),
+    {
+        let start = Instant::now();
+        let mut image = Canvas::new(self.hsize, self.vsize);
+        for y in 0..self.vsize {
+            if token.is_cancelled() {
+                return Err(Cancelled {
+                    partial: image,
+                    rows_completed: y,
+                });
+            }
+            for x in 0..self.hsize {
+                image.write_pixel(x, y, self.pixel_color(world, x, y));
+            }
+            on_progress(Progress {
+                rows_completed: y + 1,
+                total_rows: self.vsize,
+                elapsed: start.elapsed(),
+            });
+        }
+        self.post_processing.apply(&mut image);
+        Ok(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::world::default_world;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn cancellation_token_cancel() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn camera_render_cancellable_complete() {
+        let world = default_world();
+        let camera = Camera::new(4, 3, FRAC_PI_2);
+        let image = camera
+            .render_cancellable(&world, &CancellationToken::new(), |_| {})
+            .unwrap();
+        assert_eq!(image.height(), 3);
+    }
+
+    #[test]
+    fn camera_render_cancellable_cancelled() {
+        let world = default_world();
+        let camera = Camera::new(4, 5, FRAC_PI_2);
+        let token = CancellationToken::new();
+        let cancelled = camera
+            .render_cancellable(&world, &token, |progress| {
+                if progress.rows_completed == 2 {
+                    token.cancel();
+                }
+            })
+            .unwrap_err();
+        assert_eq!(cancelled.rows_completed, 2);
+        assert_eq!(cancelled.to_string(), "render cancelled after 2 of 5 rows");
+        for x in 0..camera.hsize() {
+            let expected = camera.pixel_color(&world, x, 1);
+            assert!(cancelled.partial.pixel_at(x, 1).is_equal_to(&expected));
+            assert!(cancelled
+                .partial
+                .pixel_at(x, 2)
+                .is_equal_to(&Color::new(0.0, 0.0, 0.0)));
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use super::Camera;
+use crate::color::Color;
+use crate::light::Material;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use crate::world::World;
+
+/// Why a traced ray was cast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RayKind {
+    /// Cast from the camera through the pixel.
+    Camera,
+    /// Reflected off a surface.
+    Reflection,
+    /// Refracted through a surface.
+    Refraction,
+}
+
+/// Record of a ray followed while computing a pixel's color, created by `Camera::debug_pixel`.
+#[derive(Debug)]
+pub struct RayTrace {
+    pub kind: RayKind,
+    pub ray: Ray,
+    /// Number of reflections and refractions that may still follow from this ray under the world's
+    /// `max_depth`. Materials that override the limit may follow more or fewer.
+    pub remaining: usize,
+    /// Distances along the ray of every intersection with the world, in increasing order.
+    pub intersections: Vec<f64>,
+    /// The visible intersection, or `None` if the ray sees the environment.
+    pub hit: Option<HitTrace>,
+    /// Color seen along the ray, including everything behind the hit.
+    pub color: Color,
+}
+
+/// Record of the visible intersection of a traced ray and how it was shaded.
+#[derive(Debug)]
+pub struct HitTrace {
+    pub t: f64,
+    pub point: Tuple,
+    /// Surface normal at the point, facing the ray's origin.
+    pub normal: Tuple,
+    /// Whether the ray hit the surface from inside the object.
+    pub inside: bool,
+    pub material: Material,
+    /// Test for whether the point is in shadow, or `None` if the world has no light.
+    pub shadow: Option<ShadowTest>,
+    /// Color of the lit surface itself, without reflections and refractions.
+    pub surface: Color,
+    /// The reflected ray, if the material is reflective and the recursion limit allows it. Rough materials
+    /// jitter their reflected rays around this one, which is not.
+    pub reflection: Option<Box<RayTrace>>,
+    /// The refracted ray, if the material is transparent and the recursion limit allows it.
+    pub refraction: Option<Box<RayTrace>>,
+    /// Whether the light undergoes total internal reflection rather than being refracted.
+    pub total_internal_reflection: bool,
+}
+
+/// Record of a test for whether a point is in shadow.
+#[derive(Debug)]
+pub struct ShadowTest {
+    /// Ray cast from just above the surface toward the light.
+    pub ray: Ray,
+    /// Distance from the point to the light.
+    pub light_distance: f64,
+    pub in_shadow: bool,
+}
+
+impl Camera {
+    /// Trace how the color seen through the center of the pixel at column `px` and row `py` comes about:
+    /// every ray cast, what it hits, whether the hit is in shadow, and the reflections and refractions
+    /// followed from it.
+    ///
+    /// Only the ray through the pixel's center is traced, so with anti-aliasing, motion blur or exposure
+    /// the pixel's final color can differ from the traced one. Volumes and fog are part of the traced
+    /// colors, but the rays continuing through them are not traced.
+    pub fn debug_pixel(&self, world: &World, px: usize, py: usize) -> RayTrace {
+        trace(
+            world,
+            self.ray_for_pixel(px, py),
+            RayKind::Camera,
+            0,
+            self.seed,
+        )
+    }
+}
+
+/// Trace a ray that has already gone through `bounces` reflections and refractions in a render with the
+/// given seed.
+fn trace(world: &World, ray: Ray, kind: RayKind, bounces: usize, seed: u64) -> RayTrace {
+    let intersections = world.intersect(&ray);
+    let hit = intersections.hit().map(|hit| {
+        let comps = hit.prepare_computations_with_bias(&ray, &intersections, world.shadow_bias);
+        let material = comps.object.material();
+        let shadow = world.light.as_ref().map(|light| ShadowTest {
+            ray: Ray::new(comps.over_point, light.direction_from(&comps.over_point)),
+            light_distance: light.distance_from(&comps.over_point),
+            in_shadow: world.is_shadowed(&comps.over_point),
+        });
+        let is_volume = material.medium.is_some();
+        let follows = bounces < material.max_depth.unwrap_or(world.max_depth) && !is_volume;
+        let reflection = (follows && material.reflective > 0.0).then(|| {
+            Box::new(trace(
+                world,
+                comps.reflect_ray(),
+                RayKind::Reflection,
+                bounces + 1,
+                seed,
+            ))
+        });
+        let (refraction, total_internal_reflection) = if follows && material.transparency > 0.0 {
+            match comps.refract_ray() {
+                Some(refract_ray) => (
+                    Some(Box::new(trace(
+                        world,
+                        refract_ray,
+                        RayKind::Refraction,
+                        bounces + 1,
+                        seed,
+                    ))),
+                    false,
+                ),
+                None => (None, true),
+            }
+        } else {
+            (None, false)
+        };
+        HitTrace {
+            t: comps.t,
+            surface: world.surface_color(&comps, seed),
+            point: comps.point,
+            normal: comps.normalv,
+            inside: comps.inside,
+            material: material.clone(),
+            shadow,
+            reflection,
+            refraction,
+            total_internal_reflection,
+        }
+    });
+    RayTrace {
+        kind,
+        color: world.color_after(&ray, bounces, seed),
+        intersections: intersections.iter().map(|i| i.t).collect(),
+        ray,
+        remaining: world.max_depth.saturating_sub(bounces),
+        hit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::shapes::{Plane, Shape};
+    use crate::transform::{rotation_x, translation, view_transform};
+    use crate::world::{default_world, MAX_RECURSION_DEPTH};
+    use std::f64::consts::FRAC_PI_2;
+
+    fn camera() -> Camera {
+        let mut camera = Camera::new(11, 11, FRAC_PI_2);
+        camera
+            .set_transform(view_transform(
+                &Tuple::new_point(0.0, 0.0, -5.0),
+                &Tuple::new_point(0.0, 0.0, 0.0),
+                &Tuple::new_vector(0.0, 1.0, 0.0),
+            ))
+            .unwrap();
+        camera
+    }
+
+    #[test]
+    fn camera_debug_pixel() {
+        let world = default_world();
+        let camera = camera();
+        let trace = camera.debug_pixel(&world, 5, 5);
+        assert_eq!(trace.kind, RayKind::Camera);
+        assert_eq!(trace.remaining, MAX_RECURSION_DEPTH);
+        assert_eq!(trace.intersections.len(), 4);
+        assert!(trace
+            .color
+            .is_equal_to(&camera.render(&world).pixel_at(5, 5)));
+
+        let hit = trace.hit.unwrap();
+        assert_approx_eq!(hit.t, 4.0);
+        assert!(hit.normal.is_equal_to(&Tuple::new_vector(0.0, 0.0, -1.0)));
+        assert!(!hit.inside);
+        assert!(hit.surface.is_equal_to(&trace.color));
+        let shadow = hit.shadow.unwrap();
+        assert!(!shadow.in_shadow);
+        assert!(hit.reflection.is_none() && hit.refraction.is_none());
+
+        let miss = camera.debug_pixel(&world, 0, 0);
+        assert!(miss.intersections.is_empty());
+        assert!(miss.hit.is_none());
+    }
+
+    #[test]
+    fn camera_debug_pixel_reflection() {
+        let mut world = default_world();
+        let mut mirror = Plane::new();
+        mirror
+            .set_transform(translation(0.0, 0.0, 2.0) * rotation_x(FRAC_PI_2))
+            .unwrap();
+        mirror.set_material(Material {
+            reflective: 0.5,
+            ..Material::default()
+        });
+        world.objects = vec![Box::new(mirror)];
+        let trace = camera().debug_pixel(&world, 5, 5);
+
+        let hit = trace.hit.unwrap();
+        let reflection = hit.reflection.unwrap();
+        assert_eq!(reflection.kind, RayKind::Reflection);
+        assert_eq!(reflection.remaining, MAX_RECURSION_DEPTH - 1);
+        assert!(reflection
+            .ray
+            .direction
+            .is_equal_to(&Tuple::new_vector(0.0, 0.0, -1.0)));
+        // The reflected ray sees only the environment, which is black
+        assert!(reflection.hit.is_none());
+        assert!(trace.color.is_equal_to(&hit.surface));
+    }
+}
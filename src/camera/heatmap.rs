@@ -0,0 +1,117 @@
+use super::Camera;
+use crate::canvas::Canvas;
+use crate::color::Color;
+use crate::shapes::intersection_tests;
+use crate::world::World;
+
+/// Colors of the heatmap's scale, from the fewest intersection tests to the most.
+const SCALE: [Color; 5] = [
+    Color::new(0.0, 0.0, 0.0),
+    Color::new(0.0, 0.0, 1.0),
+    Color::new(1.0, 0.0, 0.0),
+    Color::new(1.0, 1.0, 0.0),
+    Color::new(1.0, 1.0, 1.0),
+];
+
+impl Camera {
+    /// Count the ray-shape intersection tests performed to compute the color of the pixel at column `px`
+    /// and row `py`, including those of shadow, reflected and refracted rays.
+    pub fn intersection_tests_for_pixel(&self, world: &World, px: usize, py: usize) -> usize {
+        let before = intersection_tests();
+        self.pixel_color(world, px, py);
+        intersection_tests() - before
+    }
+
+    /// Render a heatmap of the work spent on each pixel instead of an image of the world.
+    ///
+    /// Pixels are colored by the number of intersection tests performed for them, from black for none,
+    /// through blue, red and yellow, to white for the most tests of any pixel. Bounding volume
+    /// hierarchies and other optimizations show up as cooler colors.
+    pub fn render_heatmap(&self, world: &World) -> Canvas {
+        let mut tests = Vec::with_capacity(self.hsize * self.vsize);
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                tests.push(self.intersection_tests_for_pixel(world, x, y));
+            }
+        }
+        let most = tests.iter().copied().max().unwrap_or_default().max(1);
+        let mut image = Canvas::new(self.hsize, self.vsize);
+        for (index, &count) in tests.iter().enumerate() {
+            let color = heat(count as f64 / most as f64);
+            image.write_pixel(index % self.hsize, index / self.hsize, color);
+        }
+        image
+    }
+}
+
+/// Look up a fraction from 0 to 1 on the heatmap's scale, blending between its colors.
+fn heat(fraction: f64) -> Color {
+    let position = fraction.clamp(0.0, 1.0) * (SCALE.len() - 1) as f64;
+    let index = (position.floor() as usize).min(SCALE.len() - 2);
+    let blend = position - index as f64;
+    SCALE[index] * (1.0 - blend) + SCALE[index + 1] * blend
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::{Group, Shape, Sphere};
+    use crate::transform::{translation, view_transform};
+    use crate::tuple::Tuple;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn heat_scale() {
+        let cases = [
+            (0.0, Color::new(0.0, 0.0, 0.0)),
+            (0.125, Color::new(0.0, 0.0, 0.5)),
+            (0.5, Color::new(1.0, 0.0, 0.0)),
+            (0.875, Color::new(1.0, 1.0, 0.5)),
+            (1.0, Color::new(1.0, 1.0, 1.0)),
+        ];
+        for (fraction, expected) in cases {
+            assert!(heat(fraction).is_equal_to(&expected), "{fraction}");
+        }
+    }
+
+    #[test]
+    fn camera_intersection_tests_for_pixel() {
+        let mut camera = Camera::new(5, 5, FRAC_PI_2);
+        camera
+            .set_transform(view_transform(
+                &Tuple::new_point(0.0, 0.0, -5.0),
+                &Tuple::new_point(0.0, 0.0, 0.0),
+                &Tuple::new_vector(0.0, 1.0, 0.0),
+            ))
+            .unwrap();
+
+        // Eight spheres far off to the side, in a group whose bounds every camera ray misses
+        let mut group = Group::new();
+        for i in 0..8 {
+            let mut sphere = Sphere::new();
+            sphere
+                .set_transform(translation(100.0 + i as f64, 0.0, 0.0))
+                .unwrap();
+            group.add_child(Box::new(sphere));
+        }
+        let mut world = World::new();
+        world.objects.push(Box::new(group));
+        assert_eq!(camera.intersection_tests_for_pixel(&world, 2, 2), 1);
+
+        let mut world = World::new();
+        for i in 0..8 {
+            let mut sphere = Sphere::new();
+            sphere
+                .set_transform(translation(100.0 + i as f64, 0.0, 0.0))
+                .unwrap();
+            world.objects.push(Box::new(sphere));
+        }
+        assert_eq!(camera.intersection_tests_for_pixel(&world, 2, 2), 8);
+
+        // Every pixel does the same work, so the whole heatmap is at the top of the scale
+        let heatmap = camera.render_heatmap(&world);
+        assert!(heatmap
+            .pixel_at(0, 4)
+            .is_equal_to(&Color::new(1.0, 1.0, 1.0)));
+    }
+}
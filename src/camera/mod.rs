@@ -0,0 +1,567 @@
+use crate::canvas::{Canvas, Pipeline};
+use crate::matrix::{Matrix4, MatrixError};
+use crate::random::{Sampler, UniformSampler};
+use crate::ray::Ray;
+use crate::transform::{translation, view_transform};
+use crate::tuple::Tuple;
+use crate::world::World;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+pub mod anti_aliasing;
+pub mod aov;
+pub mod cancel;
+pub mod debug;
+pub mod heatmap;
+pub mod motion;
+pub mod progress;
+pub mod progressive;
+pub mod projection;
+pub mod stereo;
+pub mod tiles;
+
+pub use anti_aliasing::AntiAliasing;
+pub use aov::Aovs;
+pub use cancel::{CancellationToken, Cancelled};
+pub use debug::{HitTrace, RayKind, RayTrace, ShadowTest};
+pub use motion::Shutter;
+pub use progress::Progress;
+pub use progressive::Progressive;
+pub use projection::Projection;
+pub use stereo::{Eye, Stereo, StereoLayout};
+pub use tiles::Tile;
+
+/// Camera that maps the pixels of a canvas onto rays cast into a world.
+/// The canvas sits one unit in front of the camera.
+#[derive(Debug)]
+pub struct Camera {
+    hsize: usize,
+    vsize: usize,
+    field_of_view: f64,
+    transform: Matrix4,
+    inverse_transform: Matrix4,
+    half_width: f64,
+    half_height: f64,
+    pixel_size: f64,
+    projection: Projection,
+    anti_aliasing: AntiAliasing,
+    sampler: Arc<dyn Sampler>,
+    shutter: Shutter,
+    end_transform: Option<Matrix4>,
+    end_inverse_transform: Option<Matrix4>,
+    exposure: f64,
+    seed: u64,
+    post_processing: Pipeline,
+}
+
+impl Camera {
+    /// Create a camera rendering `hsize` by `vsize` pixels with the given horizontal field of view in radians.
+    /// The camera starts at the origin looking toward negative z.
+    pub fn new(hsize: usize, vsize: usize, field_of_view: f64) -> Self {
+        let mut camera = Self {
+            hsize,
+            vsize,
+            field_of_view,
+            transform: Matrix4::IDENTITY,
+            inverse_transform: Matrix4::IDENTITY,
+            half_width: 0.0,
+            half_height: 0.0,
+            pixel_size: 0.0,
+            projection: Projection::default(),
+            anti_aliasing: AntiAliasing::default(),
+            sampler: Arc::new(UniformSampler),
+            shutter: Shutter::default(),
+            end_transform: None,
+            end_inverse_transform: None,
+            exposure: 0.0,
+            seed: 0,
+            post_processing: Pipeline::new(),
+        };
+        camera.set_field_of_view(field_of_view);
+        camera
+    }
+
+    /// Create a copy of the camera rendering `hsize` by `vsize` pixels, keeping its field of view,
+    /// projection, transforms, shutter, anti-aliasing, sampler, exposure and seed. Post-processes are not
+    /// copied.
+    pub fn resized(&self, hsize: usize, vsize: usize) -> Self {
+        let mut resized = Self::new(hsize, vsize, self.field_of_view);
+        resized.projection = self.projection;
+        resized.transform = self.transform;
+        resized.inverse_transform = self.inverse_transform;
+        resized.end_transform = self.end_transform;
+        resized.end_inverse_transform = self.end_inverse_transform;
+        resized.shutter = self.shutter;
+        resized.anti_aliasing = self.anti_aliasing;
+        resized.sampler = Arc::clone(&self.sampler);
+        resized.exposure = self.exposure;
+        resized.seed = self.seed;
+        resized
+    }
+
+    /// Get the horizontal size of the canvas in pixels.
+    pub fn hsize(&self) -> usize {
+        self.hsize
+    }
+
+    /// Get the vertical size of the canvas in pixels.
+    pub fn vsize(&self) -> usize {
+        self.vsize
+    }
+
+    /// Get the horizontal field of view in radians.
+    pub fn field_of_view(&self) -> f64 {
+        self.field_of_view
+    }
+
+    /// Set the horizontal field of view in radians, resizing the canvas in world units to match.
+    pub fn set_field_of_view(&mut self, field_of_view: f64) {
+        let half_view = (field_of_view / 2.0).tan();
+        let aspect = self.hsize as f64 / self.vsize as f64;
+        let (half_width, half_height) = if aspect >= 1.0 {
+            (half_view, half_view / aspect)
+        } else {
+            (half_view * aspect, half_view)
+        };
+        self.field_of_view = field_of_view;
+        self.half_width = half_width;
+        self.half_height = half_height;
+        self.pixel_size = half_width * 2.0 / self.hsize as f64;
+    }
+
+    /// Get the size of a pixel on the canvas in world units.
+    pub fn pixel_size(&self) -> f64 {
+        self.pixel_size
+    }
+
+    /// Get the way the camera spreads its rays across the canvas.
+    pub fn projection(&self) -> Projection {
+        self.projection
+    }
+
+    /// Set the way the camera spreads its rays across the canvas.
+    pub fn set_projection(&mut self, projection: Projection) {
+        self.projection = projection;
+    }
+
+    /// Get the transform that orients the world relative to the camera.
+    pub fn transform(&self) -> &Matrix4 {
+        &self.transform
+    }
+
+    /// Set the transform that orients the world relative to the camera, typically created with `view_transform`.
+    /// Returns an error, leaving the camera unchanged, if the transform is not invertible.
+    pub fn set_transform(&mut self, transform: Matrix4) -> Result<(), MatrixError> {
+        self.inverse_transform = transform.inverse()?;
+        self.transform = transform;
+        Ok(())
+    }
+
+    /// Get the position of the camera in world space.
+    pub fn position(&self) -> Tuple {
+        self.inverse_transform * Tuple::new_point(0.0, 0.0, 0.0)
+    }
+
+    /// Place the camera at the point `from` looking at the point `to`, with `up` roughly pointing upward,
+    /// as `view_transform` does.
+    /// Returns an error, leaving the camera unchanged, if `from` and `to` are the same point or `up` points
+    /// along the line of sight.
+    pub fn look_at(&mut self, from: &Tuple, to: &Tuple, up: &Tuple) -> Result<(), MatrixError> {
+        // With no line of sight, `view_transform` would fill the transform with NaNs
+        if from == to {
+            return Err(MatrixError::NotInvertible);
+        }
+        self.set_transform(view_transform(from, to, up))
+    }
+
+    /// Swing the camera around the point `pivot` at the same distance from it, and turn it to look at
+    /// the pivot with the y axis up.
+    ///
+    /// `yaw` moves the camera around the vertical axis through the pivot, in the same direction as
+    /// `rotation_y`, and `pitch` raises it toward the point above the pivot, both in radians. The camera
+    /// stops just short of passing over the top or under the bottom of the pivot, where it could no longer
+    /// tell which way is up. Returns an error, leaving the camera unchanged, if it is at the pivot.
+    pub fn orbit(&mut self, pivot: &Tuple, yaw: f64, pitch: f64) -> Result<(), MatrixError> {
+        const POLE_MARGIN: f64 = 1e-3;
+        let (radius, theta, phi) = (self.position() - pivot).to_spherical();
+        let phi = (phi - pitch).clamp(POLE_MARGIN, PI - POLE_MARGIN);
+        let offset = Tuple::from_spherical(radius, theta + yaw, phi);
+        let from = pivot + Tuple::new_vector(offset.x, offset.y, offset.z);
+        self.look_at(&from, pivot, &Tuple::new_vector(0.0, 1.0, 0.0))
+    }
+
+    /// Move the camera `distance` units forward along its line of sight, or backward if `distance` is
+    /// negative, without turning it.
+    pub fn dolly(&mut self, distance: f64) -> Result<(), MatrixError> {
+        self.set_transform(translation(0.0, 0.0, distance) * self.transform)
+    }
+
+    /// Get the interval during which the shutter is open.
+    pub fn shutter(&self) -> Shutter {
+        self.shutter
+    }
+
+    /// Set the interval during which the shutter is open.
+    pub fn set_shutter(&mut self, shutter: Shutter) {
+        self.shutter = shutter;
+    }
+
+    /// Get the transform of the camera when the shutter closes, if the camera moves.
+    pub fn end_transform(&self) -> Option<&Matrix4> {
+        self.end_transform.as_ref()
+    }
+
+    /// Set the transform of the camera when the shutter closes, making the camera move from its transform
+    /// to this one while the shutter is open.
+    /// Returns an error, leaving the camera unchanged, if the transform is not invertible.
+    pub fn set_end_transform(&mut self, transform: Matrix4) -> Result<(), MatrixError> {
+        self.end_inverse_transform = Some(transform.inverse()?);
+        self.end_transform = Some(transform);
+        Ok(())
+    }
+
+    /// Get the strategy used to choose how many rays are cast through each pixel.
+    pub fn anti_aliasing(&self) -> AntiAliasing {
+        self.anti_aliasing
+    }
+
+    /// Set the strategy used to choose how many rays are cast through each pixel.
+    pub fn set_anti_aliasing(&mut self, anti_aliasing: AntiAliasing) {
+        self.anti_aliasing = anti_aliasing;
+    }
+
+    /// Get the strategy used to spread the rays cast through each pixel.
+    pub fn sampler(&self) -> &dyn Sampler {
+        self.sampler.as_ref()
+    }
+
+    /// Set the strategy used to spread the rays cast through each pixel when anti-aliasing is on.
+    /// The default `UniformSampler` casts them through a regular grid.
+    pub fn set_sampler(&mut self, sampler: impl Sampler + 'static) {
+        self.sampler = Arc::new(sampler);
+    }
+
+    /// Get the exposure in stops (EV).
+    pub fn exposure(&self) -> f64 {
+        self.exposure
+    }
+
+    /// Set the exposure in stops (EV), scaling every pixel's color by `2^exposure` before any tone mapping.
+    /// Each stop doubles or halves the brightness of the render without touching the world's lights.
+    pub fn set_exposure(&mut self, exposure: f64) {
+        self.exposure = exposure;
+    }
+
+    /// Get the seed of the random numbers drawn while rendering.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Set the seed of the random numbers drawn while rendering, such as the moments seen by each ray while
+    /// the shutter is open and the offsets of jittered samples. Renders with the same seed are identical,
+    /// however their pixels are split across tiles, threads or workers, while changing the seed changes
+    /// the noise.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Get the factor scaling every pixel's color for the camera's exposure.
+    fn exposure_scale(&self) -> f64 {
+        self.exposure.exp2()
+    }
+
+    /// Get the post-processes applied to every rendered image.
+    pub fn post_processing(&self) -> &Pipeline {
+        &self.post_processing
+    }
+
+    /// Set the post-processes applied to every rendered image, in order, once all of its pixels are
+    /// rendered. Partial images, such as tiles and cancelled renders, are left unprocessed.
+    pub fn set_post_processing(&mut self, post_processing: Pipeline) {
+        self.post_processing = post_processing;
+    }
+
+    /// Create a ray from the camera through the center of the pixel at column `px` and row `py`.
+    pub fn ray_for_pixel(&self, px: usize, py: usize) -> Ray {
+        self.ray_for_pixel_offset(px, py, 0.5, 0.5)
+    }
+
+    /// Create a ray from the camera through a point within the pixel at column `px` and row `py`.
+    /// The offsets run from 0 at the pixel's top left corner to 1 at its bottom right corner.
+    pub fn ray_for_pixel_offset(&self, px: usize, py: usize, x_offset: f64, y_offset: f64) -> Ray {
+        self.ray_for_pixel_at(px, py, x_offset, y_offset, self.shutter.open)
+    }
+
+    /// Create a ray through a point within a pixel like `ray_for_pixel_offset`, from where the camera is at `time`.
+    pub fn ray_for_pixel_at(
+        &self,
+        px: usize,
+        py: usize,
+        x_offset: f64,
+        y_offset: f64,
+        time: f64,
+    ) -> Ray {
+        let target = self.camera_space_target(px as f64 + x_offset, py as f64 + y_offset);
+        let inverse_transform = self.inverse_transform_at(time);
+        let pixel = inverse_transform * target;
+        let origin = inverse_transform * Tuple::new_point(0.0, 0.0, 0.0);
+        let direction = (pixel - origin).normalize();
+        Ray::new(origin, direction)
+    }
+
+    /// Render an image of the world.
+    pub fn render(&self, world: &World) -> Canvas {
+        self.render_with_progress(world, |_| {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::color::Color;
+    use crate::random::JitteredSampler;
+    use crate::transform::rotation_y;
+    use crate::world::default_world;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, SQRT_2};
+
+    #[test]
+    fn camera_render_exposure() {
+        let world = default_world();
+        let mut camera = Camera::new(11, 11, FRAC_PI_2);
+        camera
+            .set_transform(view_transform(
+                &Tuple::new_point(0.0, 0.0, -5.0),
+                &Tuple::new_point(0.0, 0.0, 0.0),
+                &Tuple::new_vector(0.0, 1.0, 0.0),
+            ))
+            .unwrap();
+        let color = camera.render(&world).pixel_at(5, 5);
+        camera.set_exposure(1.0);
+        assert_approx_eq!(camera.exposure(), 1.0);
+        assert!(camera
+            .render(&world)
+            .pixel_at(5, 5)
+            .is_equal_to(&(color * 2.0)));
+        camera.set_exposure(-2.0);
+        assert!(camera
+            .render(&world)
+            .pixel_at(5, 5)
+            .is_equal_to(&(color * 0.25)));
+    }
+
+    #[test]
+    fn camera_render_post_processing() {
+        let world = default_world();
+        let mut camera = Camera::new(3, 2, FRAC_PI_2);
+        let mut pipeline = Pipeline::new();
+        pipeline.push(|canvas: &mut Canvas| canvas.write_pixel(1, 1, Color::new(1.0, 0.0, 0.0)));
+        pipeline.push(|canvas: &mut Canvas| {
+            let color = canvas.pixel_at(1, 1);
+            canvas.write_pixel(0, 0, color * 0.5);
+        });
+        camera.set_post_processing(pipeline);
+        assert_eq!(camera.post_processing().len(), 2);
+
+        let image = camera.render(&world);
+        assert!(image.pixel_at(0, 0).is_equal_to(&Color::new(0.5, 0.0, 0.0)));
+        let tiled = camera.render_tiles(&world, 2, |_| {});
+        assert!(tiled.pixel_at(0, 0).is_equal_to(&Color::new(0.5, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn camera_resized() {
+        let mut camera = Camera::new(200, 125, FRAC_PI_2);
+        camera
+            .set_transform(rotation_y(FRAC_PI_4) * translation(0.0, -2.0, 5.0))
+            .unwrap();
+        camera.set_anti_aliasing(AntiAliasing::adaptive());
+        camera.set_exposure(0.5);
+        camera.set_projection(Projection::Fisheye);
+        camera.set_sampler(JitteredSampler);
+        camera.set_seed(7);
+        let resized = camera.resized(100, 50);
+        assert_eq!(resized.hsize(), 100);
+        assert_eq!(resized.vsize(), 50);
+        assert_approx_eq!(resized.field_of_view(), FRAC_PI_2);
+        assert!(resized.transform().is_equal_to(camera.transform()));
+        assert_eq!(resized.anti_aliasing(), AntiAliasing::adaptive());
+        assert_eq!(resized.projection(), Projection::Fisheye);
+        assert_eq!(format!("{:?}", resized.sampler()), "JitteredSampler");
+        assert_approx_eq!(resized.exposure(), 0.5);
+        assert_eq!(resized.seed(), 7);
+        assert!(resized.end_transform().is_none());
+    }
+
+    #[test]
+    fn camera_new() {
+        let camera = Camera::new(160, 120, FRAC_PI_2);
+        assert_eq!(camera.hsize(), 160);
+        assert_eq!(camera.vsize(), 120);
+        assert_approx_eq!(camera.field_of_view(), FRAC_PI_2);
+        assert!(camera.transform().is_equal_to(&Matrix4::IDENTITY));
+    }
+
+    #[test]
+    fn camera_pixel_size() {
+        let horizontal = Camera::new(200, 125, FRAC_PI_2);
+        assert_approx_eq!(horizontal.pixel_size(), 0.01);
+
+        let vertical = Camera::new(125, 200, FRAC_PI_2);
+        assert_approx_eq!(vertical.pixel_size(), 0.01);
+    }
+
+    #[test]
+    fn camera_set_field_of_view() {
+        let mut camera = Camera::new(200, 125, FRAC_PI_4);
+        camera.set_field_of_view(FRAC_PI_2);
+        assert_approx_eq!(camera.field_of_view(), FRAC_PI_2);
+        assert_approx_eq!(camera.pixel_size(), 0.01);
+    }
+
+    #[test]
+    fn camera_look_at() {
+        let mut camera = Camera::new(10, 10, FRAC_PI_2);
+        let from = Tuple::new_point(1.0, 3.0, 2.0);
+        let to = Tuple::new_point(4.0, -2.0, 8.0);
+        let up = Tuple::new_vector(1.0, 1.0, 0.0);
+        camera.look_at(&from, &to, &up).unwrap();
+        assert!(camera
+            .transform()
+            .is_equal_to(&view_transform(&from, &to, &up)));
+        assert!(camera.position().is_equal_to(&from));
+
+        assert!(camera.look_at(&from, &from, &up).is_err());
+        assert!(camera.position().is_equal_to(&from));
+    }
+
+    #[test]
+    fn camera_orbit() {
+        let pivot = Tuple::new_point(0.0, 1.0, 0.0);
+        let mut camera = Camera::new(11, 11, FRAC_PI_2);
+        camera
+            .look_at(
+                &Tuple::new_point(0.0, 1.0, -5.0),
+                &pivot,
+                &Tuple::new_vector(0.0, 1.0, 0.0),
+            )
+            .unwrap();
+
+        camera.orbit(&pivot, FRAC_PI_2, 0.0).unwrap();
+        assert!(camera
+            .position()
+            .is_equal_to(&Tuple::new_point(-5.0, 1.0, 0.0)));
+        // The camera keeps looking at the pivot
+        let ray = camera.ray_for_pixel(5, 5);
+        assert!(ray.direction.is_equal_to(&Tuple::new_vector(1.0, 0.0, 0.0)));
+
+        camera.orbit(&pivot, 0.0, FRAC_PI_4).unwrap();
+        let height = 5.0 * FRAC_PI_4.sin();
+        assert!(camera
+            .position()
+            .is_equal_to(&Tuple::new_point(-height, 1.0 + height, 0.0)));
+
+        // Pitching past the top stops just short of it
+        camera.orbit(&pivot, 0.0, PI).unwrap();
+        let position = camera.position();
+        assert!(position.x < 0.0);
+        assert_approx_eq!((position - pivot).magnitude(), 5.0);
+        assert_approx_eq!(position.y, 6.0, epsilon = 1e-4);
+
+        // The camera cannot orbit a pivot it is at
+        let mut camera = Camera::new(11, 11, FRAC_PI_2);
+        let transform = *camera.transform();
+        assert!(camera
+            .orbit(&Tuple::new_point(0.0, 0.0, 0.0), 1.0, 0.0)
+            .is_err());
+        assert!(camera.transform().is_equal_to(&transform));
+    }
+
+    #[test]
+    fn camera_dolly() {
+        let mut camera = Camera::new(11, 11, FRAC_PI_2);
+        camera
+            .look_at(
+                &Tuple::new_point(0.0, 0.0, -5.0),
+                &Tuple::new_point(0.0, 0.0, 0.0),
+                &Tuple::new_vector(0.0, 1.0, 0.0),
+            )
+            .unwrap();
+        camera.dolly(2.0).unwrap();
+        assert!(camera
+            .position()
+            .is_equal_to(&Tuple::new_point(0.0, 0.0, -3.0)));
+        camera.dolly(-4.0).unwrap();
+        assert!(camera
+            .position()
+            .is_equal_to(&Tuple::new_point(0.0, 0.0, -7.0)));
+        let ray = camera.ray_for_pixel(5, 5);
+        assert!(ray.direction.is_equal_to(&Tuple::new_vector(0.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn camera_ray_for_pixel_center() {
+        let camera = Camera::new(201, 101, FRAC_PI_2);
+        let ray = camera.ray_for_pixel(100, 50);
+        assert!(ray.origin.is_equal_to(&Tuple::new_point(0.0, 0.0, 0.0)));
+        assert!(ray
+            .direction
+            .is_equal_to(&Tuple::new_vector(0.0, 0.0, -1.0)));
+    }
+
+    #[test]
+    fn camera_ray_for_pixel_corner() {
+        let camera = Camera::new(201, 101, FRAC_PI_2);
+        let ray = camera.ray_for_pixel(0, 0);
+        assert!(ray.origin.is_equal_to(&Tuple::new_point(0.0, 0.0, 0.0)));
+        // The book's values are rounded to five decimal places.
+        assert_approx_eq!(
+            ray.direction,
+            Tuple::new_vector(0.66519, 0.33259, -0.66851),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn camera_ray_for_pixel_offset() {
+        let camera = Camera::new(2, 2, FRAC_PI_2);
+        let ray = camera.ray_for_pixel_offset(0, 0, 0.0, 0.0);
+        assert!(ray
+            .direction
+            .is_equal_to(&Tuple::new_vector(1.0, 1.0, -1.0).normalize()));
+        let ray = camera.ray_for_pixel_offset(0, 0, 1.0, 1.0);
+        assert!(ray
+            .direction
+            .is_equal_to(&Tuple::new_vector(0.0, 0.0, -1.0)));
+    }
+
+    #[test]
+    fn camera_ray_for_pixel_transformed() {
+        let mut camera = Camera::new(201, 101, FRAC_PI_2);
+        camera
+            .set_transform(rotation_y(FRAC_PI_4) * translation(0.0, -2.0, 5.0))
+            .unwrap();
+        let ray = camera.ray_for_pixel(100, 50);
+        assert!(ray.origin.is_equal_to(&Tuple::new_point(0.0, 2.0, -5.0)));
+        assert!(ray
+            .direction
+            .is_equal_to(&Tuple::new_vector(SQRT_2 / 2.0, 0.0, -SQRT_2 / 2.0)));
+    }
+
+    #[test]
+    fn camera_render() {
+        let world = default_world();
+        let mut camera = Camera::new(11, 11, FRAC_PI_2);
+        let from = Tuple::new_point(0.0, 0.0, -5.0);
+        let to = Tuple::new_point(0.0, 0.0, 0.0);
+        let up = Tuple::new_vector(0.0, 1.0, 0.0);
+        camera
+            .set_transform(view_transform(&from, &to, &up))
+            .unwrap();
+        let image = camera.render(&world);
+        let color = image.pixel_at(5, 5);
+        // The book's values are rounded to five decimal places.
+        assert_approx_eq!(color, Color::new(0.38066, 0.47583, 0.2855), epsilon = 1e-5);
+        assert!(image.pixel_at(0, 0).is_equal_to(&Color::default()));
+    }
+}
@@ -0,0 +1,173 @@
+use super::Camera;
+use crate::animation::Interpolate;
+use crate::matrix::Matrix4;
+use crate::random::Rng;
+
+/// Interval of time during which the camera's shutter is open, and how many moments of it each pixel sees.
+///
+/// When the camera moves while the shutter is open, each ray is cast at a random moment of the interval
+/// and the image streaks along the motion, like a photograph of a moving scene.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shutter {
+    pub open: f64,
+    pub close: f64,
+    /// Number of rays cast through each pixel at different moments when anti-aliasing is off.
+    /// Adaptive anti-aliasing already casts many rays per pixel, and gives each of them its own moment.
+    pub samples: usize,
+}
+
+impl Shutter {
+    /// Create a shutter open from `open` to `close`, casting `samples` rays per pixel.
+    pub fn new(open: f64, close: f64, samples: usize) -> Self {
+        Self {
+            open,
+            close,
+            samples,
+        }
+    }
+}
+
+impl Default for Shutter {
+    /// Create a shutter that opens and closes instantly at time 0, so nothing is blurred.
+    fn default() -> Self {
+        Self::new(0.0, 0.0, 1)
+    }
+}
+
+impl Camera {
+    /// Check whether the camera moves while its shutter is open, which blurs the image.
+    pub fn is_moving(&self) -> bool {
+        self.end_inverse_transform.is_some() && self.shutter.close > self.shutter.open
+    }
+
+    /// Compute the inverse of the camera's transform at a moment while the shutter is open.
+    ///
+    /// The camera moves steadily from its transform when the shutter opens to its end transform when the
+    /// shutter closes: both are split into a translation, rotation and scale, which are blended as
+    /// `Decomposition`s interpolate, so the view keeps its shape as the camera turns. Shearing transforms
+    /// are only approximated along the way.
+    pub(super) fn inverse_transform_at(&self, time: f64) -> Matrix4 {
+        let Some(end_transform) = self.end_transform.filter(|_| self.is_moving()) else {
+            return self.inverse_transform;
+        };
+        let t =
+            ((time - self.shutter.open) / (self.shutter.close - self.shutter.open)).clamp(0.0, 1.0);
+        // Transforms squashing space too much to decompose are not blurred
+        let (Ok(start), Ok(end)) = (self.transform.decompose(), end_transform.decompose()) else {
+            return self.inverse_transform;
+        };
+        start
+            .interpolate(&end, t)
+            .to_matrix()
+            .inverse()
+            .unwrap_or(self.inverse_transform)
+    }
+
+    /// Pick a random moment while the shutter is open.
+    pub(super) fn sample_time(&self, rng: &mut Rng) -> f64 {
+        self.shutter.open + rng.next_f64() * (self.shutter.close - self.shutter.open)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::color::Color;
+    use crate::light::{Material, PointLight};
+    use crate::shapes::{Shape, Sphere};
+    use crate::transform::{rotation_y, translation};
+    use crate::tuple::Tuple;
+    use crate::world::World;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn camera_shutter_default() {
+        let camera = Camera::new(10, 10, FRAC_PI_2);
+        assert_eq!(camera.shutter(), Shutter::default());
+        assert!(camera.end_transform().is_none());
+        assert!(!camera.is_moving());
+    }
+
+    #[test]
+    fn camera_ray_for_pixel_at_time() {
+        let mut camera = Camera::new(201, 101, FRAC_PI_2);
+        camera.set_shutter(Shutter::new(0.0, 1.0, 8));
+        camera
+            .set_end_transform(translation(-4.0, 0.0, 0.0))
+            .unwrap();
+        assert!(camera.is_moving());
+
+        let cases = [(0.0, 0.0), (0.25, 1.0), (1.0, 4.0), (2.0, 4.0)];
+        for (time, x) in cases {
+            let ray = camera.ray_for_pixel_at(100, 50, 0.5, 0.5, time);
+            assert!(ray.origin.is_equal_to(&Tuple::new_point(x, 0.0, 0.0)));
+            assert!(ray
+                .direction
+                .is_equal_to(&Tuple::new_vector(0.0, 0.0, -1.0)));
+        }
+    }
+
+    #[test]
+    fn camera_inverse_transform_at_rotation() {
+        let mut camera = Camera::new(10, 10, FRAC_PI_2);
+        camera.set_shutter(Shutter::new(0.0, 1.0, 8));
+        camera.set_end_transform(rotation_y(FRAC_PI_2)).unwrap();
+        // Halfway through, the camera has turned by 45 degrees without shearing or shrinking the view
+        let midpoint = camera.inverse_transform_at(0.5);
+        for vector in [
+            Tuple::new_vector(1.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+            Tuple::new_vector(1.0, 2.0, -3.0),
+        ] {
+            assert_approx_eq!((midpoint * vector).magnitude(), vector.magnitude());
+        }
+        assert_approx_eq!(midpoint, rotation_y(FRAC_PI_2 / 2.0).inverse().unwrap());
+        assert_approx_eq!(camera.inverse_transform_at(1.0), rotation_y(-FRAC_PI_2));
+    }
+
+    #[test]
+    fn camera_sample_time() {
+        let mut camera = Camera::new(10, 10, FRAC_PI_2);
+        camera.set_shutter(Shutter::new(2.0, 3.0, 1));
+        let mut rng = Rng::new(0);
+        for _ in 0..100 {
+            let time = camera.sample_time(&mut rng);
+            assert!((2.0..3.0).contains(&time));
+        }
+    }
+
+    #[test]
+    fn camera_pixel_color_motion_blur() {
+        let mut sphere = Sphere::new();
+        sphere.set_transform(translation(0.0, 0.0, -5.0)).unwrap();
+        sphere.set_material(Material {
+            ambient: 1.0,
+            diffuse: 0.0,
+            specular: 0.0,
+            ..Material::default()
+        });
+        let mut world = World::new();
+        world.objects.push(Box::new(sphere));
+        world.light = Some(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+
+        // The center pixel sees the sphere when the shutter opens but not once the camera has moved away
+        let mut camera = Camera::new(11, 11, FRAC_PI_2);
+        assert!(camera
+            .pixel_color(&world, 5, 5)
+            .is_equal_to(&Color::new(1.0, 1.0, 1.0)));
+        camera.set_shutter(Shutter::new(0.0, 1.0, 32));
+        camera
+            .set_end_transform(translation(-4.0, 0.0, 0.0))
+            .unwrap();
+        let color = camera.pixel_color(&world, 5, 5);
+        assert!(color.red > 0.1 && color.red < 0.9);
+
+        // Renders are reproducible
+        assert_approx_eq!(camera.pixel_color(&world, 5, 5).red, color.red);
+    }
+}
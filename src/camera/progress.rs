@@ -0,0 +1,98 @@
+use super::{Camera, CancellationToken};
+use crate::canvas::Canvas;
+use crate::world::World;
+use std::time::Duration;
+
+/// Snapshot of how far a render has come, passed to the callback of `Camera::render_with_progress`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// Number of rows of pixels rendered so far.
+    pub rows_completed: usize,
+    /// Number of rows of pixels in the whole image.
+    pub total_rows: usize,
+    /// Time since the render started.
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /// Get the fraction of the image rendered so far, from 0 to 1.
+    pub fn fraction(&self) -> f64 {
+        if self.total_rows == 0 {
+            1.0
+        } else {
+            self.rows_completed as f64 / self.total_rows as f64
+        }
+    }
+
+    /// Estimate the time left to finish the render, assuming the remaining rows take as long as the
+    /// rows so far. Returns `None` before the first row is complete.
+    pub fn estimated_remaining(&self) -> Option<Duration> {
+        if self.rows_completed == 0 {
+            return None;
+        }
+        let remaining_rows = self.total_rows - self.rows_completed;
+        Some(
+            self.elapsed
+                .mul_f64(remaining_rows as f64 / self.rows_completed as f64),
+        )
+    }
+}
+
+impl Camera {
+    /// Render the world like `render`, calling `on_progress` after each row of pixels is complete.
+    pub fn render_with_progress<F>(&self, world: &World, on_progress: F) -> Canvas
+    where
+        F: FnMut(Progress),
+    {
+        self.render_cancellable(world, &CancellationToken::new(), on_progress)
+            .expect("a render whose token is never cancelled always completes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::world::default_world;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn progress_estimated_remaining() {
+        let mut progress = Progress {
+            rows_completed: 0,
+            total_rows: 4,
+            elapsed: Duration::from_secs(1),
+        };
+        assert_approx_eq!(progress.fraction(), 0.0, epsilon = f64::EPSILON);
+        assert_eq!(progress.estimated_remaining(), None);
+
+        progress.rows_completed = 1;
+        assert_approx_eq!(progress.fraction(), 0.25, epsilon = f64::EPSILON);
+        assert_eq!(progress.estimated_remaining(), Some(Duration::from_secs(3)));
+
+        progress.rows_completed = 4;
+        assert_eq!(progress.estimated_remaining(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn camera_render_with_progress() {
+        let world = default_world();
+        let camera = Camera::new(4, 3, FRAC_PI_2);
+        let mut reports = Vec::new();
+        let image = camera.render_with_progress(&world, |progress| reports.push(progress));
+
+        let rows: Vec<usize> = reports.iter().map(|report| report.rows_completed).collect();
+        assert_eq!(rows, [1, 2, 3]);
+        assert!(reports.iter().all(|report| report.total_rows == 3));
+        assert!(reports
+            .windows(2)
+            .all(|pair| pair[0].elapsed <= pair[1].elapsed));
+
+        for y in 0..camera.vsize() {
+            for x in 0..camera.hsize() {
+                let expected = camera.pixel_color(&world, x, y);
+                assert!(image.pixel_at(x, y).is_equal_to(&expected));
+            }
+        }
+    }
+}
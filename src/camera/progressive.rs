@@ -0,0 +1,165 @@
+use super::Camera;
+use crate::canvas::{Canvas, PostProcess};
+use crate::color::Color;
+use crate::random::Rng;
+use crate::world::World;
+
+/// Iterator over increasingly refined renders of a world, created by `Camera::render_progressive`.
+///
+/// Each pass quadruples the number of rays cast through every pixel, so the images have 1, 4, 16, ...
+/// samples per pixel. Samples from earlier passes are kept, so each pass costs three times the work of
+/// all the passes before it rather than starting over. The iterator never ends; use `take` to bound it.
+#[derive(Debug)]
+pub struct Progressive<'a> {
+    camera: &'a Camera,
+    world: &'a World,
+    pass: u32,
+    sums: Vec<Color>,
+    samples: usize,
+}
+
+impl<'a> Progressive<'a> {
+    /// Get the number of rays cast through each pixel so far.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Compute the color of one more ray through the pixel at column `px` and row `py`.
+    /// The first pass casts a single ray through the pixel's center, the later ones at random offsets.
+    fn sample(&self, rng: &mut Rng, px: usize, py: usize) -> Color {
+        let (x_offset, y_offset) = if self.pass == 0 {
+            (0.5, 0.5)
+        } else {
+            (rng.next_f64(), rng.next_f64())
+        };
+        let time = self.camera.sample_time(rng);
+        let ray = self
+            .camera
+            .ray_for_pixel_at(px, py, x_offset, y_offset, time);
+        self.world.trace(&ray, self.camera.seed)
+    }
+}
+
+impl Iterator for Progressive<'_> {
+    type Item = Canvas;
+
+    fn next(&mut self) -> Option<Canvas> {
+        let total = 4usize.checked_pow(self.pass)?;
+        let (hsize, vsize) = (self.camera.hsize, self.camera.vsize);
+        let mut image = Canvas::new(hsize, vsize);
+        for py in 0..vsize {
+            for px in 0..hsize {
+                let stream = (self.pass as usize * vsize + py) * hsize + px;
+                let mut rng = Rng::with_stream(self.camera.seed, stream as u64);
+                let mut sum = self.sums[py * hsize + px];
+                for _ in self.samples..total {
+                    sum = sum + self.sample(&mut rng, px, py);
+                }
+                self.sums[py * hsize + px] = sum;
+                image.write_pixel(px, py, sum * (self.camera.exposure_scale() / total as f64));
+            }
+        }
+        self.samples = total;
+        self.pass += 1;
+        self.camera.post_processing.apply(&mut image);
+        Some(image)
+    }
+}
+
+impl Camera {
+    /// Render the world progressively, yielding a noisy image straight away and refined images after it.
+    /// The camera's anti-aliasing strategy is ignored, since every pass already casts more rays per pixel.
+    pub fn render_progressive<'a>(&'a self, world: &'a World) -> Progressive<'a> {
+        Progressive {
+            camera: self,
+            world,
+            pass: 0,
+            sums: vec![Color::default(); self.hsize * self.vsize],
+            samples: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::view_transform;
+    use crate::tuple::Tuple;
+    use crate::world::default_world;
+    use std::f64::consts::FRAC_PI_6;
+
+    fn test_camera() -> Camera {
+        let mut camera = Camera::new(11, 11, FRAC_PI_6);
+        camera
+            .set_transform(view_transform(
+                &Tuple::new_point(0.0, 0.0, -5.0),
+                &Tuple::new_point(0.0, 0.0, 0.0),
+                &Tuple::new_vector(0.0, 1.0, 0.0),
+            ))
+            .unwrap();
+        camera
+    }
+
+    #[test]
+    fn camera_render_progressive_first_pass() {
+        let world = default_world();
+        let camera = test_camera();
+        let first = camera.render_progressive(&world).next().unwrap();
+        let expected = camera.render(&world);
+        for y in 0..camera.vsize() {
+            for x in 0..camera.hsize() {
+                assert!(first.pixel_at(x, y).is_equal_to(&expected.pixel_at(x, y)));
+            }
+        }
+    }
+
+    #[test]
+    fn camera_render_progressive_samples() {
+        let world = default_world();
+        let camera = test_camera();
+        let mut progressive = camera.render_progressive(&world);
+        for expected in [1, 4, 16] {
+            progressive.next().unwrap();
+            assert_eq!(progressive.samples(), expected);
+        }
+    }
+
+    #[test]
+    fn camera_render_progressive_refines() {
+        let world = default_world();
+        let camera = test_camera();
+        let images: Vec<Canvas> = camera.render_progressive(&world).take(3).collect();
+
+        // The shading barely changes across the center of the sphere, so every pass nearly agrees on it
+        let center = images[0].pixel_at(5, 5);
+        for image in &images {
+            assert_approx_eq!(image.pixel_at(5, 5), center, epsilon = 0.02);
+        }
+
+        // Along the sphere's edge, later passes blend the sphere with the background
+        let edge: Vec<Color> = images.iter().map(|image| image.pixel_at(1, 5)).collect();
+        assert!(!edge[2].is_equal_to(&edge[0]));
+    }
+
+    #[test]
+    fn camera_render_progressive_seed() {
+        let world = default_world();
+        let mut camera = test_camera();
+        let render = |camera: &Camera| camera.render_progressive(&world).nth(1).unwrap();
+        let first = render(&camera);
+        assert!(render(&camera)
+            .pixel_at(1, 5)
+            .is_equal_to(&first.pixel_at(1, 5)));
+
+        // Another seed casts rays through other points of the pixels along the sphere's edge
+        camera.set_seed(1);
+        assert_eq!(camera.seed(), 1);
+        let reseeded = render(&camera);
+        assert!(!reseeded.pixel_at(1, 5).is_equal_to(&first.pixel_at(1, 5)));
+        camera.set_seed(0);
+        assert!(render(&camera)
+            .pixel_at(1, 5)
+            .is_equal_to(&first.pixel_at(1, 5)));
+    }
+}
@@ -0,0 +1,128 @@
+use super::Camera;
+use crate::tuple::Tuple;
+use std::f64::consts::{PI, TAU};
+
+/// Way the camera spreads its rays across the canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Projection {
+    /// Cast rays through a flat canvas one unit in front of the camera, as the book does, so straight
+    /// lines stay straight. The field of view is the angle across the width of the canvas.
+    #[default]
+    Perspective,
+    /// Spread rays evenly by angle from the line of sight, like a fisheye lens, so straight lines bow
+    /// outward. The field of view is the angle across the circle touching the shorter sides of the canvas,
+    /// so a field of view of π gives a 180° fisheye. Pixels outside the circle see even further to the side.
+    Fisheye,
+    /// Spread rays over every direction, with longitude across the width of the canvas and latitude down
+    /// its height, for 360° × 180° panoramas and environment maps. The line of sight is at the center of
+    /// the canvas, and the field of view is ignored.
+    Equirectangular,
+}
+
+impl Camera {
+    /// Find the point a ray passes through on its way out of the camera in the camera's own space, where
+    /// the camera sits at the origin looking toward negative z, for a point on the canvas `x` pixels from
+    /// its left edge and `y` pixels from its top edge.
+    pub(super) fn camera_space_target(&self, x: f64, y: f64) -> Tuple {
+        match self.projection {
+            Projection::Perspective => {
+                // The camera looks toward negative z, so positive x is to the left
+                let world_x = self.half_width - x * self.pixel_size;
+                let world_y = self.half_height - y * self.pixel_size;
+                Tuple::new_point(world_x, world_y, -1.0)
+            }
+            Projection::Fisheye => {
+                let dx = self.hsize as f64 / 2.0 - x;
+                let dy = self.vsize as f64 / 2.0 - y;
+                let distance = dx.hypot(dy);
+                if distance == 0.0 {
+                    return Tuple::new_point(0.0, 0.0, -1.0);
+                }
+                let radius = self.hsize.min(self.vsize) as f64 / 2.0;
+                let (sin, cos) = (distance / radius * self.field_of_view / 2.0).sin_cos();
+                Tuple::new_point(sin * dx / distance, sin * dy / distance, -cos)
+            }
+            Projection::Equirectangular => {
+                let longitude = (0.5 - x / self.hsize as f64) * TAU;
+                let latitude = (0.5 - y / self.vsize as f64) * PI;
+                let (sin_longitude, cos_longitude) = longitude.sin_cos();
+                let (sin_latitude, cos_latitude) = latitude.sin_cos();
+                Tuple::new_point(
+                    sin_longitude * cos_latitude,
+                    sin_latitude,
+                    -cos_longitude * cos_latitude,
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::rotation_y;
+    use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2, FRAC_PI_4};
+
+    #[test]
+    fn camera_projection_default() {
+        let camera = Camera::new(10, 10, FRAC_PI_2);
+        assert_eq!(camera.projection(), Projection::Perspective);
+    }
+
+    #[test]
+    fn camera_ray_for_pixel_fisheye() {
+        let mut camera = Camera::new(100, 50, PI);
+        camera.set_projection(Projection::Fisheye);
+        assert_eq!(camera.projection(), Projection::Fisheye);
+
+        let ray = camera.ray_for_pixel_offset(50, 25, 0.0, 0.0);
+        assert!(ray
+            .direction
+            .is_equal_to(&Tuple::new_vector(0.0, 0.0, -1.0)));
+        // The edges of the circle look straight to the side
+        let ray = camera.ray_for_pixel_offset(50, 0, 0.0, 0.0);
+        assert!(ray.direction.is_equal_to(&Tuple::new_vector(0.0, 1.0, 0.0)));
+        let ray = camera.ray_for_pixel_offset(25, 25, 0.0, 0.0);
+        assert!(ray.direction.is_equal_to(&Tuple::new_vector(1.0, 0.0, 0.0)));
+        // Halfway to the edge, the angle from the line of sight is halfway to 90°
+        let ray = camera.ray_for_pixel_offset(50, 37, 0.0, 0.5);
+        assert!(ray
+            .direction
+            .is_equal_to(&Tuple::new_vector(0.0, -FRAC_1_SQRT_2, -FRAC_1_SQRT_2)));
+
+        // A narrower field of view zooms in
+        camera.set_field_of_view(FRAC_PI_2);
+        let ray = camera.ray_for_pixel_offset(25, 25, 0.0, 0.0);
+        assert!(ray
+            .direction
+            .is_equal_to(&Tuple::new_vector(FRAC_1_SQRT_2, 0.0, -FRAC_1_SQRT_2)));
+    }
+
+    #[test]
+    fn camera_ray_for_pixel_equirectangular() {
+        let mut camera = Camera::new(360, 180, FRAC_PI_2);
+        camera.set_projection(Projection::Equirectangular);
+        let cases = [
+            ((180, 90), Tuple::new_vector(0.0, 0.0, -1.0)),
+            ((90, 90), Tuple::new_vector(1.0, 0.0, 0.0)),
+            ((270, 90), Tuple::new_vector(-1.0, 0.0, 0.0)),
+            ((0, 90), Tuple::new_vector(0.0, 0.0, 1.0)),
+            ((180, 0), Tuple::new_vector(0.0, 1.0, 0.0)),
+            (
+                (180, 45),
+                Tuple::new_vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            ),
+        ];
+        for ((px, py), expected) in cases {
+            let ray = camera.ray_for_pixel_offset(px, py, 0.0, 0.0);
+            assert!(ray.direction.is_equal_to(&expected), "{px} {py}");
+        }
+
+        // The panorama turns with the camera
+        camera.set_transform(rotation_y(FRAC_PI_4)).unwrap();
+        let ray = camera.ray_for_pixel_offset(180, 90, 0.0, 0.0);
+        assert!(ray
+            .direction
+            .is_equal_to(&Tuple::new_vector(FRAC_1_SQRT_2, 0.0, -FRAC_1_SQRT_2)));
+    }
+}
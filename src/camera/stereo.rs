@@ -0,0 +1,235 @@
+use super::Camera;
+use crate::canvas::{Canvas, PostProcess};
+use crate::color::Color;
+use crate::matrix::Matrix4;
+use crate::transform::view_transform;
+use crate::tuple::Tuple;
+use crate::world::World;
+
+/// One of the two eyes of a stereo camera.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eye {
+    Left,
+    Right,
+}
+
+/// How the images seen by the two eyes are combined into one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StereoLayout {
+    /// Take the red channel from the left eye and the green and blue channels from the right eye, to be
+    /// viewed through glasses with a red filter over the left eye and a cyan filter over the right eye.
+    #[default]
+    Anaglyph,
+    /// Put the left eye's image on the left and the right eye's image on the right, as stereoscopes and
+    /// VR headsets expect.
+    SideBySide,
+}
+
+impl StereoLayout {
+    /// Combine the images seen by the left and right eyes.
+    /// Panics if the images are not the same size.
+    pub fn composite(&self, left: &Canvas, right: &Canvas) -> Canvas {
+        assert!(
+            left.width() == right.width() && left.height() == right.height(),
+            "the eyes' images are {}x{} and {}x{}",
+            left.width(),
+            left.height(),
+            right.width(),
+            right.height()
+        );
+        let (width, height) = (left.width(), left.height());
+        match self {
+            StereoLayout::Anaglyph => {
+                let mut image = Canvas::new(width, height);
+                for y in 0..height {
+                    for x in 0..width {
+                        let (left, right) = (left.pixel_at(x, y), right.pixel_at(x, y));
+                        image.write_pixel(x, y, Color::new(left.red, right.green, right.blue));
+                    }
+                }
+                image
+            }
+            StereoLayout::SideBySide => {
+                let mut image = Canvas::new(width * 2, height);
+                for y in 0..height {
+                    for x in 0..width {
+                        image.write_pixel(x, y, left.pixel_at(x, y));
+                        image.write_pixel(width + x, y, right.pixel_at(x, y));
+                    }
+                }
+                image
+            }
+        }
+    }
+}
+
+/// Settings of a stereo camera, which renders the world from two eyes side by side to give depth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stereo {
+    /// Distance between the eyes in world units, centered on the camera's position.
+    pub interocular_distance: f64,
+    /// Distance in front of the camera at which the eyes' lines of sight meet, where objects appear at the
+    /// depth of the screen. Nearer objects appear to float in front of it. The eyes look straight ahead,
+    /// so everything appears behind the screen, if the distance is infinite.
+    pub convergence_distance: f64,
+    pub layout: StereoLayout,
+}
+
+impl Stereo {
+    /// Create stereo settings producing an anaglyph.
+    pub fn new(interocular_distance: f64, convergence_distance: f64) -> Self {
+        Self {
+            interocular_distance,
+            convergence_distance,
+            layout: StereoLayout::default(),
+        }
+    }
+}
+
+impl Camera {
+    /// Create a camera for one eye of a stereo camera, which is this camera moved sideways by half the
+    /// interocular distance and turned toward the point where the eyes converge.
+    /// Post-processes are not copied.
+    pub fn eye(&self, stereo: &Stereo, eye: Eye) -> Camera {
+        // In camera space, the camera looks toward negative z and positive x is to its left
+        let offset = stereo.interocular_distance / 2.0;
+        let x = match eye {
+            Eye::Left => offset,
+            Eye::Right => -offset,
+        };
+        let from = Tuple::new_point(x, 0.0, 0.0);
+        let to = if stereo.convergence_distance.is_finite() {
+            Tuple::new_point(0.0, 0.0, -stereo.convergence_distance)
+        } else {
+            Tuple::new_point(x, 0.0, -1.0)
+        };
+        let eye_transform = view_transform(&from, &to, &Tuple::new_vector(0.0, 1.0, 0.0));
+
+        let mut camera = self.resized(self.hsize, self.vsize);
+        let transform = |transform: &Matrix4| eye_transform * *transform;
+        camera
+            .set_transform(transform(&self.transform))
+            .expect("moving an invertible camera transform keeps it invertible");
+        if let Some(end_transform) = &self.end_transform {
+            camera
+                .set_end_transform(transform(end_transform))
+                .expect("moving an invertible camera transform keeps it invertible");
+        }
+        camera
+    }
+
+    /// Render the images seen by the left and right eyes of a stereo camera, each with the camera's
+    /// post-processes applied.
+    pub fn render_eyes(&self, world: &World, stereo: &Stereo) -> (Canvas, Canvas) {
+        let [left, right] = [Eye::Left, Eye::Right].map(|eye| {
+            let mut image = self.eye(stereo, eye).render(world);
+            self.post_processing.apply(&mut image);
+            image
+        });
+        (left, right)
+    }
+
+    /// Render the world with a stereo camera, combining the eyes' images with the stereo layout.
+    pub fn render_stereo(&self, world: &World, stereo: &Stereo) -> Canvas {
+        let (left, right) = self.render_eyes(world, stereo);
+        stereo.layout.composite(&left, &right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::default_world;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn camera() -> Camera {
+        let mut camera = Camera::new(11, 11, FRAC_PI_2);
+        camera
+            .look_at(
+                &Tuple::new_point(0.0, 0.0, -5.0),
+                &Tuple::new_point(0.0, 0.0, 0.0),
+                &Tuple::new_vector(0.0, 1.0, 0.0),
+            )
+            .unwrap();
+        camera
+    }
+
+    #[test]
+    fn camera_eye() {
+        let camera = camera();
+        let stereo = Stereo::new(1.0, 5.0);
+        let left = camera.eye(&stereo, Eye::Left);
+        let right = camera.eye(&stereo, Eye::Right);
+        // Looking toward positive z, the left is negative x
+        assert!(left
+            .position()
+            .is_equal_to(&Tuple::new_point(-0.5, 0.0, -5.0)));
+        assert!(right
+            .position()
+            .is_equal_to(&Tuple::new_point(0.5, 0.0, -5.0)));
+        // Both eyes look at the point of convergence
+        assert!(left
+            .ray_for_pixel(5, 5)
+            .direction
+            .is_equal_to(&Tuple::new_vector(0.5, 0.0, 5.0).normalize()));
+        assert!(right
+            .ray_for_pixel(5, 5)
+            .direction
+            .is_equal_to(&Tuple::new_vector(-0.5, 0.0, 5.0).normalize()));
+    }
+
+    #[test]
+    fn camera_eye_parallel() {
+        let camera = camera();
+        let stereo = Stereo::new(1.0, f64::INFINITY);
+        for eye in [Eye::Left, Eye::Right] {
+            let ray = camera.eye(&stereo, eye).ray_for_pixel(5, 5);
+            assert!(ray.direction.is_equal_to(&Tuple::new_vector(0.0, 0.0, 1.0)));
+        }
+    }
+
+    #[test]
+    fn stereo_layout_composite() {
+        let mut left = Canvas::new(2, 1);
+        left.write_pixel(0, 0, Color::new(1.0, 0.5, 0.5));
+        let mut right = Canvas::new(2, 1);
+        right.write_pixel(0, 0, Color::new(0.2, 0.3, 0.4));
+
+        let anaglyph = StereoLayout::Anaglyph.composite(&left, &right);
+        assert_eq!((anaglyph.width(), anaglyph.height()), (2, 1));
+        assert!(anaglyph
+            .pixel_at(0, 0)
+            .is_equal_to(&Color::new(1.0, 0.3, 0.4)));
+
+        let side_by_side = StereoLayout::SideBySide.composite(&left, &right);
+        assert_eq!((side_by_side.width(), side_by_side.height()), (4, 1));
+        assert!(side_by_side
+            .pixel_at(0, 0)
+            .is_equal_to(&Color::new(1.0, 0.5, 0.5)));
+        assert!(side_by_side
+            .pixel_at(2, 0)
+            .is_equal_to(&Color::new(0.2, 0.3, 0.4)));
+    }
+
+    #[test]
+    #[should_panic(expected = "the eyes' images are")]
+    fn stereo_layout_composite_mismatched() {
+        StereoLayout::SideBySide.composite(&Canvas::new(2, 1), &Canvas::new(1, 2));
+    }
+
+    #[test]
+    fn camera_render_stereo() {
+        let world = default_world();
+        let camera = camera();
+        let mut stereo = Stereo::new(0.2, 5.0);
+        let (left, right) = camera.render_eyes(&world, &stereo);
+        // The eyes see the sphere at the center from slightly different angles
+        assert!(!left.pixel_at(5, 5).is_equal_to(&Color::default()));
+        assert!(!left.pixel_at(5, 5).is_equal_to(&right.pixel_at(5, 5)));
+
+        stereo.layout = StereoLayout::SideBySide;
+        let image = camera.render_stereo(&world, &stereo);
+        assert_eq!(image.width(), 22);
+        assert!(image.pixel_at(16, 5).is_equal_to(&right.pixel_at(5, 5)));
+    }
+}
@@ -0,0 +1,174 @@
+use super::Camera;
+use crate::canvas::{Canvas, PostProcess};
+use crate::world::World;
+
+/// Rectangular bucket of pixels rendered together, positioned by its top left pixel in the full image.
+#[derive(Debug)]
+pub struct Tile {
+    x: usize,
+    y: usize,
+    pixels: Canvas,
+}
+
+impl Tile {
+    /// Create a tile of pixels whose top left pixel is at column `x` and row `y` of the full image.
+    pub fn new(x: usize, y: usize, pixels: Canvas) -> Self {
+        Self { x, y, pixels }
+    }
+
+    /// Get the column of the tile's top left pixel in the full image.
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    /// Get the row of the tile's top left pixel in the full image.
+    pub fn y(&self) -> usize {
+        self.y
+    }
+
+    /// Get the rendered pixels of the tile. Tiles along the right and bottom edges of the image may be
+    /// smaller than the requested tile size.
+    pub fn pixels(&self) -> &Canvas {
+        &self.pixels
+    }
+
+    /// Copy the tile's pixels into their place in the full image.
+    /// Panics if the tile does not fit in the image.
+    pub fn paste_into(&self, image: &mut Canvas) {
+        for y in 0..self.pixels.height() {
+            for x in 0..self.pixels.width() {
+                image.write_pixel(self.x + x, self.y + y, self.pixels.pixel_at(x, y));
+            }
+        }
+    }
+}
+
+impl Camera {
+    /// Render the world in square tiles of `tile_size` pixels, row by row, calling `on_tile` with each
+    /// tile as soon as it is complete, and return the full image.
+    ///
+    /// Neighbouring pixels are rendered together, and the callback can show or stream partial results.
+    /// Panics if `tile_size` is 0.
+    pub fn render_tiles<F>(&self, world: &World, tile_size: usize, mut on_tile: F) -> Canvas
+    where
+        F: FnMut(&Tile),
+    {
+        assert!(tile_size > 0, "tiles must be at least one pixel wide");
+        let mut image = Canvas::new(self.hsize, self.vsize);
+        for tile_y in (0..self.vsize).step_by(tile_size) {
+            for tile_x in (0..self.hsize).step_by(tile_size) {
+                let width = tile_size.min(self.hsize - tile_x);
+                let height = tile_size.min(self.vsize - tile_y);
+                let tile = self.render_tile(world, tile_x, tile_y, width, height);
+                tile.paste_into(&mut image);
+                on_tile(&tile);
+            }
+        }
+        self.post_processing.apply(&mut image);
+        image
+    }
+
+    /// Render the `width` by `height` pixels of the image whose top left pixel is at column `x` and row `y`.
+    /// The camera's post-processes are not applied, since they may need the full image.
+    /// Panics if the tile does not fit in the image.
+    pub fn render_tile(
+        &self,
+        world: &World,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Tile {
+        assert!(
+            x.checked_add(width).is_some_and(|end| end <= self.hsize)
+                && y.checked_add(height).is_some_and(|end| end <= self.vsize),
+            "the tile does not fit in the {}x{} image",
+            self.hsize,
+            self.vsize
+        );
+        let mut pixels = Canvas::new(width, height);
+        for tile_y in 0..height {
+            for tile_x in 0..width {
+                pixels.write_pixel(
+                    tile_x,
+                    tile_y,
+                    self.pixel_color(world, x + tile_x, y + tile_y),
+                );
+            }
+        }
+        Tile::new(x, y, pixels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::view_transform;
+    use crate::tuple::Tuple;
+    use crate::world::default_world;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn camera_render_tiles() {
+        let world = default_world();
+        let mut camera = Camera::new(11, 7, FRAC_PI_2);
+        camera
+            .set_transform(view_transform(
+                &Tuple::new_point(0.0, 0.0, -5.0),
+                &Tuple::new_point(0.0, 0.0, 0.0),
+                &Tuple::new_vector(0.0, 1.0, 0.0),
+            ))
+            .unwrap();
+
+        let mut tiles = Vec::new();
+        let image = camera.render_tiles(&world, 4, |tile| {
+            tiles.push((
+                tile.x(),
+                tile.y(),
+                tile.pixels().width(),
+                tile.pixels().height(),
+            ));
+        });
+        assert_eq!(
+            tiles,
+            [
+                (0, 0, 4, 4),
+                (4, 0, 4, 4),
+                (8, 0, 3, 4),
+                (0, 4, 4, 3),
+                (4, 4, 4, 3),
+                (8, 4, 3, 3),
+            ]
+        );
+
+        // The tiles put together make the same image as a plain render
+        let expected = camera.render(&world);
+        for y in 0..camera.vsize() {
+            for x in 0..camera.hsize() {
+                assert!(image.pixel_at(x, y).is_equal_to(&expected.pixel_at(x, y)));
+            }
+        }
+    }
+
+    #[test]
+    fn camera_render_tiles_pixels() {
+        let world = default_world();
+        let camera = Camera::new(5, 5, FRAC_PI_2);
+        let image = camera.render_tiles(&world, 2, |tile| {
+            for y in 0..tile.pixels().height() {
+                for x in 0..tile.pixels().width() {
+                    let expected = camera.pixel_color(&world, tile.x() + x, tile.y() + y);
+                    assert!(tile.pixels().pixel_at(x, y).is_equal_to(&expected));
+                }
+            }
+        });
+        assert_eq!(image.width(), 5);
+        assert_eq!(image.height(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn camera_render_tile_overflowing() {
+        Camera::new(5, 5, FRAC_PI_2).render_tile(&default_world(), usize::MAX, 0, 2, 2);
+    }
+}
@@ -0,0 +1,155 @@
+use super::{Canvas, PostProcess};
+use crate::color::Color;
+
+/// Post-process making bright parts of the canvas glow into their surroundings.
+///
+/// The light of each channel above `threshold` is blurred with a Gaussian of standard deviation `sigma`
+/// pixels, then added back to the canvas scaled by `intensity`. Apply it to linear colors, before any
+/// tone mapping squeezes the bright parts below the threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bloom {
+    pub threshold: f64,
+    pub sigma: f64,
+    pub intensity: f64,
+}
+
+impl Default for Bloom {
+    /// Create a bloom spreading light brighter than white over a few pixels.
+    fn default() -> Self {
+        Self {
+            threshold: 1.0,
+            sigma: 4.0,
+            intensity: 1.0,
+        }
+    }
+}
+
+impl Bloom {
+    /// Compute the weights of a Gaussian kernel, from the center out to three standard deviations.
+    fn kernel(&self) -> Vec<f64> {
+        let radius = (3.0 * self.sigma).ceil() as usize;
+        (0..=radius)
+            .map(|offset| (-((offset * offset) as f64) / (2.0 * self.sigma * self.sigma)).exp())
+            .collect()
+    }
+}
+
+impl PostProcess for Bloom {
+    fn apply(&self, canvas: &mut Canvas) {
+        let (width, height) = (canvas.width, canvas.height);
+        let bright: Vec<Color> = canvas
+            .pixels
+            .iter()
+            .map(|pixel| {
+                let excess = |channel: f64| (channel - self.threshold).max(0.0);
+                Color::new(excess(pixel.red), excess(pixel.green), excess(pixel.blue))
+            })
+            .collect();
+        if self.sigma <= 0.0 {
+            return composite(canvas, &bright, self.intensity);
+        }
+        // A 2D Gaussian is the product of two 1D ones, so blur the rows, then the columns
+        let kernel = self.kernel();
+        let rows = blur(&bright, &kernel, width, |line, i| line * width + i, height);
+        let blurred = blur(&rows, &kernel, height, |line, i| i * width + line, width);
+        composite(canvas, &blurred, self.intensity);
+    }
+}
+
+/// Blur `lines` lines of `length` pixels each with a symmetric kernel, where `index` maps a line and a
+/// position along it to an index into `pixels`.
+/// Kernel weights falling outside of the canvas are left out, and the rest renormalized.
+fn blur<F>(pixels: &[Color], kernel: &[f64], length: usize, index: F, lines: usize) -> Vec<Color>
+where
+    F: Fn(usize, usize) -> usize,
+{
+    let mut blurred = vec![Color::default(); pixels.len()];
+    for line in 0..lines {
+        for i in 0..length {
+            let mut sum = Color::default();
+            let mut total = 0.0;
+            for (offset, &weight) in kernel.iter().enumerate() {
+                let mut add = |position: usize| {
+                    sum = sum + pixels[index(line, position)] * weight;
+                    total += weight;
+                };
+                if offset == 0 {
+                    add(i);
+                    continue;
+                }
+                if let Some(position) = i.checked_sub(offset) {
+                    add(position);
+                }
+                if i + offset < length {
+                    add(i + offset);
+                }
+            }
+            blurred[index(line, i)] = sum * (1.0 / total);
+        }
+    }
+    blurred
+}
+
+fn composite(canvas: &mut Canvas, glow: &[Color], intensity: f64) {
+    for (pixel, &glow) in canvas.pixels.iter_mut().zip(glow) {
+        *pixel = *pixel + glow * intensity;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn bloom_apply() {
+        let mut canvas = Canvas::new(13, 13);
+        canvas.write_pixel(6, 6, Color::new(3.0, 0.5, 0.0));
+        canvas.write_pixel(0, 0, Color::new(0.5, 0.5, 0.5));
+        let bloom = Bloom {
+            threshold: 1.0,
+            sigma: 1.0,
+            intensity: 0.5,
+        };
+        bloom.apply(&mut canvas);
+
+        // Only the red channel's excess of 2 spreads, symmetrically, without any light getting lost while
+        // the kernel stays within the canvas
+        let mut total = 0.0;
+        for y in 0..13 {
+            for x in 0..13 {
+                let pixel = canvas.pixel_at(x, y);
+                total += pixel.red;
+                let mirrored = canvas.pixel_at(12 - x, y);
+                if y != 0 {
+                    assert_approx_eq!(pixel.red, mirrored.red, "({x}, {y})");
+                }
+            }
+        }
+        assert_approx_eq!(total, (3.0 + 0.5 + 2.0 * 0.5));
+        assert!(canvas.pixel_at(6, 6).red > 3.0);
+        assert!(canvas.pixel_at(7, 6).red > canvas.pixel_at(8, 6).red);
+        assert!(canvas.pixel_at(9, 6).red > 0.0);
+        assert_approx_eq!(canvas.pixel_at(10, 6).red, 0.0);
+        assert_approx_eq!(canvas.pixel_at(6, 6).green, 0.5);
+        assert!(canvas
+            .pixel_at(0, 0)
+            .is_equal_to(&Color::new(0.5, 0.5, 0.5)));
+    }
+
+    #[test]
+    fn bloom_apply_without_blur() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.write_pixel(0, 0, Color::new(1.5, 1.0, 0.0));
+        let bloom = Bloom {
+            threshold: 0.5,
+            sigma: 0.0,
+            intensity: 1.0,
+        };
+        bloom.apply(&mut canvas);
+        assert!(canvas
+            .pixel_at(0, 0)
+            .is_equal_to(&Color::new(2.5, 1.5, 0.0)));
+        assert!(canvas.pixel_at(1, 0).is_equal_to(&Color::default()));
+    }
+}
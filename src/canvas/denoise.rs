@@ -0,0 +1,243 @@
+use super::Canvas;
+use crate::color::Color;
+
+/// Weights of the 5-tap B3 spline filter, spread further apart on each pass of the à-trous transform.
+const KERNEL: [f64; 5] = [1.0 / 16.0, 1.0 / 4.0, 3.0 / 8.0, 1.0 / 4.0, 1.0 / 16.0];
+
+/// Edge-avoiding à-trous filter smoothing the noise of renders with few samples per pixel, such as soft
+/// shadows, while keeping the edges between surfaces sharp.
+///
+/// Each of the `iterations` passes averages every pixel with neighbours twice as far apart as the last pass,
+/// so a few passes cover a wide area cheaply. A neighbour's weight falls off with how different its color,
+/// surface normal and depth are from the pixel's, according to `color_sigma`, `normal_sigma` and
+/// `depth_sigma`; larger values smooth more. A sigma of 0 or less only lets neighbours that are exactly
+/// alike in that respect count. Passes spreading their taps wider than the image would leave it unchanged,
+/// so they are skipped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Denoiser {
+    pub iterations: usize,
+    pub color_sigma: f64,
+    pub normal_sigma: f64,
+    pub depth_sigma: f64,
+}
+
+impl Default for Denoiser {
+    /// Create a denoiser with three passes, covering neighbourhoods 13 pixels wide.
+    fn default() -> Self {
+        Self {
+            iterations: 3,
+            color_sigma: 0.5,
+            normal_sigma: 0.3,
+            depth_sigma: 0.5,
+        }
+    }
+}
+
+impl Denoiser {
+    /// Denoise a rendered image, guided by the normals and depths seen through each of its pixels.
+    ///
+    /// `normals` holds each pixel's surface normal with x, y and z in its red, green and blue channels,
+    /// and `depths` each pixel's distance to the surface in its red channel, infinite where nothing was hit.
+    /// Panics if the canvases are not all the same size.
+    pub fn denoise(&self, image: &Canvas, normals: &Canvas, depths: &Canvas) -> Canvas {
+        let size = (image.width, image.height);
+        assert!(
+            size == (normals.width, normals.height) && size == (depths.width, depths.height),
+            "the normals and depths must be the same size as the image"
+        );
+        let mut current = Canvas {
+            width: image.width,
+            height: image.height,
+            pixels: image.pixels.clone(),
+        };
+        let longest = image.width.max(image.height);
+        for iteration in 0..self.iterations {
+            let step = u32::try_from(iteration)
+                .ok()
+                .and_then(|iteration| 1usize.checked_shl(iteration))
+                .filter(|step| *step < longest);
+            let Some(step) = step else {
+                break;
+            };
+            current = self.pass(&current, normals, depths, step);
+        }
+        current
+    }
+
+    /// Filter an image once, with the filter's taps `step` pixels apart.
+    fn pass(&self, image: &Canvas, normals: &Canvas, depths: &Canvas, step: usize) -> Canvas {
+        let mut filtered = Canvas::new(image.width, image.height);
+        let color_variance = self.color_sigma.max(0.0).powi(2);
+        let normal_variance = self.normal_sigma.max(0.0).powi(2);
+        let depth_scale = self.depth_sigma.max(0.0) * step as f64;
+        for y in 0..image.height {
+            for x in 0..image.width {
+                let color = image.pixel_at(x, y);
+                let normal = normals.pixel_at(x, y);
+                let depth = depths.pixel_at(x, y).red;
+                let mut sum = Color::default();
+                let mut total = 0.0;
+                for (j, vertical) in KERNEL.iter().enumerate() {
+                    let Some(ny) = offset(y, j, step, image.height) else {
+                        continue;
+                    };
+                    for (i, horizontal) in KERNEL.iter().enumerate() {
+                        let Some(nx) = offset(x, i, step, image.width) else {
+                            continue;
+                        };
+                        let neighbour = image.pixel_at(nx, ny);
+                        let color_weight =
+                            falloff(distance_squared(color, neighbour), color_variance);
+                        let normal_weight = falloff(
+                            distance_squared(normal, normals.pixel_at(nx, ny)),
+                            normal_variance,
+                        );
+                        let depth_weight = match (depth, depths.pixel_at(nx, ny).red) {
+                            (a, b) if a.is_infinite() && b.is_infinite() => 1.0,
+                            (a, b) => falloff((a - b).abs(), depth_scale),
+                        };
+                        let weight =
+                            vertical * horizontal * color_weight * normal_weight * depth_weight;
+                        sum = sum + neighbour * weight;
+                        total += weight;
+                    }
+                }
+                // The pixel itself always has a weight, so the total is never 0
+                filtered.write_pixel(x, y, sum * (1.0 / total));
+            }
+        }
+        filtered
+    }
+}
+
+/// Find the position of the filter's `tap`th tap around `center`, if it is within `length` pixels.
+fn offset(center: usize, tap: usize, step: usize, length: usize) -> Option<usize> {
+    let position = (center + tap * step).checked_sub(2 * step)?;
+    (position < length).then_some(position)
+}
+
+/// Compute the weight of a neighbour `difference` away from a pixel, falling off exponentially over
+/// `scale`. With a scale of 0, only neighbours with no difference have a weight.
+fn falloff(difference: f64, scale: f64) -> f64 {
+    if scale > 0.0 {
+        (-difference / scale).exp()
+    } else if difference == 0.0 {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+fn distance_squared(a: Color, b: Color) -> f64 {
+    let (red, green, blue) = (a.red - b.red, a.green - b.green, a.blue - b.blue);
+    red * red + green * green + blue * blue
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::random::Rng;
+
+    #[test]
+    fn denoiser_denoise_smooths_noise() {
+        let mut rng = Rng::new(7);
+        let mut image = Canvas::new(16, 16);
+        let mut normals = Canvas::new(16, 16);
+        let depths = Canvas::new(16, 16);
+        for y in 0..16 {
+            for x in 0..16 {
+                let gray = 0.3 + 0.4 * rng.next_f64();
+                image.write_pixel(x, y, Color::new(gray, gray, gray));
+                normals.write_pixel(x, y, Color::new(0.0, 1.0, 0.0));
+            }
+        }
+        let spread = |canvas: &Canvas| {
+            let values: Vec<f64> = canvas.pixels.iter().map(|pixel| pixel.red).collect();
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            let variance = values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>();
+            (mean, variance / values.len() as f64)
+        };
+        let denoised = Denoiser::default().denoise(&image, &normals, &depths);
+        let (mean, variance) = spread(&image);
+        let (denoised_mean, denoised_variance) = spread(&denoised);
+        assert_approx_eq!(mean, denoised_mean, epsilon = 0.02);
+        assert!(denoised_variance < variance / 4.0);
+    }
+
+    #[test]
+    fn denoiser_denoise_keeps_edges() {
+        let mut image = Canvas::new(8, 4);
+        let mut normals = Canvas::new(8, 4);
+        let mut depths = Canvas::new(8, 4);
+        for y in 0..4 {
+            for x in 0..8 {
+                // A wall facing the camera on the left, and nothing on the right
+                if x < 4 {
+                    let gray = if (x + y) % 2 == 0 { 0.4 } else { 0.6 };
+                    image.write_pixel(x, y, Color::new(gray, gray, gray));
+                    normals.write_pixel(x, y, Color::new(0.0, 0.0, -1.0));
+                    depths.write_pixel(x, y, Color::new(2.0, 2.0, 2.0));
+                } else {
+                    image.write_pixel(x, y, Color::new(0.0, 0.0, 1.0));
+                    depths.write_pixel(x, y, Color::new(f64::INFINITY, 0.0, 0.0));
+                }
+            }
+        }
+        let denoiser = Denoiser {
+            normal_sigma: 0.1,
+            ..Denoiser::default()
+        };
+        let denoised = denoiser.denoise(&image, &normals, &depths);
+        for y in 0..4 {
+            assert_approx_eq!(denoised.pixel_at(1, y).red, 0.5, epsilon = 0.05);
+            // No blue bleeds across the edge into the gray wall
+            let wall = denoised.pixel_at(3, y);
+            assert_approx_eq!(wall.blue, wall.red);
+            assert!(denoised
+                .pixel_at(4, y)
+                .is_equal_to(&Color::new(0.0, 0.0, 1.0)));
+        }
+        assert_approx_eq!(denoised.pixel_at(7, 3).red, 0.0);
+    }
+
+    #[test]
+    fn denoiser_denoise_zero_sigma() {
+        let mut image = Canvas::new(4, 4);
+        let normals = Canvas::new(4, 4);
+        let depths = Canvas::new(4, 4);
+        for y in 0..4 {
+            for x in 0..4 {
+                let gray = if (x + y) % 2 == 0 { 0.4 } else { 0.6 };
+                image.write_pixel(x, y, Color::new(gray, gray, gray));
+            }
+        }
+        // Only neighbours of exactly the same color count, so the checkerboard is left as it is
+        let denoiser = Denoiser {
+            color_sigma: 0.0,
+            depth_sigma: -1.0,
+            ..Denoiser::default()
+        };
+        let denoised = denoiser.denoise(&image, &normals, &depths);
+        for (pixel, expected) in denoised.pixels.iter().zip(&image.pixels) {
+            assert!(pixel.is_equal_to(expected), "{pixel}");
+        }
+    }
+
+    #[test]
+    fn denoiser_denoise_many_iterations() {
+        let mut image = Canvas::new(5, 3);
+        image.write_pixel(2, 1, Color::new(1.0, 1.0, 1.0));
+        let (normals, depths) = (Canvas::new(5, 3), Canvas::new(5, 3));
+        // Passes past the third spread their taps beyond the image and change nothing
+        let few = Denoiser::default().denoise(&image, &normals, &depths);
+        let many = Denoiser {
+            iterations: usize::MAX,
+            ..Denoiser::default()
+        }
+        .denoise(&image, &normals, &depths);
+        for (a, b) in few.pixels.iter().zip(&many.pixels) {
+            assert!(a.is_equal_to(b));
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use super::Canvas;
+use crate::color::Color;
+use std::io::{self, Write};
+
+impl Canvas {
+    /// Write the canvas as a Radiance HDR (RGBE) image, which keeps channels above 1.0 instead of clipping
+    /// them so the render can be tone-mapped later. Negative channels are written as 0.
+    ///
+    /// Scanlines are written uncompressed, which every reader of the format accepts.
+    pub fn write_hdr<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "#?RADIANCE")?;
+        writeln!(writer, "FORMAT=32-bit_rle_rgbe")?;
+        writeln!(writer)?;
+        writeln!(writer, "-Y {} +X {}", self.height, self.width)?;
+        for color in &self.pixels {
+            writer.write_all(&to_rgbe(*color))?;
+        }
+        Ok(())
+    }
+
+    /// Convert the canvas to a Radiance HDR image.
+    pub fn to_hdr(&self) -> Vec<u8> {
+        let mut hdr = Vec::new();
+        self.write_hdr(&mut hdr)
+            .expect("writing to a Vec<u8> cannot fail");
+        hdr
+    }
+}
+
+/// Encode a color as RGBE: an 8-bit mantissa per channel sharing the exponent of the brightest channel.
+fn to_rgbe(color: Color) -> [u8; 4] {
+    let channels = [color.red, color.green, color.blue].map(|channel| channel.max(0.0));
+    let brightest = channels.iter().copied().fold(0.0, f64::max);
+    if brightest < 1e-32 {
+        return [0; 4];
+    }
+    // Find the exponent that puts the brightest channel's mantissa in [0.5, 1)
+    let mut exponent = brightest.log2().floor() as i32 + 1;
+    if brightest / 2f64.powi(exponent) >= 1.0 {
+        exponent += 1;
+    } else if brightest / 2f64.powi(exponent) < 0.5 {
+        exponent -= 1;
+    }
+    let scale = 256.0 / 2f64.powi(exponent);
+    let [red, green, blue] = channels.map(|channel| (channel * scale) as u8);
+    [red, green, blue, (exponent + 128) as u8]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canvas_to_hdr() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.write_pixel(0, 0, Color::new(1.0, 0.5, 0.25));
+        canvas.write_pixel(1, 0, Color::new(-1.0, 0.0, 0.0));
+        let hdr = canvas.to_hdr();
+
+        let header = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 1 +X 2\n";
+        assert_eq!(&hdr[..header.len()], header);
+        assert_eq!(&hdr[header.len()..], &[128, 64, 32, 129, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn canvas_to_hdr_keeps_highlights() {
+        let cases = [
+            (Color::new(12.0, 3.0, 0.0), [192, 48, 0, 132]),
+            (Color::new(0.75, 0.75, 0.75), [192, 192, 192, 128]),
+            (Color::new(0.001, 0.0, 0.0), [131, 0, 0, 119]),
+        ];
+        for (color, expected) in cases {
+            assert_eq!(to_rgbe(color), expected, "{color:?}");
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use super::Canvas;
+use crate::color::Color;
+use image::{ImageFormat, ImageResult, Rgb, RgbImage};
+use std::path::Path;
+
+impl Canvas {
+    /// Read a canvas from an image file in any format supported by the `image` crate, such as PNG.
+    /// Channels are scaled from 8 bits to [0, 1] without any gamma conversion; use `decode_srgb` to get
+    /// linear colors from an sRGB image.
+    pub fn read_image<P: AsRef<Path>>(path: P) -> ImageResult<Canvas> {
+        let image = image::open(path)?.into_rgb8();
+        let mut canvas = Canvas::new(image.width() as usize, image.height() as usize);
+        for (x, y, pixel) in image.enumerate_pixels() {
+            let [red, green, blue] = pixel.0.map(|channel| channel as f64 / 255.0);
+            canvas.write_pixel(x as usize, y as usize, Color::new(red, green, blue));
+        }
+        Ok(canvas)
+    }
+
+    /// Write the canvas to a PNG file, clamping channels to [0, 1] and scaling them to 8 bits without any
+    /// gamma conversion, like `write_ppm`; use `encode_srgb` first to store linear colors as sRGB.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> ImageResult<()> {
+        self.to_rgb8().save_with_format(path, ImageFormat::Png)
+    }
+
+    /// Convert the canvas to an 8-bit image.
+    fn to_rgb8(&self) -> RgbImage {
+        RgbImage::from_fn(self.width as u32, self.height as u32, |x, y| {
+            let color = self.pixel_at(x as usize, y as usize);
+            Rgb([color.red, color.green, color.blue].map(scale_channel))
+        })
+    }
+}
+
+/// Scale a color channel from [0, 1] to [0, 255], clamping values outside of the range.
+fn scale_channel(channel: f64) -> u8 {
+    (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canvas_read_image_png() {
+        let path = std::env::temp_dir().join("canvas_read_image_png.png");
+        let mut image = image::RgbImage::new(2, 1);
+        image.put_pixel(0, 0, image::Rgb([255, 0, 51]));
+        image.put_pixel(1, 0, image::Rgb([0, 255, 0]));
+        image.save(&path).unwrap();
+
+        let canvas = Canvas::read_image(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(canvas.width(), 2);
+        assert_eq!(canvas.height(), 1);
+        assert!(canvas
+            .pixel_at(0, 0)
+            .is_equal_to(&Color::new(1.0, 0.0, 0.2)));
+        assert!(canvas
+            .pixel_at(1, 0)
+            .is_equal_to(&Color::new(0.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn canvas_save_png() {
+        let path = std::env::temp_dir().join("canvas_save_png.png");
+        let mut canvas = Canvas::new(3, 2);
+        canvas.write_pixel(0, 0, Color::new(1.5, 0.0, 0.0));
+        canvas.write_pixel(2, 1, Color::new(-0.5, 0.5, 1.0));
+        canvas.save_png(&path).unwrap();
+
+        let image = image::open(&path).unwrap().into_rgb8();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(image.dimensions(), (3, 2));
+        assert_eq!(image.get_pixel(0, 0).0, [255, 0, 0]);
+        assert_eq!(image.get_pixel(1, 0).0, [0, 0, 0]);
+        assert_eq!(image.get_pixel(2, 1).0, [0, 128, 255]);
+    }
+}
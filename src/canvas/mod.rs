@@ -0,0 +1,101 @@
+use crate::color::Color;
+
+pub mod bloom;
+pub mod denoise;
+pub mod hdr;
+#[cfg(feature = "image")]
+pub mod image;
+pub mod post_process;
+pub mod ppm;
+pub mod srgb;
+pub mod tone_map;
+
+pub use post_process::{Pipeline, PostProcess};
+pub use tone_map::ToneMapping;
+
+#[derive(Debug)]
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Canvas {
+    /// Create a canvas with every pixel initialized to black.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![Color::default(); width * height],
+        }
+    }
+
+    /// Get the width of the canvas in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the height of the canvas in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Write a color to the pixel at column `x` and row `y`.
+    /// Panics if the pixel lies outside of the canvas.
+    pub fn write_pixel(&mut self, x: usize, y: usize, color: Color) {
+        let index = self.index_of(x, y);
+        self.pixels[index] = color;
+    }
+
+    /// Read the color of the pixel at column `x` and row `y`.
+    /// Panics if the pixel lies outside of the canvas.
+    pub fn pixel_at(&self, x: usize, y: usize) -> Color {
+        self.pixels[self.index_of(x, y)]
+    }
+
+    /// Compute the index into the pixel storage of the pixel at column `x` and row `y`.
+    fn index_of(&self, x: usize, y: usize) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "pixel ({x}, {y}) is outside of the {}x{} canvas",
+            self.width,
+            self.height
+        );
+        y * self.width + x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canvas_new() {
+        let canvas = Canvas::new(10, 20);
+        assert_eq!(canvas.width(), 10);
+        assert_eq!(canvas.height(), 20);
+        for y in 0..canvas.height() {
+            for x in 0..canvas.width() {
+                assert!(canvas
+                    .pixel_at(x, y)
+                    .is_equal_to(&Color::new(0.0, 0.0, 0.0)));
+            }
+        }
+    }
+
+    #[test]
+    fn canvas_write_pixel() {
+        let mut canvas = Canvas::new(10, 20);
+        let red = Color::new(1.0, 0.0, 0.0);
+        canvas.write_pixel(2, 3, red);
+        assert!(canvas.pixel_at(2, 3).is_equal_to(&red));
+        assert!(canvas.pixel_at(3, 2).is_equal_to(&Color::default()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn canvas_write_pixel_out_of_bounds() {
+        let mut canvas = Canvas::new(10, 20);
+        canvas.write_pixel(10, 0, Color::new(1.0, 0.0, 0.0));
+    }
+}
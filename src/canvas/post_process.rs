@@ -0,0 +1,171 @@
+use super::{Canvas, ToneMapping};
+use crate::color::Color;
+use std::fmt;
+
+/// Effect applied to a whole canvas of linear colors after rendering, such as tone mapping or a vignette.
+///
+/// Closures taking a `&mut Canvas` are post-processes too, so one-off effects need no type of their own.
+pub trait PostProcess: Send + Sync {
+    fn apply(&self, canvas: &mut Canvas);
+}
+
+impl<F> PostProcess for F
+where
+    F: Fn(&mut Canvas) + Send + Sync,
+{
+    fn apply(&self, canvas: &mut Canvas) {
+        self(canvas)
+    }
+}
+
+impl PostProcess for ToneMapping {
+    fn apply(&self, canvas: &mut Canvas) {
+        canvas.tone_map(*self);
+    }
+}
+
+/// Post-process encoding linear colors with the sRGB transfer function, like `Canvas::encode_srgb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Srgb;
+
+impl PostProcess for Srgb {
+    fn apply(&self, canvas: &mut Canvas) {
+        canvas.encode_srgb();
+    }
+}
+
+/// Post-process applying a plain power-law gamma, raising each channel to `1 / gamma`.
+/// Negative channels become 0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gamma(pub f64);
+
+impl PostProcess for Gamma {
+    fn apply(&self, canvas: &mut Canvas) {
+        let exponent = 1.0 / self.0;
+        let map = |channel: f64| channel.max(0.0).powf(exponent);
+        for pixel in &mut canvas.pixels {
+            *pixel = Color::new(map(pixel.red), map(pixel.green), map(pixel.blue));
+        }
+    }
+}
+
+/// Post-process darkening the canvas toward its edges.
+/// Pixels keep `1 - strength * d²` of their color, where `d` is their distance from the center of the
+/// canvas, scaled so the corners are at a distance of 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vignette {
+    pub strength: f64,
+}
+
+impl PostProcess for Vignette {
+    fn apply(&self, canvas: &mut Canvas) {
+        let half_width = canvas.width as f64 / 2.0;
+        let half_height = canvas.height as f64 / 2.0;
+        let corner_squared = half_width * half_width + half_height * half_height;
+        for y in 0..canvas.height {
+            for x in 0..canvas.width {
+                // Measure from the center of the pixel
+                let dx = x as f64 + 0.5 - half_width;
+                let dy = y as f64 + 0.5 - half_height;
+                let falloff = 1.0 - self.strength * (dx * dx + dy * dy) / corner_squared;
+                let color = canvas.pixel_at(x, y) * falloff.max(0.0);
+                canvas.write_pixel(x, y, color);
+            }
+        }
+    }
+}
+
+/// Sequence of post-processes applied one after another, itself a post-process.
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<Box<dyn PostProcess>>,
+}
+
+impl Pipeline {
+    /// Create an empty pipeline, which leaves canvases unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a post-process at the end of the pipeline.
+    pub fn push<P: PostProcess + 'static>(&mut self, stage: P) {
+        self.stages.push(Box::new(stage));
+    }
+
+    /// Get the number of post-processes in the pipeline.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Check whether the pipeline has no post-processes.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+}
+
+impl PostProcess for Pipeline {
+    fn apply(&self, canvas: &mut Canvas) {
+        for stage in &self.stages {
+            stage.apply(canvas);
+        }
+    }
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("stages", &self.stages.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn gamma_apply() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.write_pixel(0, 0, Color::new(0.25, 1.0, -1.0));
+        Gamma(2.0).apply(&mut canvas);
+        assert!(canvas
+            .pixel_at(0, 0)
+            .is_equal_to(&Color::new(0.5, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn vignette_apply() {
+        let mut canvas = Canvas::new(4, 2);
+        for y in 0..2 {
+            for x in 0..4 {
+                canvas.write_pixel(x, y, Color::new(1.0, 1.0, 1.0));
+            }
+        }
+        Vignette { strength: 0.5 }.apply(&mut canvas);
+        // The corner pixel's center is at (1.5, 0.5) from the center, with the corner at (2, 1)
+        let corner = 1.0 - 0.5 * 2.5 / 5.0;
+        let middle = 1.0 - 0.5 * 0.5 / 5.0;
+        assert_approx_eq!(canvas.pixel_at(0, 0).red, corner);
+        assert_approx_eq!(canvas.pixel_at(3, 1).green, corner);
+        assert_approx_eq!(canvas.pixel_at(1, 0).blue, middle);
+        assert_approx_eq!(canvas.pixel_at(2, 1).red, middle);
+    }
+
+    #[test]
+    fn pipeline_apply() {
+        let mut pipeline = Pipeline::new();
+        assert!(pipeline.is_empty());
+        pipeline.push(|canvas: &mut Canvas| canvas.write_pixel(0, 0, Color::new(3.0, 1.0, 0.0)));
+        pipeline.push(ToneMapping::Reinhard);
+        pipeline.push(Gamma(0.5));
+        assert_eq!(pipeline.len(), 3);
+
+        let mut canvas = Canvas::new(1, 1);
+        pipeline.apply(&mut canvas);
+        // The stages run in order: (3, 1, 0) maps to (0.75, 0.5, 0), then squares
+        assert!(canvas
+            .pixel_at(0, 0)
+            .is_equal_to(&Color::new(0.5625, 0.25, 0.0)));
+    }
+}
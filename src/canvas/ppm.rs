@@ -0,0 +1,423 @@
+use super::Canvas;
+use crate::color::Color;
+use std::io::{self, Read, Write};
+
+/// Maximum value of a color channel in the PPM files produced by the canvas.
+const PPM_MAX_COLOR_VALUE: u8 = 255;
+
+/// Largest number of pixels in a PPM image the canvas reads, which keeps a corrupt or hostile header
+/// from requesting an enormous allocation.
+const PPM_MAX_PIXELS: usize = 1 << 26;
+
+/// Maximum length of a line of pixel data in a plain-text PPM file.
+const PPM_MAX_LINE_LENGTH: usize = 70;
+
+/// Variant of the PPM image format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PpmFormat {
+    /// Plain-text PPM where each channel is written as a decimal number.
+    P3,
+    /// Binary PPM where each channel is written as a single byte.
+    P6,
+}
+
+impl PpmFormat {
+    /// Get the magic number that identifies the format in the PPM header.
+    fn magic_number(&self) -> &'static str {
+        match self {
+            PpmFormat::P3 => "P3",
+            PpmFormat::P6 => "P6",
+        }
+    }
+
+    /// Find the format identified by a magic number.
+    fn from_magic_number(magic_number: &[u8]) -> Option<Self> {
+        match magic_number {
+            b"P3" => Some(PpmFormat::P3),
+            b"P6" => Some(PpmFormat::P6),
+            _ => None,
+        }
+    }
+}
+
+impl Canvas {
+    /// Write the canvas as a PPM image of the given format.
+    pub fn write_ppm<W: Write>(&self, writer: &mut W, format: PpmFormat) -> io::Result<()> {
+        writeln!(writer, "{}", format.magic_number())?;
+        writeln!(writer, "{} {}", self.width, self.height)?;
+        writeln!(writer, "{}", PPM_MAX_COLOR_VALUE)?;
+        match format {
+            PpmFormat::P3 => self.write_ppm_plain_pixels(writer),
+            PpmFormat::P6 => self.write_ppm_binary_pixels(writer),
+        }
+    }
+
+    /// Convert the canvas to a PPM image of the given format.
+    pub fn to_ppm(&self, format: PpmFormat) -> Vec<u8> {
+        let mut ppm = Vec::new();
+        self.write_ppm(&mut ppm, format)
+            .expect("writing to a Vec<u8> cannot fail");
+        ppm
+    }
+
+    /// Convert the canvas to a binary (P6) PPM image.
+    pub fn to_ppm_binary(&self) -> Vec<u8> {
+        self.to_ppm(PpmFormat::P6)
+    }
+
+    /// Write the pixel data of a plain-text PPM, starting each row on a new line and
+    /// wrapping lines that would exceed the maximum line length.
+    fn write_ppm_plain_pixels<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for row in self.pixels.chunks(self.width.max(1)) {
+            let mut line = String::new();
+            for channel in row.iter().flat_map(|c| [c.red, c.green, c.blue]) {
+                let value = scale_channel(channel).to_string();
+                if !line.is_empty() && line.len() + 1 + value.len() > PPM_MAX_LINE_LENGTH {
+                    writeln!(writer, "{}", line)?;
+                    line.clear();
+                }
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(&value);
+            }
+            writeln!(writer, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    /// Write the pixel data of a binary PPM, one byte per channel.
+    fn write_ppm_binary_pixels<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let bytes: Vec<u8> = self
+            .pixels
+            .iter()
+            .flat_map(|c| [c.red, c.green, c.blue])
+            .map(scale_channel)
+            .collect();
+        writer.write_all(&bytes)
+    }
+}
+
+impl Canvas {
+    /// Read a canvas from a plain-text (P3) or binary (P6) PPM image.
+    ///
+    /// Channels are scaled from the image's maximum color value to [0, 1]. Returns an error of kind
+    /// `InvalidData` if the image is malformed or has more than 2^26 pixels.
+    pub fn read_ppm<R: Read>(reader: &mut R) -> io::Result<Canvas> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let mut parser = PpmParser {
+            bytes: &bytes,
+            position: 0,
+        };
+
+        let format = PpmFormat::from_magic_number(parser.next_token()?)
+            .ok_or_else(|| invalid_ppm("unsupported magic number"))?;
+        let width = parser.next_number()?;
+        let height = parser.next_number()?;
+        let max_color_value = parser.next_number()?;
+        if max_color_value == 0 || max_color_value > u16::MAX as usize {
+            return Err(invalid_ppm("maximum color value out of range"));
+        }
+
+        let pixels = width
+            .checked_mul(height)
+            .filter(|&pixels| pixels <= PPM_MAX_PIXELS)
+            .ok_or_else(|| invalid_ppm("image too large"))?;
+
+        if format == PpmFormat::P6 {
+            parser.skip_header_end();
+        }
+        // Every channel takes at least a byte, so the data can be checked to be long enough up front
+        if pixels * 3 > bytes.len().saturating_sub(parser.position) {
+            return Err(invalid_ppm("unexpected end of data"));
+        }
+
+        let mut canvas = Canvas::new(width, height);
+        let max_color_value = max_color_value as f64;
+        for pixel in canvas.pixels.iter_mut() {
+            let mut channels = [0.0; 3];
+            for channel in channels.iter_mut() {
+                let value = match format {
+                    PpmFormat::P3 => parser.next_number()?,
+                    PpmFormat::P6 => parser.next_binary_sample(max_color_value > 255.0)?,
+                };
+                *channel = value as f64 / max_color_value;
+            }
+            *pixel = Color::new(channels[0], channels[1], channels[2]);
+        }
+        Ok(canvas)
+    }
+}
+
+/// Cursor over the bytes of a PPM image.
+struct PpmParser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> PpmParser<'a> {
+    /// Read the next whitespace-separated token, skipping comments that run from `#` to the end of the line.
+    fn next_token(&mut self) -> io::Result<&'a [u8]> {
+        loop {
+            match self.bytes.get(self.position) {
+                Some(byte) if byte.is_ascii_whitespace() => self.position += 1,
+                Some(b'#') => {
+                    while self
+                        .bytes
+                        .get(self.position)
+                        .is_some_and(|&byte| byte != b'\n')
+                    {
+                        self.position += 1;
+                    }
+                }
+                Some(_) => break,
+                None => return Err(invalid_ppm("unexpected end of data")),
+            }
+        }
+        let start = self.position;
+        while self
+            .bytes
+            .get(self.position)
+            .is_some_and(|byte| !byte.is_ascii_whitespace())
+        {
+            self.position += 1;
+        }
+        Ok(&self.bytes[start..self.position])
+    }
+
+    /// Read the next token as a decimal number.
+    fn next_number(&mut self) -> io::Result<usize> {
+        std::str::from_utf8(self.next_token()?)
+            .ok()
+            .and_then(|token| token.parse().ok())
+            .ok_or_else(|| invalid_ppm("expected a number"))
+    }
+
+    /// Skip the single whitespace character that ends the header of a binary PPM.
+    /// Only one character is skipped, since the pixel bytes that follow may look like whitespace.
+    fn skip_header_end(&mut self) {
+        self.position += 1;
+    }
+
+    /// Read the next channel of binary pixel data, which is two big-endian bytes if `wide` and one byte otherwise.
+    fn next_binary_sample(&mut self, wide: bool) -> io::Result<usize> {
+        let length = if wide { 2 } else { 1 };
+        let sample = self
+            .bytes
+            .get(self.position..self.position + length)
+            .ok_or_else(|| invalid_ppm("unexpected end of data"))?;
+        self.position += length;
+        Ok(sample
+            .iter()
+            .fold(0, |value, &byte| value << 8 | byte as usize))
+    }
+}
+
+/// Create the error returned for malformed PPM images.
+fn invalid_ppm(message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid PPM: {message}"),
+    )
+}
+
+/// Scale a color channel from [0, 1] to [0, `PPM_MAX_COLOR_VALUE`], clamping values outside of the range.
+fn scale_channel(channel: f64) -> u8 {
+    (channel.clamp(0.0, 1.0) * PPM_MAX_COLOR_VALUE as f64).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::color::Color;
+
+    #[test]
+    fn canvas_to_ppm_header() {
+        let canvas = Canvas::new(5, 3);
+        let ppm = String::from_utf8(canvas.to_ppm(PpmFormat::P3)).unwrap();
+        let header: Vec<&str> = ppm.lines().take(3).collect();
+        assert_eq!(header, vec!["P3", "5 3", "255"]);
+    }
+
+    #[test]
+    fn canvas_to_ppm_pixel_data() {
+        let mut canvas = Canvas::new(5, 3);
+        canvas.write_pixel(0, 0, Color::new(1.5, 0.0, 0.0));
+        canvas.write_pixel(2, 1, Color::new(0.0, 0.5, 0.0));
+        canvas.write_pixel(4, 2, Color::new(-0.5, 0.0, 1.0));
+        let ppm = String::from_utf8(canvas.to_ppm(PpmFormat::P3)).unwrap();
+        let pixel_data: Vec<&str> = ppm.lines().skip(3).collect();
+        assert_eq!(
+            pixel_data,
+            vec![
+                "255 0 0 0 0 0 0 0 0 0 0 0 0 0 0",
+                "0 0 0 0 0 0 0 128 0 0 0 0 0 0 0",
+                "0 0 0 0 0 0 0 0 0 0 0 0 0 0 255",
+            ]
+        );
+    }
+
+    #[test]
+    fn canvas_to_ppm_splits_long_lines() {
+        let mut canvas = Canvas::new(10, 2);
+        for y in 0..2 {
+            for x in 0..10 {
+                canvas.write_pixel(x, y, Color::new(1.0, 0.8, 0.6));
+            }
+        }
+        let ppm = String::from_utf8(canvas.to_ppm(PpmFormat::P3)).unwrap();
+        let pixel_data: Vec<&str> = ppm.lines().skip(3).collect();
+        assert_eq!(
+            pixel_data,
+            vec![
+                "255 204 153 255 204 153 255 204 153 255 204 153 255 204 153 255 204",
+                "153 255 204 153 255 204 153 255 204 153 255 204 153",
+                "255 204 153 255 204 153 255 204 153 255 204 153 255 204 153 255 204",
+                "153 255 204 153 255 204 153 255 204 153 255 204 153",
+            ]
+        );
+    }
+
+    #[test]
+    fn canvas_to_ppm_ends_with_newline() {
+        let canvas = Canvas::new(5, 3);
+        let ppm = canvas.to_ppm(PpmFormat::P3);
+        assert_eq!(ppm.last(), Some(&b'\n'));
+    }
+
+    #[test]
+    fn canvas_to_ppm_binary() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.write_pixel(0, 0, Color::new(1.5, 0.5, 0.0));
+        canvas.write_pixel(1, 0, Color::new(-0.5, 0.0, 1.0));
+        let ppm = canvas.to_ppm_binary();
+        assert_eq!(ppm, canvas.to_ppm(PpmFormat::P6));
+
+        let header = b"P6\n2 1\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(&ppm[header.len()..], &[255, 128, 0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn canvas_read_ppm_wrong_magic_number() {
+        let ppm = b"P32\n1 1\n255\n0 0 0\n";
+        let error = Canvas::read_ppm(&mut &ppm[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn canvas_read_ppm_size() {
+        let ppm = b"P3\n10 2\n255\n"
+            .iter()
+            .chain(b" 0".repeat(60).iter())
+            .copied()
+            .collect::<Vec<u8>>();
+        let canvas = Canvas::read_ppm(&mut &ppm[..]).unwrap();
+        assert_eq!(canvas.width(), 10);
+        assert_eq!(canvas.height(), 2);
+    }
+
+    #[test]
+    fn canvas_read_ppm_pixel_data() {
+        let ppm = b"P3\n4 3\n255\n255 127 0  0 127 255  127 255 0  255 255 255\n0 0 0  255 0 0  0 255 0  0 0 255\n255 255 0  0 255 255  255 0 255  127 127 127\n";
+        let canvas = Canvas::read_ppm(&mut &ppm[..]).unwrap();
+        let cases = [
+            ((0, 0), Color::new(1.0, 0.49804, 0.0)),
+            ((1, 0), Color::new(0.0, 0.49804, 1.0)),
+            ((2, 0), Color::new(0.49804, 1.0, 0.0)),
+            ((3, 0), Color::new(1.0, 1.0, 1.0)),
+            ((0, 1), Color::new(0.0, 0.0, 0.0)),
+            ((1, 1), Color::new(1.0, 0.0, 0.0)),
+            ((2, 1), Color::new(0.0, 1.0, 0.0)),
+            ((3, 1), Color::new(0.0, 0.0, 1.0)),
+            ((0, 2), Color::new(1.0, 1.0, 0.0)),
+            ((1, 2), Color::new(0.0, 1.0, 1.0)),
+            ((2, 2), Color::new(1.0, 0.0, 1.0)),
+            ((3, 2), Color::new(0.49804, 0.49804, 0.49804)),
+        ];
+        for ((x, y), expected) in cases {
+            let color = canvas.pixel_at(x, y);
+            // The book's values are rounded to five decimal places.
+            assert_approx_eq!(color, expected, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn canvas_read_ppm_comments() {
+        let ppm = b"P3\n# this is a comment\n2 1\n# this, too\n255\n# another comment\n255 255 255\n# oh, no, comments in the pixel data!\n255 0 255\n";
+        let canvas = Canvas::read_ppm(&mut &ppm[..]).unwrap();
+        assert!(canvas
+            .pixel_at(0, 0)
+            .is_equal_to(&Color::new(1.0, 1.0, 1.0)));
+        assert!(canvas
+            .pixel_at(1, 0)
+            .is_equal_to(&Color::new(1.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn canvas_read_ppm_pixel_spanning_lines() {
+        let ppm = b"P3\n1 1\n255\n51\n153\n\n204\n";
+        let canvas = Canvas::read_ppm(&mut &ppm[..]).unwrap();
+        assert!(canvas
+            .pixel_at(0, 0)
+            .is_equal_to(&Color::new(0.2, 0.6, 0.8)));
+    }
+
+    #[test]
+    fn canvas_read_ppm_scale() {
+        let ppm = b"P3\n2 2\n100\n100 100 100  50 50 50\n75 50 25  0 0 0\n";
+        let canvas = Canvas::read_ppm(&mut &ppm[..]).unwrap();
+        assert!(canvas
+            .pixel_at(0, 1)
+            .is_equal_to(&Color::new(0.75, 0.5, 0.25)));
+    }
+
+    #[test]
+    fn canvas_read_ppm_truncated() {
+        let ppm = b"P3\n2 1\n255\n255 255 255\n";
+        let error = Canvas::read_ppm(&mut &ppm[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn canvas_read_ppm_too_large() {
+        let headers: [&[u8]; 3] = [
+            b"P6
+18446744073709551615 2
+255
+",
+            b"P6
+100000 100000
+255
+",
+            b"P6
+1000 1000
+255
+\0\0\0",
+        ];
+        for ppm in headers {
+            let error = Canvas::read_ppm(&mut &ppm[..]).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn canvas_read_ppm_binary_round_trip() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.write_pixel(0, 0, Color::new(1.0, 0.0, 0.0));
+        // A channel of 10 is a newline byte, which must be read as pixel data
+        canvas.write_pixel(1, 0, Color::new(10.0 / 255.0, 32.0 / 255.0, 1.0));
+        canvas.write_pixel(2, 1, Color::new(0.2, 0.4, 0.6));
+        let ppm = canvas.to_ppm_binary();
+        let read = Canvas::read_ppm(&mut &ppm[..]).unwrap();
+        assert_eq!(read.width(), 3);
+        assert_eq!(read.height(), 2);
+        for y in 0..2 {
+            for x in 0..3 {
+                assert!(read.pixel_at(x, y).is_equal_to(&canvas.pixel_at(x, y)));
+            }
+        }
+    }
+}
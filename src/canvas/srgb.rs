@@ -0,0 +1,85 @@
+use super::Canvas;
+use crate::color::Color;
+
+/// Encode a linear color channel with the sRGB transfer function, which spends more of an 8-bit image's
+/// values on dark shades, the way displays expect them.
+/// Channels outside of [0, 1] are clamped.
+pub fn linear_to_srgb(channel: f64) -> f64 {
+    let channel = channel.clamp(0.0, 1.0);
+    if channel <= 0.0031308 {
+        12.92 * channel
+    } else {
+        1.055 * channel.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Decode an sRGB color channel back to linear light, inverting `linear_to_srgb`.
+/// Channels outside of [0, 1] are clamped.
+pub fn srgb_to_linear(channel: f64) -> f64 {
+    let channel = channel.clamp(0.0, 1.0);
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+impl Canvas {
+    /// Encode every pixel of a canvas of linear colors with the sRGB transfer function, before writing it
+    /// to an 8-bit image such as a PNG or a PPM.
+    pub fn encode_srgb(&mut self) {
+        self.map_channels(linear_to_srgb);
+    }
+
+    /// Decode every pixel of a canvas read from an sRGB image, such as a texture, to linear colors.
+    pub fn decode_srgb(&mut self) {
+        self.map_channels(srgb_to_linear);
+    }
+
+    fn map_channels(&mut self, map: fn(f64) -> f64) {
+        for pixel in &mut self.pixels {
+            *pixel = Color::new(map(pixel.red), map(pixel.green), map(pixel.blue));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn linear_to_srgb_channels() {
+        let cases = [
+            (-1.0, 0.0),
+            (0.0, 0.0),
+            (0.002, 0.02584),
+            (0.5, 0.735357),
+            (1.0, 1.0),
+            (2.0, 1.0),
+        ];
+        for (linear, srgb) in cases {
+            assert_approx_eq!(linear_to_srgb(linear), srgb, "{linear}");
+            if (0.0..=1.0).contains(&linear) {
+                assert_approx_eq!(srgb_to_linear(srgb), linear, "{srgb}");
+            }
+        }
+    }
+
+    #[test]
+    fn canvas_encode_and_decode_srgb() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.write_pixel(1, 0, Color::new(0.5, 1.0, 0.002));
+        canvas.encode_srgb();
+        assert!(canvas
+            .pixel_at(0, 0)
+            .is_equal_to(&Color::new(0.0, 0.0, 0.0)));
+        assert!(canvas
+            .pixel_at(1, 0)
+            .is_equal_to(&Color::new(0.735357, 1.0, 0.02584)));
+        canvas.decode_srgb();
+        assert!(canvas
+            .pixel_at(1, 0)
+            .is_equal_to(&Color::new(0.5, 1.0, 0.002)));
+    }
+}
@@ -0,0 +1,75 @@
+use super::Canvas;
+use crate::color::Color;
+
+/// Operator compressing the unbounded colors of a render into [0, 1] for display, so bright highlights
+/// roll off smoothly instead of clipping to flat white.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToneMapping {
+    /// Reinhard's operator, `c / (1 + c)`, which keeps dark colors and halves a channel of 1.
+    Reinhard,
+    /// Narkowicz's fit of the ACES filmic curve, with more contrast and saturation than Reinhard.
+    Aces,
+}
+
+impl ToneMapping {
+    /// Map a linear color to [0, 1], channel by channel.
+    pub fn map_color(self, color: Color) -> Color {
+        let map = |channel: f64| {
+            let channel = channel.max(0.0);
+            match self {
+                Self::Reinhard => channel / (1.0 + channel),
+                Self::Aces => {
+                    let mapped = channel * (2.51 * channel + 0.03)
+                        / (channel * (2.43 * channel + 0.59) + 0.14);
+                    mapped.clamp(0.0, 1.0)
+                }
+            }
+        };
+        Color::new(map(color.red), map(color.green), map(color.blue))
+    }
+}
+
+impl Canvas {
+    /// Tone map every pixel of the canvas.
+    pub fn tone_map(&mut self, mapping: ToneMapping) {
+        for pixel in &mut self.pixels {
+            *pixel = mapping.map_color(*pixel);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn tone_mapping_map_color() {
+        let cases = [
+            (ToneMapping::Reinhard, 0.0, 0.0),
+            (ToneMapping::Reinhard, 1.0, 0.5),
+            (ToneMapping::Reinhard, 3.0, 0.75),
+            (ToneMapping::Aces, 0.0, 0.0),
+            (ToneMapping::Aces, 1.0, 2.54 / 3.16),
+            (ToneMapping::Aces, 100.0, 1.0),
+        ];
+        for (mapping, channel, expected) in cases {
+            let mapped = mapping.map_color(Color::new(channel, channel, -1.0));
+            assert_approx_eq!(mapped.red, expected, "{mapping:?} {channel}");
+            assert_approx_eq!(mapped.blue, 0.0);
+        }
+    }
+
+    #[test]
+    fn canvas_tone_map() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.write_pixel(1, 0, Color::new(1.0, 3.0, 0.0));
+        canvas.tone_map(ToneMapping::Reinhard);
+        assert!(canvas
+            .pixel_at(0, 0)
+            .is_equal_to(&Color::new(0.0, 0.0, 0.0)));
+        assert!(canvas
+            .pixel_at(1, 0)
+            .is_equal_to(&Color::new(0.5, 0.75, 0.0)));
+    }
+}
@@ -0,0 +1,59 @@
+use crate::math::{format_number, ApproxEq};
+use std::fmt;
+
+pub mod ops;
+
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    pub red: f64,
+    pub green: f64,
+    pub blue: f64,
+}
+
+impl Color {
+    /// Create a color.
+    pub const fn new(red: f64, green: f64, blue: f64) -> Self {
+        Self { red, green, blue }
+    }
+
+    /// Test if this color is equal to another.
+    pub fn is_equal_to(&self, other: &Color) -> bool {
+        self.approx_eq(other)
+    }
+
+    /// Compute the Hadamard (component-wise) product of this color and another.
+    pub fn hadamard_product(&self, other: &Color) -> Color {
+        Color::new(
+            self.red * other.red,
+            self.green * other.green,
+            self.blue * other.blue,
+        )
+    }
+}
+
+impl ApproxEq for Color {
+    fn approx_eq_with(&self, other: &Color, epsilon: f64) -> bool {
+        self.red.approx_eq_with(&other.red, epsilon)
+            && self.green.approx_eq_with(&other.green, epsilon)
+            && self.blue.approx_eq_with(&other.blue, epsilon)
+    }
+}
+
+impl fmt::Display for Color {
+    /// Show the color as `color(red, green, blue)`, using the formatter's precision if one is given.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision();
+        let [red, green, blue] =
+            [self.red, self.green, self.blue].map(|c| format_number(c, precision));
+        write!(f, "color({red}, {green}, {blue})")
+    }
+}
+
+/// Create a color from three numbers, which may be integer literals, such as `color!(1, 0.5, 0)`.
+#[macro_export]
+macro_rules! color {
+    ($red:expr, $green:expr, $blue:expr $(,)?) => {
+        $crate::color::Color::new($red as f64, $green as f64, $blue as f64)
+    };
+}
@@ -0,0 +1,158 @@
+use super::Color;
+use std::ops::{Add, Mul, Sub};
+
+//
+// Implement the `Add` trait for a color.
+//
+
+impl Add for Color {
+    type Output = Color;
+
+    /// Add two colors channel by channel, returning a new color.
+    fn add(self, rhs: Color) -> Color {
+        Color {
+            red: self.red + rhs.red,
+            green: self.green + rhs.green,
+            blue: self.blue + rhs.blue,
+        }
+    }
+}
+
+//
+// Implement the `Sub` trait for a color.
+//
+
+impl Sub for Color {
+    type Output = Color;
+
+    /// Subtract two colors channel by channel, returning a new color.
+    fn sub(self, rhs: Color) -> Color {
+        Color {
+            red: self.red - rhs.red,
+            green: self.green - rhs.green,
+            blue: self.blue - rhs.blue,
+        }
+    }
+}
+
+//
+// Implement the `Mul` trait for a color for it to be multiplied by an f64.
+//
+
+impl Mul<f64> for Color {
+    type Output = Color;
+
+    /// Multiply each channel of a color by an f64, returning a new color.
+    fn mul(self, rhs: f64) -> Color {
+        Color {
+            red: self.red * rhs,
+            green: self.green * rhs,
+            blue: self.blue * rhs,
+        }
+    }
+}
+
+//
+// Implement the `Mul` trait for a color for it to be multiplied by another color.
+//
+
+impl Mul for Color {
+    type Output = Color;
+
+    /// Multiply two colors using the Hadamard product, returning a new color.
+    fn mul(self, rhs: Color) -> Color {
+        self.hadamard_product(&rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::ApproxEq;
+    use crate::EPSILON;
+    use crate::{assert_approx_eq, color};
+
+    #[test]
+    fn color_new() {
+        let color = Color::new(-0.5, 0.4, 1.7);
+        assert_approx_eq!(color.red, -0.5);
+        assert_approx_eq!(color.green, 0.4);
+        assert_approx_eq!(color.blue, 1.7);
+    }
+
+    #[test]
+    fn color_is_equal_to() {
+        let color = Color::new(0.9, 0.6, 0.75);
+        assert!(color.is_equal_to(&Color::new(0.9, 0.6, 0.75)));
+
+        let not_quite_different = Color::new(0.9 + 1e-7, 0.6, 0.75);
+        assert!(color.is_equal_to(&not_quite_different));
+
+        let barely_different = Color::new(0.9, 0.6 + EPSILON, 0.75);
+        assert!(!color.is_equal_to(&barely_different));
+    }
+
+    #[test]
+    fn color_approx_eq_with() {
+        let color = Color::new(0.9, 0.6, 0.75);
+        let nearby = Color::new(0.9, 0.61, 0.75);
+        assert!(!color.approx_eq(&nearby));
+        assert!(color.approx_eq_with(&nearby, 0.1));
+        assert!(!color.approx_eq_with(&nearby, 0.001));
+    }
+
+    #[test]
+    fn color_add() {
+        let color1 = Color::new(0.9, 0.6, 0.75);
+        let color2 = Color::new(0.7, 0.1, 0.25);
+        assert!((color1 + color2).is_equal_to(&Color::new(1.6, 0.7, 1.0)));
+    }
+
+    #[test]
+    fn color_sub() {
+        let color1 = Color::new(0.9, 0.6, 0.75);
+        let color2 = Color::new(0.7, 0.1, 0.25);
+        assert!((color1 - color2).is_equal_to(&Color::new(0.2, 0.5, 0.5)));
+    }
+
+    #[test]
+    fn color_mul() {
+        let color = Color::new(0.2, 0.3, 0.4);
+        assert!((color * 2.0).is_equal_to(&Color::new(0.4, 0.6, 0.8)));
+    }
+
+    #[test]
+    fn color_hadamard_product() {
+        let color1 = Color::new(1.0, 0.2, 0.4);
+        let color2 = Color::new(0.9, 1.0, 0.1);
+        assert!(color1
+            .hadamard_product(&color2)
+            .is_equal_to(&Color::new(0.9, 0.2, 0.04)));
+        assert!((color1 * color2).is_equal_to(&Color::new(0.9, 0.2, 0.04)));
+    }
+
+    #[test]
+    fn color_display() {
+        let color = Color::new(0.9, 0.6, 1.0);
+        assert_eq!(color.to_string(), "color(0.9, 0.6, 1.0)");
+        assert_eq!(format!("{color:.1}"), "color(0.9, 0.6, 1.0)");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn color_serde() {
+        let color = Color::new(0.9, 0.6, 0.75);
+        let json = serde_json::to_string(&color).unwrap();
+        assert_eq!(json, r#"{"red":0.9,"green":0.6,"blue":0.75}"#);
+        assert!(serde_json::from_str::<Color>(&json)
+            .unwrap()
+            .is_equal_to(&color));
+    }
+
+    #[test]
+    fn color_macro() {
+        assert!(color!(1, 0.5, 0).is_equal_to(&Color::new(1.0, 0.5, 0.0)));
+        let red = 0.25;
+        assert!(color!(red, red * 2.0, 1).is_equal_to(&Color::new(0.25, 0.5, 1.0)));
+    }
+}
@@ -0,0 +1,529 @@
+use crate::camera::{Camera, Tile};
+use crate::canvas::Canvas;
+use crate::color::Color;
+use crate::scene::{Scene, SceneError};
+use crate::world::World;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+/// Status byte a worker sends before a successful reply.
+const OK: u8 = 0;
+/// Status byte a worker sends before an error message, after which it closes the connection.
+const FAILED: u8 = 1;
+
+/// Largest number of pixels in an image a worker renders, which keeps a corrupt or hostile job from
+/// requesting an enormous allocation.
+const MAX_JOB_PIXELS: usize = 1 << 26;
+/// Largest recursion depth a worker follows, since each reflection or refraction takes stack space.
+const MAX_JOB_DEPTH: usize = 64;
+
+/// Scene to render across workers, with the size, exposure and seed of the image to render from its camera,
+/// and the recursion depth of its world.
+///
+/// The scene travels to the workers as its YAML source, so it must not refer to files, such as meshes or
+/// textures, that the workers cannot read.
+#[derive(Debug, Clone)]
+pub struct RenderJob {
+    pub source: String,
+    pub width: usize,
+    pub height: usize,
+    pub exposure: f64,
+    pub seed: u64,
+    /// Number of reflections and refractions followed, unless a material overrides it.
+    pub max_depth: usize,
+}
+
+impl RenderJob {
+    /// Create a job rendering a scene at the size, exposure and seed of its own camera, and the recursion
+    /// depth of its own world.
+    pub fn new(source: String) -> Result<Self, SceneError> {
+        let Scene { camera, world } = Scene::from_yaml(&source)?;
+        Ok(Self {
+            width: camera.hsize(),
+            height: camera.vsize(),
+            exposure: camera.exposure(),
+            seed: camera.seed(),
+            max_depth: world.max_depth,
+            source,
+        })
+    }
+}
+
+/// Error raised while rendering across workers.
+#[derive(Debug)]
+pub enum DistributedError {
+    Io(io::Error),
+    /// A worker refused a job, such as one whose scene it cannot parse.
+    Worker(String),
+    /// No workers were given, or every worker failed before the image was complete.
+    NoWorkers,
+}
+
+impl fmt::Display for DistributedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Worker(message) => write!(f, "worker: {message}"),
+            Self::NoWorkers => write!(f, "no worker is available to render the image"),
+        }
+    }
+}
+
+impl Error for DistributedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Worker(_) | Self::NoWorkers => None,
+        }
+    }
+}
+
+impl From<io::Error> for DistributedError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Serve render jobs from coordinators connecting to `listener`, forever, handling each connection on
+/// its own thread. Connections that fail are dropped without affecting the others.
+pub fn serve(listener: TcpListener) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        thread::spawn(move || handle_connection(stream));
+    }
+    Ok(())
+}
+
+/// Serve a single coordinator's connection until it closes it.
+///
+/// The coordinator first sends a `RenderJob`, which the worker parses once, then asks for tiles of the
+/// image one at a time, and the worker replies to each with its pixels. Every number is sent little-endian,
+/// counts and positions as 64-bit integers and colors as three 64-bit floats per pixel.
+pub fn handle_connection(stream: TcpStream) -> Result<(), DistributedError> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = BufWriter::new(stream);
+    let job = read_job(&mut reader)?;
+    let (camera, world) = match prepare(&job) {
+        Ok(prepared) => prepared,
+        Err(message) => return Err(refuse(&mut writer, message)),
+    };
+    writer.write_all(&[OK])?;
+    writer.flush()?;
+
+    loop {
+        let x = match read_u64(&mut reader) {
+            Ok(x) => x as usize,
+            // The coordinator closes the connection once it has no more tiles to ask for
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(error) => return Err(error.into()),
+        };
+        let y = read_u64(&mut reader)? as usize;
+        let width = read_u64(&mut reader)? as usize;
+        let height = read_u64(&mut reader)? as usize;
+        // The tile comes off the network, so its end may not even be representable
+        let fits = |start: usize, size: usize, limit: usize| {
+            start.checked_add(size).is_some_and(|end| end <= limit)
+        };
+        if !fits(x, width, camera.hsize()) || !fits(y, height, camera.vsize()) {
+            return Err(refuse(
+                &mut writer,
+                "the tile does not fit in the image".into(),
+            ));
+        }
+        let tile = camera.render_tile(&world, x, y, width, height);
+        writer.write_all(&[OK])?;
+        for row in 0..height {
+            for column in 0..width {
+                let color = tile.pixels().pixel_at(column, row);
+                for channel in [color.red, color.green, color.blue] {
+                    writer.write_all(&channel.to_le_bytes())?;
+                }
+            }
+        }
+        writer.flush()?;
+    }
+}
+
+/// Parse a job's scene and size its camera, or describe why the job cannot be rendered.
+fn prepare(job: &RenderJob) -> Result<(Camera, World), String> {
+    if job.width == 0 || job.height == 0 {
+        return Err("the image must be at least one pixel wide and high".into());
+    }
+    // The job comes off the network, so it is checked before anything is sized from it
+    let pixels = job.width.checked_mul(job.height);
+    if pixels.is_none_or(|pixels| pixels > MAX_JOB_PIXELS) {
+        return Err(format!(
+            "the image must have at most {MAX_JOB_PIXELS} pixels"
+        ));
+    }
+    if job.max_depth > MAX_JOB_DEPTH {
+        return Err(format!(
+            "the recursion depth must be at most {MAX_JOB_DEPTH}"
+        ));
+    }
+    let scene = Scene::from_yaml(&job.source).map_err(|error| error.to_string())?;
+    let mut camera = scene.camera.resized(job.width, job.height);
+    camera.set_exposure(job.exposure);
+    camera.set_seed(job.seed);
+    let mut world = scene.world;
+    world.max_depth = job.max_depth;
+    Ok((camera, world))
+}
+
+/// Tell the coordinator why its request is refused, and return the error ending the connection.
+fn refuse<W: Write>(writer: &mut W, message: String) -> DistributedError {
+    let sent = writer
+        .write_all(&[FAILED])
+        .and_then(|()| write_bytes(writer, message.as_bytes()))
+        .and_then(|()| writer.flush());
+    match sent {
+        Ok(()) => DistributedError::Worker(message),
+        Err(error) => error.into(),
+    }
+}
+
+/// Render a job by splitting its image into square tiles of `tile_size` pixels and sharing them among
+/// the workers at `workers`, each started with `serve`, then putting the tiles back together.
+///
+/// Each worker renders one tile at a time, so faster workers take on more tiles. When a worker fails,
+/// its tile goes back to the others, and rendering only fails once every worker has.
+/// Panics if `tile_size` is 0.
+pub fn render_distributed<A: ToSocketAddrs + Sync>(
+    job: &RenderJob,
+    workers: &[A],
+    tile_size: usize,
+) -> Result<Canvas, DistributedError> {
+    assert!(tile_size > 0, "tiles must be at least one pixel wide");
+    let mut tiles = VecDeque::new();
+    for y in (0..job.height).step_by(tile_size) {
+        for x in (0..job.width).step_by(tile_size) {
+            let width = tile_size.min(job.width - x);
+            let height = tile_size.min(job.height - y);
+            tiles.push_back((x, y, width, height));
+        }
+    }
+    let remaining = tiles.len();
+    let queue = TileQueue::new(tiles);
+    let (sender, receiver) = mpsc::channel();
+    let errors: Vec<DistributedError> = thread::scope(|scope| {
+        let handles: Vec<_> = workers
+            .iter()
+            .map(|address| {
+                let (queue, sender) = (&queue, sender.clone());
+                scope.spawn(move || coordinate_worker(job, address, queue, sender))
+            })
+            .collect();
+        drop(sender);
+        handles
+            .into_iter()
+            .filter_map(|handle| handle.join().expect("worker threads do not panic").err())
+            .collect()
+    });
+
+    let mut image = Canvas::new(job.width, job.height);
+    let mut received = 0;
+    for tile in receiver {
+        tile.paste_into(&mut image);
+        received += 1;
+    }
+    if received < remaining {
+        return Err(errors
+            .into_iter()
+            .last()
+            .unwrap_or(DistributedError::NoWorkers));
+    }
+    Ok(image)
+}
+
+/// Position and size of a tile: column, row, width and height.
+type TileRange = (usize, usize, usize, usize);
+
+/// Tiles waiting to be rendered, shared by the threads talking to the workers.
+struct TileQueue {
+    state: Mutex<QueueState>,
+    changed: Condvar,
+}
+
+struct QueueState {
+    pending: VecDeque<TileRange>,
+    /// Number of tiles being rendered, which come back to `pending` if their worker fails.
+    in_flight: usize,
+}
+
+impl TileQueue {
+    fn new(pending: VecDeque<TileRange>) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                pending,
+                in_flight: 0,
+            }),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Take the next tile to render, waiting while other workers might still hand theirs back.
+    /// Returns `None` once every tile is rendered.
+    fn take(&self) -> Option<TileRange> {
+        let mut state = self.state.lock().expect("the queue is not poisoned");
+        loop {
+            if let Some(tile) = state.pending.pop_front() {
+                state.in_flight += 1;
+                return Some(tile);
+            }
+            if state.in_flight == 0 {
+                return None;
+            }
+            state = self.changed.wait(state).expect("the queue is not poisoned");
+        }
+    }
+
+    /// Mark a taken tile as rendered, or put it back in the queue for another worker if it failed.
+    fn finish(&self, tile: TileRange, rendered: bool) {
+        let mut state = self.state.lock().expect("the queue is not poisoned");
+        state.in_flight -= 1;
+        if !rendered {
+            state.pending.push_back(tile);
+        }
+        self.changed.notify_all();
+    }
+}
+
+/// Send tiles from the queue to a worker until every tile is rendered or the worker fails, putting the
+/// tile it was rendering back in the queue if it does.
+fn coordinate_worker<A: ToSocketAddrs>(
+    job: &RenderJob,
+    address: &A,
+    queue: &TileQueue,
+    tiles: mpsc::Sender<Tile>,
+) -> Result<(), DistributedError> {
+    let stream = TcpStream::connect(address)?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = BufWriter::new(stream);
+    write_job(&mut writer, job)?;
+    writer.flush()?;
+    read_status(&mut reader)?;
+
+    while let Some(tile) = queue.take() {
+        let pixels = request_tile(&mut reader, &mut writer, tile);
+        queue.finish(tile, pixels.is_ok());
+        tiles
+            .send(Tile::new(tile.0, tile.1, pixels?))
+            .expect("the receiver outlives the workers");
+    }
+    Ok(())
+}
+
+fn request_tile<R: Read, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+    (x, y, width, height): TileRange,
+) -> Result<Canvas, DistributedError> {
+    for value in [x, y, width, height] {
+        writer.write_all(&(value as u64).to_le_bytes())?;
+    }
+    writer.flush()?;
+    read_status(reader)?;
+    let mut pixels = Canvas::new(width, height);
+    for row in 0..height {
+        for column in 0..width {
+            let color = Color::new(read_f64(reader)?, read_f64(reader)?, read_f64(reader)?);
+            pixels.write_pixel(column, row, color);
+        }
+    }
+    Ok(pixels)
+}
+
+/// Read a worker's status byte, turning a refusal into an error.
+fn read_status<R: Read>(reader: &mut R) -> Result<(), DistributedError> {
+    let mut status = [0];
+    reader.read_exact(&mut status)?;
+    match status[0] {
+        OK => Ok(()),
+        FAILED => {
+            let message = read_bytes(reader)?;
+            Err(DistributedError::Worker(
+                String::from_utf8_lossy(&message).into_owned(),
+            ))
+        }
+        status => Err(DistributedError::Worker(format!(
+            "unexpected status {status}"
+        ))),
+    }
+}
+
+fn write_job<W: Write>(writer: &mut W, job: &RenderJob) -> io::Result<()> {
+    write_bytes(writer, job.source.as_bytes())?;
+    writer.write_all(&(job.width as u64).to_le_bytes())?;
+    writer.write_all(&(job.height as u64).to_le_bytes())?;
+    writer.write_all(&job.exposure.to_le_bytes())?;
+    writer.write_all(&job.seed.to_le_bytes())?;
+    writer.write_all(&(job.max_depth as u64).to_le_bytes())
+}
+
+fn read_job<R: Read>(reader: &mut R) -> io::Result<RenderJob> {
+    let source = String::from_utf8(read_bytes(reader)?)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    Ok(RenderJob {
+        source,
+        width: read_u64(reader)? as usize,
+        height: read_u64(reader)? as usize,
+        exposure: read_f64(reader)?,
+        seed: read_u64(reader)?,
+        max_depth: read_u64(reader)? as usize,
+    })
+}
+
+/// Write a length-prefixed sequence of bytes.
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(bytes)
+}
+
+fn read_bytes<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let length = read_u64(reader)?;
+    let mut bytes = Vec::new();
+    reader.take(length).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != length {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_f64<R: Read>(reader: &mut R) -> io::Result<f64> {
+    read_u64(reader).map(f64::from_bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+
+    const SCENE: &str = "
+- add: camera
+  width: 9
+  height: 7
+  field-of-view: 1.0471975511965976
+  from: [0, 1.5, -5]
+  to: [0, 1, 0]
+  up: [0, 1, 0]
+- add: light
+  at: [-10, 10, -10]
+  intensity: [1, 1, 1]
+- add: sphere
+  material:
+    color: [1, 0.2, 0.2]
+";
+
+    /// Start a worker serving `connections` connections, returning its address.
+    fn start_worker(connections: usize) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming().take(connections) {
+                let _ = handle_connection(stream.unwrap());
+            }
+        });
+        address
+    }
+
+    /// Find an address where no worker is listening.
+    fn closed_address() -> SocketAddr {
+        TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+    }
+
+    #[test]
+    fn render_distributed_matches_local_render() {
+        let mut job = RenderJob::new(SCENE.to_string()).unwrap();
+        job.exposure = 0.5;
+        let workers = [start_worker(1), closed_address(), start_worker(1)];
+        let image = render_distributed(&job, &workers, 4).unwrap();
+
+        let scene = Scene::from_yaml(SCENE).unwrap();
+        let mut camera = scene.camera.resized(9, 7);
+        camera.set_exposure(0.5);
+        let expected = camera.render(&scene.world);
+        for y in 0..7 {
+            for x in 0..9 {
+                assert!(image.pixel_at(x, y).is_equal_to(&expected.pixel_at(x, y)));
+            }
+        }
+    }
+
+    #[test]
+    fn render_job_write_and_read() {
+        let mut job = RenderJob::new(SCENE.to_string()).unwrap();
+        job.exposure = -1.0;
+        job.seed = 42;
+        job.max_depth = 9;
+        let mut bytes = Vec::new();
+        write_job(&mut bytes, &job).unwrap();
+        let read = read_job(&mut bytes.as_slice()).unwrap();
+        assert_eq!(read.source, SCENE);
+        assert_eq!((read.width, read.height), (9, 7));
+        assert_eq!(read.exposure, -1.0);
+        assert_eq!(read.seed, 42);
+        assert_eq!(read.max_depth, 9);
+    }
+
+    #[test]
+    fn render_distributed_errors() {
+        let job = RenderJob {
+            source: "- add: nothing\n".to_string(),
+            width: 4,
+            height: 4,
+            exposure: 0.0,
+            seed: 0,
+            max_depth: 5,
+        };
+        let error = render_distributed(&job, &[start_worker(1)], 2).unwrap_err();
+        assert!(matches!(error, DistributedError::Worker(_)), "{error}");
+
+        // Jobs too large to render are refused before the worker sizes anything from them
+        let mut job = RenderJob::new(SCENE.to_string()).unwrap();
+        for (width, height, max_depth) in [
+            (1 << 40, 1 << 40, 5),
+            (1 << 20, 1 << 20, 5),
+            (9, 7, 1 << 40),
+        ] {
+            (job.width, job.height, job.max_depth) = (width, height, max_depth);
+            let stream = TcpStream::connect(start_worker(1)).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = BufWriter::new(stream);
+            write_job(&mut writer, &job).unwrap();
+            writer.flush().unwrap();
+            let error = read_status(&mut reader).unwrap_err();
+            assert!(error.to_string().contains("at most"), "{error}");
+        }
+
+        let job = RenderJob::new(SCENE.to_string()).unwrap();
+        // Tiles whose end overflows are refused rather than wrapping around into the image
+        let stream = TcpStream::connect(start_worker(1)).unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = BufWriter::new(stream);
+        write_job(&mut writer, &job).unwrap();
+        writer.flush().unwrap();
+        read_status(&mut reader).unwrap();
+        let error = request_tile(&mut reader, &mut writer, (usize::MAX, 0, 2, 2)).unwrap_err();
+        assert!(error.to_string().contains("does not fit"), "{error}");
+
+        let error = render_distributed::<SocketAddr>(&job, &[], 2).unwrap_err();
+        assert!(matches!(error, DistributedError::NoWorkers));
+        let error = render_distributed(&job, &[closed_address()], 2).unwrap_err();
+        assert!(matches!(error, DistributedError::Io(_)));
+    }
+}
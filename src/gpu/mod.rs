@@ -0,0 +1,426 @@
+use crate::camera::{AntiAliasing, Camera};
+use crate::canvas::{Canvas, PostProcess};
+use crate::color::Color;
+use crate::light::Falloff;
+use crate::random::Rng;
+use crate::shapes::{Primitive, Shape};
+use crate::tuple::Tuple;
+use crate::world::World;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::sync::mpsc;
+use wgpu::util::DeviceExt;
+
+/// Compute shader tracing the rays of every pixel.
+const SHADER: &str = include_str!("shader.wgsl");
+
+/// Number of invocations in each of the shader's workgroups.
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Largest number of workgroups along one dimension of a dispatch.
+const MAX_WORKGROUPS: u32 = 65535;
+
+/// Number of floats describing an object to the shader.
+const OBJECT_FLOATS: usize = 44;
+
+/// Error raised while setting up the GPU.
+#[derive(Debug)]
+pub enum GpuError {
+    /// No graphics adapter is available.
+    NoAdapter,
+    Device(wgpu::RequestDeviceError),
+}
+
+impl fmt::Display for GpuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoAdapter => write!(f, "no graphics adapter is available"),
+            Self::Device(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for GpuError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::NoAdapter => None,
+            Self::Device(error) => Some(error),
+        }
+    }
+}
+
+impl From<wgpu::RequestDeviceError> for GpuError {
+    fn from(error: wgpu::RequestDeviceError) -> Self {
+        Self::Device(error)
+    }
+}
+
+/// Renderer tracing simple worlds in a compute shader on the GPU.
+///
+/// Worlds made only of spheres, planes and triangles with plain colored, opaque materials, seen by a
+/// camera without anti-aliasing or motion blur, are rendered on the GPU in single precision. Anything
+/// else, such as patterns, transparency, groups, fog or an environment, falls back to `Camera::render`.
+#[derive(Debug)]
+pub struct GpuRenderer {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+}
+
+impl GpuRenderer {
+    /// Set up a renderer on the system's preferred graphics adapter.
+    pub fn new() -> Result<Self, GpuError> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            ..Default::default()
+        }))
+        .ok_or(GpuError::NoAdapter)?;
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))?;
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("ray tracer"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(SHADER)),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("ray tracer"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        Ok(Self {
+            device,
+            queue,
+            pipeline,
+        })
+    }
+
+    /// Check whether the GPU can render the world as seen by the camera.
+    pub fn supports(camera: &Camera, world: &World) -> bool {
+        camera.anti_aliasing() == AntiAliasing::Off
+            && !camera.is_moving()
+            && world.environment.is_none()
+            && world.fog.is_none()
+            && world.light.as_ref().is_none_or(|light| {
+                // The GPU lights every point with the full intensity, and casts hard shadows
+                light.falloff() == Falloff::None
+                    && light
+                        .samples_from(&Tuple::new_point(0.0, 0.0, 0.0), &mut Rng::new(0))
+                        .len()
+                        == 1
+            })
+            && !world.objects.is_empty()
+            // The GPU shows glowing objects, but does not light the world with them
+            && (world.emissive_samples == 0
+                || world.objects.iter().all(|object| {
+                    object.material().emissive.is_equal_to(&Color::default())
+                }))
+            && world.objects.iter().all(|object| {
+                let material = object.material();
+                object.primitive().is_some()
+                    && object.casts_shadow()
+                    && material.pattern.is_none()
+                    && material.transparency == 0.0
+                    && material.medium.is_none()
+                    && material.max_depth.is_none()
+                    && material.metalness == 0.0
+                    && material.roughness == 0.0
+            })
+    }
+
+    /// Render an image of the world, on the GPU if it `supports` the world and camera, and with
+    /// `Camera::render` otherwise.
+    pub fn render(&self, camera: &Camera, world: &World) -> Canvas {
+        if !Self::supports(camera, world) {
+            return camera.render(world);
+        }
+        let pixels = camera.hsize() * camera.vsize();
+        let ray_bytes = (pixels * 8 * 4) as u64;
+        if ray_bytes > u64::from(self.device.limits().max_storage_buffer_binding_size) {
+            return camera.render(world);
+        }
+
+        // The camera's rays are cast on the CPU, so the shader only has to follow them
+        let mut rays = Vec::with_capacity(pixels * 8);
+        for y in 0..camera.vsize() {
+            for x in 0..camera.hsize() {
+                let ray = camera.ray_for_pixel(x, y);
+                rays.extend(vector4(&ray.origin, 1.0));
+                rays.extend(vector4(&ray.direction, 0.0));
+            }
+        }
+        let objects: Vec<f32> = world
+            .objects
+            .iter()
+            .flat_map(|object| object_floats(object.as_ref()))
+            .collect();
+        let light_floats = match &world.light {
+            Some(light) => {
+                // Find where light comes from by looking from the origin
+                let origin = Tuple::new_point(0.0, 0.0, 0.0);
+                let direction = light.direction_from(&origin);
+                let distance = light.distance_from(&origin);
+                let intensity = light.intensity();
+                let light = if distance.is_finite() {
+                    vector4(&(direction * distance), 1.0)
+                } else {
+                    vector4(&direction, 0.0)
+                };
+                [light, color4(intensity, 1.0)]
+            }
+            None => [[0.0; 4], [0.0; 4]],
+        };
+        let mut params = floats_to_bytes(light_floats.as_flattened());
+        for count in [world.objects.len(), pixels, world.max_depth, 0] {
+            params.extend((count as u32).to_le_bytes());
+        }
+
+        let colors = self.trace(
+            &params,
+            &floats_to_bytes(&objects),
+            &floats_to_bytes(&rays),
+            pixels,
+        );
+        let scale = camera.exposure().exp2();
+        let mut image = Canvas::new(camera.hsize(), camera.vsize());
+        for (index, color) in colors.chunks_exact(4).enumerate() {
+            let color = Color::new(color[0] as f64, color[1] as f64, color[2] as f64);
+            image.write_pixel(
+                index % camera.hsize(),
+                index / camera.hsize(),
+                color * scale,
+            );
+        }
+        camera.post_processing().apply(&mut image);
+        image
+    }
+
+    /// Run the shader over every pixel and read back the colors it computes.
+    fn trace(&self, params: &[u8], objects: &[u8], rays: &[u8], pixels: usize) -> Vec<f32> {
+        let buffer = |label, contents, usage| {
+            self.device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some(label),
+                    contents,
+                    usage,
+                })
+        };
+        let params = buffer("params", params, wgpu::BufferUsages::UNIFORM);
+        let objects = buffer("objects", objects, wgpu::BufferUsages::STORAGE);
+        let rays = buffer("rays", rays, wgpu::BufferUsages::STORAGE);
+        let size = (pixels * 4 * 4) as u64;
+        let colors = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("colors"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("ray tracer"),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[&params, &objects, &rays, &colors]
+                .iter()
+                .enumerate()
+                .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                    binding: binding as u32,
+                    resource: buffer.as_entire_binding(),
+                })
+                .collect::<Vec<_>>(),
+        });
+
+        // Spread the workgroups over a second dimension once there are too many for one
+        let groups = (pixels as u32).div_ceil(WORKGROUP_SIZE);
+        let (groups_x, groups_y) = if groups <= MAX_WORKGROUPS {
+            (groups, 1)
+        } else {
+            (MAX_WORKGROUPS, groups.div_ceil(MAX_WORKGROUPS))
+        };
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&Default::default());
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(groups_x, groups_y, 1);
+        }
+        encoder.copy_buffer_to_buffer(&colors, 0, &readback, 0, size);
+        self.queue.submit([encoder.finish()]);
+
+        let slice = readback.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver
+            .recv()
+            .expect("mapping the buffer always completes after waiting")
+            .expect("the readback buffer can be mapped");
+        let bytes = slice.get_mapped_range();
+        bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect()
+    }
+}
+
+/// Render an image of the world on the GPU when one is available and supports the world, and with
+/// `Camera::render` otherwise.
+pub fn render(camera: &Camera, world: &World) -> Canvas {
+    match GpuRenderer::new() {
+        Ok(renderer) => renderer.render(camera, world),
+        Err(_) => camera.render(world),
+    }
+}
+
+/// Describe an object to the shader; see `Object` in the shader for the layout.
+fn object_floats(object: &dyn Shape) -> Vec<f32> {
+    let material = object.material();
+    let inverse = object
+        .transform()
+        .inverse()
+        .expect("transforms are invertible");
+    let mut floats = Vec::with_capacity(OBJECT_FLOATS);
+    // Matrices are stored column by column
+    for column in 0..4 {
+        for row in 0..4 {
+            floats.push(inverse[(row, column)] as f32);
+        }
+    }
+    let (kind, corners) = match object.primitive().expect("only primitives are supported") {
+        Primitive::Sphere => (0.0, None),
+        Primitive::Plane => (1.0, None),
+        Primitive::Triangle { p1, p2, p3 } => (2.0, Some((p2 - p1, p3 - p1, p1))),
+    };
+    floats.extend([
+        kind,
+        material.ambient as f32,
+        material.diffuse as f32,
+        material.specular as f32,
+    ]);
+    floats.extend([
+        material.shininess as f32,
+        material.reflective as f32,
+        0.0,
+        0.0,
+    ]);
+    floats.extend(color4(material.color, 1.0));
+    floats.extend(color4(material.emissive, 1.0));
+    match corners {
+        Some((e1, e2, p1)) => {
+            floats.extend(vector4(&p1, 1.0));
+            floats.extend(vector4(&e1, 0.0));
+            floats.extend(vector4(&e2, 0.0));
+        }
+        None => floats.extend([0.0; 12]),
+    }
+    floats
+}
+
+fn vector4(tuple: &Tuple, w: f32) -> [f32; 4] {
+    [tuple.x as f32, tuple.y as f32, tuple.z as f32, w]
+}
+
+fn color4(color: Color, w: f32) -> [f32; 4] {
+    [color.red as f32, color.green as f32, color.blue as f32, w]
+}
+
+fn floats_to_bytes(floats: &[f32]) -> Vec<u8> {
+    floats.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::light::{AreaLight, PointLight};
+    use crate::shapes::{Cone, Plane, Triangle};
+    use crate::transform::view_transform;
+    use crate::world::default_world;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn camera() -> Camera {
+        let mut camera = Camera::new(11, 11, FRAC_PI_2);
+        camera
+            .set_transform(view_transform(
+                &Tuple::new_point(0.0, 0.0, -5.0),
+                &Tuple::new_point(0.0, 0.0, 0.0),
+                &Tuple::new_vector(0.0, 1.0, 0.0),
+            ))
+            .unwrap();
+        camera
+    }
+
+    #[test]
+    fn shader_is_valid() {
+        let module = wgpu::naga::front::wgsl::parse_str(SHADER).unwrap();
+        wgpu::naga::valid::Validator::new(
+            wgpu::naga::valid::ValidationFlags::all(),
+            wgpu::naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .unwrap();
+    }
+
+    #[test]
+    fn gpu_renderer_supports() {
+        let camera = camera();
+        let mut world = default_world();
+        world.objects.push(Box::new(Plane::new()));
+        world.objects.push(Box::new(Triangle::new(
+            Tuple::new_point(0.0, 1.0, 0.0),
+            Tuple::new_point(-1.0, 0.0, 0.0),
+            Tuple::new_point(1.0, 0.0, 0.0),
+        )));
+        assert!(GpuRenderer::supports(&camera, &world));
+
+        let mut smooth = camera.resized(11, 11);
+        smooth.set_anti_aliasing(AntiAliasing::adaptive());
+        assert!(!GpuRenderer::supports(&smooth, &world));
+
+        let point_light = world.light.replace(Box::new(AreaLight::new(
+            Tuple::new_point(-10.0, 10.0, -10.0),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+            2,
+            Tuple::new_vector(0.0, 1.0, 0.0),
+            2,
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        assert!(!GpuRenderer::supports(&camera, &world));
+        let mut fading = PointLight::new(
+            Tuple::new_point(-10.0, 10.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+        fading.falloff = Falloff::Linear { radius: 100.0 };
+        world.light = Some(Box::new(fading));
+        assert!(!GpuRenderer::supports(&camera, &world));
+        world.light = point_light;
+
+        world.objects.push(Box::new(Cone::new()));
+        assert!(!GpuRenderer::supports(&camera, &world));
+        assert!(!GpuRenderer::supports(&camera, &World::new()));
+    }
+
+    #[test]
+    fn render_matches_cpu() {
+        // Without a GPU, the render falls back to the CPU and matches exactly
+        let world = default_world();
+        let camera = camera();
+        let image = render(&camera, &world);
+        let expected = camera.render(&world);
+        for y in 0..11 {
+            for x in 0..11 {
+                let (pixel, expected) = (image.pixel_at(x, y), expected.pixel_at(x, y));
+                assert_approx_eq!(pixel, expected, epsilon = 1e-3, "({x}, {y})");
+            }
+        }
+    }
+}
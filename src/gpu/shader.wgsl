@@ -0,0 +1,191 @@
+// Ray tracing of worlds of spheres, planes and triangles, one invocation per pixel.
+// Surfaces are lit with the Phong model and cast shadows, and reflections are followed iteratively.
+
+struct Object {
+    // Inverse of the object's transform
+    inverse: mat4x4<f32>,
+    // Kind of primitive, ambient, diffuse and specular
+    info: vec4<f32>,
+    // Shininess and reflectiveness
+    extra: vec4<f32>,
+    color: vec4<f32>,
+    emissive: vec4<f32>,
+    // First corner and edges of a triangle
+    p1: vec4<f32>,
+    e1: vec4<f32>,
+    e2: vec4<f32>,
+}
+
+struct Params {
+    // Position of a point light (w = 1), or direction toward a directional light (w = 0)
+    light: vec4<f32>,
+    // Color of the light, with w = 1 if there is a light at all
+    intensity: vec4<f32>,
+    // Number of objects, number of pixels and maximum number of reflections
+    counts: vec4<u32>,
+}
+
+struct Hit {
+    t: f32,
+    index: u32,
+}
+
+const SPHERE: f32 = 0.0;
+const PLANE: f32 = 1.0;
+const MISS: f32 = 3.4e38;
+// Single precision needs a larger offset than the CPU's to keep surfaces from shadowing themselves
+const EPSILON: f32 = 1e-4;
+const PARALLEL: f32 = 1e-6;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> objects: array<Object>;
+@group(0) @binding(2) var<storage, read> rays: array<vec4<f32>>;
+@group(0) @binding(3) var<storage, read_write> colors: array<vec4<f32>>;
+
+// Find the smallest non-negative distance along a ray in object space to the object, or MISS.
+fn local_intersect(object: Object, origin: vec3<f32>, direction: vec3<f32>) -> f32 {
+    if object.info.x == SPHERE {
+        let a = dot(direction, direction);
+        let b = 2.0 * dot(direction, origin);
+        let c = dot(origin, origin) - 1.0;
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return MISS;
+        }
+        let root = sqrt(discriminant);
+        let t1 = (-b - root) / (2.0 * a);
+        let t2 = (-b + root) / (2.0 * a);
+        if t1 >= 0.0 {
+            return t1;
+        }
+        if t2 >= 0.0 {
+            return t2;
+        }
+        return MISS;
+    }
+    if object.info.x == PLANE {
+        if abs(direction.y) < PARALLEL {
+            return MISS;
+        }
+        let t = -origin.y / direction.y;
+        return select(MISS, t, t >= 0.0);
+    }
+
+    // Möller–Trumbore intersection with the triangle
+    let e1 = object.e1.xyz;
+    let e2 = object.e2.xyz;
+    let dir_cross_e2 = cross(direction, e2);
+    let det = dot(e1, dir_cross_e2);
+    if abs(det) < PARALLEL {
+        return MISS;
+    }
+    let f = 1.0 / det;
+    let p1_to_origin = origin - object.p1.xyz;
+    let u = f * dot(p1_to_origin, dir_cross_e2);
+    if u < 0.0 || u > 1.0 {
+        return MISS;
+    }
+    let origin_cross_e1 = cross(p1_to_origin, e1);
+    let v = f * dot(direction, origin_cross_e1);
+    if v < 0.0 || u + v > 1.0 {
+        return MISS;
+    }
+    let t = f * dot(e2, origin_cross_e1);
+    return select(MISS, t, t >= 0.0);
+}
+
+// Find the nearest object hit by a ray in world space.
+fn find_hit(origin: vec3<f32>, direction: vec3<f32>) -> Hit {
+    var hit = Hit(MISS, 0u);
+    for (var i = 0u; i < params.counts.x; i++) {
+        let object = objects[i];
+        let local_origin = (object.inverse * vec4<f32>(origin, 1.0)).xyz;
+        let local_direction = (object.inverse * vec4<f32>(direction, 0.0)).xyz;
+        let t = local_intersect(object, local_origin, local_direction);
+        if t < hit.t {
+            hit = Hit(t, i);
+        }
+    }
+    return hit;
+}
+
+fn normal_at(object: Object, point: vec3<f32>) -> vec3<f32> {
+    var local_normal: vec3<f32>;
+    if object.info.x == SPHERE {
+        local_normal = (object.inverse * vec4<f32>(point, 1.0)).xyz;
+    } else if object.info.x == PLANE {
+        local_normal = vec3<f32>(0.0, 1.0, 0.0);
+    } else {
+        local_normal = normalize(cross(object.e2.xyz, object.e1.xyz));
+    }
+    return normalize((transpose(object.inverse) * vec4<f32>(local_normal, 0.0)).xyz);
+}
+
+fn lighting(object: Object, point: vec3<f32>, eyev: vec3<f32>, normal: vec3<f32>) -> vec3<f32> {
+    let effective_color = object.color.rgb * params.intensity.rgb;
+    let ambient = effective_color * object.info.y;
+    var lightv: vec3<f32>;
+    var distance: f32;
+    if params.light.w > 0.5 {
+        let to_light = params.light.xyz - point;
+        distance = length(to_light);
+        lightv = to_light / distance;
+    } else {
+        lightv = normalize(params.light.xyz);
+        distance = MISS;
+    }
+    if find_hit(point, lightv).t < distance {
+        return ambient;
+    }
+    let light_dot_normal = dot(lightv, normal);
+    if light_dot_normal < 0.0 {
+        return ambient;
+    }
+    let diffuse = effective_color * object.info.z * light_dot_normal;
+    let reflect_dot_eye = dot(reflect(-lightv, normal), eyev);
+    var specular = vec3<f32>(0.0);
+    if reflect_dot_eye > 0.0 {
+        specular = params.intensity.rgb * object.info.w * pow(reflect_dot_eye, object.extra.x);
+    }
+    return ambient + diffuse + specular;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let pixel = id.y * groups.x * 64u + id.x;
+    if pixel >= params.counts.y {
+        return;
+    }
+    var origin = rays[2u * pixel].xyz;
+    var direction = rays[2u * pixel + 1u].xyz;
+    var color = vec3<f32>(0.0);
+    var weight = 1.0;
+    for (var depth = 0u; depth <= params.counts.z; depth++) {
+        let hit = find_hit(origin, direction);
+        if hit.t == MISS {
+            break;
+        }
+        let object = objects[hit.index];
+        let point = origin + direction * hit.t;
+        let eyev = -direction;
+        var normal = normal_at(object, point);
+        if dot(normal, eyev) < 0.0 {
+            normal = -normal;
+        }
+        let over_point = point + normal * EPSILON;
+        var surface = object.emissive.rgb;
+        if params.intensity.w > 0.5 {
+            surface += lighting(object, over_point, eyev, normal);
+        }
+        color += surface * weight;
+
+        let reflective = object.extra.y;
+        if reflective == 0.0 || depth == params.counts.z {
+            break;
+        }
+        weight *= reflective;
+        origin = over_point;
+        direction = reflect(direction, normal);
+    }
+    colors[pixel] = vec4<f32>(color, 1.0);
+}
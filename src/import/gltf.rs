@@ -0,0 +1,213 @@
+use super::ImportError;
+use crate::color::Color;
+use crate::light::Material;
+use crate::matrix::Matrix4;
+use crate::shapes::{Group, Shape, Triangle};
+use crate::transform::scaling;
+use crate::tuple::Tuple;
+use gltf::buffer::Data;
+use gltf::mesh::Mode;
+use gltf::{Document, Node, Primitive};
+use std::path::Path;
+
+/// Load the default scene of a glTF 2.0 file (`.gltf` or `.glb`) as a group of triangles.
+///
+/// Every node becomes a group with the node's transform, and the triangles of its mesh take the base
+/// color of their material. glTF models are right-handed while the world is left-handed, so the
+/// returned group mirrors z to keep models from appearing flipped.
+/// Primitives that are not made of triangles, such as lines and points, are skipped.
+pub fn load_gltf<P: AsRef<Path>>(path: P) -> Result<Group, ImportError> {
+    let (document, buffers, _images) = gltf::import(path)?;
+    build_scene(&document, &buffers)
+}
+
+/// Parse a glTF 2.0 model from memory, like `load_gltf`.
+/// External buffers are looked up relative to the current directory.
+pub fn parse_gltf(bytes: &[u8]) -> Result<Group, ImportError> {
+    let (document, buffers, _images) = gltf::import_slice(bytes)?;
+    build_scene(&document, &buffers)
+}
+
+fn build_scene(document: &Document, buffers: &[Data]) -> Result<Group, ImportError> {
+    let scene = document
+        .default_scene()
+        .or_else(|| document.scenes().next())
+        .ok_or_else(|| ImportError::Invalid("the glTF file has no scene".to_string()))?;
+    let mut root = Group::new();
+    root.set_transform(scaling(1.0, 1.0, -1.0))?;
+    for node in scene.nodes() {
+        root.add_child(Box::new(build_node(&node, buffers)?));
+    }
+    Ok(root)
+}
+
+fn build_node(node: &Node, buffers: &[Data]) -> Result<Group, ImportError> {
+    let mut group = Group::new();
+    // glTF matrices are stored column by column
+    let columns = node.transform().matrix();
+    let mut rows = [[0.0; 4]; 4];
+    for (column, values) in columns.iter().enumerate() {
+        for (row, value) in values.iter().enumerate() {
+            rows[row][column] = *value as f64;
+        }
+    }
+    group.set_transform(Matrix4::new(rows))?;
+
+    if let Some(mesh) = node.mesh() {
+        for primitive in mesh.primitives() {
+            for triangle in build_triangles(&primitive, buffers)? {
+                group.add_child(Box::new(triangle));
+            }
+        }
+    }
+    for child in node.children() {
+        group.add_child(Box::new(build_node(&child, buffers)?));
+    }
+    Ok(group)
+}
+
+fn build_triangles(primitive: &Primitive, buffers: &[Data]) -> Result<Vec<Triangle>, ImportError> {
+    if primitive.mode() != Mode::Triangles {
+        return Ok(Vec::new());
+    }
+    let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|data| &data[..]));
+    let Some(positions) = reader.read_positions() else {
+        return Ok(Vec::new());
+    };
+    let positions: Vec<[f32; 3]> = positions.collect();
+    let indices: Vec<usize> = match reader.read_indices() {
+        Some(indices) => indices.into_u32().map(|index| index as usize).collect(),
+        None => (0..positions.len()).collect(),
+    };
+
+    let [red, green, blue, _alpha] = primitive
+        .material()
+        .pbr_metallic_roughness()
+        .base_color_factor();
+    let material = Material {
+        color: Color::new(red as f64, green as f64, blue as f64),
+        ..Material::default()
+    };
+
+    let point = |index: usize| {
+        positions
+            .get(index)
+            .map(|[x, y, z]| Tuple::new_point(*x as f64, *y as f64, *z as f64))
+            .ok_or_else(|| ImportError::Invalid(format!("vertex {index} does not exist")))
+    };
+    indices
+        .chunks_exact(3)
+        .map(|corners| {
+            let mut triangle =
+                Triangle::new(point(corners[0])?, point(corners[1])?, point(corners[2])?);
+            triangle.set_material(material.clone());
+            Ok(triangle)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::ray::Ray;
+
+    /// A single triangle with corners (0, 1, 0), (-1, 0, 0) and (1, 0, 0), moved one unit along x and
+    /// colored red.
+    const TRIANGLE: &str = r#"{
+        "asset": {"version": "2.0"},
+        "scene": 0,
+        "scenes": [{"nodes": [0]}],
+        "nodes": [{"mesh": 0, "translation": [1, 0, 0]}],
+        "meshes": [{"primitives": [{"attributes": {"POSITION": 0}, "material": 0}]}],
+        "materials": [{"pbrMetallicRoughness": {"baseColorFactor": [1, 0, 0, 1]}}],
+        "buffers": [{
+            "byteLength": 36,
+            "uri": "data:application/octet-stream;base64,AAAAAAAAgD8AAAAAAACAvwAAAAAAAAAAAACAPwAAAAAAAAAA"
+        }],
+        "bufferViews": [{"buffer": 0, "byteLength": 36}],
+        "accessors": [{
+            "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+            "min": [-1, 0, 0], "max": [1, 1, 0]
+        }]
+    }"#;
+
+    /// A unit square in the xy plane made of two indexed triangles, in a child node scaled by 2.
+    const QUAD: &str = r#"{
+        "asset": {"version": "2.0"},
+        "scenes": [{"nodes": [0]}],
+        "nodes": [{"children": [1]}, {"mesh": 0, "scale": [2, 2, 2]}],
+        "meshes": [{"primitives": [{"attributes": {"POSITION": 0}, "indices": 1}]}],
+        "buffers": [{
+            "byteLength": 60,
+            "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAACAPwAAgD8AAAAAAAAAAAAAgD8AAAAAAAABAAIAAAACAAMA"
+        }],
+        "bufferViews": [
+            {"buffer": 0, "byteLength": 48},
+            {"buffer": 0, "byteOffset": 48, "byteLength": 12}
+        ],
+        "accessors": [
+            {
+                "bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3",
+                "min": [0, 0, 0], "max": [1, 1, 0]
+            },
+            {"bufferView": 1, "componentType": 5123, "count": 6, "type": "SCALAR"}
+        ]
+    }"#;
+
+    #[test]
+    fn parse_gltf_triangle() {
+        let model = parse_gltf(TRIANGLE.as_bytes()).unwrap();
+        assert!(model.transform().is_equal_to(&scaling(1.0, 1.0, -1.0)));
+        let bounds = model.bounds();
+        assert!(bounds.min().is_equal_to(&Tuple::new_point(0.0, 0.0, 0.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(2.0, 1.0, 0.0)));
+
+        let ray = Ray::new(
+            Tuple::new_point(1.0, 0.5, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = model.intersect(&ray);
+        assert_eq!(xs.len(), 1);
+        assert_approx_eq!(xs[0].t, 2.0);
+        assert!(xs[0]
+            .object
+            .material()
+            .color
+            .is_equal_to(&Color::new(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn parse_gltf_indexed_child_node() {
+        let model = parse_gltf(QUAD.as_bytes()).unwrap();
+        let bounds = model.bounds();
+        assert!(bounds.min().is_equal_to(&Tuple::new_point(0.0, 0.0, 0.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(2.0, 2.0, 0.0)));
+
+        // Both triangles of the scaled quad are hit, and the default material is white
+        for (x, y) in [(1.5, 0.5), (0.5, 1.5)] {
+            let ray = Ray::new(
+                Tuple::new_point(x, y, 2.0),
+                Tuple::new_vector(0.0, 0.0, -1.0),
+            );
+            let xs = model.intersect(&ray);
+            assert_eq!(xs.len(), 1, "({x}, {y})");
+            assert!(xs[0]
+                .object
+                .material()
+                .color
+                .is_equal_to(&Color::new(1.0, 1.0, 1.0)));
+        }
+    }
+
+    #[test]
+    fn parse_gltf_invalid() {
+        let error = parse_gltf(b"{ not json").unwrap_err();
+        assert!(matches!(error, ImportError::Gltf(_)));
+        let error = parse_gltf(br#"{"asset": {"version": "2.0"}}"#).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid model: the glTF file has no scene"
+        );
+    }
+}
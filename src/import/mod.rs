@@ -0,0 +1,66 @@
+use crate::matrix::MatrixError;
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "gltf")]
+pub mod gltf;
+pub mod mtl;
+pub mod obj;
+pub mod ply;
+
+/// Error raised while importing a model.
+#[derive(Debug)]
+pub enum ImportError {
+    /// The model file could not be read.
+    Io(std::io::Error),
+    /// The glTF file is malformed or refers to resources that could not be loaded.
+    #[cfg(feature = "gltf")]
+    Gltf(::gltf::Error),
+    /// The model file is readable but does not describe a model, such as a face using a missing vertex.
+    Invalid(String),
+    /// A transform in the model is not invertible.
+    Matrix(MatrixError),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io(error) => write!(f, "cannot read model: {error}"),
+            #[cfg(feature = "gltf")]
+            ImportError::Gltf(error) => write!(f, "invalid glTF: {error}"),
+            ImportError::Invalid(message) => write!(f, "invalid model: {message}"),
+            ImportError::Matrix(error) => write!(f, "invalid transform: {error}"),
+        }
+    }
+}
+
+impl Error for ImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImportError::Io(error) => Some(error),
+            #[cfg(feature = "gltf")]
+            ImportError::Gltf(error) => Some(error),
+            ImportError::Invalid(_) => None,
+            ImportError::Matrix(error) => Some(error),
+        }
+    }
+}
+
+impl From<std::io::Error> for ImportError {
+    fn from(error: std::io::Error) -> Self {
+        ImportError::Io(error)
+    }
+}
+
+#[cfg(feature = "gltf")]
+impl From<::gltf::Error> for ImportError {
+    fn from(error: ::gltf::Error) -> Self {
+        ImportError::Gltf(error)
+    }
+}
+
+impl From<MatrixError> for ImportError {
+    fn from(error: MatrixError) -> Self {
+        ImportError::Matrix(error)
+    }
+}
@@ -0,0 +1,140 @@
+use super::ImportError;
+use crate::color::Color;
+use crate::light::Material;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Materials of a material library, by name.
+pub type Materials = HashMap<String, Material>;
+
+/// Load a Wavefront material library (`.mtl`).
+///
+/// The statements mapping onto the Phong model are read: `Kd` sets the color, the average of `Ks` the
+/// specular reflection, `Ns` the shininess, `d` (or its inverse `Tr`) the transparency and `Ni` the
+/// refractive index. Other statements, such as texture maps, are skipped, and properties a material does
+/// not set keep the values of `Material::default()`.
+pub fn load_mtl<P: AsRef<Path>>(path: P) -> Result<Materials, ImportError> {
+    parse_mtl(BufReader::new(File::open(path)?))
+}
+
+/// Parse a Wavefront material library, like `load_mtl`.
+pub fn parse_mtl<R: BufRead>(reader: R) -> Result<Materials, ImportError> {
+    let mut materials = Materials::new();
+    let mut current: Option<(String, Material)> = None;
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let mut words = line.split_whitespace();
+        let Some(statement) = words.next() else {
+            continue;
+        };
+        let arguments: Vec<&str> = words.collect();
+        if statement == "newmtl" {
+            let name = arguments.join(" ");
+            if name.is_empty() {
+                return Err(invalid(number, "`newmtl` needs a name"));
+            }
+            if let Some((name, material)) = current.replace((name, Material::default())) {
+                materials.insert(name, material);
+            }
+            continue;
+        }
+        if !matches!(statement, "Kd" | "Ks" | "Ns" | "d" | "Tr" | "Ni") {
+            continue;
+        }
+        let Some((_, material)) = current.as_mut() else {
+            return Err(invalid(
+                number,
+                format!("`{statement}` before any `newmtl`"),
+            ));
+        };
+        let values = numbers(number, &arguments)?;
+        match (statement, values.as_slice()) {
+            ("Kd", [red, green, blue]) => material.color = Color::new(*red, *green, *blue),
+            ("Ks", [red, green, blue]) => material.specular = (red + green + blue) / 3.0,
+            ("Ns", [shininess]) => material.shininess = *shininess,
+            ("d", [dissolve]) => material.transparency = 1.0 - dissolve,
+            ("Tr", [transparency]) => material.transparency = *transparency,
+            ("Ni", [refractive_index]) => material.refractive_index = *refractive_index,
+            _ => {
+                return Err(invalid(
+                    number,
+                    format!("wrong number of values for `{statement}`"),
+                ))
+            }
+        }
+    }
+    if let Some((name, material)) = current {
+        materials.insert(name, material);
+    }
+    Ok(materials)
+}
+
+fn invalid(line: usize, message: impl Into<String>) -> ImportError {
+    ImportError::Invalid(format!("MTL line {}: {}", line + 1, message.into()))
+}
+
+fn numbers(line: usize, words: &[&str]) -> Result<Vec<f64>, ImportError> {
+    words
+        .iter()
+        .map(|word| {
+            word.parse()
+                .map_err(|_| invalid(line, format!("`{word}` is not a number")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn parse_mtl_materials() {
+        let mtl = "# two materials
+newmtl red
+Ka 0.1 0.1 0.1
+Kd 1 0 0
+Ks 0.5 0.5 0.2
+Ns 50
+map_Kd red.png
+
+newmtl glass
+Kd 0.9 0.9 1.0
+d 0.25
+Ni 1.5
+";
+        let materials = parse_mtl(mtl.as_bytes()).unwrap();
+        assert_eq!(materials.len(), 2);
+
+        let red = &materials["red"];
+        assert!(red.color.is_equal_to(&Color::new(1.0, 0.0, 0.0)));
+        assert_approx_eq!(red.specular, 0.4);
+        assert_approx_eq!(red.shininess, 50.0);
+        assert_approx_eq!(red.transparency, 0.0);
+
+        let glass = &materials["glass"];
+        assert!(glass.color.is_equal_to(&Color::new(0.9, 0.9, 1.0)));
+        assert_approx_eq!(glass.transparency, 0.75);
+        assert_approx_eq!(glass.refractive_index, 1.5);
+        assert_approx_eq!(glass.specular, Material::default().specular);
+    }
+
+    #[test]
+    fn parse_mtl_errors() {
+        let cases = [
+            ("Kd 1 0 0\n", "line 1: `Kd` before any `newmtl`"),
+            ("newmtl\n", "line 1: `newmtl` needs a name"),
+            (
+                "newmtl red\nKd 1 0\n",
+                "line 2: wrong number of values for `Kd`",
+            ),
+            ("newmtl red\nNs shiny\n", "line 2: `shiny` is not a number"),
+        ];
+        for (mtl, expected) in cases {
+            let error = parse_mtl(mtl.as_bytes()).unwrap_err().to_string();
+            assert!(error.contains(expected), "{error}");
+        }
+    }
+}
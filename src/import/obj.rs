@@ -0,0 +1,302 @@
+use super::mtl::{load_mtl, Materials};
+use super::ImportError;
+use crate::light::Material;
+use crate::shapes::{Group, Shape, SmoothTriangle, Triangle};
+use crate::tuple::Tuple;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Load a Wavefront OBJ model as a group of triangles.
+///
+/// Vertices (`v`), vertex normals (`vn`) and faces (`f`) are read; faces with more than three corners
+/// are split into a fan of triangles, and faces whose corners all have normals become smooth triangles.
+/// Every named group (`g`) becomes a child group. Material libraries named by `mtllib` are loaded
+/// relative to the model's directory, and faces take the material last selected with `usemtl`.
+/// Other statements, such as texture coordinates, are skipped.
+pub fn load_obj<P: AsRef<Path>>(path: P) -> Result<Group, ImportError> {
+    let path = path.as_ref();
+    let directory = path.parent().unwrap_or(Path::new(""));
+    parse_obj(BufReader::new(File::open(path)?), |library| {
+        load_mtl(directory.join(library))
+    })
+}
+
+/// Parse a Wavefront OBJ model, like `load_obj`.
+/// `load_library` is called with the name of each library given to `mtllib` to get its materials.
+pub fn parse_obj<R, F>(reader: R, mut load_library: F) -> Result<Group, ImportError>
+where
+    R: BufRead,
+    F: FnMut(&str) -> Result<Materials, ImportError>,
+{
+    let mut vertices = Vec::new();
+    let mut normals = Vec::new();
+    let mut materials = Materials::new();
+    let mut material = Material::default();
+    let mut root = Group::new();
+    let mut groups = Vec::new();
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let mut words = line.split_whitespace();
+        let Some(statement) = words.next() else {
+            continue;
+        };
+        let arguments: Vec<&str> = words.collect();
+        match statement {
+            "v" => vertices.push(point(number, &arguments)?),
+            "vn" => normals.push(vector(number, &arguments)?),
+            "f" => {
+                let group = groups.last_mut().unwrap_or(&mut root);
+                for mut triangle in face(number, &arguments, &vertices, &normals)? {
+                    triangle.set_material(material.clone());
+                    group.add_child(triangle);
+                }
+            }
+            "g" => groups.push(Group::new()),
+            "mtllib" => {
+                for library in arguments {
+                    materials.extend(load_library(library)?);
+                }
+            }
+            "usemtl" => {
+                let name = arguments.join(" ");
+                material = materials.get(&name).cloned().ok_or_else(|| {
+                    invalid(number, format!("material `{name}` is not in any library"))
+                })?;
+            }
+            _ => {}
+        }
+    }
+    for group in groups.into_iter().filter(|group| !group.is_empty()) {
+        root.add_child(Box::new(group));
+    }
+    Ok(root)
+}
+
+fn invalid(line: usize, message: impl Into<String>) -> ImportError {
+    ImportError::Invalid(format!("OBJ line {}: {}", line + 1, message.into()))
+}
+
+fn coordinates(line: usize, words: &[&str]) -> Result<(f64, f64, f64), ImportError> {
+    let number = |word: &str| {
+        word.parse()
+            .map_err(|_| invalid(line, format!("`{word}` is not a number")))
+    };
+    match words {
+        // Vertices may carry a fourth, weight coordinate, which is ignored
+        [x, y, z] | [x, y, z, _] => Ok((number(x)?, number(y)?, number(z)?)),
+        _ => Err(invalid(line, "expected three coordinates")),
+    }
+}
+
+fn point(line: usize, words: &[&str]) -> Result<Tuple, ImportError> {
+    let (x, y, z) = coordinates(line, words)?;
+    Ok(Tuple::new_point(x, y, z))
+}
+
+fn vector(line: usize, words: &[&str]) -> Result<Tuple, ImportError> {
+    let (x, y, z) = coordinates(line, words)?;
+    Ok(Tuple::new_vector(x, y, z))
+}
+
+/// Look up a 1-based index, or an index counting back from the end when negative.
+fn lookup<'a>(line: usize, items: &'a [Tuple], word: &str) -> Result<&'a Tuple, ImportError> {
+    let index: isize = word
+        .parse()
+        .map_err(|_| invalid(line, format!("`{word}` is not an index")))?;
+    let position = if index < 0 {
+        items.len() as isize + index
+    } else {
+        index - 1
+    };
+    usize::try_from(position)
+        .ok()
+        .and_then(|position| items.get(position))
+        .ok_or_else(|| invalid(line, format!("index {index} does not exist")))
+}
+
+fn face(
+    line: usize,
+    words: &[&str],
+    vertices: &[Tuple],
+    normals: &[Tuple],
+) -> Result<Vec<Box<dyn Shape>>, ImportError> {
+    if words.len() < 3 {
+        return Err(invalid(line, "faces need at least three corners"));
+    }
+    // Corners are `v`, `v/vt`, `v//vn` or `v/vt/vn`
+    let corners = words
+        .iter()
+        .map(|word| {
+            let mut indices = word.split('/');
+            let vertex = lookup(line, vertices, indices.next().unwrap_or_default())?;
+            let normal = match indices.nth(1) {
+                Some(index) if !index.is_empty() => Some(lookup(line, normals, index)?),
+                _ => None,
+            };
+            Ok((vertex, normal))
+        })
+        .collect::<Result<Vec<_>, ImportError>>()?;
+
+    // Split the face into a fan of triangles around its first corner
+    let (first, first_normal) = corners[0];
+    Ok(corners[1..]
+        .windows(2)
+        .map(|pair| {
+            let [(second, second_normal), (third, third_normal)] = [pair[0], pair[1]];
+            let triangle: Box<dyn Shape> = match (first_normal, second_normal, third_normal) {
+                (Some(n1), Some(n2), Some(n3)) => {
+                    Box::new(SmoothTriangle::new(*first, *second, *third, *n1, *n2, *n3))
+                }
+                _ => Box::new(Triangle::new(*first, *second, *third)),
+            };
+            triangle
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::color::Color;
+    use crate::ray::Ray;
+    use crate::shapes::Shape;
+
+    fn no_libraries(library: &str) -> Result<Materials, ImportError> {
+        Err(ImportError::Invalid(format!(
+            "unexpected library {library}"
+        )))
+    }
+
+    fn hit(model: &Group, x: f64, y: f64) -> (&dyn Shape, Tuple) {
+        let ray = Ray::new(
+            Tuple::new_point(x, y, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = model.intersect(&ray);
+        assert_eq!(xs.len(), 1, "({x}, {y})");
+        (xs[0].object, xs[0].object.normal_at(&ray.position(xs[0].t)))
+    }
+
+    #[test]
+    fn parse_obj_faces_and_groups() {
+        let obj = "There was a young lady named Bright
+v 2 0 1
+v 3 0 1
+v 3 1 1
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 2 0
+f 4 5 6
+g offset
+f 1 2 3
+g empty
+g polygon
+f -4 -3 -2 -1
+";
+        let model = parse_obj(obj.as_bytes(), no_libraries).unwrap();
+        let children = model.children();
+        // The ungrouped triangle, then the groups with faces, the last split into two triangles
+        assert_eq!(children.len(), 3);
+        let cases = [
+            (
+                Tuple::new_point(0.0, 0.0, 0.0),
+                Tuple::new_point(1.0, 1.0, 0.0),
+            ),
+            (
+                Tuple::new_point(2.0, 0.0, 1.0),
+                Tuple::new_point(3.0, 1.0, 1.0),
+            ),
+            // The group's bounds take in both of its triangles
+            (
+                Tuple::new_point(0.0, 0.0, 0.0),
+                Tuple::new_point(1.0, 2.0, 0.0),
+            ),
+        ];
+        for (child, (min, max)) in children.iter().zip(cases) {
+            let bounds = child.bounds();
+            assert!(bounds.min().is_equal_to(&min));
+            assert!(bounds.max().is_equal_to(&max));
+        }
+        for (x, y) in [(0.75, 0.25), (0.25, 1.5)] {
+            let ray = Ray::new(
+                Tuple::new_point(x, y, -2.0),
+                Tuple::new_vector(0.0, 0.0, 1.0),
+            );
+            assert_eq!(children[2].intersect(&ray).len(), 1, "({x}, {y})");
+        }
+        let bounds = model.bounds();
+        assert!(bounds.min().is_equal_to(&Tuple::new_point(0.0, 0.0, 0.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(3.0, 2.0, 1.0)));
+    }
+
+    #[test]
+    fn parse_obj_vertex_normals() {
+        let obj = "v 0 1 0
+v -1 0 0
+v 1 0 0
+vn 0 1 0
+vn -1 0 0
+vn 1 0 0
+f 1//1 2//2 3//3
+";
+        let model = parse_obj(obj.as_bytes(), no_libraries).unwrap();
+        let (_, normal) = hit(&model, -0.2, 0.3);
+        // The book's values are rounded to four and five decimal places.
+        assert_approx_eq!(normal.x, -0.5547, epsilon = 1e-4);
+        assert_approx_eq!(normal.y, 0.83205, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn parse_obj_materials() {
+        let obj = "mtllib colors.mtl
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+f 1 2 3
+usemtl red
+f 1 3 4
+";
+        let model = parse_obj(obj.as_bytes(), |library| {
+            assert_eq!(library, "colors.mtl");
+            super::super::mtl::parse_mtl("newmtl red\nKd 1 0 0\nNs 10\n".as_bytes())
+        })
+        .unwrap();
+        let (first, _) = hit(&model, 0.75, 0.25);
+        assert!(first
+            .material()
+            .color
+            .is_equal_to(&Color::new(1.0, 1.0, 1.0)));
+        let (second, _) = hit(&model, 0.25, 0.75);
+        assert!(second
+            .material()
+            .color
+            .is_equal_to(&Color::new(1.0, 0.0, 0.0)));
+        assert_approx_eq!(second.material().shininess, 10.0);
+    }
+
+    #[test]
+    fn parse_obj_errors() {
+        let cases = [
+            ("v 1 2\n", "line 1: expected three coordinates"),
+            (
+                "v 0 0 0\nf 1 1\n",
+                "line 2: faces need at least three corners",
+            ),
+            ("v 0 0 0\nf 1 2 3\n", "line 2: index 2 does not exist"),
+            (
+                "usemtl red\n",
+                "line 1: material `red` is not in any library",
+            ),
+        ];
+        for (obj, expected) in cases {
+            let error = parse_obj(obj.as_bytes(), no_libraries)
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains(expected), "{error}");
+        }
+    }
+}
@@ -0,0 +1,431 @@
+use super::ImportError;
+use crate::shapes::{Group, SmoothTriangle, Triangle};
+use crate::tuple::Tuple;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Load a Stanford PLY model as a group of triangles.
+///
+/// ASCII and binary little-endian files are supported. Faces with more than three corners are split
+/// into a fan of triangles, and if the vertices have normals (`nx`, `ny` and `nz`) the triangles are
+/// smooth. Elements other than vertices and faces, such as edges, are skipped.
+pub fn load_ply<P: AsRef<Path>>(path: P) -> Result<Group, ImportError> {
+    parse_ply(BufReader::new(File::open(path)?))
+}
+
+/// Parse a Stanford PLY model, like `load_ply`.
+pub fn parse_ply<R: BufRead>(mut reader: R) -> Result<Group, ImportError> {
+    let header = Header::parse(&mut reader)?;
+    let mut body = Vec::new();
+    reader.read_to_end(&mut body)?;
+    let mut values = match header.format {
+        Format::Ascii => Values::ascii(&body)?,
+        Format::BinaryLittleEndian => Values::Binary(body, 0),
+    };
+
+    let mut vertices = Vec::new();
+    let mut faces = Vec::new();
+    for element in &header.elements {
+        for _ in 0..element.count {
+            let record = element.read(&mut values)?;
+            match element.name.as_str() {
+                "vertex" => vertices.push(Vertex::from_record(element, &record)?),
+                "face" => faces.push(face_from_record(element, record)?),
+                _ => {}
+            }
+        }
+    }
+
+    let mut group = Group::new();
+    for face in faces {
+        let corner = |index: f64| {
+            vertices
+                .get(index as usize)
+                .filter(|_| index >= 0.0)
+                .ok_or_else(|| invalid(format!("face uses vertex {index}, which does not exist")))
+        };
+        // Split the face into a fan of triangles around its first corner
+        for pair in 1..face.len().saturating_sub(1) {
+            let (a, b, c) = (
+                corner(face[0])?,
+                corner(face[pair])?,
+                corner(face[pair + 1])?,
+            );
+            match (&a.normal, &b.normal, &c.normal) {
+                (Some(n1), Some(n2), Some(n3)) => group.add_child(Box::new(SmoothTriangle::new(
+                    a.point(),
+                    b.point(),
+                    c.point(),
+                    vector(n1),
+                    vector(n2),
+                    vector(n3),
+                ))),
+                _ => group.add_child(Box::new(Triangle::new(a.point(), b.point(), c.point()))),
+            }
+        }
+    }
+    Ok(group)
+}
+
+fn invalid(message: impl Into<String>) -> ImportError {
+    ImportError::Invalid(format!("PLY: {}", message.into()))
+}
+
+fn vector([x, y, z]: &[f64; 3]) -> Tuple {
+    Tuple::new_vector(*x, *y, *z)
+}
+
+/// Encoding of the data following the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Ascii,
+    BinaryLittleEndian,
+}
+
+/// Type of a property's values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScalarType {
+    Int8,
+    UInt8,
+    Int16,
+    UInt16,
+    Int32,
+    UInt32,
+    Float32,
+    Float64,
+}
+
+impl ScalarType {
+    fn from_name(name: &str) -> Result<Self, ImportError> {
+        match name {
+            "char" | "int8" => Ok(Self::Int8),
+            "uchar" | "uint8" => Ok(Self::UInt8),
+            "short" | "int16" => Ok(Self::Int16),
+            "ushort" | "uint16" => Ok(Self::UInt16),
+            "int" | "int32" => Ok(Self::Int32),
+            "uint" | "uint32" => Ok(Self::UInt32),
+            "float" | "float32" => Ok(Self::Float32),
+            "double" | "float64" => Ok(Self::Float64),
+            _ => Err(invalid(format!("unknown property type `{name}`"))),
+        }
+    }
+
+    /// Get the number of bytes a value of this type takes in a binary file.
+    fn size(self) -> usize {
+        match self {
+            Self::Int8 | Self::UInt8 => 1,
+            Self::Int16 | Self::UInt16 => 2,
+            Self::Int32 | Self::UInt32 | Self::Float32 => 4,
+            Self::Float64 => 8,
+        }
+    }
+}
+
+/// Property of an element: a single value or a list of values preceded by their count.
+#[derive(Debug)]
+enum Property {
+    Scalar(String, ScalarType),
+    List(String, ScalarType, ScalarType),
+}
+
+impl Property {
+    fn name(&self) -> &str {
+        match self {
+            Property::Scalar(name, _) | Property::List(name, _, _) => name,
+        }
+    }
+}
+
+/// Kind of record, such as a vertex or a face, and how many of them the file holds.
+#[derive(Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+impl Element {
+    /// Read the values of every property of one record.
+    fn read(&self, values: &mut Values) -> Result<Vec<Vec<f64>>, ImportError> {
+        self.properties
+            .iter()
+            .map(|property| match property {
+                Property::Scalar(_, value_type) => Ok(vec![values.next(*value_type)?]),
+                Property::List(_, count_type, item_type) => {
+                    let count = values.next(*count_type)? as usize;
+                    (0..count).map(|_| values.next(*item_type)).collect()
+                }
+            })
+            .collect()
+    }
+
+    /// Find the position of a property in the element's records.
+    fn property_index(&self, name: &str) -> Option<usize> {
+        self.properties
+            .iter()
+            .position(|property| property.name() == name)
+    }
+}
+
+#[derive(Debug)]
+struct Header {
+    format: Format,
+    elements: Vec<Element>,
+}
+
+impl Header {
+    fn parse<R: BufRead>(reader: &mut R) -> Result<Self, ImportError> {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        if line.trim_end() != "ply" {
+            return Err(invalid("missing `ply` magic number"));
+        }
+
+        let mut format = None;
+        let mut elements: Vec<Element> = Vec::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(invalid("the header has no `end_header`"));
+            }
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words.as_slice() {
+                ["end_header"] => break,
+                ["comment", ..] | ["obj_info", ..] | [] => {}
+                ["format", "ascii", _] => format = Some(Format::Ascii),
+                ["format", "binary_little_endian", _] => format = Some(Format::BinaryLittleEndian),
+                ["format", other, _] => {
+                    return Err(invalid(format!("unsupported format `{other}`")))
+                }
+                ["element", name, count] => elements.push(Element {
+                    name: name.to_string(),
+                    count: count
+                        .parse()
+                        .map_err(|_| invalid(format!("invalid count of `{name}` elements")))?,
+                    properties: Vec::new(),
+                }),
+                ["property", "list", count_type, item_type, name] => elements
+                    .last_mut()
+                    .ok_or_else(|| invalid("property declared before any element"))?
+                    .properties
+                    .push(Property::List(
+                        name.to_string(),
+                        ScalarType::from_name(count_type)?,
+                        ScalarType::from_name(item_type)?,
+                    )),
+                ["property", value_type, name] => elements
+                    .last_mut()
+                    .ok_or_else(|| invalid("property declared before any element"))?
+                    .properties
+                    .push(Property::Scalar(
+                        name.to_string(),
+                        ScalarType::from_name(value_type)?,
+                    )),
+                _ => return Err(invalid(format!("unexpected header line `{}`", line.trim()))),
+            }
+        }
+        let format = format.ok_or_else(|| invalid("the header has no `format`"))?;
+        Ok(Self { format, elements })
+    }
+}
+
+/// Values following the header, read one at a time in the file's encoding.
+enum Values {
+    /// The numbers of an ASCII file, in reverse order so the next one can be popped off the end.
+    Ascii(Vec<f64>),
+    /// The bytes of a binary file and the position of the next value.
+    Binary(Vec<u8>, usize),
+}
+
+impl Values {
+    fn ascii(body: &[u8]) -> Result<Self, ImportError> {
+        let text = std::str::from_utf8(body).map_err(|_| invalid("the ASCII data is not text"))?;
+        let mut numbers = text
+            .split_whitespace()
+            .map(|word| {
+                word.parse()
+                    .map_err(|_| invalid(format!("`{word}` is not a number")))
+            })
+            .collect::<Result<Vec<f64>, _>>()?;
+        numbers.reverse();
+        Ok(Values::Ascii(numbers))
+    }
+
+    fn next(&mut self, value_type: ScalarType) -> Result<f64, ImportError> {
+        match self {
+            Values::Ascii(numbers) => numbers.pop().ok_or_else(|| invalid("the data ends early")),
+            Values::Binary(bytes, position) => {
+                let size = value_type.size();
+                let value = bytes
+                    .get(*position..*position + size)
+                    .ok_or_else(|| invalid("the data ends early"))?;
+                *position += size;
+                let mut buffer = [0; 8];
+                buffer[..size].copy_from_slice(value);
+                Ok(match value_type {
+                    ScalarType::Int8 => i8::from_le_bytes([buffer[0]]) as f64,
+                    ScalarType::UInt8 => buffer[0] as f64,
+                    ScalarType::Int16 => i16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+                    ScalarType::UInt16 => u16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+                    ScalarType::Int32 => i32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64,
+                    ScalarType::UInt32 => {
+                        u32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64
+                    }
+                    ScalarType::Float32 => {
+                        f32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64
+                    }
+                    ScalarType::Float64 => f64::from_le_bytes(buffer),
+                })
+            }
+        }
+    }
+}
+
+/// Position and, if the file has them, normal of a vertex.
+struct Vertex {
+    position: [f64; 3],
+    normal: Option<[f64; 3]>,
+}
+
+impl Vertex {
+    fn from_record(element: &Element, record: &[Vec<f64>]) -> Result<Self, ImportError> {
+        let read = |names: [&str; 3]| -> Option<[f64; 3]> {
+            let mut values = [0.0; 3];
+            for (value, name) in values.iter_mut().zip(names) {
+                *value = *record.get(element.property_index(name)?)?.first()?;
+            }
+            Some(values)
+        };
+        Ok(Self {
+            position: read(["x", "y", "z"]).ok_or_else(|| invalid("vertices need x, y and z"))?,
+            normal: read(["nx", "ny", "nz"]),
+        })
+    }
+
+    fn point(&self) -> Tuple {
+        let [x, y, z] = self.position;
+        Tuple::new_point(x, y, z)
+    }
+}
+
+/// Get the vertex indices of a face record.
+fn face_from_record(element: &Element, mut record: Vec<Vec<f64>>) -> Result<Vec<f64>, ImportError> {
+    let index = element
+        .property_index("vertex_indices")
+        .or_else(|| element.property_index("vertex_index"))
+        .ok_or_else(|| invalid("faces need a `vertex_indices` list"))?;
+    Ok(std::mem::take(&mut record[index]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::ray::Ray;
+    use crate::shapes::Shape;
+
+    fn hit_normal(model: &Group, x: f64, y: f64) -> Tuple {
+        let ray = Ray::new(
+            Tuple::new_point(x, y, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = model.intersect(&ray);
+        assert_eq!(xs.len(), 1, "({x}, {y})");
+        xs[0].object.normal_at(&ray.position(xs[0].t))
+    }
+
+    #[test]
+    fn parse_ply_ascii() {
+        let ply = b"ply
+format ascii 1.0
+comment a unit square split into a fan of two triangles
+element vertex 4
+property float x
+property float y
+property float z
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0
+1 0 0
+1 1 0
+0 1 0
+4 0 1 2 3
+";
+        let model = parse_ply(&ply[..]).unwrap();
+        assert_eq!(model.children().len(), 2);
+        let bounds = model.bounds();
+        assert!(bounds.min().is_equal_to(&Tuple::new_point(0.0, 0.0, 0.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(1.0, 1.0, 0.0)));
+        for (x, y) in [(0.75, 0.25), (0.25, 0.75)] {
+            let normal = hit_normal(&model, x, y);
+            assert_approx_eq!(normal.z.abs(), 1.0);
+        }
+    }
+
+    #[test]
+    fn parse_ply_binary_with_normals() {
+        let mut ply = b"ply
+format binary_little_endian 1.0
+element vertex 3
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+element edge 1
+property int vertex1
+property int vertex2
+element face 1
+property list uchar uint vertex_indices
+end_header
+"
+        .to_vec();
+        let vertices: [[f32; 6]; 3] = [
+            [0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+            [-1.0, 0.0, 0.0, -1.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+        ];
+        for value in vertices.iter().flatten() {
+            ply.extend(value.to_le_bytes());
+        }
+        ply.extend(0i32.to_le_bytes());
+        ply.extend(1i32.to_le_bytes());
+        ply.push(3);
+        for index in [0u32, 1, 2] {
+            ply.extend(index.to_le_bytes());
+        }
+
+        let model = parse_ply(&ply[..]).unwrap();
+        assert_eq!(model.children().len(), 1);
+        // The normals blend like the book's smooth triangle
+        let normal = hit_normal(&model, -0.2, 0.3);
+        assert_approx_eq!(normal.x, -0.5547, epsilon = 1e-4);
+        assert_approx_eq!(normal.y, 0.83205, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn parse_ply_errors() {
+        let header = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n";
+        let cases = [
+            ("obj\n".to_string(), "missing `ply` magic number"),
+            (
+                "ply\nformat binary_big_endian 1.0\nend_header\n".to_string(),
+                "unsupported format `binary_big_endian`",
+            ),
+            ("ply\nformat ascii 1.0\n".to_string(), "no `end_header`"),
+            (format!("{header}0 0 0\n"), "the data ends early"),
+            (
+                format!("{header}0 0 0\n3 0 1 2\n"),
+                "vertex 1, which does not exist",
+            ),
+            (format!("{header}0 zero 0\n"), "`zero` is not a number"),
+        ];
+        for (ply, expected) in cases {
+            let error = parse_ply(ply.as_bytes()).unwrap_err().to_string();
+            assert!(error.contains(expected), "{error}");
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::matrix::Matrix4;
+use crate::tuple::{Point, Tuple, Vector};
+use glam::{Mat4, Vec3A, Vec4};
+
+// glam stores single-precision floats, so converting to glam types rounds each component to the nearest
+// `f32`. Converting back widens them again without any further loss.
+
+impl From<Tuple> for Vec4 {
+    fn from(tuple: Tuple) -> Vec4 {
+        Vec4::new(
+            tuple.x as f32,
+            tuple.y as f32,
+            tuple.z as f32,
+            tuple.w as f32,
+        )
+    }
+}
+
+impl From<Vec4> for Tuple {
+    fn from(vec: Vec4) -> Tuple {
+        Tuple {
+            x: vec.x.into(),
+            y: vec.y.into(),
+            z: vec.z.into(),
+            w: vec.w.into(),
+        }
+    }
+}
+
+impl From<Tuple> for Vec3A {
+    /// Convert the `x`, `y` and `z` components, dropping `w`.
+    /// Since glam uses the same type for points and vectors, convert to a `Point` or a `Vector` to get back
+    /// a tuple of the right kind.
+    fn from(tuple: Tuple) -> Vec3A {
+        Vec3A::new(tuple.x as f32, tuple.y as f32, tuple.z as f32)
+    }
+}
+
+impl From<Point> for Vec3A {
+    fn from(point: Point) -> Vec3A {
+        Vec3A::new(point.x as f32, point.y as f32, point.z as f32)
+    }
+}
+
+impl From<Vec3A> for Point {
+    fn from(vec: Vec3A) -> Point {
+        Point::new(vec.x.into(), vec.y.into(), vec.z.into())
+    }
+}
+
+impl From<Vector> for Vec3A {
+    fn from(vector: Vector) -> Vec3A {
+        Vec3A::new(vector.x as f32, vector.y as f32, vector.z as f32)
+    }
+}
+
+impl From<Vec3A> for Vector {
+    fn from(vec: Vec3A) -> Vector {
+        Vector::new(vec.x.into(), vec.y.into(), vec.z.into())
+    }
+}
+
+impl From<Matrix4> for Mat4 {
+    /// Convert the matrix, which glam stores column by column.
+    fn from(matrix: Matrix4) -> Mat4 {
+        let mut columns = [[0.0; 4]; 4];
+        for (column, elements) in columns.iter_mut().enumerate() {
+            for (row, element) in elements.iter_mut().enumerate() {
+                *element = matrix[(row, column)] as f32;
+            }
+        }
+        Mat4::from_cols_array_2d(&columns)
+    }
+}
+
+impl From<Mat4> for Matrix4 {
+    fn from(matrix: Mat4) -> Matrix4 {
+        let columns = matrix.to_cols_array_2d();
+        let mut rows = [[0.0; 4]; 4];
+        for (row, elements) in rows.iter_mut().enumerate() {
+            for (column, element) in elements.iter_mut().enumerate() {
+                *element = columns[column][row].into();
+            }
+        }
+        Matrix4::new(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::{rotation_y, scaling, translation};
+    use std::f64::consts::FRAC_PI_3;
+
+    #[test]
+    fn glam_tuple_conversions() {
+        let point = Tuple::new_point(1.0, -2.5, 3.0);
+        assert_eq!(Vec4::from(point), Vec4::new(1.0, -2.5, 3.0, 1.0));
+        assert_eq!(Tuple::from(Vec4::from(point)), point);
+        assert_eq!(Vec3A::from(point), Vec3A::new(1.0, -2.5, 3.0));
+
+        let vector = Tuple::new_vector(0.5, 0.0, -4.0);
+        assert_eq!(Tuple::from(Vec4::from(vector)), vector);
+        assert_eq!(Vec3A::from(vector), Vec3A::new(0.5, 0.0, -4.0));
+    }
+
+    #[test]
+    fn glam_point_vector_conversions() {
+        let point = Point::new(1.0, -2.5, 3.0);
+        assert_eq!(Vec3A::from(point), Vec3A::new(1.0, -2.5, 3.0));
+        assert_eq!(Point::from(Vec3A::from(point)), point);
+
+        let vector = Vector::new(0.5, 0.0, -4.0);
+        assert_eq!(Vec3A::from(vector), Vec3A::new(0.5, 0.0, -4.0));
+        assert_eq!(Vector::from(Vec3A::from(vector)), vector);
+    }
+
+    #[test]
+    fn glam_matrix_conversions() {
+        let matrix = translation(1.0, 2.0, 3.0) * rotation_y(FRAC_PI_3) * scaling(2.0, 2.0, 2.0);
+        let converted = Mat4::from(matrix);
+
+        // Both transform points the same way, up to single precision
+        let point = Tuple::new_point(1.0, -1.0, 0.5);
+        let expected = matrix * point;
+        let transformed = Tuple::from(converted * Vec4::from(point));
+        assert_approx_eq!(transformed, expected, epsilon = 1e-5);
+
+        assert_approx_eq!(Matrix4::from(converted), matrix, epsilon = 1e-6);
+    }
+}
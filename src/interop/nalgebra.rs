@@ -0,0 +1,121 @@
+use crate::matrix::Matrix4;
+use crate::tuple::{Point, Tuple, TupleError, Vector};
+use nalgebra::{Point3, Vector3};
+
+impl From<Point3<f64>> for Tuple {
+    fn from(point: Point3<f64>) -> Tuple {
+        Tuple::new_point(point.x, point.y, point.z)
+    }
+}
+
+impl TryFrom<Tuple> for Point3<f64> {
+    type Error = TupleError;
+
+    /// Convert a tuple to a point, failing unless its `w` is 1.
+    fn try_from(tuple: Tuple) -> Result<Point3<f64>, TupleError> {
+        Point::try_from(tuple).map(Point3::from)
+    }
+}
+
+impl From<Vector3<f64>> for Tuple {
+    fn from(vector: Vector3<f64>) -> Tuple {
+        Tuple::new_vector(vector.x, vector.y, vector.z)
+    }
+}
+
+impl TryFrom<Tuple> for Vector3<f64> {
+    type Error = TupleError;
+
+    /// Convert a tuple to a vector, failing unless its `w` is 0.
+    fn try_from(tuple: Tuple) -> Result<Vector3<f64>, TupleError> {
+        Vector::try_from(tuple).map(Vector3::from)
+    }
+}
+
+impl From<Point> for Point3<f64> {
+    fn from(point: Point) -> Point3<f64> {
+        Point3::new(point.x, point.y, point.z)
+    }
+}
+
+impl From<Point3<f64>> for Point {
+    fn from(point: Point3<f64>) -> Point {
+        Point::new(point.x, point.y, point.z)
+    }
+}
+
+impl From<Vector> for Vector3<f64> {
+    fn from(vector: Vector) -> Vector3<f64> {
+        Vector3::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<Vector3<f64>> for Vector {
+    fn from(vector: Vector3<f64>) -> Vector {
+        Vector::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<Matrix4> for nalgebra::Matrix4<f64> {
+    fn from(matrix: Matrix4) -> nalgebra::Matrix4<f64> {
+        nalgebra::Matrix4::from_fn(|row, column| matrix[(row, column)])
+    }
+}
+
+impl From<nalgebra::Matrix4<f64>> for Matrix4 {
+    fn from(matrix: nalgebra::Matrix4<f64>) -> Matrix4 {
+        let mut rows = [[0.0; 4]; 4];
+        for (row, elements) in rows.iter_mut().enumerate() {
+            for (column, element) in elements.iter_mut().enumerate() {
+                *element = matrix[(row, column)];
+            }
+        }
+        Matrix4::new(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::{rotation_x, translation};
+    use std::f64::consts::FRAC_PI_4;
+
+    #[test]
+    fn nalgebra_tuple_conversions() {
+        let point = Tuple::new_point(1.0, -2.5, 3.0);
+        assert_eq!(Point3::try_from(point), Ok(Point3::new(1.0, -2.5, 3.0)));
+        assert_eq!(Tuple::from(Point3::new(1.0, -2.5, 3.0)), point);
+        assert_eq!(Vector3::try_from(point), Err(TupleError::NotAVector));
+
+        let vector = Tuple::new_vector(0.5, 0.0, -4.0);
+        assert_eq!(Vector3::try_from(vector), Ok(Vector3::new(0.5, 0.0, -4.0)));
+        assert_eq!(Tuple::from(Vector3::new(0.5, 0.0, -4.0)), vector);
+        assert_eq!(Point3::try_from(vector), Err(TupleError::NotAPoint));
+    }
+
+    #[test]
+    fn nalgebra_point_vector_conversions() {
+        let point = Point::new(1.0, -2.5, 3.0);
+        assert_eq!(Point3::from(point), Point3::new(1.0, -2.5, 3.0));
+        assert_eq!(Point::from(Point3::from(point)), point);
+
+        let vector = Vector::new(0.5, 0.0, -4.0);
+        assert_eq!(Vector3::from(vector), Vector3::new(0.5, 0.0, -4.0));
+        assert_eq!(Vector::from(Vector3::from(vector)), vector);
+    }
+
+    #[test]
+    fn nalgebra_matrix_conversions() {
+        let matrix = translation(1.0, 2.0, 3.0) * rotation_x(FRAC_PI_4);
+        let converted = nalgebra::Matrix4::from(matrix);
+
+        // Both transform points the same way
+        let point = Point::new(1.0, -1.0, 0.5);
+        assert_eq!(
+            Point::from(converted.transform_point(&Point3::from(point))),
+            matrix * point
+        );
+
+        assert!(Matrix4::from(converted).is_equal_to(&matrix));
+    }
+}
@@ -0,0 +1,412 @@
+use super::{Intersection, Intersections};
+use crate::random::Rng;
+use crate::ray::Ray;
+use crate::shapes::Shape;
+use crate::tuple::Tuple;
+use crate::EPSILON;
+
+/// Default distance by which `over_point` and `under_point` are offset from the surface.
+pub const DEFAULT_SHADOW_BIAS: f64 = EPSILON;
+
+/// Precomputed information about an intersection that is needed to shade it.
+#[derive(Debug)]
+pub struct Computations<'a> {
+    pub t: f64,
+    pub object: &'a dyn Shape,
+    pub point: Tuple,
+    pub eyev: Tuple,
+    pub normalv: Tuple,
+    pub inside: bool,
+    /// Point slightly above the surface, used to keep rays cast from the surface
+    /// from intersecting the surface itself due to floating-point error.
+    /// It is offset along the normal by the shadow bias.
+    pub over_point: Tuple,
+    /// Point slightly below the surface, where refracted rays originate.
+    pub under_point: Tuple,
+    /// Direction of the ray reflected off the surface.
+    pub reflectv: Tuple,
+    /// Refractive index of the material the ray is leaving.
+    pub n1: f64,
+    /// Refractive index of the material the ray is entering.
+    pub n2: f64,
+}
+
+impl Computations<'_> {
+    /// Approximate the fraction of light reflected by the surface using Schlick's approximation of the Fresnel equations.
+    pub fn schlick(&self) -> f64 {
+        let mut cos = self.eyev.dot_product(&self.normalv);
+
+        // Total internal reflection can only occur when leaving a denser material
+        if self.n1 > self.n2 {
+            let n = self.n1 / self.n2;
+            let sin2_t = n * n * (1.0 - cos * cos);
+            if sin2_t > 1.0 {
+                return 1.0;
+            }
+            // Use the angle of the transmitted ray instead
+            cos = (1.0 - sin2_t).sqrt();
+        }
+
+        let r0 = ((self.n1 - self.n2) / (self.n1 + self.n2)).powi(2);
+        r0 + (1.0 - r0) * (1.0 - cos).powi(5)
+    }
+
+    /// Create the ray reflected off the surface, starting just above it.
+    pub fn reflect_ray(&self) -> Ray {
+        Ray::new(self.over_point, self.reflectv)
+    }
+
+    /// Create a ray reflected off a rough surface: the mirror direction jittered by a random offset within
+    /// a sphere of radius `roughness`, starting just above the surface. Offsets that would send the ray
+    /// into the surface keep the mirror direction instead.
+    pub fn glossy_reflect_ray(&self, roughness: f64, rng: &mut Rng) -> Ray {
+        let direction = (self.reflectv + random_in_unit_sphere(rng) * roughness).normalize();
+        if direction.dot_product(&self.normalv) <= 0.0 {
+            return self.reflect_ray();
+        }
+        Ray::new(self.over_point, direction)
+    }
+
+    /// Create the ray refracted through the surface using Snell's law, starting just below it.
+    /// Returns `None` when the light undergoes total internal reflection instead.
+    pub fn refract_ray(&self) -> Option<Ray> {
+        (-self.eyev)
+            .refract(&self.normalv, self.n1 / self.n2)
+            .map(|direction| Ray::new(self.under_point, direction))
+    }
+}
+
+impl<'a> Intersection<'a> {
+    /// Precompute the information needed to shade this intersection of `ray`.
+    /// `intersections` are all of the intersections of the ray, which determine the materials
+    /// the ray is passing between at this intersection.
+    pub fn prepare_computations(
+        &self,
+        ray: &Ray,
+        intersections: &Intersections<'a>,
+    ) -> Computations<'a> {
+        self.prepare_computations_with_bias(ray, intersections, DEFAULT_SHADOW_BIAS)
+    }
+
+    /// Precompute the information needed to shade this intersection of `ray`, offsetting `over_point`
+    /// and `under_point` from the surface by `shadow_bias` instead of the default.
+    ///
+    /// A bias too small for the scale of the scene lets surfaces shadow themselves, speckling them with
+    /// "shadow acne", while one too large detaches shadows from the objects casting them.
+    pub fn prepare_computations_with_bias(
+        &self,
+        ray: &Ray,
+        intersections: &Intersections<'a>,
+        shadow_bias: f64,
+    ) -> Computations<'a> {
+        let point = ray.position(self.t);
+        let eyev = -&ray.direction;
+        let mut normalv = self.normal.unwrap_or_else(|| self.object.normal_at(&point));
+
+        // The normal points away from the eye when the hit occurs inside the object
+        let inside = normalv.dot_product(&eyev) < 0.0;
+        if inside {
+            normalv = -normalv;
+        }
+
+        let over_point = point + normalv * shadow_bias;
+        let under_point = point - normalv * shadow_bias;
+        let reflectv = ray.direction.reflect(&normalv);
+        let (n1, n2) = self.refractive_indices(intersections);
+        Computations {
+            t: self.t,
+            object: self.object,
+            point,
+            eyev,
+            normalv,
+            inside,
+            over_point,
+            under_point,
+            reflectv,
+            n1,
+            n2,
+        }
+    }
+
+    /// Find the refractive indices of the materials on either side of this intersection by tracking
+    /// which objects contain the ray as it passes through each of the intersections in order.
+    fn refractive_indices(&self, intersections: &Intersections<'a>) -> (f64, f64) {
+        let refractive_index = |containers: &[&dyn Shape]| {
+            containers
+                .last()
+                .map_or(1.0, |object| object.material().refractive_index)
+        };
+
+        let mut containers: Vec<&dyn Shape> = Vec::new();
+        let mut n1 = 1.0;
+        for intersection in intersections.iter() {
+            let is_this =
+                intersection.t == self.t && std::ptr::addr_eq(intersection.object, self.object);
+            if is_this {
+                n1 = refractive_index(&containers);
+            }
+
+            // The ray either exits an object that contains it or enters a new one
+            match containers
+                .iter()
+                .position(|object| std::ptr::addr_eq(*object, intersection.object))
+            {
+                Some(index) => {
+                    containers.remove(index);
+                }
+                None => containers.push(intersection.object),
+            }
+
+            if is_this {
+                return (n1, refractive_index(&containers));
+            }
+        }
+        (n1, refractive_index(&containers))
+    }
+}
+
+/// Pick a random vector within the unit sphere, rejecting the points of the enclosing cube outside it.
+fn random_in_unit_sphere(rng: &mut Rng) -> Tuple {
+    loop {
+        let mut coordinate = || rng.next_f64() * 2.0 - 1.0;
+        let vector = Tuple::new_vector(coordinate(), coordinate(), coordinate());
+        if vector.magnitude() < 1.0 {
+            return vector;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::light::Material;
+    use crate::shapes::{Plane, Sphere};
+    use crate::transform::{scaling, translation};
+    use std::f64::consts::SQRT_2;
+
+    #[test]
+    fn intersection_prepare_computations() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let shape = Sphere::new();
+        let intersection = Intersection::new(4.0, &shape);
+        let comps =
+            intersection.prepare_computations(&ray, &Intersections::new(vec![intersection]));
+        assert_approx_eq!(comps.t, intersection.t);
+        assert!(std::ptr::addr_eq(comps.object, &shape));
+        assert!(comps.point.is_equal_to(&Tuple::new_point(0.0, 0.0, -1.0)));
+        assert!(comps.eyev.is_equal_to(&Tuple::new_vector(0.0, 0.0, -1.0)));
+        assert!(comps
+            .normalv
+            .is_equal_to(&Tuple::new_vector(0.0, 0.0, -1.0)));
+    }
+
+    #[test]
+    fn intersection_prepare_computations_outside() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let shape = Sphere::new();
+        let intersection = Intersection::new(4.0, &shape);
+        let comps =
+            intersection.prepare_computations(&ray, &Intersections::new(vec![intersection]));
+        assert!(!comps.inside);
+    }
+
+    #[test]
+    fn intersection_prepare_computations_inside() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let shape = Sphere::new();
+        let intersection = Intersection::new(1.0, &shape);
+        let comps =
+            intersection.prepare_computations(&ray, &Intersections::new(vec![intersection]));
+        assert!(comps.point.is_equal_to(&Tuple::new_point(0.0, 0.0, 1.0)));
+        assert!(comps.eyev.is_equal_to(&Tuple::new_vector(0.0, 0.0, -1.0)));
+        assert!(comps.inside);
+        // The normal is inverted to point toward the eye
+        assert!(comps
+            .normalv
+            .is_equal_to(&Tuple::new_vector(0.0, 0.0, -1.0)));
+    }
+
+    #[test]
+    fn intersection_prepare_computations_over_point() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let mut shape = Sphere::new();
+        shape.set_transform(translation(0.0, 0.0, 1.0)).unwrap();
+        let intersection = Intersection::new(5.0, &shape);
+        let comps =
+            intersection.prepare_computations(&ray, &Intersections::new(vec![intersection]));
+        assert!(comps.over_point.z < -EPSILON / 2.0);
+        assert!(comps.point.z > comps.over_point.z);
+    }
+
+    #[test]
+    fn intersection_prepare_computations_reflectv() {
+        let shape = Plane::new();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 1.0, -1.0),
+            Tuple::new_vector(0.0, -SQRT_2 / 2.0, SQRT_2 / 2.0),
+        );
+        let intersection = Intersection::new(SQRT_2, &shape);
+        let comps =
+            intersection.prepare_computations(&ray, &Intersections::new(vec![intersection]));
+        assert!(comps
+            .reflectv
+            .is_equal_to(&Tuple::new_vector(0.0, SQRT_2 / 2.0, SQRT_2 / 2.0)));
+    }
+
+    #[test]
+    fn computations_glossy_reflect_ray() {
+        let shape = Plane::new();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 1.0, -1.0),
+            Tuple::new_vector(0.0, -SQRT_2 / 2.0, SQRT_2 / 2.0),
+        );
+        let intersection = Intersection::new(SQRT_2, &shape);
+        let comps =
+            intersection.prepare_computations(&ray, &Intersections::new(vec![intersection]));
+        let mut rng = Rng::new(1);
+        // A smooth surface reflects in the mirror direction
+        let glossy = comps.glossy_reflect_ray(0.0, &mut rng);
+        assert!(glossy.direction.is_equal_to(&comps.reflectv));
+        // Rough surfaces spread rays around it, all leaving the surface
+        for _ in 0..100 {
+            let glossy = comps.glossy_reflect_ray(0.5, &mut rng);
+            assert!(glossy.origin.is_equal_to(&comps.over_point));
+            assert_approx_eq!(glossy.direction.magnitude(), 1.0);
+            assert!(glossy.direction.dot_product(&comps.normalv) > 0.0);
+            // An offset of at most 0.5 turns the unit mirror direction by at most 30 degrees
+            assert!(glossy.direction.dot_product(&comps.reflectv) >= 0.75_f64.sqrt() - EPSILON);
+        }
+    }
+
+    #[test]
+    fn intersection_prepare_computations_n1_n2() {
+        let mut a = Sphere::glass();
+        a.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let mut b = Sphere::glass();
+        b.set_transform(translation(0.0, 0.0, -0.25)).unwrap();
+        b.set_material(Material {
+            refractive_index: 2.0,
+            ..b.material().clone()
+        });
+        let mut c = Sphere::glass();
+        c.set_transform(translation(0.0, 0.0, 0.25)).unwrap();
+        c.set_material(Material {
+            refractive_index: 2.5,
+            ..c.material().clone()
+        });
+
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -4.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = Intersections::new(vec![
+            Intersection::new(2.0, &a),
+            Intersection::new(2.75, &b),
+            Intersection::new(3.25, &c),
+            Intersection::new(4.75, &b),
+            Intersection::new(5.25, &c),
+            Intersection::new(6.0, &a),
+        ]);
+        let expected = [
+            (1.0, 1.5),
+            (1.5, 2.0),
+            (2.0, 2.5),
+            (2.5, 2.5),
+            (2.5, 1.5),
+            (1.5, 1.0),
+        ];
+        for (intersection, (n1, n2)) in xs.iter().zip(expected) {
+            let comps = intersection.prepare_computations(&ray, &xs);
+            assert_approx_eq!(comps.n1, n1);
+            assert_approx_eq!(comps.n2, n2);
+        }
+    }
+
+    #[test]
+    fn intersection_prepare_computations_under_point() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let mut shape = Sphere::glass();
+        shape.set_transform(translation(0.0, 0.0, 1.0)).unwrap();
+        let intersection = Intersection::new(5.0, &shape);
+        let comps =
+            intersection.prepare_computations(&ray, &Intersections::new(vec![intersection]));
+        assert!(comps.under_point.z > EPSILON / 2.0);
+        assert!(comps.point.z < comps.under_point.z);
+    }
+
+    #[test]
+    fn intersection_prepare_computations_with_bias() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let shape = Sphere::new();
+        let intersection = Intersection::new(4.0, &shape);
+        let comps = intersection.prepare_computations_with_bias(
+            &ray,
+            &Intersections::new(vec![intersection]),
+            0.01,
+        );
+        assert_approx_eq!(comps.over_point.z, -1.01);
+        assert_approx_eq!(comps.under_point.z, -0.99);
+    }
+
+    #[test]
+    fn computations_schlick_total_internal_reflection() {
+        let shape = Sphere::glass();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, SQRT_2 / 2.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        let xs = Intersections::new(vec![
+            Intersection::new(-SQRT_2 / 2.0, &shape),
+            Intersection::new(SQRT_2 / 2.0, &shape),
+        ]);
+        let comps = xs[1].prepare_computations(&ray, &xs);
+        assert_approx_eq!(comps.schlick(), 1.0);
+    }
+
+    #[test]
+    fn computations_schlick_perpendicular() {
+        let shape = Sphere::glass();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        let xs = Intersections::new(vec![
+            Intersection::new(-1.0, &shape),
+            Intersection::new(1.0, &shape),
+        ]);
+        let comps = xs[1].prepare_computations(&ray, &xs);
+        assert_approx_eq!(comps.schlick(), 0.04);
+    }
+
+    #[test]
+    fn computations_schlick_small_angle() {
+        let shape = Sphere::glass();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.99, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = Intersections::new(vec![Intersection::new(1.8589, &shape)]);
+        let comps = xs[0].prepare_computations(&ray, &xs);
+        // The book's value is rounded to five decimal places.
+        assert_approx_eq!(comps.schlick(), 0.48873, epsilon = 1e-5);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::shapes::Shape;
+use crate::tuple::Tuple;
+use std::ops::Index;
+
+pub mod computations;
+
+pub use computations::{Computations, DEFAULT_SHADOW_BIAS};
+
+/// Intersection of a ray with an object at distance `t` along the ray.
+#[derive(Debug, Clone, Copy)]
+pub struct Intersection<'a> {
+    pub t: f64,
+    pub object: &'a dyn Shape,
+    /// Surface normal in world space, when it was found while intersecting rather than by `object`.
+    /// Instances set it because the surface they hit belongs to shared geometry, not to the instance.
+    pub normal: Option<Tuple>,
+}
+
+impl<'a> Intersection<'a> {
+    /// Create an intersection at distance `t` along a ray with `object`.
+    pub fn new(t: f64, object: &'a dyn Shape) -> Self {
+        Self {
+            t,
+            object,
+            normal: None,
+        }
+    }
+
+    /// Create an intersection with `object` whose surface normal in world space is already known.
+    pub fn with_normal(t: f64, object: &'a dyn Shape, normal: Tuple) -> Self {
+        Self {
+            t,
+            object,
+            normal: Some(normal),
+        }
+    }
+}
+
+/// Collection of intersections, kept sorted by increasing `t`.
+#[derive(Debug, Default)]
+pub struct Intersections<'a> {
+    intersections: Vec<Intersection<'a>>,
+}
+
+impl<'a> Intersections<'a> {
+    /// Create a collection from the given intersections, sorting them by `t`.
+    pub fn new(mut intersections: Vec<Intersection<'a>>) -> Self {
+        intersections.sort_by(|a, b| a.t.total_cmp(&b.t));
+        Self { intersections }
+    }
+
+    /// Add the intersections from another collection, keeping this collection sorted.
+    pub fn extend(&mut self, other: Intersections<'a>) {
+        self.intersections.extend(other.intersections);
+        self.intersections.sort_by(|a, b| a.t.total_cmp(&b.t));
+    }
+
+    /// Get the number of intersections in the collection.
+    pub fn len(&self) -> usize {
+        self.intersections.len()
+    }
+
+    /// Test if the collection has no intersections.
+    pub fn is_empty(&self) -> bool {
+        self.intersections.is_empty()
+    }
+
+    /// Iterate over the intersections in order of increasing `t`.
+    pub fn iter(&self) -> std::slice::Iter<'_, Intersection<'a>> {
+        self.intersections.iter()
+    }
+
+    /// Take the intersections out of the collection in order of increasing `t`.
+    pub fn into_vec(self) -> Vec<Intersection<'a>> {
+        self.intersections
+    }
+
+    /// Find the visible intersection, which is the one with the lowest non-negative `t`.
+    pub fn hit(&self) -> Option<&Intersection<'a>> {
+        self.intersections.iter().find(|i| i.t >= 0.0)
+    }
+}
+
+impl<'a> Index<usize> for Intersections<'a> {
+    type Output = Intersection<'a>;
+
+    /// Get the intersection at `index` in order of increasing `t`.
+    fn index(&self, index: usize) -> &Intersection<'a> {
+        &self.intersections[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::shapes::Sphere;
+
+    #[test]
+    fn intersection_new() {
+        let sphere = Sphere::new();
+        let intersection = Intersection::new(3.5, &sphere);
+        assert_approx_eq!(intersection.t, 3.5);
+        assert!(std::ptr::addr_eq(intersection.object, &sphere));
+    }
+
+    #[test]
+    fn intersections_new() {
+        let sphere = Sphere::new();
+        let xs = Intersections::new(vec![
+            Intersection::new(2.0, &sphere),
+            Intersection::new(1.0, &sphere),
+        ]);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 1.0);
+        assert_approx_eq!(xs[1].t, 2.0);
+    }
+
+    #[test]
+    fn intersections_extend() {
+        let sphere = Sphere::new();
+        let mut xs = Intersections::new(vec![
+            Intersection::new(5.0, &sphere),
+            Intersection::new(1.0, &sphere),
+        ]);
+        xs.extend(Intersections::new(vec![
+            Intersection::new(3.0, &sphere),
+            Intersection::new(-2.0, &sphere),
+        ]));
+        let ts: Vec<f64> = xs.iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![-2.0, 1.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn intersections_hit_all_positive() {
+        let sphere = Sphere::new();
+        let xs = Intersections::new(vec![
+            Intersection::new(1.0, &sphere),
+            Intersection::new(2.0, &sphere),
+        ]);
+        assert_approx_eq!(xs.hit().unwrap().t, 1.0);
+    }
+
+    #[test]
+    fn intersections_hit_some_negative() {
+        let sphere = Sphere::new();
+        let xs = Intersections::new(vec![
+            Intersection::new(-1.0, &sphere),
+            Intersection::new(1.0, &sphere),
+        ]);
+        assert_approx_eq!(xs.hit().unwrap().t, 1.0);
+    }
+
+    #[test]
+    fn intersections_hit_all_negative() {
+        let sphere = Sphere::new();
+        let xs = Intersections::new(vec![
+            Intersection::new(-2.0, &sphere),
+            Intersection::new(-1.0, &sphere),
+        ]);
+        assert!(xs.hit().is_none());
+    }
+
+    #[test]
+    fn intersections_hit_lowest_non_negative() {
+        let sphere = Sphere::new();
+        let xs = Intersections::new(vec![
+            Intersection::new(5.0, &sphere),
+            Intersection::new(7.0, &sphere),
+            Intersection::new(-3.0, &sphere),
+            Intersection::new(2.0, &sphere),
+        ]);
+        assert_approx_eq!(xs.hit().unwrap().t, 2.0);
+    }
+}
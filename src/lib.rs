@@ -1 +1,28 @@
+/// Epsilon used for floating-point comparisons
+const EPSILON: f64 = 1e-6;
+
+pub mod animation;
+pub mod camera;
+pub mod canvas;
+pub mod color;
+#[cfg(feature = "yaml")]
+pub mod distributed;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+pub mod import;
+pub mod interop;
+pub mod intersection;
+pub mod light;
+pub mod math;
+pub mod matrix;
+pub mod patterns;
+pub mod random;
+pub mod ray;
+#[cfg(feature = "yaml")]
+pub mod scene;
+pub mod sdf;
+pub mod shapes;
+pub mod transform;
 pub mod tuple;
+pub mod uv;
+pub mod world;
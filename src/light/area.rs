@@ -0,0 +1,194 @@
+use super::{Falloff, Light};
+use crate::color::Color;
+use crate::random::{JitteredSampler, Rng, Sampler};
+use crate::tuple::Tuple;
+use std::sync::Arc;
+
+/// Flat rectangular light source, which casts soft shadows.
+///
+/// The rectangle spans `uvec` and `vvec` from `corner`, and is divided into a `usteps` by `vsteps` grid
+/// with one point sampled per cell, placed within the cell by `sampler`. The fraction of the points
+/// visible from a position decides how much light reaches it, so more cells give smoother penumbras at
+/// the cost of more shadow rays. Shading treats the light as if it came from the rectangle's center.
+#[derive(Debug, Clone)]
+pub struct AreaLight {
+    pub corner: Tuple,
+    pub uvec: Tuple,
+    pub usteps: usize,
+    pub vvec: Tuple,
+    pub vsteps: usize,
+    /// Strategy placing the point sampled in each cell, such as a `UniformSampler` for the cell's center.
+    pub sampler: Arc<dyn Sampler>,
+    pub intensity: Color,
+    /// Fading of the light with the distance from its center.
+    pub falloff: Falloff,
+}
+
+impl AreaLight {
+    /// Create a light spanning `uvec` and `vvec` from `corner`, sampled on a `usteps` by `vsteps` grid
+    /// with a `JitteredSampler`, which does not fade with distance.
+    pub fn new(
+        corner: Tuple,
+        uvec: Tuple,
+        usteps: usize,
+        vvec: Tuple,
+        vsteps: usize,
+        intensity: Color,
+    ) -> Self {
+        Self {
+            corner,
+            uvec,
+            usteps,
+            vvec,
+            vsteps,
+            sampler: Arc::new(JitteredSampler),
+            intensity,
+            falloff: Falloff::None,
+        }
+    }
+
+    /// Get the center of the light.
+    pub fn position(&self) -> Tuple {
+        self.corner + self.uvec * 0.5 + self.vvec * 0.5
+    }
+
+    /// Get the points sampled on the light to find how much of it is visible from a position, one per cell
+    /// of its grid, drawing any randomness from `rng`.
+    pub fn sample_points(&self, rng: &mut Rng) -> Vec<Tuple> {
+        let (usteps, vsteps) = (self.usteps.max(1), self.vsteps.max(1));
+        let mut points = Vec::with_capacity(usteps * vsteps);
+        for v in 0..vsteps {
+            for u in 0..usteps {
+                let offsets = self.sampler.sample_square(1, rng);
+                let (u_offset, v_offset) = offsets.first().copied().unwrap_or((0.5, 0.5));
+                let u = (u as f64 + u_offset) / usteps as f64;
+                let v = (v as f64 + v_offset) / vsteps as f64;
+                points.push(self.corner + self.uvec * u + self.vvec * v);
+            }
+        }
+        points
+    }
+}
+
+impl Light for AreaLight {
+    fn intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn falloff(&self) -> Falloff {
+        self.falloff
+    }
+
+    fn direction_from(&self, point: &Tuple) -> Tuple {
+        (self.position() - point).normalize()
+    }
+
+    fn distance_from(&self, point: &Tuple) -> f64 {
+        (self.position() - point).magnitude()
+    }
+
+    fn samples_from(&self, point: &Tuple, rng: &mut Rng) -> Vec<(Tuple, f64)> {
+        self.sample_points(rng)
+            .into_iter()
+            .map(|sample| {
+                let toward = sample - point;
+                let distance = toward.magnitude();
+                (toward / distance, distance)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::random::UniformSampler;
+
+    /// Sampler placing every point at the same offset, to test where the offsets land.
+    #[derive(Debug)]
+    struct FixedSampler(f64, f64);
+
+    impl Sampler for FixedSampler {
+        fn sample_square(&self, count: usize, _rng: &mut Rng) -> Vec<(f64, f64)> {
+            vec![(self.0, self.1); count]
+        }
+    }
+
+    fn light(sampler: impl Sampler + 'static) -> AreaLight {
+        let mut light = AreaLight::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(2.0, 0.0, 0.0),
+            4,
+            Tuple::new_vector(0.0, 0.0, 1.0),
+            2,
+            Color::new(1.0, 1.0, 1.0),
+        );
+        light.sampler = Arc::new(sampler);
+        light
+    }
+
+    #[test]
+    fn area_light_new() {
+        let light = light(JitteredSampler);
+        assert!(light
+            .position()
+            .is_equal_to(&Tuple::new_point(1.0, 0.0, 0.5)));
+        let point = Tuple::new_point(1.0, 0.0, -1.5);
+        assert_approx_eq!(light.distance_from(&point), 2.0);
+        assert!(light
+            .direction_from(&point)
+            .is_equal_to(&Tuple::new_vector(0.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn area_light_sample_points_centered() {
+        let points = light(UniformSampler).sample_points(&mut Rng::new(0));
+        let cases = [
+            (0, Tuple::new_point(0.25, 0.0, 0.25)),
+            (1, Tuple::new_point(0.75, 0.0, 0.25)),
+            (3, Tuple::new_point(1.75, 0.0, 0.25)),
+            (4, Tuple::new_point(0.25, 0.0, 0.75)),
+            (7, Tuple::new_point(1.75, 0.0, 0.75)),
+        ];
+        assert_eq!(points.len(), 8);
+        for (index, expected) in cases {
+            assert!(points[index].is_equal_to(&expected), "{index}");
+        }
+    }
+
+    #[test]
+    fn area_light_sample_points_offset() {
+        let points = light(FixedSampler(0.3, 0.7)).sample_points(&mut Rng::new(0));
+        assert!(points[0].is_equal_to(&Tuple::new_point(0.15, 0.0, 0.35)));
+        assert!(points[1].is_equal_to(&Tuple::new_point(0.65, 0.0, 0.35)));
+        assert!(points[7].is_equal_to(&Tuple::new_point(1.65, 0.0, 0.85)));
+    }
+
+    #[test]
+    fn area_light_sample_points_jittered() {
+        let light = light(JitteredSampler);
+        let points = light.sample_points(&mut Rng::new(3));
+        // Each point stays within its cell, and the same random numbers give the same points
+        for (index, sample) in points.iter().enumerate() {
+            let (u, v) = (index % 4, index / 4);
+            assert_eq!((sample.x * 2.0) as usize, u);
+            assert_eq!((sample.z * 2.0) as usize, v);
+        }
+        let again = light.sample_points(&mut Rng::new(3));
+        assert!(points.iter().zip(&again).all(|(a, b)| a.is_equal_to(b)));
+        let other = light.sample_points(&mut Rng::new(4));
+        assert!(!points[0].is_equal_to(&other[0]));
+    }
+
+    #[test]
+    fn area_light_samples_from() {
+        let light = light(UniformSampler);
+        let point = Tuple::new_point(0.25, 0.0, -2.75);
+        let samples = light.samples_from(&point, &mut Rng::new(0));
+        assert_eq!(samples.len(), 8);
+        let (direction, distance) = samples[0];
+        assert!(direction.is_equal_to(&Tuple::new_vector(0.0, 0.0, 1.0)));
+        assert_approx_eq!(distance, 3.0);
+    }
+}
@@ -0,0 +1,96 @@
+use super::Light;
+use crate::color::Color;
+use crate::tuple::Tuple;
+
+/// Light source infinitely far away, such as the sun, whose rays all travel in the same direction.
+///
+/// It has no position, so it does not fall off with distance and every object along the direction
+/// toward it casts a shadow, however far away it is.
+#[derive(Debug)]
+pub struct DirectionalLight {
+    direction: Tuple,
+    pub intensity: Color,
+}
+
+impl DirectionalLight {
+    /// Create a light whose rays travel along `direction` with the given `intensity`.
+    pub fn new(direction: Tuple, intensity: Color) -> Self {
+        Self {
+            direction: direction.normalize(),
+            intensity,
+        }
+    }
+
+    /// Get the normalized direction in which the light's rays travel.
+    pub fn direction(&self) -> &Tuple {
+        &self.direction
+    }
+}
+
+impl Light for DirectionalLight {
+    fn intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn direction_from(&self, _point: &Tuple) -> Tuple {
+        -&self.direction
+    }
+
+    fn distance_from(&self, _point: &Tuple) -> f64 {
+        f64::INFINITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::light::{lighting, Material};
+    use crate::shapes::Sphere;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    #[test]
+    fn directional_light_new() {
+        let light =
+            DirectionalLight::new(Tuple::new_vector(0.0, -2.0, 0.0), Color::new(1.0, 1.0, 1.0));
+        assert!(light
+            .direction()
+            .is_equal_to(&Tuple::new_vector(0.0, -1.0, 0.0)));
+        assert!(light.intensity().is_equal_to(&Color::new(1.0, 1.0, 1.0)));
+    }
+
+    #[test]
+    fn directional_light_direction_from() {
+        let light =
+            DirectionalLight::new(Tuple::new_vector(1.0, -1.0, 0.0), Color::new(1.0, 1.0, 1.0));
+        for point in [
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_point(1e6, -1e6, 1e6),
+        ] {
+            assert!(light.direction_from(&point).is_equal_to(&Tuple::new_vector(
+                -FRAC_1_SQRT_2,
+                FRAC_1_SQRT_2,
+                0.0
+            )));
+            assert_eq!(light.distance_from(&point), f64::INFINITY);
+        }
+    }
+
+    #[test]
+    fn directional_light_lighting() {
+        let material = Material::default();
+        let object = Sphere::new();
+        let light =
+            DirectionalLight::new(Tuple::new_vector(0.0, 0.0, 1.0), Color::new(1.0, 1.0, 1.0));
+        let result = lighting(
+            &material,
+            &object,
+            &light,
+            &Tuple::new_point(0.0, 0.0, 0.0),
+            &Tuple::new_vector(0.0, 0.0, -1.0),
+            &Tuple::new_vector(0.0, 0.0, -1.0),
+            false,
+        );
+        // Lit head on, exactly like a point light directly in front of the surface
+        assert!(result.is_equal_to(&Color::new(1.9, 1.9, 1.9)));
+    }
+}
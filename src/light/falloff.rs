@@ -0,0 +1,57 @@
+/// Way the brightness of a light fades with the distance from it.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Falloff {
+    /// Keep the full intensity at any distance, as the book's lights do.
+    #[default]
+    None,
+    /// Fade linearly from the full intensity at the light to nothing at `radius`, so the light only
+    /// reaches that far.
+    Linear { radius: f64 },
+    /// Fade with the inverse square of the distance, as real lights do, like a glowing ball of the given
+    /// `radius`: within it the intensity is full, and at twice the radius a quarter of it.
+    InverseSquare { radius: f64 },
+}
+
+impl Falloff {
+    /// Compute the fraction of a light's intensity that remains at `distance` from it.
+    pub fn attenuation(&self, distance: f64) -> f64 {
+        match *self {
+            Falloff::None => 1.0,
+            Falloff::Linear { radius } => (1.0 - distance / radius).clamp(0.0, 1.0),
+            Falloff::InverseSquare { radius } => {
+                let ratio = radius / distance.max(radius);
+                ratio * ratio
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn falloff_attenuation() {
+        let cases = [
+            (Falloff::None, 0.0, 1.0),
+            (Falloff::None, 1e6, 1.0),
+            (Falloff::Linear { radius: 10.0 }, 0.0, 1.0),
+            (Falloff::Linear { radius: 10.0 }, 2.5, 0.75),
+            (Falloff::Linear { radius: 10.0 }, 10.0, 0.0),
+            (Falloff::Linear { radius: 10.0 }, 20.0, 0.0),
+            (Falloff::InverseSquare { radius: 2.0 }, 0.0, 1.0),
+            (Falloff::InverseSquare { radius: 2.0 }, 2.0, 1.0),
+            (Falloff::InverseSquare { radius: 2.0 }, 4.0, 0.25),
+            (Falloff::InverseSquare { radius: 2.0 }, 20.0, 0.01),
+            (Falloff::InverseSquare { radius: 2.0 }, f64::INFINITY, 0.0),
+        ];
+        for (falloff, distance, expected) in cases {
+            assert_approx_eq!(
+                falloff.attenuation(distance),
+                expected,
+                "{falloff:?} at {distance}"
+            );
+        }
+    }
+}
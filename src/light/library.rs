@@ -0,0 +1,104 @@
+use super::Material;
+use crate::color::Color;
+use std::collections::HashMap;
+
+/// Collection of materials registered under names, so a scene can reuse a material by name instead of
+/// repeating its properties on every shape.
+///
+/// Shapes take a copy of a registered material with `set_material`, and the scene loader looks up
+/// materials given by name, such as `material: brushed-metal`, in the library it is given.
+#[derive(Debug, Clone, Default)]
+pub struct MaterialLibrary {
+    materials: HashMap<String, Material>,
+}
+
+impl MaterialLibrary {
+    /// Create an empty library.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a library of ready-made materials: `glass`, `mirror` and a white `matte`.
+    pub fn presets() -> Self {
+        let mut library = Self::new();
+        library.register("glass", Material::glass());
+        library.register("mirror", Material::mirror());
+        library.register("matte", Material::matte(Color::new(1.0, 1.0, 1.0)));
+        library
+    }
+
+    /// Register a material under a name, returning the material previously registered under it, if any.
+    pub fn register(&mut self, name: impl Into<String>, material: Material) -> Option<Material> {
+        self.materials.insert(name.into(), material)
+    }
+
+    /// Get the material registered under a name.
+    pub fn get(&self, name: &str) -> Option<&Material> {
+        self.materials.get(name)
+    }
+
+    /// Get the number of registered materials.
+    pub fn len(&self) -> usize {
+        self.materials.len()
+    }
+
+    /// Check whether no materials are registered.
+    pub fn is_empty(&self) -> bool {
+        self.materials.is_empty()
+    }
+
+    /// Iterate over the names of the registered materials, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.materials.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::shapes::{Shape, Sphere};
+
+    fn brushed_metal() -> Material {
+        Material {
+            color: Color::new(0.8, 0.8, 0.85),
+            reflective: 0.3,
+            ..Material::default()
+        }
+    }
+
+    #[test]
+    fn material_library_register() {
+        let mut library = MaterialLibrary::new();
+        assert!(library.is_empty());
+        assert!(library.register("brushed-metal", brushed_metal()).is_none());
+        assert_eq!(library.len(), 1);
+        assert_eq!(library.names().collect::<Vec<_>>(), vec!["brushed-metal"]);
+
+        let replaced = library
+            .register("brushed-metal", Material::default())
+            .unwrap();
+        assert_approx_eq!(replaced.reflective, 0.3);
+        assert_eq!(library.len(), 1);
+    }
+
+    #[test]
+    fn material_library_get() {
+        let mut library = MaterialLibrary::new();
+        library.register("brushed-metal", brushed_metal());
+        assert!(library.get("glass").is_none());
+
+        let mut sphere = Sphere::new();
+        sphere.set_material(library.get("brushed-metal").unwrap().clone());
+        assert_approx_eq!(sphere.material().reflective, 0.3);
+    }
+
+    #[test]
+    fn material_library_presets() {
+        let library = MaterialLibrary::presets();
+        assert_eq!(library.len(), 3);
+        assert_approx_eq!(library.get("glass").unwrap().refractive_index, 1.5);
+        assert_approx_eq!(library.get("mirror").unwrap().reflective, 1.0);
+        assert_approx_eq!(library.get("matte").unwrap().specular, 0.0);
+    }
+}
@@ -0,0 +1,319 @@
+use super::Medium;
+use crate::color::Color;
+use crate::patterns::Pattern;
+use crate::shapes::Shape;
+use crate::tuple::Tuple;
+use std::sync::Arc;
+
+/// Surface properties used by the Phong reflection model.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Material {
+    pub color: Color,
+    /// Pattern that, when present, replaces `color` as the color of the surface.
+    /// Serializing fails if the pattern has no serialized form.
+    #[cfg_attr(feature = "serde", serde(with = "serialized_pattern"))]
+    pub pattern: Option<Arc<dyn Pattern>>,
+    pub ambient: f64,
+    pub diffuse: f64,
+    pub specular: f64,
+    pub shininess: f64,
+    /// Light given off by the surface itself, such as by a neon sign or a light panel, which is seen
+    /// whether or not the surface is lit. Black for surfaces that give off no light.
+    pub emissive: Color,
+    /// How much of the surrounding scene the surface reflects, from 0 (not at all) to 1 (a perfect mirror).
+    pub reflective: f64,
+    /// How much the surface behaves like a metal, from 0 (a dielectric such as plastic, whose reflections
+    /// keep the colors of what they show) to 1 (a metal, whose reflections are tinted by its own color).
+    pub metalness: f64,
+    /// How rough the surface is at a scale too fine to see, from 0 (polished, with sharp reflections) to
+    /// 1 (blurring reflections over a wide cone, as brushed metal does). Rough surfaces reflect several
+    /// jittered rays, which is slower.
+    pub roughness: f64,
+    /// How much light passes through the surface, from 0 (opaque) to 1 (fully transparent).
+    /// Light passing through is tinted by the surface's color, so transparent materials cast shadows of
+    /// their color: white lets light through unchanged, and black glass casts a shadow as dark as an
+    /// opaque object's.
+    pub transparency: f64,
+    /// How much light bends when entering or leaving the material, such as 1.0 for a vacuum or 1.5 for glass.
+    pub refractive_index: f64,
+    /// Medium filling the inside of the shape, turning it into a volume such as a cloud of smoke.
+    /// The surface of a volume is invisible and casts no shadow; only the medium is seen.
+    pub medium: Option<Medium>,
+    /// Number of reflections and refractions a ray may have gone through and still be reflected and
+    /// refracted further by this surface, overriding the world's `max_depth`. Stacks of glass need more
+    /// than the rest of a scene to look clear.
+    pub max_depth: Option<usize>,
+}
+
+/// Serialization of a material's pattern through its `SerializedPattern`.
+#[cfg(feature = "serde")]
+mod serialized_pattern {
+    use crate::patterns::{Pattern, SerializedPattern};
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+    use std::sync::Arc;
+
+    pub fn serialize<S: Serializer>(
+        pattern: &Option<Arc<dyn Pattern>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let serialized = match pattern {
+            Some(pattern) => Some(pattern.serialized().ok_or_else(|| {
+                ser::Error::custom(format!("pattern cannot be serialized: {pattern:?}"))
+            })?),
+            None => None,
+        };
+        serialized.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Arc<dyn Pattern>>, D::Error> {
+        Option::<SerializedPattern>::deserialize(deserializer)?
+            .map(|serialized| {
+                Box::<dyn Pattern>::try_from(serialized)
+                    .map(Arc::from)
+                    .map_err(de::Error::custom)
+            })
+            .transpose()
+    }
+}
+
+impl Default for Material {
+    /// Create a white material with the book's default Phong parameters.
+    fn default() -> Self {
+        Self {
+            color: Color::new(1.0, 1.0, 1.0),
+            pattern: None,
+            ambient: 0.1,
+            diffuse: 0.9,
+            specular: 0.9,
+            shininess: 200.0,
+            emissive: Color::new(0.0, 0.0, 0.0),
+            reflective: 0.0,
+            metalness: 0.0,
+            roughness: 0.0,
+            transparency: 0.0,
+            refractive_index: 1.0,
+            medium: None,
+            max_depth: None,
+        }
+    }
+}
+
+impl Material {
+    /// Create clear glass with a refractive index of 1.5. The surface itself is dark apart from its
+    /// highlights, so its look comes from what it reflects and refracts, mixed by the Fresnel effect.
+    /// Its white color lets light through untinted, so it casts a clear shadow.
+    pub fn glass() -> Self {
+        Self {
+            color: Color::new(1.0, 1.0, 1.0),
+            ambient: 0.0,
+            diffuse: 0.0,
+            specular: 1.0,
+            shininess: 300.0,
+            reflective: 1.0,
+            transparency: 1.0,
+            refractive_index: 1.5,
+            ..Self::default()
+        }
+    }
+
+    /// Create polished metal of the given color, whose reflections take on the color.
+    pub fn metal(color: Color) -> Self {
+        Self {
+            color,
+            ambient: 0.0,
+            diffuse: 0.1,
+            specular: 1.0,
+            shininess: 300.0,
+            reflective: 1.0,
+            metalness: 1.0,
+            ..Self::default()
+        }
+    }
+
+    /// Create a perfect mirror, which shows only what it reflects and its highlights.
+    pub fn mirror() -> Self {
+        Self {
+            color: Color::new(0.0, 0.0, 0.0),
+            ambient: 0.0,
+            diffuse: 0.0,
+            specular: 1.0,
+            shininess: 300.0,
+            reflective: 1.0,
+            ..Self::default()
+        }
+    }
+
+    /// Create a dull surface of the given color, without highlights.
+    pub fn matte(color: Color) -> Self {
+        Self {
+            color,
+            specular: 0.0,
+            ..Self::default()
+        }
+    }
+
+    /// Get the color of the surface of `object` at a point in world space, from the pattern if there is
+    /// one and from `color` otherwise.
+    pub fn color_at(&self, object: &dyn Shape, point: &Tuple) -> Color {
+        match &self.pattern {
+            Some(pattern) => pattern.pattern_at_shape(object, point),
+            None => self.color,
+        }
+    }
+
+    /// Get the color that reflections off the surface of `object` at a point in world space are tinted
+    /// by: white for dielectrics, blending into the surface's color as `metalness` grows.
+    pub fn reflection_tint(&self, object: &dyn Shape, point: &Tuple) -> Color {
+        if self.metalness == 0.0 {
+            return Color::new(1.0, 1.0, 1.0);
+        }
+        Color::new(1.0, 1.0, 1.0) * (1.0 - self.metalness)
+            + self.color_at(object, point) * self.metalness
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn material_default() {
+        let material = Material::default();
+        assert!(material.color.is_equal_to(&Color::new(1.0, 1.0, 1.0)));
+        assert!(material.pattern.is_none());
+        assert_approx_eq!(material.ambient, 0.1);
+        assert_approx_eq!(material.diffuse, 0.9);
+        assert_approx_eq!(material.specular, 0.9);
+        assert_approx_eq!(material.shininess, 200.0);
+        assert!(material.emissive.is_equal_to(&Color::new(0.0, 0.0, 0.0)));
+        assert_approx_eq!(material.reflective, 0.0);
+        assert_approx_eq!(material.metalness, 0.0);
+        assert_approx_eq!(material.roughness, 0.0);
+        assert_approx_eq!(material.transparency, 0.0);
+        assert_approx_eq!(material.refractive_index, 1.0);
+        assert!(material.medium.is_none());
+    }
+
+    #[test]
+    fn material_presets() {
+        let glass = Material::glass();
+        assert_approx_eq!(glass.transparency, 1.0);
+        assert_approx_eq!(glass.refractive_index, 1.5);
+        assert_approx_eq!(glass.reflective, 1.0);
+        assert_approx_eq!(glass.diffuse, 0.0);
+        assert_approx_eq!(glass.color, Color::new(1.0, 1.0, 1.0));
+
+        let metal = Material::metal(Color::new(1.0, 0.8, 0.4));
+        assert_approx_eq!(metal.metalness, 1.0);
+        assert_approx_eq!(metal.reflective, 1.0);
+
+        let mirror = Material::mirror();
+        assert_approx_eq!(mirror.reflective, 1.0);
+        assert_approx_eq!(mirror.transparency, 0.0);
+        assert_approx_eq!(mirror.diffuse, 0.0);
+
+        let matte = Material::matte(Color::new(1.0, 0.5, 0.0));
+        assert!(matte.color.is_equal_to(&Color::new(1.0, 0.5, 0.0)));
+        assert_approx_eq!(matte.specular, 0.0);
+        assert_approx_eq!(matte.diffuse, Material::default().diffuse);
+    }
+
+    #[test]
+    fn material_reflection_tint() {
+        let object = crate::shapes::Sphere::new();
+        let point = Tuple::new_point(0.0, 1.0, 0.0);
+        let cases = [
+            (0.0, Color::new(1.0, 1.0, 1.0)),
+            (0.5, Color::new(1.0, 0.75, 0.5)),
+            (1.0, Color::new(1.0, 0.5, 0.0)),
+        ];
+        for (metalness, expected) in cases {
+            let material = Material {
+                color: Color::new(1.0, 0.5, 0.0),
+                metalness,
+                ..Material::default()
+            };
+            assert_approx_eq!(
+                material.reflection_tint(&object, &point),
+                expected,
+                "{metalness}"
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn material_serde() {
+        use crate::patterns::{PerturbedPattern, StripePattern};
+        use crate::shapes::Sphere;
+        use crate::transform::scaling;
+        use crate::uv::{TextureMap, UvCheckers, UvMapping};
+
+        let object = Sphere::new();
+        let point = Tuple::new_point(0.0, 1.0, 0.0);
+        let material = Material {
+            color: Color::new(1.0, 0.0, 0.0),
+            pattern: Some(Arc::new(crate::patterns::SolidPattern::new(Color::new(
+                0.0, 1.0, 0.0,
+            )))),
+            reflective: 0.5,
+            ..Material::default()
+        };
+        let json = serde_json::to_string(&material).unwrap();
+        let deserialized: Material = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.color.is_equal_to(&material.color));
+        assert_approx_eq!(deserialized.reflective, 0.5);
+        assert_approx_eq!(
+            deserialized.color_at(&object, &point),
+            Color::new(0.0, 1.0, 0.0)
+        );
+
+        // Nested patterns keep their own transforms
+        let mut stripes = StripePattern::new(
+            Color::new(1.0, 1.0, 1.0),
+            PerturbedPattern::new(Color::new(0.0, 0.0, 1.0), 0.2),
+        );
+        stripes.set_transform(scaling(0.5, 1.0, 1.0)).unwrap();
+        let material = Material {
+            pattern: Some(Arc::new(stripes)),
+            ..Material::default()
+        };
+        let deserialized: Material =
+            serde_json::from_str(&serde_json::to_string(&material).unwrap()).unwrap();
+        for x in [0.1, 0.6, 1.3] {
+            let point = Tuple::new_point(x, 0.2, 0.3);
+            assert_approx_eq!(
+                deserialized.color_at(&object, &point),
+                material.color_at(&object, &point)
+            );
+        }
+
+        // Patterns without a serialized form cannot be serialized
+        let material = Material {
+            pattern: Some(Arc::new(TextureMap::new(
+                UvCheckers::new(
+                    2.0,
+                    2.0,
+                    Color::new(0.0, 0.0, 0.0),
+                    Color::new(1.0, 1.0, 1.0),
+                ),
+                UvMapping::Planar,
+            ))),
+            ..Material::default()
+        };
+        assert!(serde_json::to_string(&material).is_err());
+
+        // Missing fields take their default values
+        let deserialized: Material = serde_json::from_str(r#"{"diffuse":0.5}"#).unwrap();
+        assert_approx_eq!(deserialized.diffuse, 0.5);
+        assert_approx_eq!(deserialized.shininess, 200.0);
+    }
+}
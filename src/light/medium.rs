@@ -0,0 +1,66 @@
+use crate::color::Color;
+
+/// Participating medium with the same density everywhere, such as fog, smoke or murky water.
+///
+/// Light passing through the medium fades by the Beer–Lambert law, keeping `exp(-density × distance)`
+/// of its color, and the medium's own `color` takes the place of the light that was lost. With `samples`
+/// set, that color is only seen where the medium is lit: points along the ray are tested for shadows so
+/// that shafts of light appear between the objects blocking the light.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Medium {
+    pub color: Color,
+    /// How quickly the medium absorbs light, per unit of distance.
+    pub density: f64,
+    /// Number of points along each ray tested for light, or 0 to color the medium evenly.
+    pub samples: usize,
+}
+
+impl Medium {
+    /// Create an evenly colored medium.
+    pub fn new(color: Color, density: f64) -> Self {
+        Self {
+            color,
+            density,
+            samples: 0,
+        }
+    }
+
+    /// Compute the fraction of light that passes through `distance` units of the medium.
+    pub fn transmittance(&self, distance: f64) -> f64 {
+        if distance == f64::INFINITY {
+            return if self.density > 0.0 { 0.0 } else { 1.0 };
+        }
+        (-self.density * distance).exp()
+    }
+
+    /// Compute how far light travels through the medium before less than a thousandth of it is left.
+    /// Lit media are only sampled up to this distance.
+    pub fn visibility(&self) -> f64 {
+        if self.density > 0.0 {
+            1000f64.ln() / self.density
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn medium_transmittance() {
+        let medium = Medium::new(Color::new(0.5, 0.5, 0.5), 0.5);
+        let cases = [(0.0, 1.0), (2.0, (-1.0f64).exp()), (f64::INFINITY, 0.0)];
+        for (distance, expected) in cases {
+            assert_approx_eq!(medium.transmittance(distance), expected);
+        }
+        assert_approx_eq!(medium.transmittance(medium.visibility()), 0.001);
+
+        let clear = Medium::new(Color::new(0.5, 0.5, 0.5), 0.0);
+        assert_approx_eq!(clear.transmittance(f64::INFINITY), 1.0);
+        assert_eq!(clear.visibility(), f64::INFINITY);
+    }
+}
@@ -0,0 +1,359 @@
+use crate::color::Color;
+use crate::random::Rng;
+use crate::shapes::Shape;
+use crate::tuple::Tuple;
+use std::fmt::Debug;
+
+pub mod area;
+pub mod directional;
+pub mod falloff;
+pub mod library;
+pub mod material;
+pub mod medium;
+
+pub use area::AreaLight;
+pub use directional::DirectionalLight;
+pub use falloff::Falloff;
+pub use library::MaterialLibrary;
+pub use material::Material;
+pub use medium::Medium;
+
+/// Source of light illuminating the objects of a world.
+///
+/// Implementors describe where light arrives from at a given point, which is all the shading and shadow
+/// computations need.
+pub trait Light: Debug + Send + Sync {
+    /// Get the color and brightness of the light.
+    fn intensity(&self) -> Color;
+
+    /// Get the way the light fades with distance. Lights do not fade unless they say otherwise.
+    fn falloff(&self) -> Falloff {
+        Falloff::None
+    }
+
+    /// Compute the color and brightness of the light arriving at a point, faded by its falloff.
+    fn intensity_at(&self, point: &Tuple) -> Color {
+        match self.falloff() {
+            Falloff::None => self.intensity(),
+            falloff => self.intensity() * falloff.attenuation(self.distance_from(point)),
+        }
+    }
+
+    /// Compute the normalized vector from a point toward the light.
+    fn direction_from(&self, point: &Tuple) -> Tuple;
+
+    /// Compute the distance from a point to the light, which is infinite for lights with no position.
+    /// Objects further away than this do not cast shadows from this light.
+    fn distance_from(&self, point: &Tuple) -> f64;
+
+    /// Compute the normalized directions and distances from a point toward the parts of the light that
+    /// shadow rays are cast toward. The fraction of them that no object blocks is the fraction of the
+    /// light reaching the point, so lights with a size cast soft shadows. Any randomness in where the
+    /// samples lie is drawn from `rng`.
+    /// Lights with no size are a single sample, toward `direction_from` at `distance_from`.
+    fn samples_from(&self, point: &Tuple, _rng: &mut Rng) -> Vec<(Tuple, f64)> {
+        vec![(self.direction_from(point), self.distance_from(point))]
+    }
+}
+
+/// Light source with no size that radiates in every direction from a single point.
+#[derive(Debug)]
+pub struct PointLight {
+    pub position: Tuple,
+    pub intensity: Color,
+    pub falloff: Falloff,
+}
+
+impl PointLight {
+    /// Create a point light at `position` with the given `intensity`, which does not fade with distance.
+    pub fn new(position: Tuple, intensity: Color) -> Self {
+        Self {
+            position,
+            intensity,
+            falloff: Falloff::None,
+        }
+    }
+}
+
+impl Light for PointLight {
+    fn intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn falloff(&self) -> Falloff {
+        self.falloff
+    }
+
+    fn direction_from(&self, point: &Tuple) -> Tuple {
+        (self.position - point).normalize()
+    }
+
+    fn distance_from(&self, point: &Tuple) -> f64 {
+        (self.position - point).magnitude()
+    }
+}
+
+/// Compute the color of a point on a surface using the Phong reflection model.
+/// `eyev` is the vector from the point to the eye and `normalv` is the surface normal at the point.
+/// A point in shadow only receives ambient light. `object` is the shape being lit, which positions the material's pattern.
+pub fn lighting(
+    material: &Material,
+    object: &dyn Shape,
+    light: &dyn Light,
+    point: &Tuple,
+    eyev: &Tuple,
+    normalv: &Tuple,
+    in_shadow: bool,
+) -> Color {
+    let visibility = if in_shadow { 0.0 } else { 1.0 };
+    let visibility = Color::new(visibility, visibility, visibility);
+    lighting_with_visibility(material, object, light, point, eyev, normalv, visibility)
+}
+
+/// Compute the color of a point on a surface like `lighting`, for a point that sees the fraction
+/// `visibility` of the light in each channel, from 0 in full shadow to 1 in full light. Only the diffuse
+/// and specular light are dimmed, so points in the penumbra of an area light are shaded in between, and
+/// light tinted by the transparent objects it passes through colors the points it reaches.
+pub fn lighting_with_visibility(
+    material: &Material,
+    object: &dyn Shape,
+    light: &dyn Light,
+    point: &Tuple,
+    eyev: &Tuple,
+    normalv: &Tuple,
+    visibility: Color,
+) -> Color {
+    let color = material.color_at(object, point);
+    let intensity = light.intensity_at(point);
+    let effective_color = color * intensity;
+    let lightv = light.direction_from(point);
+    let ambient = effective_color * material.ambient;
+    if visibility.is_equal_to(&Color::default()) {
+        return ambient;
+    }
+
+    // A negative cosine between the light vector and the normal means the light is on the other side of the surface
+    let light_dot_normal = lightv.dot_product(normalv);
+    if light_dot_normal < 0.0 {
+        return ambient;
+    }
+    let diffuse = effective_color * material.diffuse * light_dot_normal;
+
+    // A negative cosine between the reflection vector and the eye vector means the light reflects away from the eye
+    let reflectv = (-&lightv).reflect(normalv);
+    let reflect_dot_eye = reflectv.dot_product(eyev);
+    let specular = if reflect_dot_eye <= 0.0 {
+        Color::default()
+    } else {
+        intensity * material.specular * reflect_dot_eye.powf(material.shininess)
+    };
+
+    ambient + (diffuse + specular) * visibility
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::patterns::StripePattern;
+    use crate::shapes::Sphere;
+    use std::sync::Arc;
+
+    #[test]
+    fn point_light_new() {
+        let light = PointLight::new(Tuple::new_point(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0));
+        assert!(light.position.is_equal_to(&Tuple::new_point(0.0, 0.0, 0.0)));
+        assert!(light.intensity.is_equal_to(&Color::new(1.0, 1.0, 1.0)));
+    }
+
+    #[test]
+    fn point_light_direction_from() {
+        let light = PointLight::new(Tuple::new_point(0.0, 10.0, 0.0), Color::new(1.0, 1.0, 1.0));
+        let point = Tuple::new_point(0.0, 4.0, 0.0);
+        assert!(light
+            .direction_from(&point)
+            .is_equal_to(&Tuple::new_vector(0.0, 1.0, 0.0)));
+        assert_approx_eq!(light.distance_from(&point), 6.0, epsilon = crate::EPSILON);
+    }
+
+    #[test]
+    fn point_light_intensity_at() {
+        let mut light =
+            PointLight::new(Tuple::new_point(0.0, 10.0, 0.0), Color::new(1.0, 0.5, 1.0));
+        let point = Tuple::new_point(0.0, 0.0, 0.0);
+        assert!(light
+            .intensity_at(&point)
+            .is_equal_to(&Color::new(1.0, 0.5, 1.0)));
+        light.falloff = Falloff::InverseSquare { radius: 5.0 };
+        assert!(light
+            .intensity_at(&point)
+            .is_equal_to(&Color::new(0.25, 0.125, 0.25)));
+    }
+
+    #[test]
+    fn lighting_with_falloff() {
+        let material = Material::default();
+        let object = Sphere::new();
+        let position = Tuple::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::new_vector(0.0, 0.0, -1.0);
+        let mut light =
+            PointLight::new(Tuple::new_point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        light.falloff = Falloff::Linear { radius: 20.0 };
+        // Ambient, diffuse and specular light all fade by half
+        let result = lighting(
+            &material, &object, &light, &position, &eyev, &normalv, false,
+        );
+        assert!(result.is_equal_to(&Color::new(0.95, 0.95, 0.95)));
+    }
+
+    #[test]
+    fn lighting_eye_between_light_and_surface() {
+        let material = Material::default();
+        let object = Sphere::new();
+        let position = Tuple::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(Tuple::new_point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        let result = lighting(
+            &material, &object, &light, &position, &eyev, &normalv, false,
+        );
+        assert!(result.is_equal_to(&Color::new(1.9, 1.9, 1.9)));
+    }
+
+    #[test]
+    fn lighting_eye_offset_45_degrees() {
+        let material = Material::default();
+        let object = Sphere::new();
+        let position = Tuple::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::new_vector(0.0, 2.0_f64.sqrt() / 2.0, -(2.0_f64.sqrt()) / 2.0);
+        let normalv = Tuple::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(Tuple::new_point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        let result = lighting(
+            &material, &object, &light, &position, &eyev, &normalv, false,
+        );
+        assert!(result.is_equal_to(&Color::new(1.0, 1.0, 1.0)));
+    }
+
+    #[test]
+    fn lighting_light_offset_45_degrees() {
+        let material = Material::default();
+        let object = Sphere::new();
+        let position = Tuple::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(
+            Tuple::new_point(0.0, 10.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+        let result = lighting(
+            &material, &object, &light, &position, &eyev, &normalv, false,
+        );
+        // The book's values are rounded to four decimal places.
+        assert_approx_eq!(result, Color::new(0.7364, 0.7364, 0.7364), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn lighting_eye_in_path_of_reflection() {
+        let material = Material::default();
+        let object = Sphere::new();
+        let position = Tuple::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::new_vector(0.0, -(2.0_f64.sqrt()) / 2.0, -(2.0_f64.sqrt()) / 2.0);
+        let normalv = Tuple::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(
+            Tuple::new_point(0.0, 10.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+        let result = lighting(
+            &material, &object, &light, &position, &eyev, &normalv, false,
+        );
+        // The book's values are rounded to four decimal places.
+        assert_approx_eq!(result, Color::new(1.6364, 1.6364, 1.6364), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn lighting_light_behind_surface() {
+        let material = Material::default();
+        let object = Sphere::new();
+        let position = Tuple::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(Tuple::new_point(0.0, 0.0, 10.0), Color::new(1.0, 1.0, 1.0));
+        let result = lighting(
+            &material, &object, &light, &position, &eyev, &normalv, false,
+        );
+        assert!(result.is_equal_to(&Color::new(0.1, 0.1, 0.1)));
+    }
+
+    #[test]
+    fn lighting_surface_in_shadow() {
+        let material = Material::default();
+        let object = Sphere::new();
+        let position = Tuple::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(Tuple::new_point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        let result = lighting(&material, &object, &light, &position, &eyev, &normalv, true);
+        assert!(result.is_equal_to(&Color::new(0.1, 0.1, 0.1)));
+    }
+
+    #[test]
+    fn lighting_with_visibility_partial() {
+        let material = Material::default();
+        let object = Sphere::new();
+        let position = Tuple::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(Tuple::new_point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        let cases = [
+            (Color::new(1.0, 1.0, 1.0), Color::new(1.9, 1.9, 1.9)),
+            (Color::new(0.5, 0.5, 0.5), Color::new(1.0, 1.0, 1.0)),
+            (Color::new(0.0, 0.0, 0.0), Color::new(0.1, 0.1, 0.1)),
+            (Color::new(1.0, 0.5, 0.0), Color::new(1.9, 1.0, 0.1)),
+        ];
+        for (visibility, expected) in cases {
+            let result = lighting_with_visibility(
+                &material, &object, &light, &position, &eyev, &normalv, visibility,
+            );
+            assert!(result.is_equal_to(&expected));
+        }
+    }
+
+    #[test]
+    fn lighting_with_pattern() {
+        let material = Material {
+            pattern: Some(Arc::new(StripePattern::new(
+                Color::new(1.0, 1.0, 1.0),
+                Color::new(0.0, 0.0, 0.0),
+            ))),
+            ambient: 1.0,
+            diffuse: 0.0,
+            specular: 0.0,
+            ..Material::default()
+        };
+        let object = Sphere::new();
+        let eyev = Tuple::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(Tuple::new_point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        let c1 = lighting(
+            &material,
+            &object,
+            &light,
+            &Tuple::new_point(0.9, 0.0, 0.0),
+            &eyev,
+            &normalv,
+            false,
+        );
+        let c2 = lighting(
+            &material,
+            &object,
+            &light,
+            &Tuple::new_point(1.1, 0.0, 0.0),
+            &eyev,
+            &normalv,
+            false,
+        );
+        assert!(c1.is_equal_to(&Color::new(1.0, 1.0, 1.0)));
+        assert!(c2.is_equal_to(&Color::new(0.0, 0.0, 0.0)));
+    }
+}
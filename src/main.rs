@@ -1,3 +1,240 @@
-fn main() {
-    println!("Hello, world!");
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use the_ray_tracer_challenge::camera::Camera;
+use the_ray_tracer_challenge::canvas::post_process::Srgb;
+use the_ray_tracer_challenge::canvas::ppm::PpmFormat;
+use the_ray_tracer_challenge::canvas::{Canvas, Pipeline, PostProcess, ToneMapping};
+use the_ray_tracer_challenge::distributed::{render_distributed, serve, RenderJob};
+use the_ray_tracer_challenge::scene::Scene;
+
+/// Render scenes described in YAML files.
+#[derive(Debug, Parser)]
+#[command(name = "raytracer", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Render a scene to an image file.
+    Render(RenderArgs),
+    /// Render tiles of scenes for `render --worker` on other machines.
+    Worker(WorkerArgs),
+}
+
+#[derive(Debug, Args)]
+struct WorkerArgs {
+    /// Address to listen on for render jobs.
+    #[arg(long, default_value = "127.0.0.1:7878")]
+    listen: String,
+}
+
+#[derive(Debug, Args)]
+struct RenderArgs {
+    /// Scene file to render.
+    scene: PathBuf,
+    /// Image file to write, as PNG, PPM or Radiance HDR depending on its extension.
+    #[arg(short, long, default_value = "out.png")]
+    output: PathBuf,
+    /// Width of the image in pixels, overriding the scene's camera.
+    #[arg(long)]
+    width: Option<usize>,
+    /// Height of the image in pixels, overriding the scene's camera.
+    #[arg(long)]
+    height: Option<usize>,
+    /// Exposure in stops (EV), overriding the scene's camera. Each stop doubles the brightness.
+    #[arg(long, allow_negative_numbers = true)]
+    exposure: Option<f64>,
+    /// Seed of the random numbers drawn while rendering, overriding the scene's camera.
+    /// Renders with the same seed are identical.
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Number of reflections and refractions followed from each ray, overriding the default of 5.
+    /// Materials setting their own `max-depth` are not affected.
+    #[arg(long)]
+    max_depth: Option<usize>,
+    /// Tone mapping compressing bright colors into the range of PNG and PPM images.
+    /// HDR images keep the full range.
+    #[arg(long, value_enum, default_value_t = ToneMap::None)]
+    tone_map: ToneMap,
+    /// Write linear colors to PNG and PPM images instead of encoding them as sRGB.
+    #[arg(long)]
+    linear: bool,
+    /// Render a heatmap of the intersection tests performed for each pixel instead of the scene.
+    #[arg(long)]
+    heatmap: bool,
+    /// Address of a worker to share the render with, started with the `worker` command.
+    /// Repeat the option to use several workers.
+    #[arg(long = "worker")]
+    workers: Vec<String>,
+}
+
+/// Tone mapping operator chosen on the command line.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ToneMap {
+    /// Clip colors brighter than white.
+    None,
+    Reinhard,
+    Aces,
+}
+
+impl ToneMap {
+    /// Get the library's operator, if any.
+    fn mapping(self) -> Option<ToneMapping> {
+        match self {
+            Self::None => None,
+            Self::Reinhard => Some(ToneMapping::Reinhard),
+            Self::Aces => Some(ToneMapping::Aces),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Render(args) => render(&args),
+        Command::Worker(args) => work(&args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn render(args: &RenderArgs) -> Result<(), Box<dyn Error>> {
+    let output_error = |error: Box<dyn Error>| format!("{}: {error}", args.output.display());
+    // Check the output format before spending time on the render
+    let format = OutputFormat::from_path(&args.output).map_err(output_error)?;
+    let mut scene =
+        Scene::load(&args.scene).map_err(|error| format!("{}: {error}", args.scene.display()))?;
+    let mut camera = resized_camera(&scene.camera, args.width, args.height)?;
+    if let Some(exposure) = args.exposure {
+        camera.set_exposure(exposure);
+    }
+    if let Some(seed) = args.seed {
+        camera.set_seed(seed);
+    }
+    if let Some(max_depth) = args.max_depth {
+        scene.world.max_depth = max_depth;
+    }
+    if !format.is_high_dynamic_range() {
+        let mut pipeline = Pipeline::new();
+        if let Some(mapping) = args.tone_map.mapping() {
+            pipeline.push(mapping);
+        }
+        if !args.linear {
+            pipeline.push(Srgb);
+        }
+        camera.set_post_processing(pipeline);
+    }
+    let image = if args.heatmap {
+        camera.render_heatmap(&scene.world)
+    } else if !args.workers.is_empty() {
+        let job = RenderJob {
+            source: std::fs::read_to_string(&args.scene)?,
+            width: camera.hsize(),
+            height: camera.vsize(),
+            exposure: camera.exposure(),
+            seed: camera.seed(),
+            max_depth: scene.world.max_depth,
+        };
+        let mut image = render_distributed(&job, &args.workers, 32)?;
+        camera.post_processing().apply(&mut image);
+        image
+    } else {
+        render_with_progress_bar(&camera, &scene)
+    };
+    format.save(&image, &args.output).map_err(output_error)?;
+    Ok(())
+}
+
+/// Serve render jobs until the process is stopped.
+fn work(args: &WorkerArgs) -> Result<(), Box<dyn Error>> {
+    let listener =
+        TcpListener::bind(&args.listen).map_err(|error| format!("{}: {error}", args.listen))?;
+    eprintln!("listening on {}", listener.local_addr()?);
+    serve(listener)?;
+    Ok(())
+}
+
+/// Render the scene while showing a progress bar with the estimated time left on the terminal.
+fn render_with_progress_bar(camera: &Camera, scene: &Scene) -> Canvas {
+    let bar = ProgressBar::new(camera.vsize() as u64).with_message("?");
+    bar.set_style(
+        ProgressStyle::with_template("{bar:40} {pos}/{len} rows [{elapsed_precise}, {msg} left]")
+            .expect("the progress bar template is valid"),
+    );
+    let image = camera.render_with_progress(&scene.world, |progress| {
+        if let Some(remaining) = progress.estimated_remaining() {
+            bar.set_message(format!("{}s", remaining.as_secs()));
+        }
+        bar.set_position(progress.rows_completed as u64);
+    });
+    bar.finish();
+    image
+}
+
+/// Create a copy of the scene's camera with the requested size.
+fn resized_camera(
+    camera: &Camera,
+    width: Option<usize>,
+    height: Option<usize>,
+) -> Result<Camera, Box<dyn Error>> {
+    let width = width.unwrap_or(camera.hsize());
+    let height = height.unwrap_or(camera.vsize());
+    if width == 0 || height == 0 {
+        return Err("the image must be at least one pixel wide and high".into());
+    }
+    Ok(camera.resized(width, height))
+}
+
+/// Format of the rendered image file.
+#[derive(Debug, Clone, Copy)]
+enum OutputFormat {
+    Png,
+    Ppm,
+    Hdr,
+}
+
+impl OutputFormat {
+    /// Choose the format matching the extension of a path.
+    fn from_path(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("png") => Ok(Self::Png),
+            Some("ppm") => Ok(Self::Ppm),
+            Some("hdr") => Ok(Self::Hdr),
+            _ => Err("unsupported image format; use .png, .ppm or .hdr".into()),
+        }
+    }
+
+    /// Check whether the format stores colors brighter than white rather than clipping them.
+    fn is_high_dynamic_range(self) -> bool {
+        matches!(self, Self::Hdr)
+    }
+
+    /// Save an image in this format.
+    fn save(self, image: &Canvas, path: &Path) -> Result<(), Box<dyn Error>> {
+        match self {
+            Self::Png => image.save_png(path)?,
+            Self::Ppm => {
+                image.write_ppm(&mut BufWriter::new(File::create(path)?), PpmFormat::P6)?
+            }
+            Self::Hdr => image.write_hdr(&mut BufWriter::new(File::create(path)?))?,
+        }
+        Ok(())
+    }
 }
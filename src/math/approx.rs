@@ -0,0 +1,82 @@
+/// Tolerance used by `ApproxEq::approx_eq` and `assert_approx_eq!` unless another is given.
+pub const DEFAULT_EPSILON: f64 = crate::EPSILON;
+
+/// Comparison of values that are equal up to floating-point error.
+///
+/// Values are approximately equal when every pair of corresponding components differs by less than
+/// a tolerance, which is `DEFAULT_EPSILON` unless another is given with `approx_eq_with`.
+pub trait ApproxEq<Rhs: ?Sized = Self> {
+    /// Test if every component differs from the other value's by less than `epsilon`.
+    fn approx_eq_with(&self, other: &Rhs, epsilon: f64) -> bool;
+
+    /// Test if every component differs from the other value's by less than the default tolerance.
+    fn approx_eq(&self, other: &Rhs) -> bool {
+        self.approx_eq_with(other, DEFAULT_EPSILON)
+    }
+}
+
+impl ApproxEq for f64 {
+    fn approx_eq_with(&self, other: &f64, epsilon: f64) -> bool {
+        (self - other).abs() < epsilon
+    }
+}
+
+/// Assert that two values are approximately equal using `ApproxEq`, optionally with a tolerance given
+/// as `epsilon = ...` and a message formatted like `assert!`'s.
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_approx_eq!($left, $right, epsilon = $crate::math::DEFAULT_EPSILON)
+    };
+    ($left:expr, $right:expr, epsilon = $epsilon:expr $(,)?) => {{
+        use $crate::math::ApproxEq as _;
+        let (left, right) = (&$left, &$right);
+        assert!(
+            left.approx_eq_with(right, $epsilon),
+            "assertion `left ≈ right` failed\n  left: {:?}\n right: {:?}",
+            left,
+            right,
+        );
+    }};
+    ($left:expr, $right:expr, epsilon = $epsilon:expr, $($message:tt)+) => {{
+        use $crate::math::ApproxEq as _;
+        let (left, right) = (&$left, &$right);
+        assert!(
+            left.approx_eq_with(right, $epsilon),
+            "assertion `left ≈ right` failed: {}\n  left: {:?}\n right: {:?}",
+            format_args!($($message)+),
+            left,
+            right,
+        );
+    }};
+    ($left:expr, $right:expr, $($message:tt)+) => {{
+        $crate::assert_approx_eq!($left, $right, epsilon = $crate::math::DEFAULT_EPSILON, $($message)+)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approx_eq_f64() {
+        assert!(1.0.approx_eq(&(1.0 + DEFAULT_EPSILON / 2.0)));
+        assert!(!1.0.approx_eq(&(1.0 + DEFAULT_EPSILON * 2.0)));
+        assert!(1.0.approx_eq_with(&1.01, 0.1));
+        assert!(!1.0.approx_eq_with(&1.2, 0.1));
+    }
+
+    #[test]
+    fn assert_approx_eq_passes() {
+        assert_approx_eq!(0.1 + 0.2, 0.3);
+        assert_approx_eq!(1.0, 1.05, epsilon = 0.1);
+        assert_approx_eq!(2.0, 2.0, "exactly {}", 2);
+        assert_approx_eq!(1.0, 1.05, epsilon = 0.1, "within {}", 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "left ≈ right")]
+    fn assert_approx_eq_fails() {
+        assert_approx_eq!(1.0, 1.1);
+    }
+}
@@ -0,0 +1,19 @@
+pub mod approx;
+pub mod noise;
+pub mod onb;
+pub mod quaternion;
+pub mod roots;
+
+pub use approx::{ApproxEq, DEFAULT_EPSILON};
+pub use noise::{fractal_perlin, perlin};
+pub use onb::Onb;
+pub use quaternion::Quaternion;
+
+/// Format a number for display with the formatter's precision, or with at least one decimal place if
+/// none is given, so whole numbers read as `1.0` rather than `1`.
+pub(crate) fn format_number(value: f64, precision: Option<usize>) -> String {
+    match precision {
+        Some(precision) => format!("{value:.precision$}"),
+        None => format!("{value:?}"),
+    }
+}
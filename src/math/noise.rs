@@ -0,0 +1,141 @@
+use crate::random::Rng;
+use crate::tuple::Tuple;
+
+/// Compute Ken Perlin's improved gradient noise at a point: a smooth, random-looking function of space
+/// with features about one unit apart, ranging over roughly [-1, 1] and zero at every whole-numbered
+/// point.
+///
+/// Each whole-numbered point is given a pseudo-random gradient, picked from the twelve directions
+/// toward the edges of a cube, and the noise blends the slopes of the gradients of the eight points
+/// around a point. The same point always has the same noise.
+pub fn perlin(point: &Tuple) -> f64 {
+    let (x0, y0, z0) = (point.x.floor(), point.y.floor(), point.z.floor());
+    let (x, y, z) = (point.x - x0, point.y - y0, point.z - z0);
+    let (i, j, k) = (x0 as i64, y0 as i64, z0 as i64);
+    let corner = |di: i64, dj: i64, dk: i64| {
+        gradient(
+            lattice_hash(i + di, j + dj, k + dk),
+            x - di as f64,
+            y - dj as f64,
+            z - dk as f64,
+        )
+    };
+    let (u, v, w) = (fade(x), fade(y), fade(z));
+    lerp(
+        w,
+        lerp(
+            v,
+            lerp(u, corner(0, 0, 0), corner(1, 0, 0)),
+            lerp(u, corner(0, 1, 0), corner(1, 1, 0)),
+        ),
+        lerp(
+            v,
+            lerp(u, corner(0, 0, 1), corner(1, 0, 1)),
+            lerp(u, corner(0, 1, 1), corner(1, 1, 1)),
+        ),
+    )
+}
+
+/// Sum `octaves` layers of `perlin` noise, each with twice the frequency of the one before and
+/// `persistence` times its amplitude, giving detail at every scale like clouds or stone have.
+///
+/// The sum is divided by the total amplitude, so it keeps the range of a single layer. Persistence
+/// near 0 leaves the first layer's smooth shapes, while persistence near 1 makes the result rough.
+pub fn fractal_perlin(point: &Tuple, octaves: u32, persistence: f64) -> f64 {
+    let (mut sum, mut total, mut amplitude, mut frequency) = (0.0, 0.0, 1.0, 1.0);
+    for _ in 0..octaves {
+        let scaled = Tuple::new_point(
+            point.x * frequency,
+            point.y * frequency,
+            point.z * frequency,
+        );
+        sum += perlin(&scaled) * amplitude;
+        total += amplitude;
+        amplitude *= persistence;
+        frequency *= 2.0;
+    }
+    if total == 0.0 {
+        0.0
+    } else {
+        sum / total
+    }
+}
+
+/// Mix the coordinates of a whole-numbered point into random bits.
+fn lattice_hash(i: i64, j: i64, k: i64) -> u64 {
+    let seed = (i as u64).wrapping_mul(0x8da6_b343)
+        ^ (j as u64).wrapping_mul(0xd816_3841)
+        ^ (k as u64).wrapping_mul(0xcb1a_b31f);
+    Rng::new(seed).next_u64()
+}
+
+/// Compute the dot product of the gradient picked by `hash` with the offset (x, y, z) from its point.
+fn gradient(hash: u64, x: f64, y: f64, z: f64) -> f64 {
+    // The low four bits pick one of twelve edge directions, four of which are repeated
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = match h {
+        0..=3 => y,
+        12 | 14 => x,
+        _ => z,
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+/// Ease a fraction in [0, 1] with 6t⁵ - 15t⁴ + 10t³, whose first and second derivatives vanish at both
+/// ends so the noise is smooth across lattice cells.
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn perlin_lattice_points() {
+        for (x, y, z) in [(0.0, 0.0, 0.0), (1.0, -2.0, 3.0), (-7.0, 5.0, -1.0)] {
+            assert_approx_eq!(perlin(&Tuple::new_point(x, y, z)), 0.0);
+        }
+    }
+
+    #[test]
+    fn fractal_perlin_octaves() {
+        let point = Tuple::new_point(0.3, 1.7, -2.2);
+        assert_approx_eq!(fractal_perlin(&point, 1, 0.5), perlin(&point));
+        assert_approx_eq!(fractal_perlin(&point, 0, 0.5), 0.0);
+        // Each octave adds noise of double the frequency, weighted by the persistence
+        let doubled = Tuple::new_point(0.6, 3.4, -4.4);
+        assert_approx_eq!(
+            fractal_perlin(&point, 2, 0.5),
+            (perlin(&point) + perlin(&doubled) * 0.5) / 1.5
+        );
+        assert_approx_eq!(fractal_perlin(&point, 4, 0.0), perlin(&point));
+    }
+
+    #[test]
+    fn perlin_range_and_continuity() {
+        let mut values = Vec::new();
+        for step in 0..1000 {
+            let t = step as f64 * 0.0137;
+            let point = Tuple::new_point(t, t * 0.7 - 3.0, 2.5 - t * 1.3);
+            let value = perlin(&point);
+            assert!((-1.5..=1.5).contains(&value), "{value}");
+            // Nearby points have nearby values
+            let nearby = perlin(&Tuple::new_point(point.x + 1e-4, point.y, point.z));
+            assert_approx_eq!(value, nearby, epsilon = 1e-3);
+            values.push(value);
+        }
+        // The noise is not constant, and is the same every time
+        assert!(values.iter().any(|value| value.abs() > 0.1));
+        assert_eq!(
+            perlin(&Tuple::new_point(0.5, 0.25, 0.75)),
+            perlin(&Tuple::new_point(0.5, 0.25, 0.75))
+        );
+    }
+}
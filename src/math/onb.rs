@@ -0,0 +1,95 @@
+use crate::tuple::Tuple;
+
+/// Orthonormal basis: three perpendicular unit vectors, with `normal` playing the part of the z axis.
+///
+/// A basis built around a surface normal converts directions given relative to the surface, such as
+/// samples of the hemisphere above it or normals read from a normal map, into world space.
+#[derive(Debug, Clone, Copy)]
+pub struct Onb {
+    pub tangent: Tuple,
+    pub bitangent: Tuple,
+    pub normal: Tuple,
+}
+
+impl Onb {
+    /// Build a basis around a normalized vector, choosing the tangent and bitangent so that
+    /// `tangent × bitangent = normal`.
+    ///
+    /// Uses the method of Duff et al., "Building an Orthonormal Basis, Revisited", which has no special
+    /// cases for normals that are nearly parallel to an axis.
+    pub fn from_normal(normal: &Tuple) -> Self {
+        let sign = 1.0f64.copysign(normal.z);
+        let a = -1.0 / (sign + normal.z);
+        let b = normal.x * normal.y * a;
+        Self {
+            tangent: Tuple::new_vector(
+                1.0 + sign * normal.x * normal.x * a,
+                sign * b,
+                -sign * normal.x,
+            ),
+            bitangent: Tuple::new_vector(b, sign + normal.y * normal.y * a, -normal.y),
+            normal: *normal,
+        }
+    }
+
+    /// Convert a vector given in the basis, whose z component lies along the normal, to world space.
+    pub fn local_to_world(&self, vector: &Tuple) -> Tuple {
+        self.tangent * vector.x + self.bitangent * vector.y + self.normal * vector.z
+    }
+
+    /// Convert a vector in world space to the basis, the inverse of `local_to_world`.
+    pub fn world_to_local(&self, vector: &Tuple) -> Tuple {
+        Tuple::new_vector(
+            vector.dot_product(&self.tangent),
+            vector.dot_product(&self.bitangent),
+            vector.dot_product(&self.normal),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn onb_from_normal() {
+        let normals = [
+            Tuple::new_vector(0.0, 0.0, 1.0),
+            Tuple::new_vector(0.0, 0.0, -1.0),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, -1.0, 0.0),
+            Tuple::new_vector(1.0, 2.0, -3.0).normalize(),
+            Tuple::new_vector(1e-9, 0.0, -1.0).normalize(),
+        ];
+        for normal in normals {
+            let onb = Onb::from_normal(&normal);
+            assert!(onb.normal.is_equal_to(&normal));
+            for axis in [onb.tangent, onb.bitangent] {
+                assert!(axis.is_vector());
+                assert_approx_eq!(axis.magnitude(), 1.0, "{normal}");
+                assert_approx_eq!(axis.dot_product(&normal), 0.0, "{normal}");
+            }
+            assert_approx_eq!(onb.tangent.dot_product(&onb.bitangent), 0.0, "{normal}");
+            assert!(onb
+                .tangent
+                .cross_product(&onb.bitangent)
+                .is_equal_to(&normal));
+        }
+    }
+
+    #[test]
+    fn onb_local_to_world() {
+        let normal = Tuple::new_vector(0.0, 1.0, 0.0);
+        let onb = Onb::from_normal(&normal);
+        assert!(onb
+            .local_to_world(&Tuple::new_vector(0.0, 0.0, 1.0))
+            .is_equal_to(&normal));
+
+        let local = Tuple::new_vector(0.3, -0.4, 0.5);
+        let world = onb.local_to_world(&local);
+        assert_approx_eq!(world.magnitude(), local.magnitude());
+        assert_approx_eq!(world.y, 0.5);
+        assert!(onb.world_to_local(&world).is_equal_to(&local));
+    }
+}
@@ -0,0 +1,315 @@
+use crate::math::ApproxEq;
+use crate::matrix::Matrix4;
+use crate::tuple::Tuple;
+use std::ops::Mul;
+
+/// Rotation stored as a unit quaternion `w + xi + yj + zk`.
+///
+/// Unlike chained `rotation_x`, `rotation_y` and `rotation_z` matrices, quaternions can be blended with
+/// `slerp` into a steady rotation from one orientation to another, without losing an axis of rotation
+/// (gimbal lock) or shrinking objects partway through. Multiplying two quaternions combines their
+/// rotations in the same order as multiplying the matching matrices.
+#[derive(Debug, Clone, Copy)]
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quaternion {
+    /// Quaternion that does not rotate.
+    pub const IDENTITY: Quaternion = Quaternion::new(1.0, 0.0, 0.0, 0.0);
+
+    /// Create a quaternion from its components, which should have a magnitude of 1 to be a rotation.
+    pub const fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// Create a rotation around `axis` by `radians`, turning the same way as `rotation_x` and friends.
+    /// The axis does not need to be normalized.
+    pub fn from_axis_angle(axis: &Tuple, radians: f64) -> Self {
+        let axis = Tuple::new_vector(axis.x, axis.y, axis.z).normalize();
+        let (sin, cos) = (radians / 2.0).sin_cos();
+        Self::new(cos, axis.x * sin, axis.y * sin, axis.z * sin)
+    }
+
+    /// Compute the length of the quaternion as a vector of four components.
+    pub fn magnitude(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Return a quaternion with the same rotation and a magnitude of 1.
+    pub fn normalize(&self) -> Quaternion {
+        let magnitude = self.magnitude();
+        Quaternion::new(
+            self.w / magnitude,
+            self.x / magnitude,
+            self.y / magnitude,
+            self.z / magnitude,
+        )
+    }
+
+    /// Return the conjugate, which undoes the rotation of a unit quaternion.
+    pub fn conjugate(&self) -> Quaternion {
+        Quaternion::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    /// Compute the dot product of the components of this quaternion and another.
+    pub fn dot(&self, other: &Quaternion) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Rotate a point or a vector, leaving its `w` unchanged.
+    pub fn rotate(&self, tuple: &Tuple) -> Tuple {
+        let axis = Tuple::new_vector(self.x, self.y, self.z);
+        let vector = Tuple::new_vector(tuple.x, tuple.y, tuple.z);
+        let twice_cross = axis.cross_product(&vector) * 2.0;
+        let rotated = vector + twice_cross * self.w + axis.cross_product(&twice_cross);
+        Tuple {
+            w: tuple.w,
+            ..rotated
+        }
+    }
+
+    /// Interpolate spherically between two rotations, turning at a constant rate around a single axis
+    /// from this rotation when `t` is 0 to `other` when `t` is 1, the short way around.
+    /// Values of `t` outside of [0, 1] continue turning past either rotation.
+    pub fn slerp(&self, other: &Quaternion, t: f64) -> Quaternion {
+        // A quaternion and its negation are the same rotation; pick the one closer to this one
+        let mut cos = self.dot(other);
+        let other = if cos < 0.0 {
+            cos = -cos;
+            Quaternion::new(-other.w, -other.x, -other.y, -other.z)
+        } else {
+            *other
+        };
+        let (from, to) = if cos > 1.0 - 1e-9 {
+            // The rotations are too close together to divide by the sine of the angle between them,
+            // and blending them linearly is just as accurate
+            (1.0 - t, t)
+        } else {
+            let angle = cos.acos();
+            let sin = angle.sin();
+            (((1.0 - t) * angle).sin() / sin, (t * angle).sin() / sin)
+        };
+        Quaternion::new(
+            self.w * from + other.w * to,
+            self.x * from + other.x * to,
+            self.y * from + other.y * to,
+            self.z * from + other.z * to,
+        )
+        .normalize()
+    }
+}
+
+impl Default for Quaternion {
+    /// Create the quaternion that does not rotate.
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl ApproxEq for Quaternion {
+    /// Compare the components, so a quaternion is not approximately equal to its negation even though
+    /// both are the same rotation.
+    fn approx_eq_with(&self, other: &Quaternion, epsilon: f64) -> bool {
+        self.w.approx_eq_with(&other.w, epsilon)
+            && self.x.approx_eq_with(&other.x, epsilon)
+            && self.y.approx_eq_with(&other.y, epsilon)
+            && self.z.approx_eq_with(&other.z, epsilon)
+    }
+}
+
+impl Mul for Quaternion {
+    type Output = Quaternion;
+
+    /// Combine two rotations, applying `rhs` first and then `self`.
+    fn mul(self, rhs: Quaternion) -> Quaternion {
+        Quaternion::new(
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        )
+    }
+}
+
+impl From<Quaternion> for Matrix4 {
+    /// Create the rotation matrix of a unit quaternion.
+    fn from(q: Quaternion) -> Matrix4 {
+        let (w, x, y, z) = (q.w, q.x, q.y, q.z);
+        Matrix4::new([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+                0.0,
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+                0.0,
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+                0.0,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+}
+
+impl From<Matrix4> for Quaternion {
+    /// Find the rotation of a matrix whose upper-left 3x3 part is a rotation, ignoring any translation.
+    /// Matrices that also scale or shear give meaningless results; use `Matrix4::decompose` for those.
+    fn from(m: Matrix4) -> Quaternion {
+        // Shepperd's method: divide by the largest of the four possible denominators to stay accurate
+        let trace = m[(0, 0)] + m[(1, 1)] + m[(2, 2)];
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Quaternion::new(
+                s / 4.0,
+                (m[(2, 1)] - m[(1, 2)]) / s,
+                (m[(0, 2)] - m[(2, 0)]) / s,
+                (m[(1, 0)] - m[(0, 1)]) / s,
+            )
+        } else if m[(0, 0)] > m[(1, 1)] && m[(0, 0)] > m[(2, 2)] {
+            let s = (1.0 + m[(0, 0)] - m[(1, 1)] - m[(2, 2)]).sqrt() * 2.0;
+            Quaternion::new(
+                (m[(2, 1)] - m[(1, 2)]) / s,
+                s / 4.0,
+                (m[(0, 1)] + m[(1, 0)]) / s,
+                (m[(0, 2)] + m[(2, 0)]) / s,
+            )
+        } else if m[(1, 1)] > m[(2, 2)] {
+            let s = (1.0 + m[(1, 1)] - m[(0, 0)] - m[(2, 2)]).sqrt() * 2.0;
+            Quaternion::new(
+                (m[(0, 2)] - m[(2, 0)]) / s,
+                (m[(0, 1)] + m[(1, 0)]) / s,
+                s / 4.0,
+                (m[(1, 2)] + m[(2, 1)]) / s,
+            )
+        } else {
+            let s = (1.0 + m[(2, 2)] - m[(0, 0)] - m[(1, 1)]).sqrt() * 2.0;
+            Quaternion::new(
+                (m[(1, 0)] - m[(0, 1)]) / s,
+                (m[(0, 2)] + m[(2, 0)]) / s,
+                (m[(1, 2)] + m[(2, 1)]) / s,
+                s / 4.0,
+            )
+        };
+        q.normalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::{rotation_x, rotation_y, rotation_z, translation};
+    use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn quaternion_from_axis_angle() {
+        let q = Quaternion::from_axis_angle(&Tuple::new_vector(0.0, 2.0, 0.0), FRAC_PI_2);
+        assert_approx_eq!(q, Quaternion::new(FRAC_1_SQRT_2, 0.0, FRAC_1_SQRT_2, 0.0));
+        assert_approx_eq!(q.magnitude(), 1.0);
+        assert_approx_eq!(
+            Quaternion::from_axis_angle(&Tuple::new_vector(1.0, 0.0, 0.0), 0.0),
+            Quaternion::IDENTITY
+        );
+    }
+
+    #[test]
+    fn quaternion_rotate() {
+        let q = Quaternion::from_axis_angle(&Tuple::new_vector(1.0, 0.0, 0.0), FRAC_PI_4);
+        let point = Tuple::new_point(0.0, 1.0, 0.0);
+        assert!(q
+            .rotate(&point)
+            .is_equal_to(&Tuple::new_point(0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2)));
+        assert!(q
+            .rotate(&point)
+            .is_equal_to(&(rotation_x(FRAC_PI_4) * point)));
+        assert!(q.conjugate().rotate(&q.rotate(&point)).is_equal_to(&point));
+
+        let vector = Tuple::new_vector(1.0, 2.0, 3.0);
+        let q = Quaternion::from_axis_angle(&Tuple::new_vector(1.0, 1.0, 1.0), 2.0 * PI / 3.0);
+        // A third of a turn around the diagonal cycles the axes
+        assert!(q
+            .rotate(&vector)
+            .is_equal_to(&Tuple::new_vector(3.0, 1.0, 2.0)));
+    }
+
+    #[test]
+    fn quaternion_to_matrix() {
+        let cases = [
+            (Tuple::new_vector(1.0, 0.0, 0.0), rotation_x(0.7)),
+            (Tuple::new_vector(0.0, 1.0, 0.0), rotation_y(0.7)),
+            (Tuple::new_vector(0.0, 0.0, 1.0), rotation_z(0.7)),
+        ];
+        for (axis, expected) in cases {
+            let matrix = Matrix4::from(Quaternion::from_axis_angle(&axis, 0.7));
+            assert_approx_eq!(matrix, expected, "{axis}");
+        }
+    }
+
+    #[test]
+    fn quaternion_from_matrix() {
+        let angles = [0.0, 0.5, FRAC_PI_2, 2.5, PI];
+        for x in angles {
+            for y in angles {
+                for z in angles {
+                    let matrix = rotation_z(z) * rotation_y(y) * rotation_x(x);
+                    let q = Quaternion::from(matrix);
+                    assert_approx_eq!(q.magnitude(), 1.0);
+                    assert_approx_eq!(Matrix4::from(q), matrix, "{x} {y} {z}");
+                }
+            }
+        }
+        // Translation is ignored
+        let q = Quaternion::from(translation(1.0, 2.0, 3.0) * rotation_y(0.5));
+        assert_approx_eq!(Matrix4::from(q), rotation_y(0.5));
+    }
+
+    #[test]
+    fn quaternion_mul() {
+        let x = Quaternion::from_axis_angle(&Tuple::new_vector(1.0, 0.0, 0.0), 0.3);
+        let y = Quaternion::from_axis_angle(&Tuple::new_vector(0.0, 1.0, 0.0), 1.1);
+        assert_approx_eq!(Matrix4::from(y * x), rotation_y(1.1) * rotation_x(0.3));
+        assert_approx_eq!(x * Quaternion::IDENTITY, x);
+        assert_approx_eq!(x * x.conjugate(), Quaternion::IDENTITY);
+    }
+
+    #[test]
+    fn quaternion_slerp() {
+        let axis = Tuple::new_vector(0.0, 0.0, 1.0);
+        let start = Quaternion::IDENTITY;
+        let end = Quaternion::from_axis_angle(&axis, FRAC_PI_2);
+        assert_approx_eq!(start.slerp(&end, 0.0), start);
+        assert_approx_eq!(start.slerp(&end, 1.0), end);
+        assert_approx_eq!(
+            start.slerp(&end, 0.5),
+            Quaternion::from_axis_angle(&axis, FRAC_PI_4)
+        );
+        assert_approx_eq!(
+            start.slerp(&end, 2.0),
+            Quaternion::from_axis_angle(&axis, PI)
+        );
+
+        // The negation of a rotation is the same rotation, so the blend takes the short way around
+        let negated = Quaternion::new(-end.w, -end.x, -end.y, -end.z);
+        let halfway = start.slerp(&negated, 0.5);
+        assert_approx_eq!(
+            Matrix4::from(halfway),
+            Matrix4::from(Quaternion::from_axis_angle(&axis, FRAC_PI_4))
+        );
+
+        // Nearly equal rotations blend without dividing by zero
+        let close = Quaternion::from_axis_angle(&axis, 1e-10);
+        assert_approx_eq!(start.slerp(&close, 0.5), start);
+    }
+}
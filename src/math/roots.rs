@@ -0,0 +1,199 @@
+use std::f64::consts::PI;
+
+/// Coefficients smaller than this are treated as zero by the solvers.
+const TOLERANCE: f64 = 1e-9;
+
+/// Find the real roots of `ax² + bx + c` in ascending order, falling back to the linear equation
+/// when `a` is zero. A repeated root is returned twice.
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    if a.abs() < TOLERANCE {
+        return if b.abs() < TOLERANCE {
+            Vec::new()
+        } else {
+            vec![-c / b]
+        };
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return Vec::new();
+    }
+    // Avoid subtracting nearly equal numbers when b is large
+    let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+    if q.abs() < TOLERANCE {
+        return vec![-b / (2.0 * a); 2];
+    }
+    let (first, second) = (q / a, c / q);
+    if first <= second {
+        vec![first, second]
+    } else {
+        vec![second, first]
+    }
+}
+
+/// Find the real roots of `ax³ + bx² + cx + d` in ascending order with Cardano's method.
+pub fn solve_cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    if a.abs() < TOLERANCE {
+        return solve_quadratic(b, c, d);
+    }
+    let (b, c, d) = (b / a, c / a, d / a);
+
+    // Substitute x = y - b/3 to get the depressed cubic y³ + py + q
+    let p = (3.0 * c - b * b) / 9.0;
+    let q = (2.0 * b * b * b - 9.0 * b * c + 27.0 * d) / 54.0;
+    let p_cubed = p * p * p;
+    let discriminant = q * q + p_cubed;
+
+    let roots = if discriminant.abs() < TOLERANCE {
+        if q.abs() < TOLERANCE {
+            vec![0.0]
+        } else {
+            let u = (-q).cbrt();
+            vec![2.0 * u, -u]
+        }
+    } else if discriminant < 0.0 {
+        // Three distinct real roots, found with trigonometry
+        let phi = (-q / (-p_cubed).sqrt()).clamp(-1.0, 1.0).acos() / 3.0;
+        let scale = 2.0 * (-p).sqrt();
+        vec![
+            scale * phi.cos(),
+            -scale * (phi + PI / 3.0).cos(),
+            -scale * (phi - PI / 3.0).cos(),
+        ]
+    } else {
+        let sqrt_discriminant = discriminant.sqrt();
+        vec![(sqrt_discriminant - q).cbrt() - (sqrt_discriminant + q).cbrt()]
+    };
+    let mut roots: Vec<f64> = roots.into_iter().map(|y| y - b / 3.0).collect();
+    roots.sort_by(f64::total_cmp);
+    roots
+}
+
+/// Find the real roots of `ax⁴ + bx³ + cx² + dx + e` in ascending order with Ferrari's method,
+/// refining each root with Newton's method to undo the rounding of the closed-form solution.
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    if a.abs() < TOLERANCE {
+        return solve_cubic(b, c, d, e);
+    }
+    let (b, c, d, e) = (b / a, c / a, d / a, e / a);
+
+    // Substitute x = y - b/4 to get the depressed quartic y⁴ + py² + qy + r
+    let b_squared = b * b;
+    let p = -3.0 / 8.0 * b_squared + c;
+    let q = b_squared * b / 8.0 - b * c / 2.0 + d;
+    let r = -3.0 / 256.0 * b_squared * b_squared + b_squared * c / 16.0 - b * d / 4.0 + e;
+
+    let mut roots = if r.abs() < TOLERANCE {
+        // y(y³ + py + q) = 0
+        let mut roots = solve_cubic(1.0, 0.0, p, q);
+        roots.push(0.0);
+        roots
+    } else {
+        // The largest real root of the resolvent cubic splits the quartic into two real quadratics
+        let resolvent = solve_cubic(1.0, -p / 2.0, -r, r * p / 2.0 - q * q / 8.0);
+        let z = resolvent[resolvent.len() - 1];
+        let u = z * z - r;
+        let v = 2.0 * z - p;
+        if u < -TOLERANCE || v < -TOLERANCE {
+            Vec::new()
+        } else {
+            let u = u.max(0.0).sqrt();
+            let v = if q < 0.0 {
+                -v.max(0.0).sqrt()
+            } else {
+                v.max(0.0).sqrt()
+            };
+            let mut roots = solve_quadratic(1.0, v, z - u);
+            roots.extend(solve_quadratic(1.0, -v, z + u));
+            roots
+        }
+    };
+
+    let polynomial = |x: f64| (((x + b) * x + c) * x + d) * x + e;
+    let derivative = |x: f64| ((4.0 * x + 3.0 * b) * x + 2.0 * c) * x + d;
+    for root in roots.iter_mut() {
+        *root -= b / 4.0;
+        for _ in 0..4 {
+            let slope = derivative(*root);
+            if slope.abs() < TOLERANCE {
+                break;
+            }
+            *root -= polynomial(*root) / slope;
+        }
+    }
+    roots.sort_by(f64::total_cmp);
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    fn assert_roots(roots: &[f64], expected: &[f64]) {
+        assert_eq!(roots.len(), expected.len(), "{roots:?}");
+        for (root, expected) in roots.iter().zip(expected) {
+            assert_approx_eq!(root, expected, "{roots:?}");
+        }
+    }
+
+    #[test]
+    fn solve_quadratic_known_roots() {
+        let cases: [([f64; 3], &[f64]); 5] = [
+            // (x - 1)(x - 3)
+            ([1.0, -4.0, 3.0], &[1.0, 3.0]),
+            // -(x + 2)(x - 5)
+            ([-1.0, 3.0, 10.0], &[-2.0, 5.0]),
+            // (x - 2)²
+            ([1.0, -4.0, 4.0], &[2.0, 2.0]),
+            // x² + 1 has no real roots
+            ([1.0, 0.0, 1.0], &[]),
+            // 2x - 4
+            ([0.0, 2.0, -4.0], &[2.0]),
+        ];
+        for ([a, b, c], expected) in cases {
+            assert_roots(&solve_quadratic(a, b, c), expected);
+        }
+    }
+
+    #[test]
+    fn solve_quadratic_without_cancellation() {
+        // The small root is lost to cancellation by the textbook formula
+        let roots = solve_quadratic(1.0, -1e8, 1.0);
+        assert_approx_eq!(roots[0], 1e-8, epsilon = 1e-20);
+        assert_approx_eq!(roots[1], 1e8);
+    }
+
+    #[test]
+    fn solve_cubic_known_roots() {
+        let cases: [([f64; 4], &[f64]); 4] = [
+            // (x - 1)(x - 2)(x - 3)
+            ([1.0, -6.0, 11.0, -6.0], &[1.0, 2.0, 3.0]),
+            // 2(x + 1)(x² + 1)
+            ([2.0, 2.0, 2.0, 2.0], &[-1.0]),
+            // x³
+            ([1.0, 0.0, 0.0, 0.0], &[0.0]),
+            // (x - 1)²(x + 2)
+            ([1.0, 0.0, -3.0, 2.0], &[-2.0, 1.0]),
+        ];
+        for ([a, b, c, d], expected) in cases {
+            assert_roots(&solve_cubic(a, b, c, d), expected);
+        }
+    }
+
+    #[test]
+    fn solve_quartic_known_roots() {
+        let cases: [([f64; 5], &[f64]); 4] = [
+            // (x - 1)(x - 2)(x - 3)(x - 4)
+            ([1.0, -10.0, 35.0, -50.0, 24.0], &[1.0, 2.0, 3.0, 4.0]),
+            // (x² - 1)(x² + 1)
+            ([1.0, 0.0, 0.0, 0.0, -1.0], &[-1.0, 1.0]),
+            // x⁴ + 1 has no real roots
+            ([1.0, 0.0, 0.0, 0.0, 1.0], &[]),
+            // 2x(x - 1)(x + 2)(x - 5)
+            ([2.0, -8.0, -14.0, 20.0, 0.0], &[-2.0, 0.0, 1.0, 5.0]),
+        ];
+        for ([a, b, c, d, e], expected) in cases {
+            assert_roots(&solve_quartic(a, b, c, d, e), expected);
+        }
+    }
+}
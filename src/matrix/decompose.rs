@@ -0,0 +1,133 @@
+use super::{Matrix4, MatrixError};
+use crate::math::{ApproxEq, Quaternion};
+use crate::transform::{scaling, translation};
+use crate::tuple::Tuple;
+
+/// Translation, rotation and scale that make up a transform, applied in the reverse order:
+/// the transform scales first, then rotates and finally translates.
+#[derive(Debug, Clone, Copy)]
+pub struct Decomposition {
+    /// Vector by which the transform moves the origin.
+    pub translation: Tuple,
+    pub rotation: Quaternion,
+    /// Vector of the scale factors along the x, y and z axes. A mirrored transform has a negative x scale.
+    pub scale: Tuple,
+}
+
+impl Decomposition {
+    /// Compose the parts back into a transform.
+    pub fn to_matrix(&self) -> Matrix4 {
+        translation(self.translation.x, self.translation.y, self.translation.z)
+            * Matrix4::from(self.rotation)
+            * scaling(self.scale.x, self.scale.y, self.scale.z)
+    }
+}
+
+impl ApproxEq for Decomposition {
+    fn approx_eq_with(&self, other: &Decomposition, epsilon: f64) -> bool {
+        self.translation.approx_eq_with(&other.translation, epsilon)
+            && self.rotation.approx_eq_with(&other.rotation, epsilon)
+            && self.scale.approx_eq_with(&other.scale, epsilon)
+    }
+}
+
+impl Matrix4 {
+    /// Split a transform into its translation, rotation and scale.
+    ///
+    /// Transforms made only of translations, rotations and scalings compose back exactly with
+    /// `Decomposition::to_matrix`. Shearing and perspective cannot be represented, so for transforms with
+    /// either the result is only an approximation. Returns an error if the transform flattens space along
+    /// some direction, which leaves no rotation to find.
+    pub fn decompose(&self) -> Result<Decomposition, MatrixError> {
+        let translation = Tuple::new_vector(self[(0, 3)], self[(1, 3)], self[(2, 3)]);
+        let mut axes = [0, 1, 2].map(|column| {
+            Tuple::new_vector(self[(0, column)], self[(1, column)], self[(2, column)])
+        });
+        let mut scale = axes.map(|axis| axis.magnitude());
+        if scale.iter().any(|factor| factor.approx_eq(&0.0)) {
+            return Err(MatrixError::NotInvertible);
+        }
+        // A rotation cannot turn space inside out, so a mirroring transform gets a negative scale instead
+        if self.submatrix(3, 3).determinant() < 0.0 {
+            scale[0] = -scale[0];
+        }
+
+        let mut rotation = Matrix4::IDENTITY;
+        for (column, (axis, factor)) in axes.iter_mut().zip(scale).enumerate() {
+            *axis = *axis / factor;
+            for row in 0..3 {
+                rotation[(row, column)] = axis[row];
+            }
+        }
+        Ok(Decomposition {
+            translation,
+            rotation: Quaternion::from(rotation),
+            scale: Tuple::new_vector(scale[0], scale[1], scale[2]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::{rotation_x, rotation_y, rotation_z, shearing};
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn matrix_decompose() {
+        let matrix = translation(1.0, -2.0, 3.0)
+            * rotation_y(FRAC_PI_2)
+            * rotation_x(0.5)
+            * scaling(2.0, 3.0, 0.5);
+        let decomposition = matrix.decompose().unwrap();
+        assert!(decomposition
+            .translation
+            .is_equal_to(&Tuple::new_vector(1.0, -2.0, 3.0)));
+        assert!(decomposition
+            .scale
+            .is_equal_to(&Tuple::new_vector(2.0, 3.0, 0.5)));
+        assert_approx_eq!(
+            Matrix4::from(decomposition.rotation),
+            rotation_y(FRAC_PI_2) * rotation_x(0.5)
+        );
+        assert_approx_eq!(decomposition.to_matrix(), matrix);
+    }
+
+    #[test]
+    fn matrix_decompose_identity() {
+        let decomposition = Matrix4::IDENTITY.decompose().unwrap();
+        assert_approx_eq!(
+            decomposition,
+            Decomposition {
+                translation: Tuple::new_vector(0.0, 0.0, 0.0),
+                rotation: Quaternion::IDENTITY,
+                scale: Tuple::new_vector(1.0, 1.0, 1.0),
+            }
+        );
+    }
+
+    #[test]
+    fn matrix_decompose_mirrored() {
+        let matrix = rotation_z(0.3) * scaling(1.0, -2.0, 1.0);
+        let decomposition = matrix.decompose().unwrap();
+        assert!(decomposition.scale.x < 0.0);
+        assert_approx_eq!(decomposition.rotation.magnitude(), 1.0);
+        assert_approx_eq!(decomposition.to_matrix(), matrix);
+    }
+
+    #[test]
+    fn matrix_decompose_flattened() {
+        let matrix = rotation_x(0.3) * scaling(1.0, 0.0, 1.0);
+        assert_eq!(matrix.decompose().unwrap_err(), MatrixError::NotInvertible);
+    }
+
+    #[test]
+    fn matrix_decompose_sheared() {
+        // Shearing cannot be represented, but the parts are still well formed
+        let matrix = shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        let decomposition = matrix.decompose().unwrap();
+        assert_approx_eq!(decomposition.rotation.magnitude(), 1.0);
+        assert!(!decomposition.to_matrix().is_equal_to(&matrix));
+    }
+}
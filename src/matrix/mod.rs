@@ -0,0 +1,280 @@
+use crate::math::{format_number, ApproxEq};
+use crate::EPSILON;
+use std::error::Error;
+use std::fmt;
+
+pub mod decompose;
+pub mod ops;
+
+pub use decompose::Decomposition;
+
+/// Error produced by matrix operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixError {
+    /// The matrix has a determinant of zero and therefore cannot be inverted.
+    NotInvertible,
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::NotInvertible => write!(f, "matrix is not invertible"),
+        }
+    }
+}
+
+impl Error for MatrixError {}
+
+/// Square matrix of `N` rows and `N` columns.
+#[derive(Debug, Clone, Copy)]
+pub struct Matrix<const N: usize> {
+    elements: [[f64; N]; N],
+}
+
+/// 2x2 matrix.
+pub type Matrix2 = Matrix<2>;
+
+/// 3x3 matrix.
+pub type Matrix3 = Matrix<3>;
+
+/// 4x4 matrix, used to transform tuples.
+pub type Matrix4 = Matrix<4>;
+
+impl<const N: usize> Matrix<N> {
+    /// Identity matrix.
+    pub const IDENTITY: Self = {
+        let mut elements = [[0.0; N]; N];
+        let mut i = 0;
+        while i < N {
+            elements[i][i] = 1.0;
+            i += 1;
+        }
+        Self { elements }
+    };
+
+    /// Create a matrix from its rows.
+    pub fn new(rows: [[f64; N]; N]) -> Self {
+        Self { elements: rows }
+    }
+
+    /// Test if this matrix is equal to another.
+    pub fn is_equal_to(&self, other: &Matrix<N>) -> bool {
+        self.approx_eq(other)
+    }
+
+    /// Return a new matrix that is this matrix transposed.
+    pub fn transpose(&self) -> Matrix<N> {
+        let mut elements = [[0.0; N]; N];
+        for (row, transposed_row) in elements.iter_mut().enumerate() {
+            for (column, transposed) in transposed_row.iter_mut().enumerate() {
+                *transposed = self.elements[column][row];
+            }
+        }
+        Self { elements }
+    }
+
+    /// Copy the elements of this matrix, excluding the given row and column, into a matrix of size `M`.
+    /// `M` must be one less than `N`.
+    fn remove_row_and_column<const M: usize>(&self, row: usize, column: usize) -> Matrix<M> {
+        debug_assert_eq!(M + 1, N);
+        let mut elements = [[0.0; M]; M];
+        for (sub_row, source_row) in (0..N).filter(|&r| r != row).enumerate() {
+            for (sub_column, source_column) in (0..N).filter(|&c| c != column).enumerate() {
+                elements[sub_row][sub_column] = self.elements[source_row][source_column];
+            }
+        }
+        Matrix { elements }
+    }
+
+    /// Compute the sign applied to a minor to get the cofactor at `(row, column)`.
+    fn cofactor_sign(row: usize, column: usize) -> f64 {
+        if (row + column).is_multiple_of(2) {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
+    /// Compute the inverse of this matrix using Gauss-Jordan elimination.
+    /// Returns an error rather than panicking if the matrix is not invertible,
+    /// so callers such as scene loaders can report the bad matrix.
+    pub fn inverse(&self) -> Result<Matrix<N>, MatrixError> {
+        let mut elements = self.elements;
+        let mut inverse = Self::IDENTITY.elements;
+        // Rounding error grows with the size of the elements, so a pivot counts as zero relative to them
+        let largest = elements
+            .iter()
+            .flatten()
+            .fold(0.0, |largest: f64, element| largest.max(element.abs()));
+        let tolerance = EPSILON * largest;
+        for column in 0..N {
+            // Use the row with the largest magnitude in this column as the pivot to limit rounding error.
+            let pivot_row = (column..N)
+                .max_by(|&a, &b| {
+                    elements[a][column]
+                        .abs()
+                        .total_cmp(&elements[b][column].abs())
+                })
+                .unwrap_or(column);
+            if elements[pivot_row][column].abs() <= tolerance {
+                return Err(MatrixError::NotInvertible);
+            }
+            elements.swap(column, pivot_row);
+            inverse.swap(column, pivot_row);
+
+            let pivot = elements[column][column];
+            for i in 0..N {
+                elements[column][i] /= pivot;
+                inverse[column][i] /= pivot;
+            }
+            for row in (0..N).filter(|&row| row != column) {
+                let factor = elements[row][column];
+                for i in 0..N {
+                    elements[row][i] -= factor * elements[column][i];
+                    inverse[row][i] -= factor * inverse[column][i];
+                }
+            }
+        }
+        Ok(Self { elements: inverse })
+    }
+}
+
+impl<const N: usize> ApproxEq for Matrix<N> {
+    fn approx_eq_with(&self, other: &Matrix<N>, epsilon: f64) -> bool {
+        self.elements
+            .iter()
+            .flatten()
+            .zip(other.elements.iter().flatten())
+            .all(|(a, b)| a.approx_eq_with(b, epsilon))
+    }
+}
+
+impl<const N: usize> fmt::Display for Matrix<N> {
+    /// Show one row per line between bars, with the elements of each column right-aligned, using the
+    /// formatter's precision if one is given.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision();
+        let elements = self
+            .elements
+            .map(|row| row.map(|element| format_number(element, precision)));
+        let mut widths = [0; N];
+        for row in &elements {
+            for (width, element) in widths.iter_mut().zip(row) {
+                *width = (*width).max(element.chars().count());
+            }
+        }
+        for (i, row) in elements.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "|")?;
+            for (element, width) in row.iter().zip(widths) {
+                write!(f, " {element:>width$}")?;
+            }
+            write!(f, " |")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for Matrix<N> {
+    /// Serialize the matrix as a sequence of rows.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.elements.iter().map(|row| row.as_slice()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for Matrix<N> {
+    /// Deserialize the matrix from a sequence of `N` rows of `N` elements each.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error as _;
+
+        let rows = Vec::<Vec<f64>>::deserialize(deserializer)?;
+        let expected = format!("{N} rows of {N} elements");
+        if rows.len() != N {
+            return Err(D::Error::invalid_length(rows.len(), &expected.as_str()));
+        }
+        let mut elements = [[0.0; N]; N];
+        for (row, elements) in rows.iter().zip(elements.iter_mut()) {
+            if row.len() != N {
+                return Err(D::Error::invalid_length(row.len(), &expected.as_str()));
+            }
+            elements.copy_from_slice(row);
+        }
+        Ok(Self { elements })
+    }
+}
+
+impl<const N: usize> Default for Matrix<N> {
+    /// Create the identity matrix.
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Matrix<2> {
+    /// Compute the determinant of the matrix.
+    pub fn determinant(&self) -> f64 {
+        self.elements[0][0] * self.elements[1][1] - self.elements[0][1] * self.elements[1][0]
+    }
+}
+
+impl Matrix<3> {
+    /// Return a new matrix that is this matrix with the given row and column removed.
+    pub fn submatrix(&self, row: usize, column: usize) -> Matrix<2> {
+        self.remove_row_and_column(row, column)
+    }
+
+    /// Compute the minor at `(row, column)`, which is the determinant of the corresponding submatrix.
+    pub fn minor(&self, row: usize, column: usize) -> f64 {
+        self.submatrix(row, column).determinant()
+    }
+
+    /// Compute the cofactor at `(row, column)`, which is the minor with its sign possibly flipped.
+    pub fn cofactor(&self, row: usize, column: usize) -> f64 {
+        Self::cofactor_sign(row, column) * self.minor(row, column)
+    }
+
+    /// Compute the determinant of the matrix by cofactor expansion along the first row.
+    pub fn determinant(&self) -> f64 {
+        (0..3)
+            .map(|column| self.elements[0][column] * self.cofactor(0, column))
+            .sum()
+    }
+}
+
+impl Matrix<4> {
+    /// Return a new matrix that is this matrix with the given row and column removed.
+    pub fn submatrix(&self, row: usize, column: usize) -> Matrix<3> {
+        self.remove_row_and_column(row, column)
+    }
+
+    /// Compute the minor at `(row, column)`, which is the determinant of the corresponding submatrix.
+    pub fn minor(&self, row: usize, column: usize) -> f64 {
+        self.submatrix(row, column).determinant()
+    }
+
+    /// Compute the cofactor at `(row, column)`, which is the minor with its sign possibly flipped.
+    pub fn cofactor(&self, row: usize, column: usize) -> f64 {
+        Self::cofactor_sign(row, column) * self.minor(row, column)
+    }
+
+    /// Compute the determinant of the matrix by cofactor expansion along the first row.
+    pub fn determinant(&self) -> f64 {
+        (0..4)
+            .map(|column| self.elements[0][column] * self.cofactor(0, column))
+            .sum()
+    }
+}
+
+/// Create a matrix from rows of numbers separated by semicolons, which may be integer literals, such as
+/// `matrix![[1, 2]; [3, 4]]`. The size of the matrix is the number of rows, each of which must have as
+/// many elements.
+#[macro_export]
+macro_rules! matrix {
+    ($([$($element:expr),+ $(,)?]);+ $(;)?) => {
+        $crate::matrix::Matrix::new([$([$($element as f64),+]),+])
+    };
+}
@@ -0,0 +1,455 @@
+use super::Matrix;
+use crate::tuple::Tuple;
+use std::ops::{Index, IndexMut, Mul};
+
+//
+// Implement the `Index` and `IndexMut` traits for a matrix, where the index is `(row, column)`.
+//
+
+impl<const N: usize> Index<(usize, usize)> for Matrix<N> {
+    type Output = f64;
+
+    /// Get the element at `(row, column)`.
+    fn index(&self, (row, column): (usize, usize)) -> &f64 {
+        &self.elements[row][column]
+    }
+}
+
+impl<const N: usize> IndexMut<(usize, usize)> for Matrix<N> {
+    /// Get a mutable reference to the element at `(row, column)`.
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut f64 {
+        &mut self.elements[row][column]
+    }
+}
+
+//
+// Implement the `Mul` trait for a matrix for it to be multiplied by another matrix.
+//
+
+impl<const N: usize> Mul for Matrix<N> {
+    type Output = Matrix<N>;
+
+    /// Multiply two matrices, returning a new matrix.
+    fn mul(self, rhs: Matrix<N>) -> Matrix<N> {
+        let mut elements = [[0.0; N]; N];
+        for (row, product_row) in elements.iter_mut().enumerate() {
+            for (column, product) in product_row.iter_mut().enumerate() {
+                *product = (0..N)
+                    .map(|i| self.elements[row][i] * rhs.elements[i][column])
+                    .sum();
+            }
+        }
+        Matrix { elements }
+    }
+}
+
+//
+// Implement the `Mul` trait for a 4x4 matrix for it to be multiplied by a tuple.
+//
+
+impl Mul<&Tuple> for Matrix<4> {
+    type Output = Tuple;
+
+    /// Multiply a matrix by a tuple reference, borrowing the tuple and returning a new tuple.
+    fn mul(self, rhs: &Tuple) -> Tuple {
+        let mut product = Tuple::default();
+        for (r, row) in self.elements.iter().enumerate() {
+            product[r] = row
+                .iter()
+                .enumerate()
+                .map(|(c, element)| element * rhs[c])
+                .sum();
+        }
+        product
+    }
+}
+
+impl Mul<Tuple> for Matrix<4> {
+    type Output = Tuple;
+
+    /// Multiply a matrix by a tuple, consuming the tuple and returning a new tuple.
+    fn mul(self, rhs: Tuple) -> Tuple {
+        self * &rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Matrix2, Matrix3, Matrix4, MatrixError};
+    use super::*;
+    use crate::math::ApproxEq;
+    use crate::transform::scaling;
+    use crate::{assert_approx_eq, matrix};
+
+    #[test]
+    fn matrix_new() {
+        let m = Matrix4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.5, 6.5, 7.5, 8.5],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.5, 14.5, 15.5, 16.5],
+        ]);
+        assert_approx_eq!(m[(0, 0)], 1.0);
+        assert_approx_eq!(m[(0, 3)], 4.0);
+        assert_approx_eq!(m[(1, 0)], 5.5);
+        assert_approx_eq!(m[(1, 2)], 7.5);
+        assert_approx_eq!(m[(2, 2)], 11.0);
+        assert_approx_eq!(m[(3, 0)], 13.5);
+        assert_approx_eq!(m[(3, 2)], 15.5);
+
+        let m = Matrix2::new([[-3.0, 5.0], [1.0, -2.0]]);
+        assert_approx_eq!(m[(0, 0)], -3.0);
+        assert_approx_eq!(m[(0, 1)], 5.0);
+        assert_approx_eq!(m[(1, 0)], 1.0);
+        assert_approx_eq!(m[(1, 1)], -2.0);
+
+        let m = Matrix3::new([[-3.0, 5.0, 0.0], [1.0, -2.0, -7.0], [0.0, 1.0, 1.0]]);
+        assert_approx_eq!(m[(0, 0)], -3.0);
+        assert_approx_eq!(m[(1, 1)], -2.0);
+        assert_approx_eq!(m[(2, 2)], 1.0);
+    }
+
+    #[test]
+    fn matrix_index_mut() {
+        let mut m = Matrix2::new([[-3.0, 5.0], [1.0, -2.0]]);
+        m[(1, 0)] = 4.0;
+        assert_approx_eq!(m[(1, 0)], 4.0);
+    }
+
+    #[test]
+    fn matrix_is_equal_to() {
+        let a = Matrix4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 8.0, 7.0, 6.0],
+            [5.0, 4.0, 3.0, 2.0],
+        ]);
+        let b = Matrix4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 8.0, 7.0, 6.0],
+            [5.0, 4.0, 3.0, 2.0 + 1e-7],
+        ]);
+        assert!(a.is_equal_to(&b));
+
+        let c = Matrix4::new([
+            [2.0, 3.0, 4.0, 5.0],
+            [6.0, 7.0, 8.0, 9.0],
+            [8.0, 7.0, 6.0, 5.0],
+            [4.0, 3.0, 2.0, 1.0],
+        ]);
+        assert!(!a.is_equal_to(&c));
+    }
+
+    #[test]
+    fn matrix_approx_eq_with() {
+        let a = Matrix2::new([[1.0, 2.0], [3.0, 4.0]]);
+        let b = Matrix2::new([[1.0, 2.0], [3.0, 4.005]]);
+        assert!(!a.approx_eq(&b));
+        assert_approx_eq!(a, b, epsilon = 0.01);
+    }
+
+    #[test]
+    fn matrix_mul() {
+        let a = Matrix4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 8.0, 7.0, 6.0],
+            [5.0, 4.0, 3.0, 2.0],
+        ]);
+        let b = Matrix4::new([
+            [-2.0, 1.0, 2.0, 3.0],
+            [3.0, 2.0, 1.0, -1.0],
+            [4.0, 3.0, 6.0, 5.0],
+            [1.0, 2.0, 7.0, 8.0],
+        ]);
+        assert!((a * b).is_equal_to(&Matrix4::new([
+            [20.0, 22.0, 50.0, 48.0],
+            [44.0, 54.0, 114.0, 108.0],
+            [40.0, 58.0, 110.0, 102.0],
+            [16.0, 26.0, 46.0, 42.0],
+        ])));
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)] // The reference impl is kept for backward compatibility
+    fn matrix_mul_tuple() {
+        let a = Matrix4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [2.0, 4.0, 4.0, 2.0],
+            [8.0, 6.0, 4.0, 1.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let b = Tuple::new_point(1.0, 2.0, 3.0);
+        let product = a * &b;
+        assert!(product.is_equal_to(&Tuple::new_point(18.0, 24.0, 33.0)));
+        assert!(product.is_point());
+
+        let product = a * b;
+        assert!(product.is_equal_to(&Tuple::new_point(18.0, 24.0, 33.0)));
+    }
+
+    #[test]
+    fn matrix_identity() {
+        let a = Matrix4::new([
+            [0.0, 1.0, 2.0, 4.0],
+            [1.0, 2.0, 4.0, 8.0],
+            [2.0, 4.0, 8.0, 16.0],
+            [4.0, 8.0, 16.0, 32.0],
+        ]);
+        assert!((a * Matrix4::IDENTITY).is_equal_to(&a));
+        assert!(Matrix4::default().is_equal_to(&Matrix4::IDENTITY));
+
+        let tuple = Tuple {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            w: 4.0,
+        };
+        let product = Matrix4::IDENTITY * tuple;
+        assert!(product.is_equal_to(&tuple));
+        assert_approx_eq!(product.w, 4.0);
+    }
+
+    #[test]
+    fn matrix_inverse() {
+        let a = Matrix4::new([
+            [-5.0, 2.0, 6.0, -8.0],
+            [1.0, -5.0, 1.0, 8.0],
+            [7.0, 7.0, -6.0, -7.0],
+            [1.0, -3.0, 7.0, 4.0],
+        ]);
+        let inverse = a.inverse().unwrap();
+        let expected = [
+            [0.21805, 0.45113, 0.24060, -0.04511],
+            [-0.80827, -1.45677, -0.44361, 0.52068],
+            [-0.07895, -0.22368, -0.05263, 0.19737],
+            [-0.52256, -0.81391, -0.30075, 0.30639],
+        ];
+        for (row, expected_row) in expected.iter().enumerate() {
+            for (column, expected) in expected_row.iter().enumerate() {
+                // The book's values are rounded to five decimal places.
+                assert_approx_eq!(inverse[(row, column)], expected, epsilon = 1e-5);
+            }
+        }
+        assert!((a * inverse).is_equal_to(&Matrix4::IDENTITY));
+
+        let a = Matrix4::new([
+            [8.0, -5.0, 9.0, 2.0],
+            [7.0, 5.0, 6.0, 1.0],
+            [-6.0, 0.0, 9.0, 6.0],
+            [-3.0, 0.0, -9.0, -4.0],
+        ]);
+        let inverse = a.inverse().unwrap();
+        assert_approx_eq!(inverse[(0, 0)], -0.15385, epsilon = 1e-5);
+        assert_approx_eq!(inverse[(1, 3)], 0.03077, epsilon = 1e-5);
+        assert_approx_eq!(inverse[(3, 3)], -1.92308, epsilon = 1e-5);
+        assert!((a * inverse).is_equal_to(&Matrix4::IDENTITY));
+    }
+
+    #[test]
+    fn matrix_inverse_small_elements() {
+        let small = scaling(1e-4, 1e-4, 1e-4);
+        assert_approx_eq!(small.inverse().unwrap(), scaling(1e4, 1e4, 1e4));
+
+        // Whether a matrix is invertible does not depend on the size of its elements
+        let rows = [
+            [-5.0, 2.0, 6.0, -8.0],
+            [1.0, -5.0, 1.0, 8.0],
+            [7.0, 7.0, -6.0, -7.0],
+            [1.0, -3.0, 7.0, 4.0],
+        ];
+        let a = Matrix4::new(rows);
+        let tiny = Matrix4::new(rows.map(|row| row.map(|element| element * 1e-8)));
+        let inverse = tiny.inverse().unwrap();
+        assert!((tiny * inverse).is_equal_to(&Matrix4::IDENTITY));
+        assert_approx_eq!(
+            inverse[(0, 0)],
+            a.inverse().unwrap()[(0, 0)] * 1e8,
+            epsilon = 1e-2
+        );
+
+        let singular = Matrix4::new([
+            [-4e-8, 2e-8, -2e-8, -3e-8],
+            [9e-8, 6e-8, 2e-8, 6e-8],
+            [0.0, -5e-8, 1e-8, -5e-8],
+            [5e-8, 8e-8, 0.0, 3e-8],
+        ]);
+        assert_eq!(singular.inverse().unwrap_err(), MatrixError::NotInvertible);
+    }
+
+    #[test]
+    fn matrix_inverse_undoes_mul() {
+        let a = Matrix4::new([
+            [3.0, -9.0, 7.0, 3.0],
+            [3.0, -8.0, 2.0, -9.0],
+            [-4.0, 4.0, 4.0, 1.0],
+            [-6.0, 5.0, -1.0, 1.0],
+        ]);
+        let b = Matrix4::new([
+            [8.0, 2.0, 2.0, 2.0],
+            [3.0, -1.0, 7.0, 0.0],
+            [7.0, 0.0, 5.0, 4.0],
+            [6.0, -2.0, 0.0, 5.0],
+        ]);
+        let c = a * b;
+        assert!((c * b.inverse().unwrap()).is_equal_to(&a));
+    }
+
+    #[test]
+    fn matrix_inverse_not_invertible() {
+        let a = Matrix4::new([
+            [-4.0, 2.0, -2.0, -3.0],
+            [9.0, 6.0, 2.0, 6.0],
+            [0.0, -5.0, 1.0, -5.0],
+            [0.0, 0.0, 0.0, 0.0],
+        ]);
+        assert_eq!(a.inverse().unwrap_err(), MatrixError::NotInvertible);
+    }
+
+    #[test]
+    fn matrix_transpose() {
+        let a = Matrix4::new([
+            [0.0, 9.0, 3.0, 0.0],
+            [9.0, 8.0, 0.0, 8.0],
+            [1.0, 8.0, 5.0, 3.0],
+            [0.0, 0.0, 5.0, 8.0],
+        ]);
+        assert!(a.transpose().is_equal_to(&Matrix4::new([
+            [0.0, 9.0, 1.0, 0.0],
+            [9.0, 8.0, 8.0, 0.0],
+            [3.0, 0.0, 5.0, 5.0],
+            [0.0, 8.0, 3.0, 8.0],
+        ])));
+        assert!(Matrix4::IDENTITY
+            .transpose()
+            .is_equal_to(&Matrix4::IDENTITY));
+    }
+
+    #[test]
+    fn matrix_submatrix() {
+        let a = Matrix3::new([[1.0, 5.0, 0.0], [-3.0, 2.0, 7.0], [0.0, 6.0, -3.0]]);
+        assert!(a
+            .submatrix(0, 2)
+            .is_equal_to(&Matrix2::new([[-3.0, 2.0], [0.0, 6.0]])));
+
+        let a = Matrix4::new([
+            [-6.0, 1.0, 1.0, 6.0],
+            [-8.0, 5.0, 8.0, 6.0],
+            [-1.0, 0.0, 8.0, 2.0],
+            [-7.0, 1.0, -1.0, 1.0],
+        ]);
+        assert!(a.submatrix(2, 1).is_equal_to(&Matrix3::new([
+            [-6.0, 1.0, 6.0],
+            [-8.0, 8.0, 6.0],
+            [-7.0, -1.0, 1.0],
+        ])));
+    }
+
+    #[test]
+    fn matrix_minor() {
+        let a = Matrix3::new([[3.0, 5.0, 0.0], [2.0, -1.0, -7.0], [6.0, -1.0, 5.0]]);
+        assert_approx_eq!(a.submatrix(1, 0).determinant(), 25.0);
+        assert_approx_eq!(a.minor(1, 0), 25.0);
+    }
+
+    #[test]
+    fn matrix_cofactor() {
+        let a = Matrix3::new([[3.0, 5.0, 0.0], [2.0, -1.0, -7.0], [6.0, -1.0, 5.0]]);
+        assert_approx_eq!(a.minor(0, 0), -12.0);
+        assert_approx_eq!(a.cofactor(0, 0), -12.0);
+        assert_approx_eq!(a.minor(1, 0), 25.0);
+        assert_approx_eq!(a.cofactor(1, 0), -25.0);
+    }
+
+    #[test]
+    fn matrix_determinant() {
+        let a = Matrix2::new([[1.0, 5.0], [-3.0, 2.0]]);
+        assert_approx_eq!(a.determinant(), 17.0);
+
+        let a = Matrix3::new([[1.0, 2.0, 6.0], [-5.0, 8.0, -4.0], [2.0, 6.0, 4.0]]);
+        assert_approx_eq!(a.cofactor(0, 0), 56.0);
+        assert_approx_eq!(a.cofactor(0, 1), 12.0);
+        assert_approx_eq!(a.cofactor(0, 2), -46.0);
+        assert_approx_eq!(a.determinant(), -196.0);
+
+        let a = Matrix4::new([
+            [-2.0, -8.0, 3.0, 5.0],
+            [-3.0, 1.0, 7.0, 3.0],
+            [1.0, 2.0, -9.0, 6.0],
+            [-6.0, 7.0, 7.0, -9.0],
+        ]);
+        assert_approx_eq!(a.cofactor(0, 0), 690.0);
+        assert_approx_eq!(a.cofactor(0, 1), 447.0);
+        assert_approx_eq!(a.cofactor(0, 2), 210.0);
+        assert_approx_eq!(a.cofactor(0, 3), 51.0);
+        assert_approx_eq!(a.determinant(), -4071.0);
+    }
+
+    #[test]
+    fn matrix_determinant_agrees_with_inverse() {
+        let invertible = Matrix4::new([
+            [6.0, 4.0, 4.0, 4.0],
+            [5.0, 5.0, 7.0, 6.0],
+            [4.0, -9.0, 3.0, -7.0],
+            [9.0, 1.0, 7.0, -6.0],
+        ]);
+        assert_approx_eq!(invertible.determinant(), -2120.0);
+        assert!(invertible.inverse().is_ok());
+
+        let not_invertible = Matrix4::new([
+            [-4.0, 2.0, -2.0, -3.0],
+            [9.0, 6.0, 2.0, 6.0],
+            [0.0, -5.0, 1.0, -5.0],
+            [0.0, 0.0, 0.0, 0.0],
+        ]);
+        assert_approx_eq!(not_invertible.determinant(), 0.0);
+        assert!(not_invertible.inverse().is_err());
+    }
+
+    #[test]
+    fn matrix_display() {
+        let matrix = Matrix2::new([[1.0, -20.5], [-3.0, 4.0]]);
+        assert_eq!(matrix.to_string(), "|  1.0 -20.5 |\n| -3.0   4.0 |");
+        assert_eq!(
+            format!("{:.0}", Matrix3::IDENTITY),
+            "| 1 0 0 |\n| 0 1 0 |\n| 0 0 1 |"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn matrix_serde() {
+        let matrix = Matrix2::new([[1.0, -2.0], [3.5, 4.0]]);
+        let json = serde_json::to_string(&matrix).unwrap();
+        assert_eq!(json, "[[1.0,-2.0],[3.5,4.0]]");
+        assert!(serde_json::from_str::<Matrix2>(&json)
+            .unwrap()
+            .is_equal_to(&matrix));
+
+        // Rows and columns must both have the matrix's size
+        assert!(serde_json::from_str::<Matrix2>("[[1.0,2.0]]").is_err());
+        assert!(serde_json::from_str::<Matrix2>("[[1.0,2.0],[3.0]]").is_err());
+        assert!(serde_json::from_str::<Matrix3>(&json).is_err());
+    }
+
+    #[test]
+    fn matrix_macro() {
+        let m: Matrix2 = matrix![[-3, 5]; [1, -2.5]];
+        assert!(m.is_equal_to(&Matrix2::new([[-3.0, 5.0], [1.0, -2.5]])));
+
+        let m = matrix![
+            [1, 2, 3, 4];
+            [5.5, 6.5, 7.5, 8.5];
+            [9, 10, 11, 12];
+            [13.5, 14.5, 15.5, 16.5];
+        ];
+        assert!(m.is_equal_to(&Matrix4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.5, 6.5, 7.5, 8.5],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.5, 14.5, 15.5, 16.5],
+        ])));
+        assert!(matrix![[1, 0, 0]; [0, 1, 0]; [0, 0, 1]].is_equal_to(&Matrix3::IDENTITY));
+    }
+}
@@ -0,0 +1,76 @@
+use super::{is_floor_even, Pattern, PatternProperties};
+#[cfg(feature = "serde")]
+use super::{PatternKind, SerializedPattern};
+use crate::color::Color;
+use crate::tuple::Tuple;
+
+/// Pattern that alternates between two patterns in unit cubes, like a three-dimensional checkerboard.
+#[derive(Debug)]
+pub struct CheckersPattern {
+    properties: PatternProperties,
+    a: Box<dyn Pattern>,
+    b: Box<dyn Pattern>,
+}
+
+impl CheckersPattern {
+    /// Create a checkers pattern that is `a` in the cube at the origin and alternates with `b`.
+    pub fn new(a: impl Into<Box<dyn Pattern>>, b: impl Into<Box<dyn Pattern>>) -> Self {
+        Self {
+            properties: PatternProperties::default(),
+            a: a.into(),
+            b: b.into(),
+        }
+    }
+}
+
+impl Pattern for CheckersPattern {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn local_pattern_at(&self, pattern_point: &Tuple) -> Color {
+        let sum = pattern_point.x.floor() + pattern_point.y.floor() + pattern_point.z.floor();
+        if is_floor_even(sum) {
+            self.a.pattern_at(pattern_point)
+        } else {
+            self.b.pattern_at(pattern_point)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn serialized(&self) -> Option<SerializedPattern> {
+        let kind = PatternKind::Checkers {
+            a: Box::new(self.a.serialized()?),
+            b: Box::new(self.b.serialized()?),
+        };
+        Some(SerializedPattern::new(kind, *self.transform()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkers_pattern_repeats_in_each_dimension() {
+        let white = Color::new(1.0, 1.0, 1.0);
+        let black = Color::new(0.0, 0.0, 0.0);
+        let pattern = CheckersPattern::new(white, black);
+        let cases = [
+            (Tuple::new_point(0.0, 0.0, 0.0), white),
+            (Tuple::new_point(0.99, 0.0, 0.0), white),
+            (Tuple::new_point(1.01, 0.0, 0.0), black),
+            (Tuple::new_point(0.0, 0.99, 0.0), white),
+            (Tuple::new_point(0.0, 1.01, 0.0), black),
+            (Tuple::new_point(0.0, 0.0, 0.99), white),
+            (Tuple::new_point(0.0, 0.0, 1.01), black),
+        ];
+        for (point, expected) in cases {
+            assert!(pattern.pattern_at(&point).is_equal_to(&expected));
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use super::{Pattern, PatternProperties};
+#[cfg(feature = "serde")]
+use super::{PatternKind, SerializedPattern};
+use crate::color::Color;
+use crate::tuple::Tuple;
+
+/// Pattern that blends linearly from one pattern to another along each unit of the x axis.
+#[derive(Debug)]
+pub struct GradientPattern {
+    properties: PatternProperties,
+    a: Box<dyn Pattern>,
+    b: Box<dyn Pattern>,
+}
+
+impl GradientPattern {
+    /// Create a gradient pattern that is `a` at x = 0 and approaches `b` as x approaches 1.
+    pub fn new(a: impl Into<Box<dyn Pattern>>, b: impl Into<Box<dyn Pattern>>) -> Self {
+        Self {
+            properties: PatternProperties::default(),
+            a: a.into(),
+            b: b.into(),
+        }
+    }
+}
+
+impl Pattern for GradientPattern {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn local_pattern_at(&self, pattern_point: &Tuple) -> Color {
+        let a = self.a.pattern_at(pattern_point);
+        let b = self.b.pattern_at(pattern_point);
+        let fraction = pattern_point.x - pattern_point.x.floor();
+        a + (b - a) * fraction
+    }
+
+    #[cfg(feature = "serde")]
+    fn serialized(&self) -> Option<SerializedPattern> {
+        let kind = PatternKind::Gradient {
+            a: Box::new(self.a.serialized()?),
+            b: Box::new(self.b.serialized()?),
+        };
+        Some(SerializedPattern::new(kind, *self.transform()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_pattern_interpolates() {
+        let pattern = GradientPattern::new(Color::new(1.0, 1.0, 1.0), Color::new(0.0, 0.0, 0.0));
+        let cases = [
+            (0.0, Color::new(1.0, 1.0, 1.0)),
+            (0.25, Color::new(0.75, 0.75, 0.75)),
+            (0.5, Color::new(0.5, 0.5, 0.5)),
+            (0.75, Color::new(0.25, 0.25, 0.25)),
+        ];
+        for (x, expected) in cases {
+            assert!(pattern
+                .pattern_at(&Tuple::new_point(x, 0.0, 0.0))
+                .is_equal_to(&expected));
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use crate::color::Color;
+use crate::matrix::{Matrix4, MatrixError};
+use crate::shapes::Shape;
+use crate::tuple::Tuple;
+use std::fmt::Debug;
+
+pub mod checkers;
+pub mod gradient;
+pub mod noise;
+pub mod perturbed;
+pub mod ring;
+pub mod solid;
+pub mod stripe;
+
+pub use checkers::CheckersPattern;
+pub use gradient::GradientPattern;
+pub use noise::NoisePattern;
+pub use perturbed::PerturbedPattern;
+pub use ring::RingPattern;
+pub use solid::SolidPattern;
+pub use stripe::StripePattern;
+
+/// Properties shared by every pattern, stored by the pattern and exposed through the `Pattern` trait.
+#[derive(Debug, Clone, Default)]
+pub struct PatternProperties {
+    transform: Matrix4,
+    inverse_transform: Matrix4,
+}
+
+/// Function from points in space to colors, used to texture the surface of a shape.
+///
+/// Implementors only store a `PatternProperties` and describe their colors in pattern space with
+/// `local_pattern_at`. Patterns that combine other patterns pass their pattern space point on to
+/// the nested patterns, which then apply their own transforms.
+pub trait Pattern: Debug + Send + Sync {
+    /// Get the properties shared by every pattern.
+    fn properties(&self) -> &PatternProperties;
+
+    /// Get a mutable reference to the properties shared by every pattern.
+    fn properties_mut(&mut self) -> &mut PatternProperties;
+
+    /// Compute the color of the pattern at a point given in pattern space.
+    fn local_pattern_at(&self, pattern_point: &Tuple) -> Color;
+
+    /// Get the transform from pattern space to the space of the pattern's parent,
+    /// which is object space unless the pattern is nested in another pattern.
+    fn transform(&self) -> &Matrix4 {
+        &self.properties().transform
+    }
+
+    /// Set the transform from pattern space to the space of the pattern's parent.
+    /// Returns an error, leaving the pattern unchanged, if the transform is not invertible.
+    fn set_transform(&mut self, transform: Matrix4) -> Result<(), MatrixError> {
+        let properties = self.properties_mut();
+        properties.inverse_transform = transform.inverse()?;
+        properties.transform = transform;
+        Ok(())
+    }
+
+    /// Compute the color of the pattern at a point given in the space of the pattern's parent.
+    fn pattern_at(&self, point: &Tuple) -> Color {
+        self.local_pattern_at(&(self.properties().inverse_transform * point))
+    }
+
+    /// Compute the color of the pattern at a point on a shape given in world space,
+    /// converting the point through the shape's object space into pattern space.
+    fn pattern_at_shape(&self, shape: &dyn Shape, world_point: &Tuple) -> Color {
+        self.pattern_at(&shape.world_to_object(world_point))
+    }
+
+    /// Describe the pattern for serialization, or return `None`, as by default, if it has no serialized
+    /// form, such as a texture read from an image.
+    #[cfg(feature = "serde")]
+    fn serialized(&self) -> Option<SerializedPattern> {
+        None
+    }
+}
+
+/// Serialized form of a pattern: its kind, including any nested patterns, and its transform.
+#[cfg(feature = "serde")]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct SerializedPattern {
+    #[serde(flatten)]
+    kind: PatternKind,
+    #[serde(default)]
+    transform: Matrix4,
+}
+
+/// Built-in kinds of pattern, tagged by `type` when serialized.
+#[cfg(feature = "serde")]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum PatternKind {
+    Solid {
+        color: Color,
+    },
+    Stripes {
+        a: Box<SerializedPattern>,
+        b: Box<SerializedPattern>,
+    },
+    Checkers {
+        a: Box<SerializedPattern>,
+        b: Box<SerializedPattern>,
+    },
+    Gradient {
+        a: Box<SerializedPattern>,
+        b: Box<SerializedPattern>,
+    },
+    Rings {
+        a: Box<SerializedPattern>,
+        b: Box<SerializedPattern>,
+    },
+    Noise {
+        a: Box<SerializedPattern>,
+        b: Box<SerializedPattern>,
+        octaves: u32,
+        persistence: f64,
+    },
+    Perturbed {
+        pattern: Box<SerializedPattern>,
+        scale: f64,
+    },
+}
+
+#[cfg(feature = "serde")]
+impl SerializedPattern {
+    /// Describe a pattern of the given kind and transform.
+    pub fn new(kind: PatternKind, transform: Matrix4) -> Self {
+        Self { kind, transform }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerializedPattern> for Box<dyn Pattern> {
+    type Error = MatrixError;
+
+    /// Fail if the transform of the pattern, or of a pattern nested in it, is not invertible.
+    fn try_from(serialized: SerializedPattern) -> Result<Self, MatrixError> {
+        let pair = |a: Box<SerializedPattern>, b: Box<SerializedPattern>| {
+            Ok::<_, MatrixError>((Self::try_from(*a)?, Self::try_from(*b)?))
+        };
+        let mut pattern: Self = match serialized.kind {
+            PatternKind::Solid { color } => Box::new(SolidPattern::new(color)),
+            PatternKind::Stripes { a, b } => {
+                let (a, b) = pair(a, b)?;
+                Box::new(StripePattern::new(a, b))
+            }
+            PatternKind::Checkers { a, b } => {
+                let (a, b) = pair(a, b)?;
+                Box::new(CheckersPattern::new(a, b))
+            }
+            PatternKind::Gradient { a, b } => {
+                let (a, b) = pair(a, b)?;
+                Box::new(GradientPattern::new(a, b))
+            }
+            PatternKind::Rings { a, b } => {
+                let (a, b) = pair(a, b)?;
+                Box::new(RingPattern::new(a, b))
+            }
+            PatternKind::Noise {
+                a,
+                b,
+                octaves,
+                persistence,
+            } => {
+                let (a, b) = pair(a, b)?;
+                Box::new(NoisePattern::new(a, b, octaves, persistence))
+            }
+            PatternKind::Perturbed { pattern, scale } => {
+                Box::new(PerturbedPattern::new(Self::try_from(*pattern)?, scale))
+            }
+        };
+        pattern.set_transform(serialized.transform)?;
+        Ok(pattern)
+    }
+}
+
+impl<P: Pattern + 'static> From<P> for Box<dyn Pattern> {
+    /// Box a pattern so it can be nested in another pattern.
+    fn from(pattern: P) -> Self {
+        Box::new(pattern)
+    }
+}
+
+impl From<Color> for Box<dyn Pattern> {
+    /// Wrap a color in a solid pattern so it can be used wherever a pattern is expected.
+    fn from(color: Color) -> Self {
+        Box::new(SolidPattern::new(color))
+    }
+}
+
+/// Test if the floor of a coordinate is even, which is how the alternating patterns pick between their two colors.
+fn is_floor_even(value: f64) -> bool {
+    (value.floor() as i64).rem_euclid(2) == 0
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::shapes::Sphere;
+    use crate::transform::{scaling, translation};
+
+    /// Pattern whose color is the coordinates of the pattern space point.
+    #[derive(Debug, Default)]
+    pub(crate) struct TestPattern {
+        properties: PatternProperties,
+    }
+
+    impl Pattern for TestPattern {
+        fn properties(&self) -> &PatternProperties {
+            &self.properties
+        }
+
+        fn properties_mut(&mut self) -> &mut PatternProperties {
+            &mut self.properties
+        }
+
+        fn local_pattern_at(&self, pattern_point: &Tuple) -> Color {
+            Color::new(pattern_point.x, pattern_point.y, pattern_point.z)
+        }
+    }
+
+    #[test]
+    fn pattern_transform() {
+        let mut pattern = TestPattern::default();
+        assert!(pattern.transform().is_equal_to(&Matrix4::IDENTITY));
+
+        pattern.set_transform(translation(1.0, 2.0, 3.0)).unwrap();
+        assert!(pattern.transform().is_equal_to(&translation(1.0, 2.0, 3.0)));
+    }
+
+    #[test]
+    fn pattern_at_shape_object_transformation() {
+        let mut shape = Sphere::new();
+        shape.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let pattern = TestPattern::default();
+        let color = pattern.pattern_at_shape(&shape, &Tuple::new_point(2.0, 3.0, 4.0));
+        assert!(color.is_equal_to(&Color::new(1.0, 1.5, 2.0)));
+    }
+
+    #[test]
+    fn pattern_at_shape_pattern_transformation() {
+        let shape = Sphere::new();
+        let mut pattern = TestPattern::default();
+        pattern.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let color = pattern.pattern_at_shape(&shape, &Tuple::new_point(2.0, 3.0, 4.0));
+        assert!(color.is_equal_to(&Color::new(1.0, 1.5, 2.0)));
+    }
+
+    #[test]
+    fn pattern_at_shape_object_and_pattern_transformation() {
+        let mut shape = Sphere::new();
+        shape.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let mut pattern = TestPattern::default();
+        pattern.set_transform(translation(0.5, 1.0, 1.5)).unwrap();
+        let color = pattern.pattern_at_shape(&shape, &Tuple::new_point(2.5, 3.0, 3.5));
+        assert!(color.is_equal_to(&Color::new(0.75, 0.5, 0.25)));
+    }
+
+    #[test]
+    fn pattern_nested_transformation() {
+        // The nested pattern's transform is applied after its parent's
+        let mut nested = TestPattern::default();
+        nested.set_transform(translation(1.0, 0.0, 0.0)).unwrap();
+        let mut pattern = StripePattern::new(nested, Color::default());
+        pattern.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let color = pattern.pattern_at(&Tuple::new_point(1.0, 2.0, 3.0));
+        assert!(color.is_equal_to(&Color::new(-0.5, 1.0, 1.5)));
+    }
+}
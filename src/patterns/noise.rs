@@ -0,0 +1,105 @@
+use super::{Pattern, PatternProperties};
+#[cfg(feature = "serde")]
+use super::{PatternKind, SerializedPattern};
+use crate::color::Color;
+use crate::math::fractal_perlin;
+use crate::tuple::Tuple;
+
+/// Pattern that blends between two patterns by solid Perlin noise, for cloudy or grainy surfaces such
+/// as clouds, granite or rough concrete. Being solid, the noise carves through objects the way the grain
+/// of a block of stone would, without stretching over curved surfaces.
+#[derive(Debug)]
+pub struct NoisePattern {
+    properties: PatternProperties,
+    a: Box<dyn Pattern>,
+    b: Box<dyn Pattern>,
+    octaves: u32,
+    persistence: f64,
+}
+
+impl NoisePattern {
+    /// Create a noise pattern that blends from `a` where the noise is lowest to `b` where it is highest,
+    /// summing `octaves` layers of noise of doubling frequency, each `persistence` times as strong as the
+    /// one before. The coarsest features are about one unit of pattern space across.
+    pub fn new(
+        a: impl Into<Box<dyn Pattern>>,
+        b: impl Into<Box<dyn Pattern>>,
+        octaves: u32,
+        persistence: f64,
+    ) -> Self {
+        Self {
+            properties: PatternProperties::default(),
+            a: a.into(),
+            b: b.into(),
+            octaves,
+            persistence,
+        }
+    }
+
+    /// Get the number of layers of noise summed.
+    pub fn octaves(&self) -> u32 {
+        self.octaves
+    }
+
+    /// Get the strength of each layer of noise relative to the one before.
+    pub fn persistence(&self) -> f64 {
+        self.persistence
+    }
+}
+
+impl Pattern for NoisePattern {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn local_pattern_at(&self, pattern_point: &Tuple) -> Color {
+        let a = self.a.pattern_at(pattern_point);
+        let b = self.b.pattern_at(pattern_point);
+        let noise = fractal_perlin(pattern_point, self.octaves, self.persistence);
+        let fraction = ((noise + 1.0) / 2.0).clamp(0.0, 1.0);
+        a + (b - a) * fraction
+    }
+
+    #[cfg(feature = "serde")]
+    fn serialized(&self) -> Option<SerializedPattern> {
+        let kind = PatternKind::Noise {
+            a: Box::new(self.a.serialized()?),
+            b: Box::new(self.b.serialized()?),
+            octaves: self.octaves,
+            persistence: self.persistence,
+        };
+        Some(SerializedPattern::new(kind, *self.transform()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn noise_pattern_blends_by_noise() {
+        let pattern =
+            NoisePattern::new(Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0), 3, 0.5);
+        // Whole-numbered points have no noise, halfway between the colors
+        assert_approx_eq!(
+            pattern.pattern_at(&Tuple::new_point(2.0, -1.0, 0.0)),
+            Color::new(0.5, 0.5, 0.5)
+        );
+        let mut grays = Vec::new();
+        for step in 0..200 {
+            let t = step as f64 * 0.071;
+            let color = pattern.pattern_at(&Tuple::new_point(t, 0.5 - t, t * 0.3));
+            assert!((0.0..=1.0).contains(&color.red));
+            assert_approx_eq!(color.red, color.green);
+            grays.push(color.red);
+        }
+        let darkest = grays.iter().copied().fold(f64::INFINITY, f64::min);
+        let lightest = grays.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        assert!(darkest < 0.4 && lightest > 0.6, "{darkest} {lightest}");
+    }
+}
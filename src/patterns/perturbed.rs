@@ -0,0 +1,102 @@
+use super::{Pattern, PatternProperties};
+#[cfg(feature = "serde")]
+use super::{PatternKind, SerializedPattern};
+use crate::color::Color;
+use crate::math::perlin;
+use crate::tuple::Tuple;
+
+/// Pattern that looks up another pattern at points jittered by Perlin noise, bending its regular bands
+/// and edges into organic shapes, such as stripes into wood grain or marble veins.
+#[derive(Debug)]
+pub struct PerturbedPattern {
+    properties: PatternProperties,
+    pattern: Box<dyn Pattern>,
+    scale: f64,
+}
+
+impl PerturbedPattern {
+    /// Create a pattern that moves each point it looks `pattern` up at by up to about `scale` units in
+    /// every direction. The noise varies about once per unit of pattern space, so scaling this pattern's
+    /// transform changes how wavy the result is.
+    pub fn new(pattern: impl Into<Box<dyn Pattern>>, scale: f64) -> Self {
+        Self {
+            properties: PatternProperties::default(),
+            pattern: pattern.into(),
+            scale,
+        }
+    }
+
+    /// Get how far, in units of pattern space, the points are moved at most.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+}
+
+impl Pattern for PerturbedPattern {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn local_pattern_at(&self, pattern_point: &Tuple) -> Color {
+        // Sampling the noise at far apart points gives each axis an independent offset
+        let offset = Tuple::new_vector(
+            perlin(pattern_point),
+            perlin(&(*pattern_point + Tuple::new_vector(31.4, 15.9, 26.5))),
+            perlin(&(*pattern_point + Tuple::new_vector(-35.8, 97.9, -32.3))),
+        );
+        self.pattern
+            .pattern_at(&(*pattern_point + offset * self.scale))
+    }
+
+    #[cfg(feature = "serde")]
+    fn serialized(&self) -> Option<SerializedPattern> {
+        let kind = PatternKind::Perturbed {
+            pattern: Box::new(self.pattern.serialized()?),
+            scale: self.scale,
+        };
+        Some(SerializedPattern::new(kind, *self.transform()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::tests::TestPattern;
+    use crate::patterns::StripePattern;
+
+    #[test]
+    fn perturbed_pattern_offsets_lookups() {
+        let pattern = PerturbedPattern::new(TestPattern::default(), 0.5);
+        let mut moved = false;
+        for step in 0..100 {
+            let t = step as f64 * 0.173;
+            let point = Tuple::new_point(t, 1.0 - t, t * 0.5);
+            let color = pattern.pattern_at(&point);
+            let offset = Tuple::new_vector(
+                color.red - point.x,
+                color.green - point.y,
+                color.blue - point.z,
+            );
+            // Offsets stay within the noise's range, scaled
+            assert!(offset.x.abs().max(offset.y.abs()).max(offset.z.abs()) <= 0.75);
+            moved |= offset.magnitude() > 0.05;
+        }
+        assert!(moved);
+    }
+
+    #[test]
+    fn perturbed_pattern_zero_scale() {
+        let stripes = || StripePattern::new(Color::new(1.0, 1.0, 1.0), Color::new(0.0, 0.0, 0.0));
+        let pattern = PerturbedPattern::new(stripes(), 0.0);
+        for x in [0.2, 0.9, 1.1, -0.5] {
+            let point = Tuple::new_point(x, 0.3, 0.7);
+            assert!(pattern
+                .pattern_at(&point)
+                .is_equal_to(&stripes().pattern_at(&point)));
+        }
+    }
+}
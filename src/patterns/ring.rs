@@ -0,0 +1,75 @@
+use super::{is_floor_even, Pattern, PatternProperties};
+#[cfg(feature = "serde")]
+use super::{PatternKind, SerializedPattern};
+use crate::color::Color;
+use crate::tuple::Tuple;
+
+/// Pattern that alternates between two patterns in unit-wide concentric rings around the y axis.
+#[derive(Debug)]
+pub struct RingPattern {
+    properties: PatternProperties,
+    a: Box<dyn Pattern>,
+    b: Box<dyn Pattern>,
+}
+
+impl RingPattern {
+    /// Create a ring pattern that starts with `a` at the y axis and alternates with `b`.
+    pub fn new(a: impl Into<Box<dyn Pattern>>, b: impl Into<Box<dyn Pattern>>) -> Self {
+        Self {
+            properties: PatternProperties::default(),
+            a: a.into(),
+            b: b.into(),
+        }
+    }
+}
+
+impl Pattern for RingPattern {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn local_pattern_at(&self, pattern_point: &Tuple) -> Color {
+        let distance =
+            (pattern_point.x * pattern_point.x + pattern_point.z * pattern_point.z).sqrt();
+        if is_floor_even(distance) {
+            self.a.pattern_at(pattern_point)
+        } else {
+            self.b.pattern_at(pattern_point)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn serialized(&self) -> Option<SerializedPattern> {
+        let kind = PatternKind::Rings {
+            a: Box::new(self.a.serialized()?),
+            b: Box::new(self.b.serialized()?),
+        };
+        Some(SerializedPattern::new(kind, *self.transform()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_pattern_extends_in_x_and_z() {
+        let white = Color::new(1.0, 1.0, 1.0);
+        let black = Color::new(0.0, 0.0, 0.0);
+        let pattern = RingPattern::new(white, black);
+        let cases = [
+            (Tuple::new_point(0.0, 0.0, 0.0), white),
+            (Tuple::new_point(1.0, 0.0, 0.0), black),
+            (Tuple::new_point(0.0, 0.0, 1.0), black),
+            // 0.708 is just slightly more than sqrt(2) / 2
+            (Tuple::new_point(0.708, 0.0, 0.708), black),
+        ];
+        for (point, expected) in cases {
+            assert!(pattern.pattern_at(&point).is_equal_to(&expected));
+        }
+    }
+}
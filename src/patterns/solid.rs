@@ -0,0 +1,59 @@
+use super::{Pattern, PatternProperties};
+#[cfg(feature = "serde")]
+use super::{PatternKind, SerializedPattern};
+use crate::color::Color;
+use crate::tuple::Tuple;
+
+/// Pattern that is the same color everywhere.
+#[derive(Debug, Default)]
+pub struct SolidPattern {
+    properties: PatternProperties,
+    color: Color,
+}
+
+impl SolidPattern {
+    /// Create a pattern that is `color` everywhere.
+    pub fn new(color: Color) -> Self {
+        Self {
+            properties: PatternProperties::default(),
+            color,
+        }
+    }
+}
+
+impl Pattern for SolidPattern {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn local_pattern_at(&self, _pattern_point: &Tuple) -> Color {
+        self.color
+    }
+
+    #[cfg(feature = "serde")]
+    fn serialized(&self) -> Option<SerializedPattern> {
+        let kind = PatternKind::Solid { color: self.color };
+        Some(SerializedPattern::new(kind, *self.transform()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solid_pattern_at() {
+        let color = Color::new(0.2, 0.4, 0.6);
+        let pattern = SolidPattern::new(color);
+        assert!(pattern
+            .pattern_at(&Tuple::new_point(0.0, 0.0, 0.0))
+            .is_equal_to(&color));
+        assert!(pattern
+            .pattern_at(&Tuple::new_point(-3.5, 12.0, 7.25))
+            .is_equal_to(&color));
+    }
+}
@@ -0,0 +1,111 @@
+use super::{is_floor_even, Pattern, PatternProperties};
+#[cfg(feature = "serde")]
+use super::{PatternKind, SerializedPattern};
+use crate::color::Color;
+use crate::tuple::Tuple;
+
+/// Pattern that alternates between two patterns in unit-wide stripes along the x axis.
+#[derive(Debug)]
+pub struct StripePattern {
+    properties: PatternProperties,
+    a: Box<dyn Pattern>,
+    b: Box<dyn Pattern>,
+}
+
+impl StripePattern {
+    /// Create a stripe pattern that starts with `a` at x = 0 and alternates with `b`.
+    pub fn new(a: impl Into<Box<dyn Pattern>>, b: impl Into<Box<dyn Pattern>>) -> Self {
+        Self {
+            properties: PatternProperties::default(),
+            a: a.into(),
+            b: b.into(),
+        }
+    }
+}
+
+impl Pattern for StripePattern {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn local_pattern_at(&self, pattern_point: &Tuple) -> Color {
+        if is_floor_even(pattern_point.x) {
+            self.a.pattern_at(pattern_point)
+        } else {
+            self.b.pattern_at(pattern_point)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn serialized(&self) -> Option<SerializedPattern> {
+        let kind = PatternKind::Stripes {
+            a: Box::new(self.a.serialized()?),
+            b: Box::new(self.b.serialized()?),
+        };
+        Some(SerializedPattern::new(kind, *self.transform()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn white() -> Color {
+        Color::new(1.0, 1.0, 1.0)
+    }
+
+    fn black() -> Color {
+        Color::new(0.0, 0.0, 0.0)
+    }
+
+    #[test]
+    fn stripe_pattern_constant_in_y_and_z() {
+        let pattern = StripePattern::new(white(), black());
+        for point in [
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_point(0.0, 1.0, 0.0),
+            Tuple::new_point(0.0, 2.0, 0.0),
+            Tuple::new_point(0.0, 0.0, 1.0),
+            Tuple::new_point(0.0, 0.0, 2.0),
+        ] {
+            assert!(pattern.pattern_at(&point).is_equal_to(&white()));
+        }
+    }
+
+    #[test]
+    fn stripe_pattern_alternates_in_x() {
+        let pattern = StripePattern::new(white(), black());
+        let cases = [
+            (0.0, white()),
+            (0.9, white()),
+            (1.0, black()),
+            (-0.1, black()),
+            (-1.0, black()),
+            (-1.1, white()),
+        ];
+        for (x, expected) in cases {
+            assert!(pattern
+                .pattern_at(&Tuple::new_point(x, 0.0, 0.0))
+                .is_equal_to(&expected));
+        }
+    }
+
+    #[test]
+    fn stripe_pattern_nested() {
+        let inner = StripePattern::new(Color::new(1.0, 0.0, 0.0), Color::new(0.0, 1.0, 0.0));
+        let pattern = StripePattern::new(inner, black());
+        assert!(pattern
+            .pattern_at(&Tuple::new_point(0.5, 0.0, 0.0))
+            .is_equal_to(&Color::new(1.0, 0.0, 0.0)));
+        assert!(pattern
+            .pattern_at(&Tuple::new_point(1.5, 0.0, 0.0))
+            .is_equal_to(&black()));
+        assert!(pattern
+            .pattern_at(&Tuple::new_point(2.5, 0.0, 0.0))
+            .is_equal_to(&Color::new(1.0, 0.0, 0.0)));
+    }
+}
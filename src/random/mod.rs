@@ -0,0 +1,102 @@
+pub mod sampler;
+
+use crate::tuple::Tuple;
+
+pub use sampler::{BlueNoiseSampler, HaltonSampler, JitteredSampler, Sampler, UniformSampler};
+
+/// Small, fast pseudo-random number generator used where the renderer needs random samples.
+///
+/// This is SplitMix64, which produces well-distributed values even from consecutive seeds, so a generator
+/// can be seeded from a pixel's index. It is deterministic and not suitable for cryptography.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create a generator from a seed. Generators created from the same seed produce the same values.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Create a generator for one of many independent streams of random numbers drawn by a render with
+    /// the given seed, such as one stream per pixel. Changing the seed changes every stream, while the
+    /// streams of seed 0 are those of `Rng::new(stream)`.
+    pub fn with_stream(seed: u64, stream: u64) -> Self {
+        // Scramble the seed so that neighboring seeds do not just shift the streams onto each other.
+        // Starting one step back makes the first value the scrambled seed itself, which is 0 for seed 0.
+        let scrambled = Rng::new(seed.wrapping_sub(0x9e37_79b9_7f4a_7c15)).next_u64();
+        Self::new(stream ^ scrambled)
+    }
+
+    /// Generate the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Generate a number uniformly distributed in [0, 1).
+    pub fn next_f64(&mut self) -> f64 {
+        // The top 53 bits fill the mantissa of a double exactly
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Mix the bits of a position into a stream number, so random choices made for a point, such as the
+/// samples taken to shade it, are the same every time it is shaded with the same seed.
+pub(crate) fn position_hash(point: &Tuple) -> u64 {
+    point.x.to_bits() ^ point.y.to_bits().rotate_left(21) ^ point.z.to_bits().rotate_left(42)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn rng_same_seed() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn rng_with_stream() {
+        assert_eq!(Rng::with_stream(0, 5).next_u64(), Rng::new(5).next_u64());
+        let mut a = Rng::with_stream(1, 5);
+        let mut b = Rng::with_stream(1, 5);
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        // Streams of different seeds do not overlap with each other, even for consecutive seeds and streams
+        let first = |seed, stream| Rng::with_stream(seed, stream).next_u64();
+        let values = [
+            first(0, 0),
+            first(0, 1),
+            first(1, 0),
+            first(1, 1),
+            first(2, 0),
+        ];
+        for (index, value) in values.iter().enumerate() {
+            assert!(!values[index + 1..].contains(value));
+        }
+        let mut rng = Rng::with_stream(1, 0);
+        rng.next_u64();
+        assert_ne!(rng.next_u64(), first(2, 0));
+    }
+
+    #[test]
+    fn rng_next_f64() {
+        let mut rng = Rng::new(7);
+        let values: Vec<f64> = (0..1000).map(|_| rng.next_f64()).collect();
+        assert!(values.iter().all(|value| (0.0..1.0).contains(value)));
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        assert_approx_eq!(mean, 0.5, epsilon = 0.05);
+    }
+}
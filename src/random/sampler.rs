@@ -0,0 +1,231 @@
+use super::Rng;
+use std::fmt::Debug;
+
+/// Strategy for spreading sample points over the unit square, such as the offsets of the rays cast
+/// through a pixel.
+///
+/// How evenly the points cover the square decides how quickly an average over them converges: clumps
+/// waste samples and gaps show up as noise, while regular patterns show up as aliasing.
+pub trait Sampler: Debug + Send + Sync {
+    /// Generate `count` points in [0, 1) x [0, 1), drawing any randomness from `rng`.
+    fn sample_square(&self, count: usize, rng: &mut Rng) -> Vec<(f64, f64)>;
+}
+
+/// Place the points at the centers of the cells of a regular grid, filling it row by row.
+/// Renders are free of noise, but fine detail can alias into patterns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UniformSampler;
+
+impl Sampler for UniformSampler {
+    fn sample_square(&self, count: usize, _rng: &mut Rng) -> Vec<(f64, f64)> {
+        grid(count, || (0.5, 0.5))
+    }
+}
+
+/// Place each point at a random position within its own cell of a regular grid, trading the aliasing of
+/// `UniformSampler` for fine noise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct JitteredSampler;
+
+impl Sampler for JitteredSampler {
+    fn sample_square(&self, count: usize, rng: &mut Rng) -> Vec<(f64, f64)> {
+        grid(count, || (rng.next_f64(), rng.next_f64()))
+    }
+}
+
+/// Place the points along the Halton sequence in bases 2 and 3, which covers the square evenly for any
+/// number of points. The sequence is shifted by a random offset, so neighboring pixels see different
+/// points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HaltonSampler;
+
+impl Sampler for HaltonSampler {
+    fn sample_square(&self, count: usize, rng: &mut Rng) -> Vec<(f64, f64)> {
+        let (x_shift, y_shift) = (rng.next_f64(), rng.next_f64());
+        // The sequence starts at (0, 0), which is skipped
+        (1..=count as u64)
+            .map(|index| {
+                (
+                    (radical_inverse(index, 2) + x_shift).fract(),
+                    (radical_inverse(index, 3) + y_shift).fract(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Place random points that keep their distance from each other, so they are spread evenly without any
+/// regular pattern and their noise is fine-grained.
+///
+/// This is Mitchell's best-candidate algorithm: each point is the one of several random candidates that
+/// lies furthest from the points before it, measuring distance across the edges of the square as if it
+/// wrapped around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BlueNoiseSampler;
+
+/// Number of random candidates considered for each point by `BlueNoiseSampler`.
+const BLUE_NOISE_CANDIDATES: usize = 8;
+
+impl Sampler for BlueNoiseSampler {
+    fn sample_square(&self, count: usize, rng: &mut Rng) -> Vec<(f64, f64)> {
+        let mut points: Vec<(f64, f64)> = Vec::with_capacity(count);
+        while points.len() < count {
+            let mut best = (rng.next_f64(), rng.next_f64());
+            let mut best_distance = nearest_distance_squared(&points, best);
+            for _ in 1..BLUE_NOISE_CANDIDATES {
+                let candidate = (rng.next_f64(), rng.next_f64());
+                let distance = nearest_distance_squared(&points, candidate);
+                if distance > best_distance {
+                    best = candidate;
+                    best_distance = distance;
+                }
+            }
+            points.push(best);
+        }
+        points
+    }
+}
+
+/// Generate `count` points in the cells of the smallest near-square grid with enough cells, filling it
+/// row by row and placing each point at the offset within its cell returned by `offset`.
+fn grid(count: usize, mut offset: impl FnMut() -> (f64, f64)) -> Vec<(f64, f64)> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let columns = (count as f64).sqrt().ceil() as usize;
+    let rows = count.div_ceil(columns);
+    (0..count)
+        .map(|index| {
+            let (x_offset, y_offset) = offset();
+            (
+                ((index % columns) as f64 + x_offset) / columns as f64,
+                ((index / columns) as f64 + y_offset) / rows as f64,
+            )
+        })
+        .collect()
+}
+
+/// Mirror the digits of `index` in the given base around the radix point, giving a number in [0, 1).
+fn radical_inverse(mut index: u64, base: u64) -> f64 {
+    let mut result = 0.0;
+    let mut digit_value = 1.0 / base as f64;
+    while index > 0 {
+        result += (index % base) as f64 * digit_value;
+        index /= base;
+        digit_value /= base as f64;
+    }
+    result
+}
+
+/// Compute the squared distance from `point` to the nearest of `points`, wrapping around the edges of
+/// the unit square. Returns infinity if there are no points.
+fn nearest_distance_squared(points: &[(f64, f64)], point: (f64, f64)) -> f64 {
+    points
+        .iter()
+        .map(|&(x, y)| {
+            let dx = (x - point.0).abs();
+            let dy = (y - point.1).abs();
+            let (dx, dy) = (dx.min(1.0 - dx), dy.min(1.0 - dy));
+            dx * dx + dy * dy
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    fn samplers() -> [Box<dyn Sampler>; 4] {
+        [
+            Box::new(UniformSampler),
+            Box::new(JitteredSampler),
+            Box::new(HaltonSampler),
+            Box::new(BlueNoiseSampler),
+        ]
+    }
+
+    #[test]
+    fn sampler_sample_square() {
+        for sampler in samplers() {
+            let mut rng = Rng::new(3);
+            for count in [0, 1, 5, 16] {
+                let points = sampler.sample_square(count, &mut rng);
+                assert_eq!(points.len(), count, "{sampler:?}");
+                assert!(
+                    points
+                        .iter()
+                        .all(|(x, y)| (0.0..1.0).contains(x) && (0.0..1.0).contains(y)),
+                    "{sampler:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn uniform_sampler_sample_square() {
+        let points = UniformSampler.sample_square(4, &mut Rng::new(0));
+        assert_eq!(
+            points,
+            vec![(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)]
+        );
+        // Counts that are not square fill the grid row by row
+        let points = UniformSampler.sample_square(3, &mut Rng::new(0));
+        assert_eq!(points, vec![(0.25, 0.25), (0.75, 0.25), (0.25, 0.75)]);
+    }
+
+    #[test]
+    fn jittered_sampler_sample_square() {
+        let points = JitteredSampler.sample_square(16, &mut Rng::new(1));
+        // Every cell of the 4x4 grid holds exactly one point
+        for (index, (x, y)) in points.into_iter().enumerate() {
+            assert_eq!((x * 4.0) as usize, index % 4);
+            assert_eq!((y * 4.0) as usize, index / 4);
+        }
+    }
+
+    #[test]
+    fn halton_sampler_radical_inverse() {
+        let base_2: Vec<f64> = (1..=4).map(|index| radical_inverse(index, 2)).collect();
+        let base_3: Vec<f64> = (1..=4).map(|index| radical_inverse(index, 3)).collect();
+        for (actual, expected) in base_2.iter().zip([0.5, 0.25, 0.75, 0.125]) {
+            assert_approx_eq!(*actual, expected);
+        }
+        for (actual, expected) in base_3
+            .iter()
+            .zip([1.0 / 3.0, 2.0 / 3.0, 1.0 / 9.0, 4.0 / 9.0])
+        {
+            assert_approx_eq!(*actual, expected);
+        }
+    }
+
+    #[test]
+    fn halton_sampler_sample_square() {
+        // The points follow the sequence, shifted by the same random offset
+        let points = HaltonSampler.sample_square(3, &mut Rng::new(2));
+        let mut rng = Rng::new(2);
+        let (x_shift, y_shift) = (rng.next_f64(), rng.next_f64());
+        let expected = [(0.5, 1.0 / 3.0), (0.25, 2.0 / 3.0), (0.75, 1.0 / 9.0)];
+        for ((x, y), (expected_x, expected_y)) in points.into_iter().zip(expected) {
+            assert_approx_eq!(x, (expected_x + x_shift).fract());
+            assert_approx_eq!(y, (expected_y + y_shift).fract());
+        }
+    }
+
+    #[test]
+    fn blue_noise_sampler_sample_square() {
+        // Points keep further apart than independent random points do
+        let count = 64;
+        let points = BlueNoiseSampler.sample_square(count, &mut Rng::new(4));
+        let mut rng = Rng::new(4);
+        let random: Vec<(f64, f64)> = (0..count)
+            .map(|_| (rng.next_f64(), rng.next_f64()))
+            .collect();
+        let closest = |points: &[(f64, f64)]| {
+            (1..points.len())
+                .map(|index| nearest_distance_squared(&points[..index], points[index]))
+                .fold(f64::INFINITY, f64::min)
+        };
+        assert!(closest(&points) > 2.0 * closest(&random));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::matrix::Matrix4;
+use crate::tuple::Tuple;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Ray {
+    pub origin: Tuple,
+    pub direction: Tuple,
+}
+
+impl Ray {
+    /// Create a ray starting at the `origin` point and travelling along the `direction` vector.
+    pub fn new(origin: Tuple, direction: Tuple) -> Self {
+        Self { origin, direction }
+    }
+
+    /// Compute the point at distance `t` along the ray.
+    pub fn position(&self, t: f64) -> Tuple {
+        self.origin + self.direction * t
+    }
+
+    /// Return a new ray that is this ray transformed by `transform`.
+    pub fn transform(&self, transform: &Matrix4) -> Ray {
+        Ray::new(*transform * self.origin, *transform * self.direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::{scaling, translation};
+
+    #[test]
+    fn ray_new() {
+        let origin = Tuple::new_point(1.0, 2.0, 3.0);
+        let direction = Tuple::new_vector(4.0, 5.0, 6.0);
+        let ray = Ray::new(origin, direction);
+        assert!(ray.origin.is_equal_to(&Tuple::new_point(1.0, 2.0, 3.0)));
+        assert!(ray.origin.is_point());
+        assert!(ray.direction.is_equal_to(&Tuple::new_vector(4.0, 5.0, 6.0)));
+        assert!(ray.direction.is_vector());
+    }
+
+    #[test]
+    fn ray_position() {
+        let ray = Ray::new(
+            Tuple::new_point(2.0, 3.0, 4.0),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+        );
+        assert!(ray
+            .position(0.0)
+            .is_equal_to(&Tuple::new_point(2.0, 3.0, 4.0)));
+        assert!(ray
+            .position(1.0)
+            .is_equal_to(&Tuple::new_point(3.0, 3.0, 4.0)));
+        assert!(ray
+            .position(-1.0)
+            .is_equal_to(&Tuple::new_point(1.0, 3.0, 4.0)));
+        assert!(ray
+            .position(2.5)
+            .is_equal_to(&Tuple::new_point(4.5, 3.0, 4.0)));
+    }
+
+    #[test]
+    fn ray_transform() {
+        let ray = Ray::new(
+            Tuple::new_point(1.0, 2.0, 3.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+
+        let translated = ray.transform(&translation(3.0, 4.0, 5.0));
+        assert!(translated
+            .origin
+            .is_equal_to(&Tuple::new_point(4.0, 6.0, 8.0)));
+        assert!(translated
+            .direction
+            .is_equal_to(&Tuple::new_vector(0.0, 1.0, 0.0)));
+
+        let scaled = ray.transform(&scaling(2.0, 3.0, 4.0));
+        assert!(scaled.origin.is_equal_to(&Tuple::new_point(2.0, 6.0, 12.0)));
+        assert!(scaled
+            .direction
+            .is_equal_to(&Tuple::new_vector(0.0, 3.0, 0.0)));
+    }
+}
@@ -0,0 +1,920 @@
+use crate::camera::Camera;
+use crate::color::Color;
+use crate::light::{AreaLight, DirectionalLight, Falloff, Material, MaterialLibrary, PointLight};
+use crate::matrix::{Matrix4, MatrixError};
+use crate::patterns::{
+    CheckersPattern, GradientPattern, NoisePattern, Pattern, PerturbedPattern, RingPattern,
+    StripePattern,
+};
+use crate::random::UniformSampler;
+use crate::shapes::{Cone, Csg, CsgOperation, Disk, Group, Plane, Shape, Sphere, Torus, Triangle};
+use crate::transform::{view_transform, Transform};
+use crate::tuple::Tuple;
+use crate::world::World;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use yaml_rust2::{ScanError, Yaml, YamlLoader};
+
+/// Error raised while loading a scene.
+#[derive(Debug)]
+pub enum SceneError {
+    /// The scene file could not be read.
+    Io(std::io::Error),
+    /// The scene is not valid YAML.
+    Yaml(ScanError),
+    /// The scene is valid YAML but does not describe a scene, such as a shape missing a required field.
+    Invalid(String),
+    /// A transform in the scene is not invertible.
+    Matrix(MatrixError),
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneError::Io(error) => write!(f, "cannot read scene: {error}"),
+            SceneError::Yaml(error) => write!(f, "invalid YAML: {error}"),
+            SceneError::Invalid(message) => write!(f, "invalid scene: {message}"),
+            SceneError::Matrix(error) => write!(f, "invalid transform: {error}"),
+        }
+    }
+}
+
+impl Error for SceneError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SceneError::Io(error) => Some(error),
+            SceneError::Yaml(error) => Some(error),
+            SceneError::Invalid(_) => None,
+            SceneError::Matrix(error) => Some(error),
+        }
+    }
+}
+
+impl From<std::io::Error> for SceneError {
+    fn from(error: std::io::Error) -> Self {
+        SceneError::Io(error)
+    }
+}
+
+impl From<ScanError> for SceneError {
+    fn from(error: ScanError) -> Self {
+        SceneError::Yaml(error)
+    }
+}
+
+impl From<MatrixError> for SceneError {
+    fn from(error: MatrixError) -> Self {
+        SceneError::Matrix(error)
+    }
+}
+
+/// Create the error for a scene that does not match the expected format.
+fn invalid(message: impl Into<String>) -> SceneError {
+    SceneError::Invalid(message.into())
+}
+
+/// World and camera described by a scene file.
+///
+/// Scenes use the YAML format from the book's bonus chapters: a list of `add` entries for the camera,
+/// the light and shapes, and `define` entries naming materials and transforms for reuse.
+/// Materials named by shapes may also come from a `MaterialLibrary` given to the loader.
+#[derive(Debug)]
+pub struct Scene {
+    pub camera: Camera,
+    pub world: World,
+}
+
+impl Scene {
+    /// Load a scene from a YAML file, which may name the materials of `MaterialLibrary::presets`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Scene, SceneError> {
+        Self::from_yaml(&std::fs::read_to_string(path)?)
+    }
+
+    /// Load a scene from a YAML file, looking up the materials it names in a library as well as in its
+    /// own definitions.
+    pub fn load_with_library<P: AsRef<Path>>(
+        path: P,
+        library: &MaterialLibrary,
+    ) -> Result<Scene, SceneError> {
+        Self::from_yaml_with_library(&std::fs::read_to_string(path)?, library)
+    }
+
+    /// Parse a scene from YAML source, which may name the materials of `MaterialLibrary::presets`.
+    pub fn from_yaml(source: &str) -> Result<Scene, SceneError> {
+        Self::from_yaml_with_library(source, &MaterialLibrary::presets())
+    }
+
+    /// Parse a scene from YAML source, looking up the materials it names in a library as well as in its
+    /// own definitions. The scene's definitions take precedence over materials of the same name.
+    pub fn from_yaml_with_library(
+        source: &str,
+        library: &MaterialLibrary,
+    ) -> Result<Scene, SceneError> {
+        let documents = YamlLoader::load_from_str(source)?;
+        let entries = documents
+            .first()
+            .and_then(Yaml::as_vec)
+            .ok_or_else(|| invalid("a scene must be a list of entries"))?;
+
+        let mut parser = SceneParser::new(library);
+        for entry in entries {
+            parser.parse_entry(entry)?;
+        }
+        let camera = parser
+            .camera
+            .ok_or_else(|| invalid("the scene has no camera"))?;
+        Ok(Scene {
+            camera,
+            world: parser.world,
+        })
+    }
+}
+
+/// State built up while reading the entries of a scene.
+struct SceneParser<'a> {
+    library: &'a MaterialLibrary,
+    definitions: HashMap<String, Yaml>,
+    camera: Option<Camera>,
+    world: World,
+}
+
+impl<'a> SceneParser<'a> {
+    /// Create a parser for a scene that may name materials from `library`.
+    fn new(library: &'a MaterialLibrary) -> Self {
+        Self {
+            library,
+            definitions: HashMap::new(),
+            camera: None,
+            world: World::default(),
+        }
+    }
+
+    /// Read a top-level `add` or `define` entry.
+    fn parse_entry(&mut self, entry: &Yaml) -> Result<(), SceneError> {
+        if let Some(name) = entry["define"].as_str() {
+            return self.define(name, entry);
+        }
+        match entry["add"].as_str() {
+            Some("camera") => self.camera = Some(self.parse_camera(entry)?),
+            Some("light") => {
+                if self.world.light.is_some() {
+                    return Err(invalid("the world supports a single light"));
+                }
+                self.world.light = Some(self.parse_light(entry)?);
+            }
+            Some(_) => {
+                let shape = self.parse_shape(entry)?;
+                self.world.objects.push(shape);
+            }
+            None => return Err(invalid("every entry must `add` or `define` something")),
+        }
+        Ok(())
+    }
+
+    /// Name a value, merging it over the value of another definition if it `extend`s one.
+    fn define(&mut self, name: &str, entry: &Yaml) -> Result<(), SceneError> {
+        let mut value = field(entry, "value", name)?.clone();
+        if let Some(base) = entry["extend"].as_str() {
+            let mut merged = self.definition(base)?.as_hash().cloned().ok_or_else(|| {
+                invalid(format!(
+                    "{name}: only definitions of materials can be extended"
+                ))
+            })?;
+            let overrides = value
+                .as_hash()
+                .ok_or_else(|| invalid(format!("{name}: the extension must be a material")))?;
+            for (key, override_value) in overrides {
+                merged.insert(key.clone(), override_value.clone());
+            }
+            value = Yaml::Hash(merged);
+        }
+        self.definitions.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    /// Look up a defined value by name.
+    fn definition(&self, name: &str) -> Result<&Yaml, SceneError> {
+        self.definitions
+            .get(name)
+            .ok_or_else(|| invalid(format!("`{name}` is not defined")))
+    }
+
+    fn parse_camera(&self, entry: &Yaml) -> Result<Camera, SceneError> {
+        let mut camera = Camera::new(
+            size(entry, "width", "camera")?,
+            size(entry, "height", "camera")?,
+            number(field(entry, "field-of-view", "camera")?, "camera")?,
+        );
+        camera.set_transform(view_transform(
+            &point(entry, "from", "camera")?,
+            &point(entry, "to", "camera")?,
+            &vector(entry, "up", "camera")?,
+        ))?;
+        if let Some(exposure) = optional_number(entry, "exposure", "camera")? {
+            camera.set_exposure(exposure);
+        }
+        if !entry["seed"].is_badvalue() {
+            let seed = entry["seed"]
+                .as_i64()
+                .and_then(|seed| u64::try_from(seed).ok())
+                .ok_or_else(|| invalid("camera: `seed` must be a non-negative whole number"))?;
+            camera.set_seed(seed);
+        }
+        Ok(camera)
+    }
+
+    /// Read a point light placed `at` a position, a directional light shining along a `direction`, or an
+    /// area light spanning `uvec` and `vvec` from a `corner`, sampled on a `usteps` by `vsteps` grid and
+    /// jittered unless `jitter` is false. Point and area lights fade with distance if they have a
+    /// `falloff` of linear or inverse-square, with a `falloff-radius`.
+    fn parse_light(&self, entry: &Yaml) -> Result<Box<dyn crate::light::Light>, SceneError> {
+        let intensity = color(field(entry, "intensity", "light")?, "light")?;
+        if !entry["corner"].is_badvalue() {
+            let mut light = AreaLight::new(
+                point(entry, "corner", "light")?,
+                vector(entry, "uvec", "light")?,
+                size(entry, "usteps", "light")?,
+                vector(entry, "vvec", "light")?,
+                size(entry, "vsteps", "light")?,
+                intensity,
+            );
+            if entry["jitter"].as_bool() == Some(false) {
+                light.sampler = Arc::new(UniformSampler);
+            }
+            light.falloff = falloff(entry)?;
+            Ok(Box::new(light))
+        } else if entry["direction"].is_badvalue() {
+            let mut light = PointLight::new(point(entry, "at", "light")?, intensity);
+            light.falloff = falloff(entry)?;
+            Ok(Box::new(light))
+        } else {
+            let direction = vector(entry, "direction", "light")?;
+            Ok(Box::new(DirectionalLight::new(direction, intensity)))
+        }
+    }
+
+    fn parse_shape(&self, entry: &Yaml) -> Result<Box<dyn Shape>, SceneError> {
+        let kind = entry["add"]
+            .as_str()
+            .ok_or_else(|| invalid("every shape must say which shape it `add`s"))?;
+        let mut shape: Box<dyn Shape> = match kind {
+            "sphere" => Box::new(Sphere::new()),
+            "plane" => Box::new(Plane::new()),
+            "cone" => Box::new(Cone::truncated(
+                optional_number(entry, "min", kind)?.unwrap_or(f64::NEG_INFINITY),
+                optional_number(entry, "max", kind)?.unwrap_or(f64::INFINITY),
+                entry["closed"].as_bool().unwrap_or(false),
+            )),
+            "disk" => Box::new(Disk::annulus(
+                optional_number(entry, "inner-radius", kind)?.unwrap_or(0.0),
+                optional_number(entry, "outer-radius", kind)?.unwrap_or(1.0),
+            )),
+            "torus" => {
+                let default = Torus::default();
+                Box::new(Torus::new(
+                    optional_number(entry, "major-radius", kind)?.unwrap_or(default.major_radius),
+                    optional_number(entry, "minor-radius", kind)?.unwrap_or(default.minor_radius),
+                ))
+            }
+            "triangle" => Box::new(Triangle::new(
+                point(entry, "p1", kind)?,
+                point(entry, "p2", kind)?,
+                point(entry, "p3", kind)?,
+            )),
+            "group" => {
+                let mut group = Group::new();
+                let children = field(entry, "children", kind)?
+                    .as_vec()
+                    .ok_or_else(|| invalid("group: `children` must be a list of shapes"))?;
+                for child in children {
+                    group.add_child(self.parse_shape(child)?);
+                }
+                Box::new(group)
+            }
+            "csg" => {
+                let operation = match field(entry, "operation", kind)?.as_str() {
+                    Some("union") => CsgOperation::Union,
+                    Some("intersection") => CsgOperation::Intersection,
+                    Some("difference") => CsgOperation::Difference,
+                    _ => {
+                        return Err(invalid(
+                            "csg: `operation` must be union, intersection or difference",
+                        ))
+                    }
+                };
+                Box::new(Csg::new(
+                    operation,
+                    self.parse_shape(field(entry, "left", kind)?)?,
+                    self.parse_shape(field(entry, "right", kind)?)?,
+                ))
+            }
+            _ => return Err(invalid(format!("unknown shape `{kind}`"))),
+        };
+
+        if !entry["material"].is_badvalue() {
+            shape.set_material(self.parse_material(&entry["material"], kind)?);
+        }
+        if let Some(casts_shadow) = entry["shadow"].as_bool() {
+            shape.set_casts_shadow(casts_shadow);
+        }
+        if !entry["transform"].is_badvalue() {
+            shape.set_transform(self.parse_transform(&entry["transform"], kind)?)?;
+        }
+        Ok(shape)
+    }
+
+    /// Read a material given inline, as the name of a definition or as the name of a material in the library.
+    fn parse_material(&self, value: &Yaml, context: &str) -> Result<Material, SceneError> {
+        let value = match value.as_str() {
+            Some(name) => match self.library.get(name) {
+                Some(material) if !self.definitions.contains_key(name) => {
+                    return Ok(material.clone())
+                }
+                _ => self.definition(name)?,
+            },
+            None => value,
+        };
+        let properties = value
+            .as_hash()
+            .ok_or_else(|| invalid(format!("{context}: a material must be a mapping")))?;
+
+        let mut material = Material::default();
+        for (key, property) in properties {
+            let key = key
+                .as_str()
+                .ok_or_else(|| invalid(format!("{context}: material keys must be names")))?;
+            match key {
+                "color" => material.color = color(property, context)?,
+                "pattern" => material.pattern = Some(self.parse_pattern(property, context)?),
+                "ambient" => material.ambient = number(property, context)?,
+                "diffuse" => material.diffuse = number(property, context)?,
+                "specular" => material.specular = number(property, context)?,
+                "shininess" => material.shininess = number(property, context)?,
+                "emissive" => material.emissive = color(property, context)?,
+                "reflective" => material.reflective = number(property, context)?,
+                "metalness" => material.metalness = number(property, context)?,
+                "roughness" => material.roughness = number(property, context)?,
+                "transparency" => material.transparency = number(property, context)?,
+                "refractive-index" => material.refractive_index = number(property, context)?,
+                "max-depth" => {
+                    let depth = property
+                        .as_i64()
+                        .and_then(|depth| usize::try_from(depth).ok())
+                        .ok_or_else(|| {
+                            invalid(format!(
+                                "{context}: `max-depth` must be a non-negative whole number"
+                            ))
+                        })?;
+                    material.max_depth = Some(depth);
+                }
+                _ => {
+                    return Err(invalid(format!(
+                        "{context}: unknown material property `{key}`"
+                    )))
+                }
+            }
+        }
+        Ok(material)
+    }
+
+    /// Read a pattern of two colors: stripes, checkers, gradient, rings or noise, jittered by noise of
+    /// the strength given by `perturb`, if any. Noise sums 4 `octaves` with a `persistence` of 0.5
+    /// unless given otherwise.
+    fn parse_pattern(&self, value: &Yaml, context: &str) -> Result<Arc<dyn Pattern>, SceneError> {
+        let colors = match field(value, "colors", context)?.as_vec().map(Vec::as_slice) {
+            Some([a, b]) => (color(a, context)?, color(b, context)?),
+            _ => return Err(invalid(format!("{context}: a pattern needs two `colors`"))),
+        };
+        let mut pattern: Box<dyn Pattern> = match value["type"].as_str() {
+            Some("stripes") => Box::new(StripePattern::new(colors.0, colors.1)),
+            Some("checkers") => Box::new(CheckersPattern::new(colors.0, colors.1)),
+            Some("gradient") => Box::new(GradientPattern::new(colors.0, colors.1)),
+            Some("rings") => Box::new(RingPattern::new(colors.0, colors.1)),
+            Some("noise") => {
+                let octaves = match &value["octaves"] {
+                    Yaml::BadValue => 4,
+                    _ => u32::try_from(size(value, "octaves", context)?)
+                        .map_err(|_| invalid(format!("{context}: `octaves` is too large")))?,
+                };
+                let persistence = optional_number(value, "persistence", context)?.unwrap_or(0.5);
+                Box::new(NoisePattern::new(colors.0, colors.1, octaves, persistence))
+            }
+            _ => {
+                let kinds = "stripes, checkers, gradient, rings or noise";
+                return Err(invalid(format!(
+                    "{context}: a pattern's `type` must be {kinds}"
+                )));
+            }
+        };
+        if !value["transform"].is_badvalue() {
+            pattern.set_transform(self.parse_transform(&value["transform"], context)?)?;
+        }
+        if !value["perturb"].is_badvalue() {
+            let scale = number(&value["perturb"], context)?;
+            pattern = Box::new(PerturbedPattern::new(pattern, scale));
+        }
+        Ok(Arc::from(pattern))
+    }
+
+    /// Read a list of transformations applied in order, such as `[scale, 2, 2, 2]`, or names of defined lists.
+    fn parse_transform(&self, value: &Yaml, context: &str) -> Result<Matrix4, SceneError> {
+        let steps = value
+            .as_vec()
+            .ok_or_else(|| invalid(format!("{context}: a transform must be a list")))?;
+        let mut transform = Transform::identity();
+        for step in steps {
+            if let Some(name) = step.as_str() {
+                transform = transform.then(self.parse_transform(self.definition(name)?, context)?);
+                continue;
+            }
+            let (operation, arguments) = match step.as_vec().map(Vec::as_slice) {
+                Some([operation, arguments @ ..]) => (operation.as_str(), arguments),
+                _ => return Err(invalid(format!("{context}: empty transformation"))),
+            };
+            let arguments = arguments
+                .iter()
+                .map(|argument| number(argument, context))
+                .collect::<Result<Vec<_>, _>>()?;
+            transform = match (operation, arguments.as_slice()) {
+                (Some("translate"), &[x, y, z]) => transform.translate(x, y, z),
+                (Some("scale"), &[x, y, z]) => transform.scale(x, y, z),
+                (Some("rotate-x"), &[radians]) => transform.rotate_x(radians),
+                (Some("rotate-y"), &[radians]) => transform.rotate_y(radians),
+                (Some("rotate-z"), &[radians]) => transform.rotate_z(radians),
+                (Some("shear"), &[x_y, x_z, y_x, y_z, z_x, z_y]) => {
+                    transform.shear(x_y, x_z, y_x, y_z, z_x, z_y)
+                }
+                _ => {
+                    return Err(invalid(format!(
+                        "{context}: unknown transformation {operation:?} with {} arguments",
+                        arguments.len()
+                    )))
+                }
+            };
+        }
+        Ok(transform.matrix())
+    }
+}
+
+/// Get a required field of an entry.
+fn field<'a>(entry: &'a Yaml, key: &str, context: &str) -> Result<&'a Yaml, SceneError> {
+    match &entry[key] {
+        Yaml::BadValue => Err(invalid(format!("{context}: missing field `{key}`"))),
+        value => Ok(value),
+    }
+}
+
+/// Read a number, which YAML may have parsed as an integer or a real.
+fn number(value: &Yaml, context: &str) -> Result<f64, SceneError> {
+    match value {
+        Yaml::Integer(integer) => Some(*integer as f64),
+        Yaml::Real(_) => value.as_f64(),
+        _ => None,
+    }
+    .ok_or_else(|| invalid(format!("{context}: expected a number, found {value:?}")))
+}
+
+/// Read an optional number field.
+fn optional_number(entry: &Yaml, key: &str, context: &str) -> Result<Option<f64>, SceneError> {
+    match &entry[key] {
+        Yaml::BadValue => Ok(None),
+        value => number(value, context).map(Some),
+    }
+}
+
+/// Read the way a light fades with distance, which is not at all without a `falloff` field.
+fn falloff(entry: &Yaml) -> Result<Falloff, SceneError> {
+    let radius = || {
+        let radius = number(field(entry, "falloff-radius", "light")?, "light")?;
+        if radius > 0.0 {
+            Ok(radius)
+        } else {
+            Err(invalid("light: `falloff-radius` must be positive"))
+        }
+    };
+    match &entry["falloff"] {
+        Yaml::BadValue => Ok(Falloff::None),
+        value => match value.as_str() {
+            Some("none") => Ok(Falloff::None),
+            Some("linear") => Ok(Falloff::Linear { radius: radius()? }),
+            Some("inverse-square") => Ok(Falloff::InverseSquare { radius: radius()? }),
+            _ => Err(invalid(
+                "light: `falloff` must be none, linear or inverse-square",
+            )),
+        },
+    }
+}
+
+/// Read a positive whole number field, such as the width of the camera.
+fn size(entry: &Yaml, key: &str, context: &str) -> Result<usize, SceneError> {
+    field(entry, key, context)?
+        .as_i64()
+        .and_then(|size| usize::try_from(size).ok())
+        .filter(|&size| size > 0)
+        .ok_or_else(|| {
+            invalid(format!(
+                "{context}: `{key}` must be a positive whole number"
+            ))
+        })
+}
+
+/// Read a list of three numbers.
+fn triple(value: &Yaml, context: &str) -> Result<[f64; 3], SceneError> {
+    match value.as_vec().map(Vec::as_slice) {
+        Some([x, y, z]) => Ok([
+            number(x, context)?,
+            number(y, context)?,
+            number(z, context)?,
+        ]),
+        _ => Err(invalid(format!(
+            "{context}: expected three numbers, found {value:?}"
+        ))),
+    }
+}
+
+fn point(entry: &Yaml, key: &str, context: &str) -> Result<Tuple, SceneError> {
+    let [x, y, z] = triple(field(entry, key, context)?, context)?;
+    Ok(Tuple::new_point(x, y, z))
+}
+
+fn vector(entry: &Yaml, key: &str, context: &str) -> Result<Tuple, SceneError> {
+    let [x, y, z] = triple(field(entry, key, context)?, context)?;
+    Ok(Tuple::new_vector(x, y, z))
+}
+
+fn color(value: &Yaml, context: &str) -> Result<Color, SceneError> {
+    let [red, green, blue] = triple(value, context)?;
+    Ok(Color::new(red, green, blue))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::random::Rng;
+    use crate::transform::{scaling, translation};
+    use std::f64::consts::FRAC_PI_2;
+
+    const CAMERA: &str = "
+- add: camera
+  width: 100
+  height: 50
+  field-of-view: 1.5707963267948966
+  from: [0, 0, -5]
+  to: [0, 0, 0]
+  up: [0, 1, 0]
+";
+
+    #[test]
+    fn scene_from_yaml_camera_and_light() {
+        let source =
+            format!("{CAMERA}- add: light\n  at: [-10, 10, -10]\n  intensity: [1, 1, 1]\n");
+        let scene = Scene::from_yaml(&source).unwrap();
+        assert_eq!(scene.camera.hsize(), 100);
+        assert_eq!(scene.camera.vsize(), 50);
+        assert_approx_eq!(scene.camera.field_of_view(), FRAC_PI_2);
+        assert!(scene.camera.transform().is_equal_to(&view_transform(
+            &Tuple::new_point(0.0, 0.0, -5.0),
+            &Tuple::new_point(0.0, 0.0, 0.0),
+            &Tuple::new_vector(0.0, 1.0, 0.0),
+        )));
+
+        let light = scene.world.light.unwrap();
+        assert!(light.intensity().is_equal_to(&Color::new(1.0, 1.0, 1.0)));
+        assert_approx_eq!(
+            light.distance_from(&Tuple::new_point(-10.0, 10.0, -9.0)),
+            1.0
+        );
+        assert_approx_eq!(scene.camera.exposure(), 0.0);
+    }
+
+    #[test]
+    fn scene_from_yaml_area_light() {
+        let source = format!(
+            "{CAMERA}- add: light
+  corner: [-1, 2, 4]
+  uvec: [2, 0, 0]
+  vvec: [0, 2, 0]
+  usteps: 4
+  vsteps: 2
+  jitter: false
+  intensity: [1.5, 1.5, 1.5]
+"
+        );
+        let scene = Scene::from_yaml(&source).unwrap();
+        let light = scene.world.light.unwrap();
+        assert!(light.intensity().is_equal_to(&Color::new(1.5, 1.5, 1.5)));
+        let point = Tuple::new_point(0.0, 3.0, 0.0);
+        assert_approx_eq!(light.distance_from(&point), 4.0);
+        let samples = light.samples_from(&point, &mut Rng::new(0));
+        assert_eq!(samples.len(), 8);
+        // Without jitter, the first sample is at the center of the first cell
+        assert_approx_eq!(
+            samples[0].1,
+            Tuple::new_vector(-0.75, -0.5, 4.0).magnitude()
+        );
+
+        let error = Scene::from_yaml(&source.replace("usteps: 4", "usteps: 0")).unwrap_err();
+        assert!(error.to_string().contains("`usteps`"), "{error}");
+    }
+
+    #[test]
+    fn scene_from_yaml_light_falloff() {
+        let light = |extra: &str| {
+            Scene::from_yaml(&format!(
+                "{CAMERA}- add: light\n  at: [0, 10, 0]\n  intensity: [1, 1, 1]\n{extra}"
+            ))
+            .map(|scene| scene.world.light.unwrap())
+        };
+        let point = Tuple::new_point(0.0, 0.0, 0.0);
+        let cases = [
+            ("", 1.0),
+            ("  falloff: linear\n  falloff-radius: 40\n", 0.75),
+            ("  falloff: inverse-square\n  falloff-radius: 5\n", 0.25),
+        ];
+        for (extra, expected) in cases {
+            let light = light(extra).unwrap();
+            assert_approx_eq!(light.intensity_at(&point).red, expected, "{extra}");
+        }
+
+        let errors = [
+            ("  falloff: cubic\n", "`falloff` must be"),
+            ("  falloff: linear\n", "missing field `falloff-radius`"),
+            (
+                "  falloff: linear\n  falloff-radius: 0\n",
+                "`falloff-radius` must be positive",
+            ),
+        ];
+        for (extra, expected) in errors {
+            let error = light(extra).unwrap_err().to_string();
+            assert!(error.contains(expected), "{error}");
+        }
+    }
+
+    #[test]
+    fn scene_from_yaml_camera_exposure() {
+        let source = format!(
+            "{CAMERA}  exposure: -1.5
+"
+        );
+        let scene = Scene::from_yaml(&source).unwrap();
+        assert_approx_eq!(scene.camera.exposure(), -1.5);
+    }
+
+    #[test]
+    fn scene_from_yaml_camera_seed() {
+        let scene = Scene::from_yaml(&format!("{CAMERA}  seed: 42\n")).unwrap();
+        assert_eq!(scene.camera.seed(), 42);
+        let error = Scene::from_yaml(&format!("{CAMERA}  seed: -1\n")).unwrap_err();
+        assert!(error.to_string().contains("`seed`"), "{error}");
+    }
+
+    #[test]
+    fn scene_from_yaml_shape() {
+        let source = format!(
+            "{CAMERA}
+- add: sphere
+  material:
+    color: [1, 0.5, 0]
+    diffuse: 0.7
+    refractive-index: 1.5
+    max-depth: 12
+    metalness: 1
+    roughness: 0.25
+  shadow: false
+  transform:
+    - [scale, 2, 2, 2]
+    - [translate, 1, 0, 0]
+"
+        );
+        let scene = Scene::from_yaml(&source).unwrap();
+        assert_eq!(scene.world.objects.len(), 1);
+        let sphere = &scene.world.objects[0];
+        assert!(sphere
+            .transform()
+            .is_equal_to(&(translation(1.0, 0.0, 0.0) * scaling(2.0, 2.0, 2.0))));
+        let material = sphere.material();
+        assert!(material.color.is_equal_to(&Color::new(1.0, 0.5, 0.0)));
+        assert_approx_eq!(material.diffuse, 0.7);
+        assert_approx_eq!(material.refractive_index, 1.5);
+        assert_eq!(material.max_depth, Some(12));
+        assert_approx_eq!(material.metalness, 1.0);
+        assert_approx_eq!(material.roughness, 0.25);
+        assert_approx_eq!(material.ambient, Material::default().ambient);
+        assert!(!sphere.casts_shadow());
+    }
+
+    #[test]
+    fn scene_from_yaml_definitions() {
+        let source = format!(
+            "{CAMERA}
+- define: white-material
+  value:
+    color: [1, 1, 1]
+    diffuse: 0.7
+- define: blue-material
+  extend: white-material
+  value:
+    color: [0.5, 0.5, 1]
+- define: standard-transform
+  value:
+    - [translate, 1, -1, 1]
+    - [scale, 0.5, 0.5, 0.5]
+- add: plane
+  material: blue-material
+  transform:
+    - standard-transform
+    - [scale, 2, 2, 2]
+"
+        );
+        let scene = Scene::from_yaml(&source).unwrap();
+        let plane = &scene.world.objects[0];
+        assert!(plane
+            .material()
+            .color
+            .is_equal_to(&Color::new(0.5, 0.5, 1.0)));
+        assert_approx_eq!(plane.material().diffuse, 0.7);
+        let expected = Transform::identity()
+            .translate(1.0, -1.0, 1.0)
+            .scale(0.5, 0.5, 0.5)
+            .scale(2.0, 2.0, 2.0)
+            .matrix();
+        assert!(plane.transform().is_equal_to(&expected));
+    }
+
+    #[test]
+    fn scene_from_yaml_with_library() {
+        let mut library = MaterialLibrary::new();
+        library.register(
+            "brushed-metal",
+            Material {
+                reflective: 0.3,
+                ..Material::default()
+            },
+        );
+        library.register("red", Material::default());
+        let source = format!(
+            "{CAMERA}
+- define: red
+  value:
+    color: [1, 0, 0]
+- add: sphere
+  material: brushed-metal
+- add: sphere
+  material: red
+"
+        );
+        let scene = Scene::from_yaml_with_library(&source, &library).unwrap();
+        let metal = scene.world.objects[0].material();
+        assert_approx_eq!(metal.reflective, 0.3);
+        // The scene's own definition wins over the library's material
+        let red = scene.world.objects[1].material();
+        assert!(red.color.is_equal_to(&Color::new(1.0, 0.0, 0.0)));
+
+        let error = Scene::from_yaml(&source).unwrap_err().to_string();
+        assert!(error.contains("`brushed-metal` is not defined"), "{error}");
+    }
+
+    #[test]
+    fn scene_load_presets() {
+        let source = format!(
+            "{CAMERA}
+- define: mirror
+  value:
+    color: [1, 0, 0]
+- add: sphere
+  material: glass
+- add: sphere
+  material: mirror
+"
+        );
+        let path = std::env::temp_dir().join("scene_load_presets.yml");
+        std::fs::write(&path, &source).unwrap();
+        let scene = Scene::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_approx_eq!(scene.world.objects[0].material().refractive_index, 1.5);
+        // The scene's own definition wins over the preset
+        let mirror = scene.world.objects[1].material();
+        assert_approx_eq!(mirror.reflective, 0.0);
+        assert!(mirror.color.is_equal_to(&Color::new(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn scene_from_yaml_group_and_pattern() {
+        let source = format!(
+            "{CAMERA}
+- add: group
+  transform:
+    - [translate, 0, 1, 0]
+  children:
+    - add: cone
+      min: -1
+      max: 0
+      closed: true
+    - add: sphere
+      material:
+        pattern:
+          type: checkers
+          colors:
+            - [1, 1, 1]
+            - [0, 0, 0]
+          transform:
+            - [scale, 0.25, 0.25, 0.25]
+"
+        );
+        let scene = Scene::from_yaml(&source).unwrap();
+        let group = &scene.world.objects[0];
+        let bounds = group.bounds();
+        assert!(bounds
+            .min()
+            .is_equal_to(&Tuple::new_point(-1.0, -1.0, -1.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(1.0, 1.0, 1.0)));
+    }
+
+    #[test]
+    fn scene_from_yaml_perturbed_pattern() {
+        let source = format!(
+            "{CAMERA}
+- add: plane
+  material:
+    pattern:
+      type: stripes
+      colors:
+        - [1, 1, 1]
+        - [0, 0, 0]
+      perturb: 0.3
+"
+        );
+        let scene = Scene::from_yaml(&source).unwrap();
+        let pattern = scene.world.objects[0].material().pattern.clone().unwrap();
+        assert!(format!("{pattern:?}").starts_with("PerturbedPattern"));
+        let error = Scene::from_yaml(&source.replace("perturb: 0.3", "perturb: wavy")).unwrap_err();
+        assert!(error.to_string().contains("expected a number"), "{error}");
+    }
+
+    #[test]
+    fn scene_from_yaml_noise_pattern() {
+        let source = format!(
+            "{CAMERA}
+- add: sphere
+  material:
+    pattern:
+      type: noise
+      colors:
+        - [0.4, 0.4, 0.4]
+        - [0.9, 0.9, 0.9]
+      octaves: 6
+      persistence: 0.7
+"
+        );
+        let scene = Scene::from_yaml(&source).unwrap();
+        let pattern = scene.world.objects[0].material().pattern.clone().unwrap();
+        let debug = format!("{pattern:?}");
+        assert!(debug.starts_with("NoisePattern"), "{debug}");
+        assert!(debug.contains("octaves: 6") && debug.contains("persistence: 0.7"));
+        for octaves in ["0", "4294967296"] {
+            let source = source.replace("octaves: 6", &format!("octaves: {octaves}"));
+            let error = Scene::from_yaml(&source).unwrap_err();
+            assert!(error.to_string().contains("`octaves`"), "{error}");
+        }
+    }
+
+    #[test]
+    fn scene_from_yaml_errors() {
+        let cases = [
+            ("not: a list", "a scene must be a list of entries"),
+            (
+                "- add: light\n  at: [0, 0, 0]\n  intensity: [1, 1, 1]",
+                "the scene has no camera",
+            ),
+            ("- add: cube", "unknown shape `cube`"),
+            (
+                "- add: sphere\n  material: crystal",
+                "`crystal` is not defined",
+            ),
+            (
+                "- add: sphere\n  transform:\n    - [scale, 1]",
+                "sphere: unknown transformation",
+            ),
+            (
+                "- add: camera\n  height: 10",
+                "camera: missing field `width`",
+            ),
+        ];
+        for (source, expected) in cases {
+            let error = Scene::from_yaml(source).unwrap_err().to_string();
+            assert!(error.contains(expected), "{error}");
+        }
+        assert!(matches!(
+            Scene::from_yaml("- [unclosed"),
+            Err(SceneError::Yaml(_))
+        ));
+        assert!(matches!(
+            Scene::from_yaml("- add: sphere\n  transform:\n    - [scale, 0, 1, 1]"),
+            Err(SceneError::Matrix(_))
+        ));
+    }
+}
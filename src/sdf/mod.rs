@@ -0,0 +1,24 @@
+use crate::shapes::BoundingBox;
+use crate::tuple::Tuple;
+use std::fmt::Debug;
+
+pub mod operators;
+pub mod primitives;
+pub mod ray_marched;
+
+pub use operators::{Intersection, SmoothUnion, Subtraction, Translated, Union};
+pub use primitives::{BoxField, SphereField, TorusField};
+pub use ray_marched::RayMarched;
+
+/// Signed distance field describing a surface implicitly, by the distance from any point to it.
+///
+/// Fields are rendered with `RayMarched`, which steps along rays by the distance to the surface, so the
+/// distance must never overestimate how far the surface is. Underestimating is allowed but makes
+/// rendering slower.
+pub trait DistanceField: Debug + Send + Sync {
+    /// Compute the distance from a point to the surface, negative when the point is inside it.
+    fn distance(&self, point: &Tuple) -> f64;
+
+    /// Compute the box enclosing the surface.
+    fn bounds(&self) -> BoundingBox;
+}
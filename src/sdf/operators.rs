@@ -0,0 +1,221 @@
+use super::DistanceField;
+use crate::shapes::BoundingBox;
+use crate::tuple::Tuple;
+
+/// Surface covering both of two fields.
+#[derive(Debug)]
+pub struct Union {
+    first: Box<dyn DistanceField>,
+    second: Box<dyn DistanceField>,
+}
+
+impl Union {
+    /// Combine two fields.
+    pub fn new(first: Box<dyn DistanceField>, second: Box<dyn DistanceField>) -> Self {
+        Self { first, second }
+    }
+}
+
+impl DistanceField for Union {
+    fn distance(&self, point: &Tuple) -> f64 {
+        self.first.distance(point).min(self.second.distance(point))
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = self.first.bounds();
+        bounds.merge(&self.second.bounds());
+        bounds
+    }
+}
+
+/// Union of two fields that blends them together where they come within `smoothness` of each other,
+/// like drops of liquid merging.
+#[derive(Debug)]
+pub struct SmoothUnion {
+    first: Box<dyn DistanceField>,
+    second: Box<dyn DistanceField>,
+    smoothness: f64,
+}
+
+impl SmoothUnion {
+    /// Combine two fields, blending over a distance of `smoothness`. A smoothness of 0 is a plain union.
+    pub fn new(
+        first: Box<dyn DistanceField>,
+        second: Box<dyn DistanceField>,
+        smoothness: f64,
+    ) -> Self {
+        Self {
+            first,
+            second,
+            smoothness,
+        }
+    }
+}
+
+impl DistanceField for SmoothUnion {
+    /// Blend the distances with a polynomial smooth minimum.
+    fn distance(&self, point: &Tuple) -> f64 {
+        let first = self.first.distance(point);
+        let second = self.second.distance(point);
+        if self.smoothness <= 0.0 {
+            return first.min(second);
+        }
+        let h = (0.5 + 0.5 * (second - first) / self.smoothness).clamp(0.0, 1.0);
+        second + (first - second) * h - self.smoothness * h * (1.0 - h)
+    }
+
+    /// The blend swells the union by at most a quarter of the smoothness.
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = self.first.bounds();
+        bounds.merge(&self.second.bounds());
+        let swell = self.smoothness.max(0.0) / 4.0;
+        let (min, max) = (bounds.min(), bounds.max());
+        BoundingBox::new(
+            Tuple::new_point(min.x - swell, min.y - swell, min.z - swell),
+            Tuple::new_point(max.x + swell, max.y + swell, max.z + swell),
+        )
+    }
+}
+
+/// Surface covering only the space inside both of two fields.
+#[derive(Debug)]
+pub struct Intersection {
+    first: Box<dyn DistanceField>,
+    second: Box<dyn DistanceField>,
+}
+
+impl Intersection {
+    /// Combine two fields.
+    pub fn new(first: Box<dyn DistanceField>, second: Box<dyn DistanceField>) -> Self {
+        Self { first, second }
+    }
+}
+
+impl DistanceField for Intersection {
+    fn distance(&self, point: &Tuple) -> f64 {
+        self.first.distance(point).max(self.second.distance(point))
+    }
+
+    /// The intersection is no bigger than the first field.
+    fn bounds(&self) -> BoundingBox {
+        self.first.bounds()
+    }
+}
+
+/// Surface of the first of two fields with the space inside the second carved out of it.
+#[derive(Debug)]
+pub struct Subtraction {
+    first: Box<dyn DistanceField>,
+    second: Box<dyn DistanceField>,
+}
+
+impl Subtraction {
+    /// Carve `second` out of `first`.
+    pub fn new(first: Box<dyn DistanceField>, second: Box<dyn DistanceField>) -> Self {
+        Self { first, second }
+    }
+}
+
+impl DistanceField for Subtraction {
+    fn distance(&self, point: &Tuple) -> f64 {
+        self.first.distance(point).max(-self.second.distance(point))
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.first.bounds()
+    }
+}
+
+/// Field moved by an offset, for placing the fields combined by the other operators.
+#[derive(Debug)]
+pub struct Translated {
+    field: Box<dyn DistanceField>,
+    offset: Tuple,
+}
+
+impl Translated {
+    /// Move a field by the vector `offset`.
+    pub fn new(field: Box<dyn DistanceField>, offset: Tuple) -> Self {
+        Self { field, offset }
+    }
+}
+
+impl DistanceField for Translated {
+    fn distance(&self, point: &Tuple) -> f64 {
+        self.field.distance(&(point - self.offset))
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let bounds = self.field.bounds();
+        BoundingBox::new(bounds.min() + self.offset, bounds.max() + self.offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::sdf::{BoxField, SphereField};
+
+    fn spheres_apart() -> (Box<dyn DistanceField>, Box<dyn DistanceField>) {
+        (
+            Box::new(Translated::new(
+                Box::new(SphereField::new(1.0)),
+                Tuple::new_vector(-1.5, 0.0, 0.0),
+            )),
+            Box::new(Translated::new(
+                Box::new(SphereField::new(1.0)),
+                Tuple::new_vector(1.5, 0.0, 0.0),
+            )),
+        )
+    }
+
+    #[test]
+    fn union_distance() {
+        let (first, second) = spheres_apart();
+        let union = Union::new(first, second);
+        assert_approx_eq!(union.distance(&Tuple::new_point(0.0, 0.0, 0.0)), 0.5);
+        assert_approx_eq!(union.distance(&Tuple::new_point(1.5, 0.0, 0.0)), -1.0);
+        let bounds = union.bounds();
+        assert!(bounds
+            .min()
+            .is_equal_to(&Tuple::new_point(-2.5, -1.0, -1.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(2.5, 1.0, 1.0)));
+    }
+
+    #[test]
+    fn smooth_union_distance() {
+        let (first, second) = spheres_apart();
+        let smooth = SmoothUnion::new(first, second, 2.0);
+        // Halfway between the spheres both are 0.5 away, and the blend fills the gap up to that point
+        let point = Tuple::new_point(0.0, 0.0, 0.0);
+        assert_approx_eq!(smooth.distance(&point), 0.0);
+        // Far from the other sphere the blend has no effect
+        let point = Tuple::new_point(4.5, 0.0, 0.0);
+        assert_approx_eq!(smooth.distance(&point), 2.0);
+        let bounds = smooth.bounds();
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(3.0, 1.5, 1.5)));
+    }
+
+    #[test]
+    fn subtraction_and_intersection_distance() {
+        let subtraction = Subtraction::new(
+            Box::new(BoxField::new(1.0, 1.0, 1.0)),
+            Box::new(SphereField::new(0.5)),
+        );
+        let intersection = Intersection::new(
+            Box::new(BoxField::new(1.0, 1.0, 1.0)),
+            Box::new(SphereField::new(0.5)),
+        );
+        let cases = [
+            ((0.0, 0.0, 0.0), 0.5, -0.5),
+            ((0.0, 0.75, 0.0), -0.25, 0.25),
+            ((0.0, 2.0, 0.0), 1.0, 1.5),
+        ];
+        for ((x, y, z), subtracted, intersected) in cases {
+            let point = Tuple::new_point(x, y, z);
+            assert_approx_eq!(subtraction.distance(&point), subtracted);
+            assert_approx_eq!(intersection.distance(&point), intersected);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use super::DistanceField;
+use crate::shapes::BoundingBox;
+use crate::tuple::Tuple;
+
+/// Sphere centered at the origin.
+#[derive(Debug)]
+pub struct SphereField {
+    pub radius: f64,
+}
+
+impl SphereField {
+    /// Create a sphere with the given radius.
+    pub fn new(radius: f64) -> Self {
+        Self { radius }
+    }
+}
+
+impl DistanceField for SphereField {
+    fn distance(&self, point: &Tuple) -> f64 {
+        (point - Tuple::new_point(0.0, 0.0, 0.0)).magnitude() - self.radius
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let radius = self.radius;
+        BoundingBox::new(
+            Tuple::new_point(-radius, -radius, -radius),
+            Tuple::new_point(radius, radius, radius),
+        )
+    }
+}
+
+/// Box centered at the origin, reaching `half_size` along each axis in both directions.
+#[derive(Debug)]
+pub struct BoxField {
+    pub half_size: Tuple,
+}
+
+impl BoxField {
+    /// Create a box reaching the given distances from its center along x, y and z.
+    pub fn new(half_x: f64, half_y: f64, half_z: f64) -> Self {
+        Self {
+            half_size: Tuple::new_vector(half_x, half_y, half_z),
+        }
+    }
+}
+
+impl DistanceField for BoxField {
+    fn distance(&self, point: &Tuple) -> f64 {
+        // Distance beyond the faces along each axis, negative inside
+        let x = point.x.abs() - self.half_size.x;
+        let y = point.y.abs() - self.half_size.y;
+        let z = point.z.abs() - self.half_size.z;
+        let outside = Tuple::new_vector(x.max(0.0), y.max(0.0), z.max(0.0)).magnitude();
+        let inside = x.max(y).max(z).min(0.0);
+        outside + inside
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let half = &self.half_size;
+        BoundingBox::new(
+            Tuple::new_point(-half.x, -half.y, -half.z),
+            Tuple::new_point(half.x, half.y, half.z),
+        )
+    }
+}
+
+/// Torus lying in the xz plane, centered at the origin, like `shapes::Torus`.
+#[derive(Debug)]
+pub struct TorusField {
+    pub major_radius: f64,
+    pub minor_radius: f64,
+}
+
+impl TorusField {
+    /// Create a torus with the given radii.
+    pub fn new(major_radius: f64, minor_radius: f64) -> Self {
+        Self {
+            major_radius,
+            minor_radius,
+        }
+    }
+}
+
+impl DistanceField for TorusField {
+    fn distance(&self, point: &Tuple) -> f64 {
+        // Distance from the circle running through the middle of the tube
+        let across = (point.x * point.x + point.z * point.z).sqrt() - self.major_radius;
+        (across * across + point.y * point.y).sqrt() - self.minor_radius
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let outer = self.major_radius + self.minor_radius;
+        BoundingBox::new(
+            Tuple::new_point(-outer, -self.minor_radius, -outer),
+            Tuple::new_point(outer, self.minor_radius, outer),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn primitives_distance() {
+        let sphere = SphereField::new(2.0);
+        let cuboid = BoxField::new(1.0, 2.0, 3.0);
+        let torus = TorusField::new(2.0, 0.5);
+        let cases = [
+            (&sphere as &dyn DistanceField, (0.0, 0.0, 0.0), -2.0),
+            (&sphere, (0.0, 3.0, 4.0), 3.0),
+            (&cuboid, (0.0, 0.0, 0.0), -1.0),
+            (&cuboid, (4.0, 0.0, 0.0), 3.0),
+            (&cuboid, (4.0, 6.0, 0.0), 5.0),
+            (&torus, (2.0, 0.0, 0.0), -0.5),
+            (&torus, (0.0, 0.0, 0.0), 1.5),
+        ];
+        for (field, (x, y, z), expected) in cases {
+            let distance = field.distance(&Tuple::new_point(x, y, z));
+            assert_approx_eq!(distance, expected, "({x}, {y}, {z})");
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use super::DistanceField;
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::shapes::{BoundingBox, Shape, ShapeProperties};
+use crate::tuple::Tuple;
+
+/// Distance within which a point counts as being on the surface of a field.
+const SURFACE_DISTANCE: f64 = 1e-5;
+
+/// Shape rendering a distance field by sphere tracing: rays step forward by the distance to the surface,
+/// which can never overshoot it, until they get close enough to count as a hit.
+#[derive(Debug)]
+pub struct RayMarched {
+    properties: ShapeProperties,
+    field: Box<dyn DistanceField>,
+    max_steps: usize,
+}
+
+impl RayMarched {
+    /// Create a shape for a distance field with an identity transform, giving up on rays that take more
+    /// than 256 steps.
+    pub fn new(field: Box<dyn DistanceField>) -> Self {
+        Self {
+            properties: ShapeProperties::default(),
+            field,
+            max_steps: 256,
+        }
+    }
+
+    /// Get the distance field.
+    pub fn field(&self) -> &dyn DistanceField {
+        self.field.as_ref()
+    }
+
+    /// Get the number of steps after which a ray is treated as missing the surface.
+    pub fn max_steps(&self) -> usize {
+        self.max_steps
+    }
+
+    /// Set the number of steps after which a ray is treated as missing the surface.
+    /// More steps find rays grazing the surface at the cost of speed.
+    pub fn set_max_steps(&mut self, max_steps: usize) {
+        self.max_steps = max_steps;
+    }
+}
+
+impl Shape for RayMarched {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    /// March through the field's bounds, recording every point where the ray crosses the surface so
+    /// that rays leaving the shape, such as refracted ones, find the exit too.
+    fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_> {
+        let Some((start, end)) = self.field.bounds().intersection_range(local_ray) else {
+            return Intersections::default();
+        };
+        // Rays in object space are not normalized, so distances are converted into steps along the ray
+        let length = local_ray.direction.magnitude();
+        let mut t = start - SURFACE_DISTANCE / length;
+        let end = end + SURFACE_DISTANCE / length;
+        let mut intersections = Vec::new();
+        for _ in 0..self.max_steps {
+            if t > end {
+                break;
+            }
+            let distance = self.field.distance(&local_ray.position(t)).abs();
+            if distance < SURFACE_DISTANCE {
+                intersections.push(Intersection::new(t, self));
+                // Step through the surface before marching on
+                t += 4.0 * SURFACE_DISTANCE / length;
+            } else {
+                t += distance / length;
+            }
+        }
+        Intersections::new(intersections)
+    }
+
+    /// The normal is the gradient of the field, estimated from the distances at nearby points.
+    fn local_normal_at(&self, local_point: &Tuple) -> Tuple {
+        let h = SURFACE_DISTANCE;
+        let gradient = |offset: Tuple| {
+            self.field.distance(&(local_point + offset))
+                - self.field.distance(&(local_point - offset))
+        };
+        Tuple::new_vector(
+            gradient(Tuple::new_vector(h, 0.0, 0.0)),
+            gradient(Tuple::new_vector(0.0, h, 0.0)),
+            gradient(Tuple::new_vector(0.0, 0.0, h)),
+        )
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.field.bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::sdf::{SphereField, Subtraction, TorusField};
+    use crate::shapes::Sphere;
+    use crate::transform::scaling;
+
+    #[test]
+    fn ray_marched_local_intersect_matches_sphere() {
+        let marched = RayMarched::new(Box::new(SphereField::new(1.0)));
+        let sphere = Sphere::new();
+        let cases = [
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0)),
+            ((0.5, 0.3, -5.0), (0.0, 0.0, 1.0)),
+            ((0.0, 0.0, 0.0), (0.0, 0.0, 1.0)),
+            ((-3.0, 2.0, -4.0), (3.0, -2.0, 4.5)),
+        ];
+        for ((ox, oy, oz), (dx, dy, dz)) in cases {
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), Tuple::new_vector(dx, dy, dz));
+            let expected: Vec<f64> = sphere
+                .local_intersect(&ray)
+                .iter()
+                .map(|x| x.t)
+                .filter(|&t| t >= -1.0)
+                .collect();
+            let xs = marched.local_intersect(&ray);
+            let ts: Vec<f64> = xs.iter().map(|x| x.t).filter(|&t| t >= -1.0).collect();
+            assert_eq!(ts.len(), expected.len(), "({ox}, {oy}, {oz})");
+            for (t, expected) in ts.iter().zip(&expected) {
+                assert_approx_eq!(t, expected, epsilon = 1e-4, "({ox}, {oy}, {oz})");
+            }
+        }
+    }
+
+    #[test]
+    fn ray_marched_local_intersect_miss() {
+        let marched = RayMarched::new(Box::new(TorusField::new(1.0, 0.25)));
+        // Down through the hole of the torus, inside its bounds but never near its surface
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 5.0, 0.0),
+            Tuple::new_vector(0.0, -1.0, 0.0),
+        );
+        assert!(marched.local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn ray_marched_normal_at() {
+        let mut marched = RayMarched::new(Box::new(Subtraction::new(
+            Box::new(SphereField::new(1.0)),
+            Box::new(SphereField::new(0.5)),
+        )));
+        marched.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let cases = [
+            ((0.0, 2.0, 0.0), (0.0, 1.0, 0.0)),
+            ((2.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            // The hollow inside faces inward
+            ((0.0, 0.0, 1.0), (0.0, 0.0, -1.0)),
+        ];
+        for ((px, py, pz), (nx, ny, nz)) in cases {
+            let normal = marched.normal_at(&Tuple::new_point(px, py, pz));
+            assert!(normal.is_equal_to(&Tuple::new_vector(nx, ny, nz)));
+        }
+    }
+}
@@ -0,0 +1,463 @@
+use super::Shape;
+use crate::matrix::Matrix4;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use crate::EPSILON;
+
+/// Axis-aligned box enclosing a shape, described by its minimum and maximum corners.
+///
+/// A default box is empty: its minimum is at positive infinity and its maximum at negative infinity,
+/// so adding the first point or merging the first box makes it enclose exactly that.
+#[derive(Debug, Clone)]
+pub struct BoundingBox {
+    min: Tuple,
+    max: Tuple,
+}
+
+impl BoundingBox {
+    /// Create a box from its minimum and maximum corners.
+    pub fn new(min: Tuple, max: Tuple) -> Self {
+        Self { min, max }
+    }
+
+    /// Create a box enclosing all of space, used for unbounded shapes.
+    pub fn infinite() -> Self {
+        Self::new(
+            Tuple::new_point(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            Tuple::new_point(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        )
+    }
+
+    /// Get the minimum corner of the box.
+    pub fn min(&self) -> &Tuple {
+        &self.min
+    }
+
+    /// Get the maximum corner of the box.
+    pub fn max(&self) -> &Tuple {
+        &self.max
+    }
+
+    /// Check whether every component of both corners is finite.
+    pub fn is_finite(&self) -> bool {
+        [
+            self.min.x, self.min.y, self.min.z, self.max.x, self.max.y, self.max.z,
+        ]
+        .iter()
+        .all(|value| value.is_finite())
+    }
+
+    /// Grow the box to enclose a point.
+    pub fn add_point(&mut self, point: &Tuple) {
+        self.min.x = self.min.x.min(point.x);
+        self.min.y = self.min.y.min(point.y);
+        self.min.z = self.min.z.min(point.z);
+        self.max.x = self.max.x.max(point.x);
+        self.max.y = self.max.y.max(point.y);
+        self.max.z = self.max.z.max(point.z);
+    }
+
+    /// Check whether the box encloses nothing, as for a default box.
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    /// Grow the box to enclose another box. Merging an empty box leaves this box unchanged.
+    pub fn merge(&mut self, other: &BoundingBox) {
+        if other.is_empty() {
+            return;
+        }
+        self.add_point(&other.min);
+        self.add_point(&other.max);
+    }
+
+    /// Check whether a point is inside the box or on its surface.
+    pub fn contains_point(&self, point: &Tuple) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// Check whether another box is entirely inside this one.
+    pub fn contains_box(&self, other: &BoundingBox) -> bool {
+        self.contains_point(&other.min) && self.contains_point(&other.max)
+    }
+
+    /// Compute the axis-aligned box enclosing this box after it is transformed by a matrix.
+    ///
+    /// Empty boxes stay empty. Boxes with infinite extents transform into an infinite box, since
+    /// multiplying their corners would produce NaN components.
+    pub fn transform(&self, matrix: &Matrix4) -> BoundingBox {
+        if self.is_empty() {
+            return BoundingBox::default();
+        }
+        if !self.is_finite() {
+            return BoundingBox::infinite();
+        }
+        let mut transformed = BoundingBox::default();
+        for x in [self.min.x, self.max.x] {
+            for y in [self.min.y, self.max.y] {
+                for z in [self.min.z, self.max.z] {
+                    transformed.add_point(&(*matrix * Tuple::new_point(x, y, z)));
+                }
+            }
+        }
+        transformed
+    }
+
+    /// Check whether a ray, given in the same space as the box, intersects it.
+    pub fn intersects(&self, ray: &Ray) -> bool {
+        self.intersection_range(ray).is_some()
+    }
+
+    /// Find the distances along a ray, given in the same space as the box, at which it enters and
+    /// leaves the box, or `None` if it misses the box.
+    pub fn intersection_range(&self, ray: &Ray) -> Option<(f64, f64)> {
+        let mut t_min = f64::NEG_INFINITY;
+        let mut t_max = f64::INFINITY;
+        for axis in 0..3 {
+            let (axis_min, axis_max) = check_axis(
+                ray.origin[axis],
+                ray.direction[axis],
+                self.min[axis],
+                self.max[axis],
+            );
+            t_min = t_min.max(axis_min);
+            t_max = t_max.min(axis_max);
+        }
+        (t_min <= t_max).then_some((t_min, t_max))
+    }
+
+    /// Split the box in half across its longest axis.
+    pub fn split(&self) -> (BoundingBox, BoundingBox) {
+        let dx = self.max.x - self.min.x;
+        let dy = self.max.y - self.min.y;
+        let dz = self.max.z - self.min.z;
+        let greatest = dx.max(dy).max(dz);
+
+        let (mut x0, mut y0, mut z0) = (self.min.x, self.min.y, self.min.z);
+        let (mut x1, mut y1, mut z1) = (self.max.x, self.max.y, self.max.z);
+        if greatest == dx {
+            x0 += dx / 2.0;
+            x1 = x0;
+        } else if greatest == dy {
+            y0 += dy / 2.0;
+            y1 = y0;
+        } else {
+            z0 += dz / 2.0;
+            z1 = z0;
+        }
+
+        let left = BoundingBox::new(self.min, Tuple::new_point(x1, y1, z1));
+        let right = BoundingBox::new(Tuple::new_point(x0, y0, z0), self.max);
+        (left, right)
+    }
+
+    /// Compute the bounds of a shape in the space of its parent, by applying the shape's transform.
+    pub fn parent_space_bounds_of(shape: &dyn Shape) -> BoundingBox {
+        shape.bounds().transform(shape.transform())
+    }
+}
+
+/// Find the distances along a ray at which it enters and leaves the slab between `min` and `max` on one axis.
+fn check_axis(origin: f64, direction: f64, min: f64, max: f64) -> (f64, f64) {
+    let min_numerator = min - origin;
+    let max_numerator = max - origin;
+    let (t_min, t_max) = if direction.abs() >= EPSILON {
+        (min_numerator / direction, max_numerator / direction)
+    } else {
+        // A ray parallel to the slab is either always or never inside it
+        (min_numerator * f64::INFINITY, max_numerator * f64::INFINITY)
+    };
+    if t_min > t_max {
+        (t_max, t_min)
+    } else {
+        (t_min, t_max)
+    }
+}
+
+impl Default for BoundingBox {
+    /// Create an empty box.
+    fn default() -> Self {
+        Self::new(
+            Tuple::new_point(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            Tuple::new_point(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::shapes::Sphere;
+    use crate::transform::{rotation_x, rotation_y, scaling, translation};
+    use std::f64::consts::{FRAC_PI_4, SQRT_2};
+
+    #[test]
+    fn bounding_box_default() {
+        let bounds = BoundingBox::default();
+        assert_eq!(bounds.min().x, f64::INFINITY);
+        assert_eq!(bounds.min().y, f64::INFINITY);
+        assert_eq!(bounds.min().z, f64::INFINITY);
+        assert_eq!(bounds.max().x, f64::NEG_INFINITY);
+        assert_eq!(bounds.max().y, f64::NEG_INFINITY);
+        assert_eq!(bounds.max().z, f64::NEG_INFINITY);
+        assert!(bounds.is_empty());
+    }
+
+    #[test]
+    fn bounding_box_new() {
+        let bounds = BoundingBox::new(
+            Tuple::new_point(-1.0, -2.0, -3.0),
+            Tuple::new_point(3.0, 2.0, 1.0),
+        );
+        assert!(bounds
+            .min()
+            .is_equal_to(&Tuple::new_point(-1.0, -2.0, -3.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(3.0, 2.0, 1.0)));
+        assert!(bounds.is_finite());
+        assert!(!BoundingBox::infinite().is_finite());
+    }
+
+    #[test]
+    fn bounding_box_add_point() {
+        let mut bounds = BoundingBox::default();
+        bounds.add_point(&Tuple::new_point(-5.0, 2.0, 0.0));
+        bounds.add_point(&Tuple::new_point(7.0, 0.0, -3.0));
+        assert!(bounds.min().is_equal_to(&Tuple::new_point(-5.0, 0.0, -3.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(7.0, 2.0, 0.0)));
+    }
+
+    #[test]
+    fn bounding_box_merge() {
+        let mut bounds = BoundingBox::new(
+            Tuple::new_point(-5.0, -2.0, 0.0),
+            Tuple::new_point(7.0, 4.0, 4.0),
+        );
+        bounds.merge(&BoundingBox::new(
+            Tuple::new_point(8.0, -7.0, -2.0),
+            Tuple::new_point(14.0, 2.0, 8.0),
+        ));
+        assert!(bounds
+            .min()
+            .is_equal_to(&Tuple::new_point(-5.0, -7.0, -2.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(14.0, 4.0, 8.0)));
+    }
+
+    #[test]
+    fn bounding_box_merge_empty() {
+        let mut bounds = BoundingBox::new(
+            Tuple::new_point(-1.0, -1.0, -1.0),
+            Tuple::new_point(1.0, 1.0, 1.0),
+        );
+        bounds.merge(&BoundingBox::default());
+        assert!(bounds
+            .min()
+            .is_equal_to(&Tuple::new_point(-1.0, -1.0, -1.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(1.0, 1.0, 1.0)));
+    }
+
+    #[test]
+    fn bounding_box_contains_point() {
+        let bounds = BoundingBox::new(
+            Tuple::new_point(5.0, -2.0, 0.0),
+            Tuple::new_point(11.0, 4.0, 7.0),
+        );
+        let cases = [
+            ((5.0, -2.0, 0.0), true),
+            ((11.0, 4.0, 7.0), true),
+            ((8.0, 1.0, 3.0), true),
+            ((3.0, 0.0, 3.0), false),
+            ((8.0, -4.0, 3.0), false),
+            ((8.0, 1.0, -1.0), false),
+            ((13.0, 1.0, 3.0), false),
+            ((8.0, 5.0, 3.0), false),
+            ((8.0, 1.0, 8.0), false),
+        ];
+        for ((x, y, z), expected) in cases {
+            assert_eq!(
+                bounds.contains_point(&Tuple::new_point(x, y, z)),
+                expected,
+                "({x}, {y}, {z})"
+            );
+        }
+    }
+
+    #[test]
+    fn bounding_box_contains_box() {
+        let bounds = BoundingBox::new(
+            Tuple::new_point(5.0, -2.0, 0.0),
+            Tuple::new_point(11.0, 4.0, 7.0),
+        );
+        let cases = [
+            ((5.0, -2.0, 0.0), (11.0, 4.0, 7.0), true),
+            ((6.0, -1.0, 1.0), (10.0, 3.0, 6.0), true),
+            ((4.0, -3.0, -1.0), (10.0, 3.0, 6.0), false),
+            ((6.0, -1.0, 1.0), (12.0, 5.0, 8.0), false),
+        ];
+        for ((min_x, min_y, min_z), (max_x, max_y, max_z), expected) in cases {
+            let other = BoundingBox::new(
+                Tuple::new_point(min_x, min_y, min_z),
+                Tuple::new_point(max_x, max_y, max_z),
+            );
+            assert_eq!(bounds.contains_box(&other), expected);
+        }
+    }
+
+    #[test]
+    fn bounding_box_transform() {
+        let bounds = BoundingBox::new(
+            Tuple::new_point(-1.0, -1.0, -1.0),
+            Tuple::new_point(1.0, 1.0, 1.0),
+        );
+        let transformed = bounds.transform(&(rotation_x(FRAC_PI_4) * rotation_y(FRAC_PI_4)));
+        // The book's values are rounded to four decimal places.
+        assert_approx_eq!(
+            transformed.min(),
+            Tuple::new_point(-SQRT_2, -1.7071, -1.7071),
+            epsilon = 1e-4
+        );
+        assert_approx_eq!(
+            transformed.max(),
+            Tuple::new_point(SQRT_2, 1.7071, 1.7071),
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn bounding_box_transform_infinite() {
+        let transformed = BoundingBox::infinite().transform(&translation(1.0, 2.0, 3.0));
+        assert!(!transformed.is_finite());
+        assert!(transformed.contains_point(&Tuple::new_point(1e10, -1e10, 0.0)));
+    }
+
+    #[test]
+    fn bounding_box_parent_space_bounds_of() {
+        let mut sphere = Sphere::new();
+        sphere
+            .set_transform(translation(1.0, -3.0, 5.0) * scaling(0.5, 2.0, 4.0))
+            .unwrap();
+        let bounds = BoundingBox::parent_space_bounds_of(&sphere);
+        assert!(bounds.min().is_equal_to(&Tuple::new_point(0.5, -5.0, 1.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(1.5, -1.0, 9.0)));
+    }
+
+    #[test]
+    fn bounding_box_intersects_cube() {
+        let bounds = BoundingBox::new(
+            Tuple::new_point(-1.0, -1.0, -1.0),
+            Tuple::new_point(1.0, 1.0, 1.0),
+        );
+        let cases = [
+            ((5.0, 0.5, 0.0), (-1.0, 0.0, 0.0), true),
+            ((-5.0, 0.5, 0.0), (1.0, 0.0, 0.0), true),
+            ((0.5, 5.0, 0.0), (0.0, -1.0, 0.0), true),
+            ((0.5, -5.0, 0.0), (0.0, 1.0, 0.0), true),
+            ((0.5, 0.0, 5.0), (0.0, 0.0, -1.0), true),
+            ((0.5, 0.0, -5.0), (0.0, 0.0, 1.0), true),
+            ((0.0, 0.5, 0.0), (0.0, 0.0, 1.0), true),
+            ((-2.0, 0.0, 0.0), (2.0, 4.0, 6.0), false),
+            ((0.0, -2.0, 0.0), (6.0, 2.0, 4.0), false),
+            ((0.0, 0.0, -2.0), (4.0, 6.0, 2.0), false),
+            ((2.0, 0.0, 2.0), (0.0, 0.0, -1.0), false),
+            ((0.0, 2.0, 2.0), (0.0, -1.0, 0.0), false),
+            ((2.0, 2.0, 0.0), (-1.0, 0.0, 0.0), false),
+        ];
+        for ((ox, oy, oz), (dx, dy, dz), expected) in cases {
+            let ray = Ray::new(
+                Tuple::new_point(ox, oy, oz),
+                Tuple::new_vector(dx, dy, dz).normalize(),
+            );
+            assert_eq!(bounds.intersects(&ray), expected, "({ox}, {oy}, {oz})");
+        }
+    }
+
+    #[test]
+    fn bounding_box_intersection_range() {
+        let bounds = BoundingBox::new(
+            Tuple::new_point(-1.0, -1.0, -1.0),
+            Tuple::new_point(1.0, 1.0, 1.0),
+        );
+        let ray = Ray::new(
+            Tuple::new_point(-5.0, 0.5, 0.0),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+        );
+        let (t_min, t_max) = bounds.intersection_range(&ray).unwrap();
+        assert_approx_eq!(t_min, 4.0);
+        assert_approx_eq!(t_max, 6.0);
+
+        let ray = Ray::new(
+            Tuple::new_point(-5.0, 2.0, 0.0),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+        );
+        assert!(bounds.intersection_range(&ray).is_none());
+    }
+
+    #[test]
+    fn bounding_box_intersects_non_cubic() {
+        let bounds = BoundingBox::new(
+            Tuple::new_point(5.0, -2.0, 0.0),
+            Tuple::new_point(11.0, 4.0, 7.0),
+        );
+        let cases = [
+            ((15.0, 1.0, 2.0), (-1.0, 0.0, 0.0), true),
+            ((-5.0, -1.0, 4.0), (1.0, 0.0, 0.0), true),
+            ((7.0, 6.0, 5.0), (0.0, -1.0, 0.0), true),
+            ((9.0, -5.0, 6.0), (0.0, 1.0, 0.0), true),
+            ((8.0, 2.0, 12.0), (0.0, 0.0, -1.0), true),
+            ((6.0, 0.0, -5.0), (0.0, 0.0, 1.0), true),
+            ((8.0, 1.0, 3.5), (0.0, 0.0, 1.0), true),
+            ((9.0, -1.0, -8.0), (2.0, 4.0, 6.0), false),
+            ((8.0, 3.0, -4.0), (6.0, 2.0, 4.0), false),
+            ((9.0, -1.0, -2.0), (4.0, 6.0, 2.0), false),
+            ((4.0, 0.0, 9.0), (0.0, 0.0, -1.0), false),
+            ((8.0, 6.0, -1.0), (0.0, -1.0, 0.0), false),
+            ((12.0, 5.0, 4.0), (-1.0, 0.0, 0.0), false),
+        ];
+        for ((ox, oy, oz), (dx, dy, dz), expected) in cases {
+            let ray = Ray::new(
+                Tuple::new_point(ox, oy, oz),
+                Tuple::new_vector(dx, dy, dz).normalize(),
+            );
+            assert_eq!(bounds.intersects(&ray), expected, "({ox}, {oy}, {oz})");
+        }
+    }
+
+    #[test]
+    fn bounding_box_split() {
+        let cases = [
+            (
+                ((-1.0, -4.0, -5.0), (9.0, 6.0, 5.0)),
+                ((-1.0, -4.0, -5.0), (4.0, 6.0, 5.0)),
+                ((4.0, -4.0, -5.0), (9.0, 6.0, 5.0)),
+            ),
+            (
+                ((-1.0, -2.0, -3.0), (9.0, 5.5, 3.0)),
+                ((-1.0, -2.0, -3.0), (4.0, 5.5, 3.0)),
+                ((4.0, -2.0, -3.0), (9.0, 5.5, 3.0)),
+            ),
+            (
+                ((-1.0, -2.0, -3.0), (5.0, 8.0, 3.0)),
+                ((-1.0, -2.0, -3.0), (5.0, 3.0, 3.0)),
+                ((-1.0, 3.0, -3.0), (5.0, 8.0, 3.0)),
+            ),
+            (
+                ((-1.0, -2.0, -3.0), (5.0, 3.0, 7.0)),
+                ((-1.0, -2.0, -3.0), (5.0, 3.0, 2.0)),
+                ((-1.0, -2.0, 2.0), (5.0, 3.0, 7.0)),
+            ),
+        ];
+        let make = |((x0, y0, z0), (x1, y1, z1)): ((f64, f64, f64), (f64, f64, f64))| {
+            BoundingBox::new(Tuple::new_point(x0, y0, z0), Tuple::new_point(x1, y1, z1))
+        };
+        for (bounds, expected_left, expected_right) in cases {
+            let (left, right) = make(bounds).split();
+            let (expected_left, expected_right) = (make(expected_left), make(expected_right));
+            assert!(left.min().is_equal_to(expected_left.min()));
+            assert!(left.max().is_equal_to(expected_left.max()));
+            assert!(right.min().is_equal_to(expected_right.min()));
+            assert!(right.max().is_equal_to(expected_right.max()));
+        }
+    }
+}
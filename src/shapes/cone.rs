@@ -0,0 +1,281 @@
+use super::{BoundingBox, Shape, ShapeProperties};
+use crate::intersection::{Intersection, Intersections};
+use crate::math::roots::solve_quadratic;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use crate::EPSILON;
+
+/// Double-napped cone around the y axis of its object space, whose radius at any y is the magnitude of y.
+/// The cone can be truncated to `minimum` < y < `maximum` and, if `closed`, capped at those ends.
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Cone {
+    properties: ShapeProperties,
+    pub minimum: f64,
+    pub maximum: f64,
+    pub closed: bool,
+}
+
+impl Default for Cone {
+    /// Create an infinite, open cone.
+    fn default() -> Self {
+        Self {
+            properties: ShapeProperties::default(),
+            minimum: f64::NEG_INFINITY,
+            maximum: f64::INFINITY,
+            closed: false,
+        }
+    }
+}
+
+impl Cone {
+    /// Create an infinite, open cone with an identity transform.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a cone truncated to `minimum` < y < `maximum`, optionally capped at both ends.
+    pub fn truncated(minimum: f64, maximum: f64, closed: bool) -> Self {
+        Self {
+            minimum,
+            maximum,
+            closed,
+            ..Self::default()
+        }
+    }
+
+    /// Intersect a ray with the caps of the cone, if it is closed, adding any hits to `intersections`.
+    fn intersect_caps<'a>(&'a self, local_ray: &Ray, intersections: &mut Vec<Intersection<'a>>) {
+        // Caps only matter if the cone is closed and the ray could reach them
+        if !self.closed || local_ray.direction.y.abs() < EPSILON {
+            return;
+        }
+        for y in [self.minimum, self.maximum] {
+            let t = (y - local_ray.origin.y) / local_ray.direction.y;
+            // The radius of each cap is the magnitude of its y
+            if check_cap(local_ray, t, y.abs()) {
+                intersections.push(Intersection::new(t, self));
+            }
+        }
+    }
+}
+
+impl Shape for Cone {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_> {
+        let origin = &local_ray.origin;
+        let direction = &local_ray.direction;
+        let a = direction.x * direction.x - direction.y * direction.y + direction.z * direction.z;
+        let b = 2.0 * origin.x * direction.x - 2.0 * origin.y * direction.y
+            + 2.0 * origin.z * direction.z;
+        let c = origin.x * origin.x - origin.y * origin.y + origin.z * origin.z;
+
+        let ts = if a.abs() < EPSILON {
+            // The ray is parallel to one of the cone's halves, so it can hit at most the other half
+            if b.abs() >= EPSILON {
+                vec![-c / (2.0 * b)]
+            } else {
+                Vec::new()
+            }
+        } else {
+            solve_quadratic(a, b, c)
+        };
+
+        let mut intersections: Vec<Intersection> = ts
+            .into_iter()
+            .filter(|&t| {
+                let y = origin.y + t * direction.y;
+                self.minimum < y && y < self.maximum
+            })
+            .map(|t| Intersection::new(t, self))
+            .collect();
+        self.intersect_caps(local_ray, &mut intersections);
+        Intersections::new(intersections)
+    }
+
+    fn local_normal_at(&self, local_point: &Tuple) -> Tuple {
+        let distance = local_point.x * local_point.x + local_point.z * local_point.z;
+        if distance < self.maximum * self.maximum && local_point.y >= self.maximum - EPSILON {
+            return Tuple::new_vector(0.0, 1.0, 0.0);
+        }
+        if distance < self.minimum * self.minimum && local_point.y <= self.minimum + EPSILON {
+            return Tuple::new_vector(0.0, -1.0, 0.0);
+        }
+
+        // The normal on the body slopes away from the apex
+        let y = if local_point.y > 0.0 {
+            -distance.sqrt()
+        } else {
+            distance.sqrt()
+        };
+        Tuple::new_vector(local_point.x, y, local_point.z)
+    }
+
+    /// The cone is widest at whichever end is furthest from the apex.
+    fn bounds(&self) -> BoundingBox {
+        let limit = self.minimum.abs().max(self.maximum.abs());
+        BoundingBox::new(
+            Tuple::new_point(-limit, self.minimum, -limit),
+            Tuple::new_point(limit, self.maximum, limit),
+        )
+    }
+}
+
+/// Test if the intersection at `t` is within `radius` of the y axis, and therefore on a cap.
+fn check_cap(ray: &Ray, t: f64, radius: f64) -> bool {
+    let x = ray.origin.x + t * ray.direction.x;
+    let z = ray.origin.z + t * ray.direction.z;
+    x * x + z * z <= radius * radius + EPSILON
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use std::f64::consts::SQRT_2;
+
+    #[test]
+    fn cone_new() {
+        let cone = Cone::new();
+        assert_eq!(cone.minimum, f64::NEG_INFINITY);
+        assert_eq!(cone.maximum, f64::INFINITY);
+        assert!(!cone.closed);
+    }
+
+    #[test]
+    fn cone_local_intersect() {
+        let cone = Cone::new();
+        let cases = [
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 5.0, 5.0),
+            ((0.0, 0.0, -5.0), (1.0, 1.0, 1.0), 8.66025, 8.66025),
+            ((1.0, 1.0, -5.0), (-0.5, -1.0, 1.0), 4.55006, 49.44994),
+        ];
+        for ((ox, oy, oz), (dx, dy, dz), t0, t1) in cases {
+            let ray = Ray::new(
+                Tuple::new_point(ox, oy, oz),
+                Tuple::new_vector(dx, dy, dz).normalize(),
+            );
+            let xs = cone.local_intersect(&ray);
+            assert_eq!(xs.len(), 2);
+            // The book's values are rounded to five decimal places.
+            assert_approx_eq!(xs[0].t, t0, epsilon = 1e-4);
+            assert_approx_eq!(xs[1].t, t1, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn cone_local_intersect_parallel_to_one_half() {
+        let cone = Cone::new();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -1.0),
+            Tuple::new_vector(0.0, 1.0, 1.0).normalize(),
+        );
+        let xs = cone.local_intersect(&ray);
+        assert_eq!(xs.len(), 1);
+        // The book's value is rounded to five decimal places.
+        assert_approx_eq!(xs[0].t, 0.35355, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn cone_local_intersect_truncated() {
+        let cone = Cone::truncated(-0.5, 0.5, false);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(1.0, 1.0, 1.0).normalize(),
+        );
+        // The same ray hits the infinite cone at y = 5
+        assert!(cone.local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn cone_local_intersect_caps() {
+        let cone = Cone::truncated(-0.5, 0.5, true);
+        let cases = [
+            ((0.0, 0.0, -5.0), (0.0, 1.0, 0.0), 0),
+            ((0.0, 0.0, -0.25), (0.0, 1.0, 1.0), 2),
+            ((0.0, 0.0, -0.25), (0.0, 1.0, 0.0), 4),
+        ];
+        for ((ox, oy, oz), (dx, dy, dz), count) in cases {
+            let ray = Ray::new(
+                Tuple::new_point(ox, oy, oz),
+                Tuple::new_vector(dx, dy, dz).normalize(),
+            );
+            assert_eq!(cone.local_intersect(&ray).len(), count);
+        }
+    }
+
+    #[test]
+    fn cone_local_normal_at() {
+        let cone = Cone::new();
+        let cases = [
+            ((0.0, 0.0, 0.0), (0.0, 0.0, 0.0)),
+            ((1.0, 1.0, 1.0), (1.0, -SQRT_2, 1.0)),
+            ((-1.0, -1.0, 0.0), (-1.0, 1.0, 0.0)),
+        ];
+        for ((px, py, pz), (nx, ny, nz)) in cases {
+            assert!(cone
+                .local_normal_at(&Tuple::new_point(px, py, pz))
+                .is_equal_to(&Tuple::new_vector(nx, ny, nz)));
+        }
+    }
+
+    #[test]
+    fn cone_local_normal_at_caps() {
+        let cone = Cone::truncated(-1.0, 2.0, true);
+        assert!(cone
+            .local_normal_at(&Tuple::new_point(0.5, 2.0, 0.5))
+            .is_equal_to(&Tuple::new_vector(0.0, 1.0, 0.0)));
+        assert!(cone
+            .local_normal_at(&Tuple::new_point(0.0, -1.0, 0.5))
+            .is_equal_to(&Tuple::new_vector(0.0, -1.0, 0.0)));
+    }
+
+    #[test]
+    fn cone_bounds() {
+        let bounds = Cone::new().bounds();
+        assert!(!bounds.is_finite());
+
+        let bounds = Cone::truncated(-5.0, 3.0, false).bounds();
+        assert!(bounds
+            .min()
+            .is_equal_to(&Tuple::new_point(-5.0, -5.0, -5.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(5.0, 3.0, 5.0)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn cone_serde() {
+        use crate::assert_approx_eq;
+        use crate::transform::translation;
+
+        let mut cone = Cone::truncated(-1.0, 2.0, true);
+        cone.set_transform(translation(0.0, 1.0, 0.0)).unwrap();
+        cone.set_casts_shadow(false);
+        let json = serde_json::to_string(&cone).unwrap();
+        let deserialized: Cone = serde_json::from_str(&json).unwrap();
+        assert_approx_eq!(deserialized.minimum, -1.0);
+        assert_approx_eq!(deserialized.maximum, 2.0);
+        assert!(deserialized.closed);
+        assert!(!deserialized.casts_shadow());
+        assert!(deserialized.transform().is_equal_to(cone.transform()));
+        // The inverse transform is computed again
+        assert!(deserialized
+            .world_to_object(&Tuple::new_point(0.0, 1.0, 0.0))
+            .is_equal_to(&Tuple::new_point(0.0, 0.0, 0.0)));
+
+        // A transform that cannot be inverted is rejected
+        let json = r#"{"properties":{"transform":[[0,0,0,0],[0,0,0,0],[0,0,0,0],[0,0,0,0]]}}"#;
+        assert!(serde_json::from_str::<Cone>(json).is_err());
+    }
+}
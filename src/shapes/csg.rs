@@ -0,0 +1,303 @@
+use super::{BoundingBox, ParentLink, Shape, ShapeProperties};
+use crate::intersection::Intersections;
+use crate::matrix::{Matrix4, MatrixError};
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use std::sync::Arc;
+
+/// Boolean operation used to combine the two shapes of a `Csg`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CsgOperation {
+    /// Keep the surfaces of both shapes that are not inside the other.
+    Union,
+    /// Keep only the surfaces of each shape that are inside the other.
+    Intersection,
+    /// Keep the surfaces of the left shape outside the right one, and of the right shape inside the left one.
+    Difference,
+}
+
+impl CsgOperation {
+    /// Decide whether an intersection is part of the combined shape.
+    ///
+    /// `left_hit` is true if the intersection is with the left shape, and `in_left` and `in_right` tell
+    /// whether the intersection is inside the left and right shapes respectively.
+    pub fn intersection_allowed(self, left_hit: bool, in_left: bool, in_right: bool) -> bool {
+        match self {
+            Self::Union => (left_hit && !in_right) || (!left_hit && !in_left),
+            Self::Intersection => (left_hit && in_right) || (!left_hit && in_left),
+            Self::Difference => (left_hit && !in_right) || (!left_hit && in_left),
+        }
+    }
+}
+
+/// Constructive solid geometry: two shapes combined by a boolean operation.
+///
+/// Both shapes are children of the CSG shape, so its transform applies to them and they record a link
+/// back to it like the children of a `Group`.
+#[derive(Debug)]
+pub struct Csg {
+    properties: ShapeProperties,
+    operation: CsgOperation,
+    left: Box<dyn Shape>,
+    right: Box<dyn Shape>,
+    link: Arc<ParentLink>,
+}
+
+impl Csg {
+    /// Combine two shapes with an operation, using an identity transform.
+    pub fn new(operation: CsgOperation, left: Box<dyn Shape>, right: Box<dyn Shape>) -> Self {
+        let properties = ShapeProperties::default();
+        let link = Arc::new(ParentLink::new(&properties));
+        let mut csg = Self {
+            properties,
+            operation,
+            left,
+            right,
+            link,
+        };
+        csg.relink_children();
+        csg
+    }
+
+    /// Get the operation combining the two shapes.
+    pub fn operation(&self) -> CsgOperation {
+        self.operation
+    }
+
+    /// Get the left shape of the operation.
+    pub fn left(&self) -> &dyn Shape {
+        self.left.as_ref()
+    }
+
+    /// Get the right shape of the operation.
+    pub fn right(&self) -> &dyn Shape {
+        self.right.as_ref()
+    }
+
+    /// Keep only the intersections that are part of the combined shape.
+    /// The intersections must be sorted, which `Intersections` guarantees.
+    pub fn filter_intersections<'a>(&self, intersections: &Intersections<'a>) -> Intersections<'a> {
+        let mut in_left = false;
+        let mut in_right = false;
+        let mut filtered = Vec::new();
+        for intersection in intersections.iter() {
+            let left_hit = self.left.includes(intersection.object);
+            if self
+                .operation
+                .intersection_allowed(left_hit, in_left, in_right)
+            {
+                filtered.push(*intersection);
+            }
+            // Every intersection enters or leaves the shape that was hit
+            if left_hit {
+                in_left = !in_left;
+            } else {
+                in_right = !in_right;
+            }
+        }
+        Intersections::new(filtered)
+    }
+
+    /// Rebuild the link given to both shapes after the CSG's transform or parent changed.
+    fn relink_children(&mut self) {
+        self.link = Arc::new(ParentLink::new(&self.properties));
+        self.left.set_parent(Some(Arc::clone(&self.link)));
+        self.right.set_parent(Some(Arc::clone(&self.link)));
+    }
+}
+
+impl Shape for Csg {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn set_transform(&mut self, transform: Matrix4) -> Result<(), MatrixError> {
+        self.properties.set_transform(transform)?;
+        self.relink_children();
+        Ok(())
+    }
+
+    fn set_parent(&mut self, parent: Option<Arc<ParentLink>>) {
+        self.properties.parent = parent;
+        self.relink_children();
+    }
+
+    fn includes(&self, other: &dyn Shape) -> bool {
+        self.left.includes(other) || self.right.includes(other)
+    }
+
+    fn divide(&mut self, threshold: usize) {
+        self.left.divide(threshold);
+        self.right.divide(threshold);
+    }
+
+    fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_> {
+        let mut intersections = self.left.intersect(local_ray);
+        intersections.extend(self.right.intersect(local_ray));
+        self.filter_intersections(&intersections)
+    }
+
+    /// CSG shapes have no surface of their own; normals are always computed on the child that was hit.
+    fn local_normal_at(&self, _local_point: &Tuple) -> Tuple {
+        unreachable!("a CSG shape has no surface, so its normal is never computed")
+    }
+
+    /// The bounds enclose both shapes, which is conservative for intersections and differences.
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = BoundingBox::parent_space_bounds_of(self.left.as_ref());
+        bounds.merge(&BoundingBox::parent_space_bounds_of(self.right.as_ref()));
+        bounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::intersection::Intersection;
+    use crate::shapes::{Group, Sphere};
+    use crate::transform::translation;
+
+    fn sphere_csg(operation: CsgOperation) -> Csg {
+        Csg::new(operation, Box::new(Sphere::new()), Box::new(Sphere::new()))
+    }
+
+    #[test]
+    fn csg_new() {
+        let csg = sphere_csg(CsgOperation::Union);
+        assert_eq!(csg.operation(), CsgOperation::Union);
+        assert!(Arc::ptr_eq(csg.left().parent().unwrap(), &csg.link));
+        assert!(Arc::ptr_eq(csg.right().parent().unwrap(), &csg.link));
+    }
+
+    #[test]
+    fn csg_operation_intersection_allowed() {
+        use CsgOperation::{Difference, Intersection, Union};
+        let cases = [
+            (Union, true, true, true, false),
+            (Union, true, true, false, true),
+            (Union, true, false, true, false),
+            (Union, true, false, false, true),
+            (Union, false, true, true, false),
+            (Union, false, true, false, false),
+            (Union, false, false, true, true),
+            (Union, false, false, false, true),
+            (Intersection, true, true, true, true),
+            (Intersection, true, true, false, false),
+            (Intersection, true, false, true, true),
+            (Intersection, true, false, false, false),
+            (Intersection, false, true, true, true),
+            (Intersection, false, true, false, true),
+            (Intersection, false, false, true, false),
+            (Intersection, false, false, false, false),
+            (Difference, true, true, true, false),
+            (Difference, true, true, false, true),
+            (Difference, true, false, true, false),
+            (Difference, true, false, false, true),
+            (Difference, false, true, true, true),
+            (Difference, false, true, false, true),
+            (Difference, false, false, true, false),
+            (Difference, false, false, false, false),
+        ];
+        for (operation, left_hit, in_left, in_right, expected) in cases {
+            assert_eq!(
+                operation.intersection_allowed(left_hit, in_left, in_right),
+                expected,
+                "{operation:?} {left_hit} {in_left} {in_right}"
+            );
+        }
+    }
+
+    #[test]
+    fn csg_filter_intersections() {
+        let cases = [
+            (CsgOperation::Union, 0, 3),
+            (CsgOperation::Intersection, 1, 2),
+            (CsgOperation::Difference, 0, 1),
+        ];
+        for (operation, first, second) in cases {
+            let csg = sphere_csg(operation);
+            let xs = Intersections::new(vec![
+                Intersection::new(1.0, csg.left()),
+                Intersection::new(2.0, csg.right()),
+                Intersection::new(3.0, csg.left()),
+                Intersection::new(4.0, csg.right()),
+            ]);
+            let result = csg.filter_intersections(&xs);
+            assert_eq!(result.len(), 2);
+            assert_approx_eq!(result[0].t, xs[first].t);
+            assert_approx_eq!(result[1].t, xs[second].t);
+        }
+    }
+
+    #[test]
+    fn csg_local_intersect_miss() {
+        let csg = sphere_csg(CsgOperation::Union);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 2.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        assert!(csg.local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn csg_local_intersect_hit() {
+        let mut right = Sphere::new();
+        right.set_transform(translation(0.0, 0.0, 0.5)).unwrap();
+        let csg = Csg::new(
+            CsgOperation::Union,
+            Box::new(Sphere::new()),
+            Box::new(right),
+        );
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = csg.local_intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 4.0);
+        assert!(std::ptr::addr_eq(xs[0].object, csg.left()));
+        assert_approx_eq!(xs[1].t, 6.5);
+        assert!(std::ptr::addr_eq(xs[1].object, csg.right()));
+    }
+
+    #[test]
+    fn csg_filter_intersections_nested_group() {
+        // The left shape is a group, so hits on its children must still count as left hits
+        let mut group = Group::new();
+        group.add_child(Box::new(Sphere::new()));
+        let mut right = Sphere::new();
+        right.set_transform(translation(0.0, 0.0, 0.5)).unwrap();
+        let csg = Csg::new(CsgOperation::Difference, Box::new(group), Box::new(right));
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = csg.local_intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 4.0);
+        assert_approx_eq!(xs[1].t, 4.5);
+        assert!(std::ptr::addr_eq(xs[1].object, csg.right()));
+    }
+
+    #[test]
+    fn csg_bounds() {
+        let mut right = Sphere::new();
+        right.set_transform(translation(2.0, 3.0, 4.0)).unwrap();
+        let csg = Csg::new(
+            CsgOperation::Difference,
+            Box::new(Sphere::new()),
+            Box::new(right),
+        );
+        let bounds = csg.bounds();
+        assert!(bounds
+            .min()
+            .is_equal_to(&Tuple::new_point(-1.0, -1.0, -1.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(3.0, 4.0, 5.0)));
+    }
+}
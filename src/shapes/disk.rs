@@ -0,0 +1,129 @@
+use super::{BoundingBox, Shape, ShapeProperties};
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use crate::EPSILON;
+
+/// Flat disk in the xz plane of its object space, centered at the origin and facing up the y axis.
+/// A disk with an `inner_radius` greater than zero has a hole in the middle, making it an annulus.
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Disk {
+    properties: ShapeProperties,
+    pub inner_radius: f64,
+    pub outer_radius: f64,
+}
+
+impl Default for Disk {
+    /// Create a solid disk with a radius of 1.
+    fn default() -> Self {
+        Self::annulus(0.0, 1.0)
+    }
+}
+
+impl Disk {
+    /// Create a solid disk with a radius of 1 and an identity transform.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a disk covering the points between `inner_radius` and `outer_radius` from its center.
+    pub fn annulus(inner_radius: f64, outer_radius: f64) -> Self {
+        Self {
+            properties: ShapeProperties::default(),
+            inner_radius,
+            outer_radius,
+        }
+    }
+}
+
+impl Shape for Disk {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    /// Intersect the ray with the disk's plane like a `Plane`, keeping the hit only if it lies between
+    /// the radii.
+    fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_> {
+        if local_ray.direction.y.abs() < EPSILON {
+            return Intersections::default();
+        }
+        let t = -local_ray.origin.y / local_ray.direction.y;
+        let point = local_ray.position(t);
+        let distance = point.x * point.x + point.z * point.z;
+        if distance < self.inner_radius * self.inner_radius
+            || distance > self.outer_radius * self.outer_radius
+        {
+            return Intersections::default();
+        }
+        Intersections::new(vec![Intersection::new(t, self)])
+    }
+
+    /// The normal of a disk is the same everywhere.
+    fn local_normal_at(&self, _local_point: &Tuple) -> Tuple {
+        Tuple::new_vector(0.0, 1.0, 0.0)
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Tuple::new_point(-self.outer_radius, 0.0, -self.outer_radius),
+            Tuple::new_point(self.outer_radius, 0.0, self.outer_radius),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disk_new() {
+        let disk = Disk::new();
+        assert_eq!(disk.inner_radius, 0.0);
+        assert_eq!(disk.outer_radius, 1.0);
+    }
+
+    #[test]
+    fn disk_local_intersect() {
+        let disk = Disk::annulus(0.5, 1.0);
+        let cases = [
+            ((0.75, 1.0, 0.0), (0.0, -1.0, 0.0), Some(1.0)),
+            ((0.0, -2.0, -0.75), (0.0, 1.0, 0.0), Some(2.0)),
+            // Through the hole
+            ((0.25, 1.0, 0.0), (0.0, -1.0, 0.0), None),
+            // Beyond the edge
+            ((1.0, 1.0, 1.0), (0.0, -1.0, 0.0), None),
+            // Parallel to the disk
+            ((-2.0, 0.0, 0.75), (1.0, 0.0, 0.0), None),
+        ];
+        for ((ox, oy, oz), (dx, dy, dz), expected) in cases {
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), Tuple::new_vector(dx, dy, dz));
+            let xs = disk.local_intersect(&ray);
+            assert_eq!(xs.iter().next().map(|x| x.t), expected);
+            assert!(xs.len() <= 1);
+        }
+    }
+
+    #[test]
+    fn disk_local_normal_at() {
+        let disk = Disk::new();
+        assert!(disk
+            .local_normal_at(&Tuple::new_point(0.5, 0.0, -0.5))
+            .is_equal_to(&Tuple::new_vector(0.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn disk_bounds() {
+        let bounds = Disk::annulus(1.0, 2.0).bounds();
+        assert!(bounds.min().is_equal_to(&Tuple::new_point(-2.0, 0.0, -2.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(2.0, 0.0, 2.0)));
+    }
+}
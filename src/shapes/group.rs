@@ -0,0 +1,506 @@
+use super::{BoundingBox, ParentLink, Shape, ShapeProperties};
+use crate::intersection::Intersections;
+use crate::matrix::{Matrix4, MatrixError};
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use std::sync::Arc;
+
+/// Shapes owned by a group.
+pub type Children = Vec<Box<dyn Shape>>;
+
+/// Collection of shapes that are transformed together as a single unit.
+///
+/// The group's transform applies to all of its children, which may themselves be groups. Every child
+/// records a link back to the group so its points and normals can be converted through the hierarchy.
+/// The group keeps the bounds of its children so rays that miss them skip the children entirely.
+#[derive(Debug)]
+pub struct Group {
+    properties: ShapeProperties,
+    children: Children,
+    link: Arc<ParentLink>,
+    bounds: BoundingBox,
+}
+
+impl Group {
+    /// Create an empty group with an identity transform.
+    pub fn new() -> Self {
+        let properties = ShapeProperties::default();
+        let link = Arc::new(ParentLink::new(&properties));
+        Self {
+            properties,
+            children: Vec::new(),
+            link,
+            bounds: BoundingBox::default(),
+        }
+    }
+
+    /// Get the shapes contained in the group.
+    pub fn children(&self) -> &[Box<dyn Shape>] {
+        &self.children
+    }
+
+    /// Check whether the group contains no shapes.
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Add a shape to the group, linking it to the group as its parent.
+    /// The shape's transform should be set before it is added, since it determines the group's bounds.
+    pub fn add_child(&mut self, mut child: Box<dyn Shape>) {
+        child.set_parent(Some(Arc::clone(&self.link)));
+        self.bounds
+            .merge(&BoundingBox::parent_space_bounds_of(child.as_ref()));
+        self.children.push(child);
+    }
+
+    /// Remove the children that fit entirely in either half of the group's bounds, returning those
+    /// in the left half and those in the right half. Children that straddle both halves are kept.
+    ///
+    /// Only children with finite bounds are used to find the halves, so unbounded shapes such as
+    /// planes stay in the group without preventing the others from being partitioned.
+    pub fn partition_children(&mut self) -> (Children, Children) {
+        let mut finite_bounds = BoundingBox::default();
+        for child in &self.children {
+            let child_bounds = BoundingBox::parent_space_bounds_of(child.as_ref());
+            if child_bounds.is_finite() {
+                finite_bounds.merge(&child_bounds);
+            }
+        }
+        if finite_bounds.is_empty() {
+            return (Vec::new(), Vec::new());
+        }
+
+        let (left_bounds, right_bounds) = finite_bounds.split();
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        let mut kept = Vec::new();
+        for child in self.children.drain(..) {
+            let child_bounds = BoundingBox::parent_space_bounds_of(child.as_ref());
+            if left_bounds.contains_box(&child_bounds) {
+                left.push(child);
+            } else if right_bounds.contains_box(&child_bounds) {
+                right.push(child);
+            } else {
+                kept.push(child);
+            }
+        }
+        self.children = kept;
+        (left, right)
+    }
+
+    /// Add a new group containing the given shapes as a child of this group.
+    pub fn make_subgroup(&mut self, shapes: Children) {
+        let mut subgroup = Group::new();
+        for shape in shapes {
+            subgroup.add_child(shape);
+        }
+        self.add_child(Box::new(subgroup));
+    }
+
+    /// Rebuild the link given to children after the group's transform or parent changed.
+    fn relink_children(&mut self) {
+        self.link = Arc::new(ParentLink::new(&self.properties));
+        for child in &mut self.children {
+            child.set_parent(Some(Arc::clone(&self.link)));
+        }
+    }
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shape for Group {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn set_transform(&mut self, transform: Matrix4) -> Result<(), MatrixError> {
+        self.properties.set_transform(transform)?;
+        self.relink_children();
+        Ok(())
+    }
+
+    fn set_parent(&mut self, parent: Option<Arc<ParentLink>>) {
+        self.properties.parent = parent;
+        self.relink_children();
+    }
+
+    fn includes(&self, other: &dyn Shape) -> bool {
+        self.children.iter().any(|child| child.includes(other))
+    }
+
+    /// Split the children into subgroups by halving the group's bounds, then divide every child,
+    /// recursively building a bounding volume hierarchy.
+    fn divide(&mut self, threshold: usize) {
+        if threshold <= self.children.len() {
+            let (left, right) = self.partition_children();
+            if !left.is_empty() {
+                self.make_subgroup(left);
+            }
+            if !right.is_empty() {
+                self.make_subgroup(right);
+            }
+        }
+        for child in &mut self.children {
+            child.divide(threshold);
+        }
+    }
+
+    /// The ray is already in the group's space, which is the parent space of every child.
+    /// Children are only tested if the ray hits the group's bounds.
+    fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_> {
+        let mut intersections = Intersections::default();
+        if !self.bounds.intersects(local_ray) {
+            return intersections;
+        }
+        for child in &self.children {
+            intersections.extend(child.intersect(local_ray));
+        }
+        intersections
+    }
+
+    /// Groups have no surface of their own; normals are always computed on the child that was hit.
+    fn local_normal_at(&self, _local_point: &Tuple) -> Tuple {
+        unreachable!("a group has no surface, so its normal is never computed")
+    }
+
+    /// The group's bounds enclose the bounds of every child, transformed into the group's space.
+    fn bounds(&self) -> BoundingBox {
+        self.bounds.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::shapes::tests::TestShape;
+    use crate::shapes::Sphere;
+    use crate::transform::{rotation_y, scaling, translation};
+    use std::f64::consts::PI;
+
+    /// Build the book's nested groups: a translated sphere inside a scaled group inside a rotated group.
+    fn nested_sphere(inner_scaling: Matrix4) -> Group {
+        let mut outer = Group::new();
+        outer.set_transform(rotation_y(PI / 2.0)).unwrap();
+        let mut inner = Group::new();
+        inner.set_transform(inner_scaling).unwrap();
+        let mut sphere = Sphere::new();
+        sphere.set_transform(translation(5.0, 0.0, 0.0)).unwrap();
+        inner.add_child(Box::new(sphere));
+        outer.add_child(Box::new(inner));
+        outer
+    }
+
+    /// Get the first object hit by a ray along the z axis, through a point with the given x and y.
+    fn first_hit_along_z(group: &Group, x: f64, y: f64) -> &dyn Shape {
+        let ray = Ray::new(
+            Tuple::new_point(x, y, -100.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        group.intersect(&ray).hit().unwrap().object
+    }
+
+    #[test]
+    fn group_new() {
+        let group = Group::new();
+        assert!(group.transform().is_equal_to(&Matrix4::IDENTITY));
+        assert!(group.is_empty());
+        assert!(group.parent().is_none());
+    }
+
+    #[test]
+    fn group_add_child() {
+        let mut group = Group::new();
+        group.add_child(Box::new(Sphere::new()));
+        assert!(!group.is_empty());
+        assert_eq!(group.children().len(), 1);
+        let parent = group.children()[0].parent().unwrap();
+        assert!(Arc::ptr_eq(parent, &group.link));
+    }
+
+    #[test]
+    fn group_set_transform_relinks_children() {
+        let mut group = Group::new();
+        group.add_child(Box::new(Sphere::new()));
+        group.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let parent = group.children()[0].parent().unwrap();
+        assert!(Arc::ptr_eq(parent, &group.link));
+        assert!(parent
+            .inverse_transform()
+            .is_equal_to(&scaling(0.5, 0.5, 0.5)));
+    }
+
+    #[test]
+    fn group_add_to_group() {
+        let mut inner = Group::new();
+        inner.add_child(Box::new(Sphere::new()));
+        let mut outer = Group::new();
+        outer.add_child(Box::new(inner));
+        let inner = &outer.children()[0];
+        assert!(Arc::ptr_eq(inner.parent().unwrap(), &outer.link));
+    }
+
+    #[test]
+    fn group_includes() {
+        let mut inner = Group::new();
+        inner.add_child(Box::new(Sphere::new()));
+        let mut outer = Group::new();
+        outer.add_child(Box::new(inner));
+        let outside = Sphere::new();
+
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = outer.intersect(&ray);
+        assert!(outer.includes(xs[0].object));
+        assert!(outer.children()[0].includes(xs[0].object));
+        assert!(!outer.includes(&outside));
+        assert!(outside.includes(&outside));
+    }
+
+    #[test]
+    fn group_local_intersect_empty() {
+        let group = Group::new();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        assert!(group.local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn group_local_intersect() {
+        let mut group = Group::new();
+        group.add_child(Box::new(Sphere::new()));
+        let mut sphere = Sphere::new();
+        sphere.set_transform(translation(0.0, 0.0, -3.0)).unwrap();
+        group.add_child(Box::new(sphere));
+        let mut sphere = Sphere::new();
+        sphere.set_transform(translation(5.0, 0.0, 0.0)).unwrap();
+        group.add_child(Box::new(sphere));
+
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = group.local_intersect(&ray);
+        assert_eq!(xs.len(), 4);
+        let children = group.children();
+        assert!(std::ptr::addr_eq(xs[0].object, children[1].as_ref()));
+        assert!(std::ptr::addr_eq(xs[1].object, children[1].as_ref()));
+        assert!(std::ptr::addr_eq(xs[2].object, children[0].as_ref()));
+        assert!(std::ptr::addr_eq(xs[3].object, children[0].as_ref()));
+    }
+
+    #[test]
+    fn group_intersect_transformed() {
+        let mut group = Group::new();
+        group.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let mut sphere = Sphere::new();
+        sphere.set_transform(translation(5.0, 0.0, 0.0)).unwrap();
+        group.add_child(Box::new(sphere));
+
+        let ray = Ray::new(
+            Tuple::new_point(10.0, 0.0, -10.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        assert_eq!(group.intersect(&ray).len(), 2);
+    }
+
+    #[test]
+    fn group_world_to_object_nested() {
+        let group = nested_sphere(scaling(2.0, 2.0, 2.0));
+        let sphere = first_hit_along_z(&group, 0.0, 0.0);
+        let point = sphere.world_to_object(&Tuple::new_point(-2.0, 0.0, -10.0));
+        assert!(point.is_equal_to(&Tuple::new_point(0.0, 0.0, -1.0)));
+    }
+
+    #[test]
+    fn group_normal_to_world_nested() {
+        let group = nested_sphere(scaling(1.0, 2.0, 3.0));
+        let sphere = first_hit_along_z(&group, 1.7321, 1.1547);
+        let value = 3.0_f64.sqrt() / 3.0;
+        let normal = sphere.normal_to_world(&Tuple::new_vector(value, value, value));
+        // The book's values are rounded to four decimal places.
+        assert_approx_eq!(
+            normal,
+            Tuple::new_vector(0.2857, 0.4286, -0.8571),
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn group_normal_at_nested() {
+        let group = nested_sphere(scaling(1.0, 2.0, 3.0));
+        let sphere = first_hit_along_z(&group, 1.7321, 1.1547);
+        let normal = sphere.normal_at(&Tuple::new_point(1.7321, 1.1547, -5.5774));
+        // The book's values are rounded to four decimal places.
+        assert_approx_eq!(
+            normal,
+            Tuple::new_vector(0.2857, 0.4286, -0.8571),
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn group_bounds() {
+        let mut sphere = Sphere::new();
+        sphere
+            .set_transform(translation(2.0, 5.0, -3.0) * scaling(2.0, 2.0, 2.0))
+            .unwrap();
+        let mut cone = crate::shapes::Cone::truncated(-2.0, 2.0, false);
+        cone.set_transform(translation(-4.0, -1.0, 4.0) * scaling(0.5, 1.0, 0.5))
+            .unwrap();
+        let mut group = Group::new();
+        group.add_child(Box::new(sphere));
+        group.add_child(Box::new(cone));
+
+        let bounds = group.bounds();
+        assert!(bounds
+            .min()
+            .is_equal_to(&Tuple::new_point(-5.0, -3.0, -5.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(4.0, 7.0, 5.0)));
+        assert!(Group::new().bounds().is_empty());
+    }
+
+    fn translated_sphere(x: f64, y: f64, z: f64) -> Box<dyn Shape> {
+        let mut sphere = Sphere::new();
+        sphere.set_transform(translation(x, y, z)).unwrap();
+        Box::new(sphere)
+    }
+
+    fn assert_bounds(shape: &dyn Shape, min: (f64, f64, f64), max: (f64, f64, f64)) {
+        let bounds = BoundingBox::parent_space_bounds_of(shape);
+        assert!(bounds
+            .min()
+            .is_equal_to(&Tuple::new_point(min.0, min.1, min.2)));
+        assert!(bounds
+            .max()
+            .is_equal_to(&Tuple::new_point(max.0, max.1, max.2)));
+    }
+
+    #[test]
+    fn group_local_intersect_misses_bounds() {
+        let child = TestShape::default();
+        let saved_ray = Arc::clone(&child.saved_ray);
+        let mut group = Group::new();
+        group.add_child(Box::new(child));
+
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        group.local_intersect(&ray);
+        assert!(saved_ray.lock().unwrap().is_none());
+
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        group.local_intersect(&ray);
+        assert!(saved_ray.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn group_partition_children() {
+        let mut group = Group::new();
+        group.add_child(translated_sphere(-2.0, 0.0, 0.0));
+        group.add_child(translated_sphere(2.0, 0.0, 0.0));
+        group.add_child(Box::new(Sphere::new()));
+
+        let (left, right) = group.partition_children();
+        assert_eq!(group.children().len(), 1);
+        assert_bounds(
+            group.children()[0].as_ref(),
+            (-1.0, -1.0, -1.0),
+            (1.0, 1.0, 1.0),
+        );
+        assert_eq!(left.len(), 1);
+        assert_bounds(left[0].as_ref(), (-3.0, -1.0, -1.0), (-1.0, 1.0, 1.0));
+        assert_eq!(right.len(), 1);
+        assert_bounds(right[0].as_ref(), (1.0, -1.0, -1.0), (3.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn group_make_subgroup() {
+        let mut group = Group::new();
+        group.make_subgroup(vec![
+            translated_sphere(-2.0, 0.0, 0.0),
+            translated_sphere(2.0, 0.0, 0.0),
+        ]);
+        assert_eq!(group.children().len(), 1);
+        assert_bounds(
+            group.children()[0].as_ref(),
+            (-3.0, -1.0, -1.0),
+            (3.0, 1.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn group_divide() {
+        let mut big = Sphere::new();
+        big.set_transform(scaling(4.0, 4.0, 4.0)).unwrap();
+        let mut group = Group::new();
+        group.add_child(translated_sphere(-2.0, -2.0, 0.0));
+        group.add_child(translated_sphere(-2.0, 2.0, 0.0));
+        group.add_child(Box::new(big));
+        group.divide(1);
+
+        // The big sphere straddles both halves and stays, the others move to a subgroup
+        assert_eq!(group.children().len(), 2);
+        assert_bounds(
+            group.children()[0].as_ref(),
+            (-4.0, -4.0, -4.0),
+            (4.0, 4.0, 4.0),
+        );
+        assert_bounds(
+            group.children()[1].as_ref(),
+            (-3.0, -3.0, -1.0),
+            (-1.0, 3.0, 1.0),
+        );
+
+        // The subgroup was divided again along y, and hits on its spheres still have correct normals
+        let ray = Ray::new(
+            Tuple::new_point(-2.0, 2.0, -10.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = group.children()[1].intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        let normal = xs[0].object.normal_at(&Tuple::new_point(-2.0, 2.0, -1.0));
+        assert!(normal.is_equal_to(&Tuple::new_vector(0.0, 0.0, -1.0)));
+    }
+
+    #[test]
+    fn group_divide_too_few_children() {
+        let mut subgroup = Group::new();
+        subgroup.add_child(translated_sphere(-2.0, 0.0, 0.0));
+        subgroup.add_child(translated_sphere(2.0, 1.0, 0.0));
+        subgroup.add_child(translated_sphere(2.0, -1.0, 0.0));
+        let mut group = Group::new();
+        group.add_child(Box::new(subgroup));
+        group.add_child(Box::new(Sphere::new()));
+        group.divide(3);
+
+        // The top group has too few children to be split, but its subgroup is split
+        assert_eq!(group.children().len(), 2);
+        assert_bounds(
+            group.children()[0].as_ref(),
+            (-3.0, -2.0, -1.0),
+            (3.0, 2.0, 1.0),
+        );
+        assert_bounds(
+            group.children()[1].as_ref(),
+            (-1.0, -1.0, -1.0),
+            (1.0, 1.0, 1.0),
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use super::{BoundingBox, Shape, ShapeProperties, Triangle};
+use crate::canvas::Canvas;
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use crate::EPSILON;
+
+/// Terrain made from a grid of elevations, such as a landscape.
+///
+/// The grid covers the unit square from (0, 0) to (1, 1) in the xz plane of its object space, with
+/// columns running along x and rows along z, and each elevation sets the y of its grid point. Every
+/// cell of the grid is split into two triangles, and rays only test the cells they pass over, so large
+/// grids render far faster than the same triangles in a group. Normals are blended across cells to
+/// keep the terrain smooth.
+#[derive(Debug)]
+pub struct Heightfield {
+    properties: ShapeProperties,
+    columns: usize,
+    rows: usize,
+    heights: Vec<f64>,
+    bounds: BoundingBox,
+}
+
+impl Heightfield {
+    /// Create a heightfield from `columns` × `rows` elevations listed row by row, with an identity transform.
+    ///
+    /// Panics if the grid is smaller than 2 × 2 or the number of elevations does not match its size.
+    pub fn new(columns: usize, rows: usize, heights: Vec<f64>) -> Self {
+        assert!(
+            columns >= 2 && rows >= 2,
+            "a heightfield needs at least 2 × 2 elevations"
+        );
+        assert_eq!(
+            heights.len(),
+            columns * rows,
+            "a {columns} × {rows} heightfield needs {} elevations",
+            columns * rows
+        );
+        let lowest = heights.iter().copied().fold(f64::INFINITY, f64::min);
+        let highest = heights.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        Self {
+            properties: ShapeProperties::default(),
+            columns,
+            rows,
+            heights,
+            bounds: BoundingBox::new(
+                Tuple::new_point(0.0, lowest, 0.0),
+                Tuple::new_point(1.0, highest, 1.0),
+            ),
+        }
+    }
+
+    /// Create a heightfield from the brightness of each pixel of a canvas, from 0 for black to 1 for white,
+    /// such as a grayscale elevation map read with `Canvas::read_ppm` or `Canvas::read_image`.
+    /// The first row of the canvas lies along z = 0.
+    ///
+    /// Panics if the canvas is smaller than 2 × 2 pixels.
+    pub fn from_canvas(canvas: &Canvas) -> Self {
+        let mut heights = Vec::with_capacity(canvas.width() * canvas.height());
+        for y in 0..canvas.height() {
+            for x in 0..canvas.width() {
+                let color = canvas.pixel_at(x, y);
+                heights.push((color.red + color.green + color.blue) / 3.0);
+            }
+        }
+        Self::new(canvas.width(), canvas.height(), heights)
+    }
+
+    /// Get the number of elevations along x.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Get the number of elevations along z.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Get the elevation at a grid point.
+    pub fn height_at(&self, column: usize, row: usize) -> f64 {
+        self.heights[row * self.columns + column]
+    }
+
+    /// Get the object space position of a grid point.
+    fn grid_point(&self, column: usize, row: usize) -> Tuple {
+        Tuple::new_point(
+            column as f64 / (self.columns - 1) as f64,
+            self.height_at(column, row),
+            row as f64 / (self.rows - 1) as f64,
+        )
+    }
+
+    /// Estimate the normal at a grid point from the slope to its neighbors.
+    fn grid_normal(&self, column: usize, row: usize) -> Tuple {
+        let (left, right) = (column.saturating_sub(1), (column + 1).min(self.columns - 1));
+        let (back, front) = (row.saturating_sub(1), (row + 1).min(self.rows - 1));
+        let dx = (right - left) as f64 / (self.columns - 1) as f64;
+        let dz = (front - back) as f64 / (self.rows - 1) as f64;
+        Tuple::new_vector(
+            -(self.height_at(right, row) - self.height_at(left, row)) / dx,
+            1.0,
+            -(self.height_at(column, front) - self.height_at(column, back)) / dz,
+        )
+    }
+
+    /// Intersect a ray with the two triangles of the cell at `column` and `row`.
+    fn intersect_cell(&self, ray: &Ray, column: usize, row: usize, ts: &mut Vec<f64>) {
+        let corner = self.grid_point(column, row);
+        let diagonal = self.grid_point(column + 1, row + 1) - corner;
+        for other in [
+            self.grid_point(column + 1, row),
+            self.grid_point(column, row + 1),
+        ] {
+            let edge = other - corner;
+            if let Some(t) =
+                Triangle::intersection_distance_with_edges(ray, &corner, &edge, &diagonal)
+            {
+                ts.push(t);
+            }
+        }
+    }
+}
+
+impl Shape for Heightfield {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    /// Walk the cells under the ray, from where it enters the grid's bounds to where it leaves them.
+    fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_> {
+        let Some((start, end)) = self.bounds.intersection_range(local_ray) else {
+            return Intersections::default();
+        };
+        let cells_x = (self.columns - 1) as f64;
+        let cells_z = (self.rows - 1) as f64;
+        let entry = local_ray.position(start);
+        let cell = |position: f64, cells: f64| (position * cells).floor().clamp(0.0, cells - 1.0);
+        let (mut column, mut row) = (cell(entry.x, cells_x), cell(entry.z, cells_z));
+
+        // Distance along the ray to the next cell boundary on each axis, and between boundaries
+        let walk = |direction: f64, position: f64, index: f64, cells: f64| {
+            if direction.abs() < EPSILON {
+                return (0.0, f64::INFINITY, f64::INFINITY);
+            }
+            let step = direction.signum();
+            let boundary = (index + step.max(0.0)) / cells;
+            (
+                step,
+                start + (boundary - position) / direction,
+                1.0 / (cells * direction.abs()),
+            )
+        };
+        let direction = &local_ray.direction;
+        let (step_x, mut next_x, delta_x) = walk(direction.x, entry.x, column, cells_x);
+        let (step_z, mut next_z, delta_z) = walk(direction.z, entry.z, row, cells_z);
+
+        let mut ts = Vec::new();
+        loop {
+            self.intersect_cell(local_ray, column as usize, row as usize, &mut ts);
+            if next_x.min(next_z) > end {
+                break;
+            }
+            if next_x < next_z {
+                column += step_x;
+                next_x += delta_x;
+            } else {
+                row += step_z;
+                next_z += delta_z;
+            }
+            if column < 0.0 || column >= cells_x || row < 0.0 || row >= cells_z {
+                break;
+            }
+        }
+        Intersections::new(ts.into_iter().map(|t| Intersection::new(t, self)).collect())
+    }
+
+    /// Blend the normals at the corners of the cell containing the point by how close the point is to each.
+    fn local_normal_at(&self, local_point: &Tuple) -> Tuple {
+        let x = local_point.x.clamp(0.0, 1.0) * (self.columns - 1) as f64;
+        let z = local_point.z.clamp(0.0, 1.0) * (self.rows - 1) as f64;
+        let column = (x.floor() as usize).min(self.columns - 2);
+        let row = (z.floor() as usize).min(self.rows - 2);
+        let (u, v) = (x - column as f64, z - row as f64);
+        self.grid_normal(column, row) * ((1.0 - u) * (1.0 - v))
+            + self.grid_normal(column + 1, row) * (u * (1.0 - v))
+            + self.grid_normal(column, row + 1) * ((1.0 - u) * v)
+            + self.grid_normal(column + 1, row + 1) * (u * v)
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.bounds.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::color::Color;
+
+    /// A 5 × 5 grid of a bump rising from the flat edges to a peak of 1 in the middle.
+    fn bump() -> Heightfield {
+        let mut heights = Vec::new();
+        for row in 0..5 {
+            for column in 0..5 {
+                let distance = (column as f64 - 2.0).abs().max((row as f64 - 2.0).abs());
+                heights.push(1.0 - distance / 2.0);
+            }
+        }
+        Heightfield::new(5, 5, heights)
+    }
+
+    #[test]
+    fn heightfield_new() {
+        let field = bump();
+        assert_eq!(field.columns(), 5);
+        assert_eq!(field.rows(), 5);
+        assert_approx_eq!(field.height_at(2, 2), 1.0);
+        let bounds = field.bounds();
+        assert!(bounds.min().is_equal_to(&Tuple::new_point(0.0, 0.0, 0.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(1.0, 1.0, 1.0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "needs 4 elevations")]
+    fn heightfield_new_wrong_size() {
+        Heightfield::new(2, 2, vec![0.0; 3]);
+    }
+
+    #[test]
+    fn heightfield_local_intersect_from_above() {
+        let field = bump();
+        let cases = [
+            // The peak, the middle of a slope and the flat edge
+            ((0.5, 0.5), 1.0),
+            ((0.375, 0.5), 0.75),
+            ((0.1, 0.9), 0.0),
+        ];
+        for ((x, z), height) in cases {
+            let ray = Ray::new(
+                Tuple::new_point(x, 5.0, z),
+                Tuple::new_vector(0.0, -1.0, 0.0),
+            );
+            let xs = field.local_intersect(&ray);
+            let hit = xs.hit().unwrap();
+            assert_approx_eq!(hit.t, (5.0 - height), "({x}, {z})");
+        }
+    }
+
+    #[test]
+    fn heightfield_local_intersect_walks_cells() {
+        let field = bump();
+        // A ray skimming along the middle row enters the bump on its way up and leaves it on its way down
+        let ray = Ray::new(
+            Tuple::new_point(-1.0, 0.25, 0.5),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+        );
+        let xs = field.local_intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 1.125);
+        assert_approx_eq!(xs[1].t, 1.875);
+
+        // Walking the cells finds the same hits as testing every cell
+        let cases = [
+            ((-1.0, 0.6, -0.8), (1.0, 0.0, 0.9)),
+            ((1.5, 0.9, 0.2), (-1.0, -0.3, 0.4)),
+            ((0.3, 2.0, 1.7), (0.1, -1.0, -0.5)),
+        ];
+        for ((ox, oy, oz), (dx, dy, dz)) in cases {
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), Tuple::new_vector(dx, dy, dz));
+            let mut expected = Vec::new();
+            for row in 0..4 {
+                for column in 0..4 {
+                    field.intersect_cell(&ray, column, row, &mut expected);
+                }
+            }
+            expected.sort_by(f64::total_cmp);
+            let ts: Vec<f64> = field.local_intersect(&ray).iter().map(|x| x.t).collect();
+            assert!(!expected.is_empty());
+            assert_eq!(ts, expected, "({ox}, {oy}, {oz})");
+        }
+    }
+
+    #[test]
+    fn heightfield_local_normal_at() {
+        let field = Heightfield::new(2, 2, vec![0.0, 0.0, 1.0, 1.0]);
+        // The grid rises one unit along z over one unit
+        let normal = field
+            .local_normal_at(&Tuple::new_point(0.3, 0.6, 0.6))
+            .normalize();
+        assert!(normal.is_equal_to(&Tuple::new_vector(0.0, 1.0, -1.0).normalize()));
+    }
+
+    #[test]
+    fn heightfield_from_canvas() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.write_pixel(1, 0, Color::new(1.0, 1.0, 1.0));
+        canvas.write_pixel(2, 1, Color::new(0.5, 0.5, 0.5));
+        let field = Heightfield::from_canvas(&canvas);
+        assert_eq!(field.columns(), 3);
+        assert_eq!(field.rows(), 2);
+        assert_approx_eq!(field.height_at(1, 0), 1.0);
+        assert_approx_eq!(field.height_at(2, 1), 0.5);
+        assert_approx_eq!(field.height_at(0, 1), 0.0);
+    }
+}
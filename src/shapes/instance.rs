@@ -0,0 +1,223 @@
+use super::{BoundingBox, Shape, ShapeProperties};
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use std::sync::Arc;
+
+/// Copy of a shape that shares the shape's geometry instead of duplicating it.
+///
+/// Any number of instances can refer to the same shape, such as a group holding a large triangle mesh,
+/// each with its own transform and material. The shared shape is placed in the instance's object space,
+/// and every hit on it is reported as a hit on the instance, so it is shaded with the instance's material.
+/// The shared shape must not be part of a group itself, and should be divided before it is shared,
+/// since it cannot be changed afterwards.
+#[derive(Debug)]
+pub struct Instance {
+    properties: ShapeProperties,
+    shape: Arc<dyn Shape>,
+}
+
+impl Instance {
+    /// Create an instance of a shared shape with an identity transform and the default material.
+    pub fn new(shape: Arc<dyn Shape>) -> Self {
+        Self {
+            properties: ShapeProperties::default(),
+            shape,
+        }
+    }
+
+    /// Get the shape whose geometry the instance shares.
+    pub fn shape(&self) -> &Arc<dyn Shape> {
+        &self.shape
+    }
+}
+
+impl Shape for Instance {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    /// The normal of each hit is found on the shared surface that was hit, since it cannot be found
+    /// from the instance later, and converted from the instance's object space to world space.
+    fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_> {
+        let intersections = self
+            .shape
+            .intersect(local_ray)
+            .into_vec()
+            .into_iter()
+            .map(|intersection| {
+                let local_normal = match intersection.normal {
+                    Some(normal) => normal,
+                    None => intersection
+                        .object
+                        .normal_at(&local_ray.position(intersection.t)),
+                };
+                Intersection::with_normal(intersection.t, self, self.normal_to_world(&local_normal))
+            })
+            .collect();
+        Intersections::new(intersections)
+    }
+
+    /// Instances have no surface of their own; normals are found on the shared shape when it is intersected.
+    fn local_normal_at(&self, _local_point: &Tuple) -> Tuple {
+        unreachable!("an instance has no surface, so its normal is never computed")
+    }
+
+    /// The bounds enclose the shared shape, transformed into the instance's object space.
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::parent_space_bounds_of(self.shape.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::color::Color;
+    use crate::light::Material;
+    use crate::matrix::Matrix4;
+    use crate::shapes::{Group, Sphere};
+    use crate::transform::{scaling, translation};
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    /// Share a group holding a sphere translated along the x axis.
+    fn shared_sphere() -> Arc<dyn Shape> {
+        let mut sphere = Sphere::new();
+        sphere.set_transform(translation(5.0, 0.0, 0.0)).unwrap();
+        let mut group = Group::new();
+        group.add_child(Box::new(sphere));
+        Arc::new(group)
+    }
+
+    /// Create an instance of a shared shape with the given transform.
+    fn instance_with_transform(shape: &Arc<dyn Shape>, transform: Matrix4) -> Instance {
+        let mut instance = Instance::new(Arc::clone(shape));
+        instance.set_transform(transform).unwrap();
+        instance
+    }
+
+    #[test]
+    fn instance_new() {
+        let shape = shared_sphere();
+        let instance = Instance::new(Arc::clone(&shape));
+        assert!(Arc::ptr_eq(instance.shape(), &shape));
+        assert!(instance.transform().is_equal_to(&Matrix4::IDENTITY));
+        assert_eq!(Arc::strong_count(&shape), 2);
+    }
+
+    #[test]
+    fn instance_intersect() {
+        let shape = shared_sphere();
+        let near = instance_with_transform(&shape, translation(-5.0, 0.0, 0.0));
+        let far = instance_with_transform(&shape, translation(-5.0, 0.0, 10.0));
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        let xs = near.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 4.0);
+        assert_approx_eq!(xs[1].t, 6.0);
+        assert!(std::ptr::addr_eq(xs[0].object, &near));
+
+        let xs = far.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 14.0);
+        assert!(std::ptr::addr_eq(xs[0].object, &far));
+    }
+
+    #[test]
+    fn instance_intersect_normal() {
+        let shape = shared_sphere();
+        let instance =
+            instance_with_transform(&shape, scaling(1.0, 2.0, 1.0) * translation(-5.0, 0.0, 0.0));
+        let ray = Ray::new(
+            Tuple::new_point(0.0, FRAC_1_SQRT_2 * 2.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = instance.intersect(&ray);
+        let comps = xs[0].prepare_computations(&ray, &xs);
+        // The normal of a sphere stretched along y tilts toward z
+        let expected = Tuple::new_vector(0.0, 0.5, -1.0).normalize();
+        assert!(comps.normalv.is_equal_to(&expected));
+    }
+
+    #[test]
+    fn instance_material() {
+        let shape = shared_sphere();
+        let mut red = instance_with_transform(&shape, translation(-5.0, 0.0, 0.0));
+        red.set_material(Material {
+            color: Color::new(1.0, 0.0, 0.0),
+            ..Material::default()
+        });
+        let blue = instance_with_transform(&shape, translation(-5.0, 0.0, 0.0));
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        let xs = red.intersect(&ray);
+        assert!(xs[0]
+            .object
+            .material()
+            .color
+            .is_equal_to(&Color::new(1.0, 0.0, 0.0)));
+        let xs = blue.intersect(&ray);
+        assert!(xs[0]
+            .object
+            .material()
+            .color
+            .is_equal_to(&Material::default().color));
+    }
+
+    #[test]
+    fn instance_nested() {
+        let inner: Arc<dyn Shape> = Arc::new(instance_with_transform(
+            &shared_sphere(),
+            translation(-5.0, 0.0, 0.0),
+        ));
+        let mut group = Group::new();
+        group.add_child(Box::new(instance_with_transform(
+            &inner,
+            scaling(2.0, 2.0, 2.0),
+        )));
+        let outer = instance_with_transform(
+            &(Arc::new(group) as Arc<dyn Shape>),
+            translation(0.0, 0.0, 10.0),
+        );
+        let ray = Ray::new(
+            Tuple::new_point(2.0, 0.0, 0.0),
+            Tuple::new_vector(-1.0, 0.0, 0.0),
+        );
+        let xs = outer.intersect(&ray);
+        assert!(xs.is_empty());
+
+        // The sphere has a radius of 2 and is centered at z = 10
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = outer.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 8.0);
+        assert!(std::ptr::addr_eq(xs[0].object, &outer));
+        let comps = xs[0].prepare_computations(&ray, &xs);
+        assert!(comps
+            .normalv
+            .is_equal_to(&Tuple::new_vector(0.0, 0.0, -1.0)));
+    }
+
+    #[test]
+    fn instance_bounds() {
+        let shape = shared_sphere();
+        let instance = instance_with_transform(&shape, scaling(2.0, 2.0, 2.0));
+        let bounds = instance.bounds();
+        assert!(bounds.min().is_equal_to(&Tuple::new_point(4.0, -1.0, -1.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(6.0, 1.0, 1.0)));
+    }
+}
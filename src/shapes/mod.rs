@@ -0,0 +1,458 @@
+use crate::intersection::Intersections;
+use crate::light::Material;
+use crate::matrix::{Matrix4, MatrixError};
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+pub mod bounds;
+pub mod cone;
+pub mod csg;
+pub mod disk;
+pub mod group;
+pub mod heightfield;
+pub mod instance;
+pub mod plane;
+pub mod smooth_triangle;
+pub mod sphere;
+pub mod torus;
+pub mod triangle;
+
+pub use bounds::BoundingBox;
+pub use cone::Cone;
+pub use csg::{Csg, CsgOperation};
+pub use disk::Disk;
+pub use group::Group;
+pub use heightfield::Heightfield;
+pub use instance::Instance;
+pub use plane::Plane;
+pub use smooth_triangle::SmoothTriangle;
+pub use sphere::Sphere;
+pub use torus::Torus;
+pub use triangle::Triangle;
+
+/// Properties shared by every shape, stored by the shape and exposed through the `Shape` trait.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        into = "SerializedShapeProperties",
+        try_from = "SerializedShapeProperties"
+    )
+)]
+pub struct ShapeProperties {
+    transform: Matrix4,
+    inverse_transform: Matrix4,
+    material: Material,
+    casts_shadow: bool,
+    parent: Option<Arc<ParentLink>>,
+}
+
+impl Default for ShapeProperties {
+    /// Create the properties of an untransformed shape with the default material that casts shadows.
+    fn default() -> Self {
+        Self {
+            transform: Matrix4::IDENTITY,
+            inverse_transform: Matrix4::IDENTITY,
+            material: Material::default(),
+            casts_shadow: true,
+            parent: None,
+        }
+    }
+}
+
+impl ShapeProperties {
+    /// Set the transform and its cached inverse.
+    /// Returns an error, leaving the properties unchanged, if the transform is not invertible.
+    fn set_transform(&mut self, transform: Matrix4) -> Result<(), MatrixError> {
+        self.inverse_transform = transform.inverse()?;
+        self.transform = transform;
+        Ok(())
+    }
+}
+
+/// Serialized form of `ShapeProperties`, without the inverse transform, which is computed again, or the
+/// link to a parent, which is made when the shape is added to one.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+struct SerializedShapeProperties {
+    transform: Matrix4,
+    material: Material,
+    casts_shadow: bool,
+}
+
+#[cfg(feature = "serde")]
+impl Default for SerializedShapeProperties {
+    fn default() -> Self {
+        ShapeProperties::default().into()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<ShapeProperties> for SerializedShapeProperties {
+    fn from(properties: ShapeProperties) -> Self {
+        Self {
+            transform: properties.transform,
+            material: properties.material,
+            casts_shadow: properties.casts_shadow,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerializedShapeProperties> for ShapeProperties {
+    type Error = MatrixError;
+
+    /// Fail if the transform is not invertible.
+    fn try_from(serialized: SerializedShapeProperties) -> Result<Self, MatrixError> {
+        let mut properties = ShapeProperties {
+            material: serialized.material,
+            casts_shadow: serialized.casts_shadow,
+            ..ShapeProperties::default()
+        };
+        properties.set_transform(serialized.transform)?;
+        Ok(properties)
+    }
+}
+
+/// Link from a shape to the shape containing it, such as a `Group`.
+///
+/// The link records the parent's inverse transform and the parent's own link, so the chain can be followed
+/// up to the world. It is shared by every child of the parent and rebuilt whenever the parent's transform
+/// or position in the hierarchy changes.
+#[derive(Debug)]
+pub struct ParentLink {
+    inverse_transform: Matrix4,
+    parent: Option<Arc<ParentLink>>,
+}
+
+impl ParentLink {
+    /// Create a link to a parent with the given properties.
+    pub fn new(properties: &ShapeProperties) -> Self {
+        Self {
+            inverse_transform: properties.inverse_transform,
+            parent: properties.parent.clone(),
+        }
+    }
+
+    /// Get the inverse of the parent's transform.
+    pub fn inverse_transform(&self) -> &Matrix4 {
+        &self.inverse_transform
+    }
+
+    /// Get the link from the parent to its own parent, if it has one.
+    pub fn parent(&self) -> Option<&Arc<ParentLink>> {
+        self.parent.as_ref()
+    }
+
+    /// Convert a point from world space to the parent's object space, walking down from the outermost parent.
+    pub fn world_to_object(&self, point: &Tuple) -> Tuple {
+        match &self.parent {
+            Some(parent) => self.inverse_transform * parent.world_to_object(point),
+            None => self.inverse_transform * point,
+        }
+    }
+
+    /// Convert a normal from the parent's object space to world space, walking up to the outermost parent.
+    pub fn normal_to_world(&self, normal: &Tuple) -> Tuple {
+        let normal = transform_normal(&self.inverse_transform, normal);
+        match &self.parent {
+            Some(parent) => parent.normal_to_world(&normal),
+            None => normal,
+        }
+    }
+}
+
+thread_local! {
+    /// Number of times `Shape::intersect` has been called on the current thread.
+    static INTERSECTION_TESTS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Get the number of ray-shape intersection tests performed on the current thread so far.
+/// Every call to `Shape::intersect` counts as one test, including calls on groups and their children,
+/// so a group whose bounds a ray misses costs a single test.
+pub fn intersection_tests() -> usize {
+    INTERSECTION_TESTS.with(Cell::get)
+}
+
+/// Transform a normal by the transpose of an inverse transform and normalize it.
+fn transform_normal(inverse_transform: &Matrix4, normal: &Tuple) -> Tuple {
+    let mut transformed = inverse_transform.transpose() * normal;
+    // Transforming by the transpose of a matrix with a translation can mess up w
+    transformed.w = 0.0;
+    transformed.normalize()
+}
+
+/// Geometry of the simplest shapes in object space, for renderers that cannot call `local_intersect`,
+/// such as the GPU backend.
+#[derive(Debug)]
+pub enum Primitive {
+    /// Unit sphere centered at the origin.
+    Sphere,
+    /// Plane spanning the x and z axes.
+    Plane,
+    /// Triangle with the given corners.
+    Triangle { p1: Tuple, p2: Tuple, p3: Tuple },
+}
+
+/// Object that can be placed in a world and intersected by rays.
+///
+/// Implementors only store a `ShapeProperties` and describe their geometry in object space with
+/// `local_intersect` and `local_normal_at`; conversion between world and object space is handled here.
+pub trait Shape: Debug + Send + Sync {
+    /// Get the properties shared by every shape.
+    fn properties(&self) -> &ShapeProperties;
+
+    /// Get a mutable reference to the properties shared by every shape.
+    fn properties_mut(&mut self) -> &mut ShapeProperties;
+
+    /// Intersect a ray given in object space with the shape.
+    fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_>;
+
+    /// Compute the surface normal at a point on the shape, both given in object space.
+    fn local_normal_at(&self, local_point: &Tuple) -> Tuple;
+
+    /// Compute the box enclosing the shape in object space.
+    fn bounds(&self) -> BoundingBox;
+
+    /// Get the transform from object space to world space.
+    fn transform(&self) -> &Matrix4 {
+        &self.properties().transform
+    }
+
+    /// Set the transform from object space to world space.
+    /// Returns an error, leaving the shape unchanged, if the transform is not invertible.
+    fn set_transform(&mut self, transform: Matrix4) -> Result<(), MatrixError> {
+        self.properties_mut().set_transform(transform)
+    }
+
+    /// Get the link to the shape containing this one, if it is part of a group.
+    fn parent(&self) -> Option<&Arc<ParentLink>> {
+        self.properties().parent.as_ref()
+    }
+
+    /// Set the link to the shape containing this one.
+    /// This is called by containers such as `Group` when the shape is added to them.
+    fn set_parent(&mut self, parent: Option<Arc<ParentLink>>) {
+        self.properties_mut().parent = parent;
+    }
+
+    /// Check whether another shape is this shape or, for containers such as `Group`, one of its descendants.
+    fn includes(&self, other: &dyn Shape) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
+
+    /// Subdivide containers such as `Group` into a bounding volume hierarchy, so that rays only test
+    /// children whose bounds they hit. Groups with at least `threshold` children are split.
+    /// Shapes that contain no other shapes are left unchanged.
+    fn divide(&mut self, _threshold: usize) {}
+
+    /// Describe the shape's geometry as a `Primitive`, if it is one.
+    fn primitive(&self) -> Option<Primitive> {
+        None
+    }
+
+    /// Get the material of the shape's surface.
+    fn material(&self) -> &Material {
+        &self.properties().material
+    }
+
+    /// Set the material of the shape's surface.
+    fn set_material(&mut self, material: Material) {
+        self.properties_mut().material = material;
+    }
+
+    /// Check whether the shape blocks light from reaching the shapes behind it.
+    fn casts_shadow(&self) -> bool {
+        self.properties().casts_shadow
+    }
+
+    /// Choose whether the shape blocks light from reaching the shapes behind it. Shapes that cast no
+    /// shadow, such as a glass pane or the surface of water, are still seen and lit themselves.
+    /// Only the shape that is hit counts, so a group's children keep their own setting.
+    fn set_casts_shadow(&mut self, casts_shadow: bool) {
+        self.properties_mut().casts_shadow = casts_shadow;
+    }
+
+    /// Intersect a ray given in world space with the shape.
+    fn intersect(&self, ray: &Ray) -> Intersections<'_> {
+        INTERSECTION_TESTS.with(|tests| tests.set(tests.get() + 1));
+        self.local_intersect(&ray.transform(&self.properties().inverse_transform))
+    }
+
+    /// Compute the surface normal at a point on the shape given in world space.
+    fn normal_at(&self, world_point: &Tuple) -> Tuple {
+        let local_point = self.world_to_object(world_point);
+        let local_normal = self.local_normal_at(&local_point);
+        self.normal_to_world(&local_normal)
+    }
+
+    /// Convert a point from world space to object space, passing through the space of every parent group.
+    fn world_to_object(&self, point: &Tuple) -> Tuple {
+        let properties = self.properties();
+        match &properties.parent {
+            Some(parent) => properties.inverse_transform * parent.world_to_object(point),
+            None => properties.inverse_transform * point,
+        }
+    }
+
+    /// Convert a normal from object space to world space using the transpose of the inverse transform,
+    /// then repeating the conversion for every parent group.
+    fn normal_to_world(&self, normal: &Tuple) -> Tuple {
+        let properties = self.properties();
+        let normal = transform_normal(&properties.inverse_transform, normal);
+        match &properties.parent {
+            Some(parent) => parent.normal_to_world(&normal),
+            None => normal,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::{rotation_z, scaling, translation};
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
+    use std::sync::Mutex;
+
+    /// Shape that records the object space ray it was intersected with.
+    /// The record is shared so it can still be read after the shape is moved into a group.
+    #[derive(Debug, Default)]
+    pub(crate) struct TestShape {
+        properties: ShapeProperties,
+        pub(crate) saved_ray: Arc<Mutex<Option<Ray>>>,
+    }
+
+    impl Shape for TestShape {
+        fn properties(&self) -> &ShapeProperties {
+            &self.properties
+        }
+
+        fn properties_mut(&mut self) -> &mut ShapeProperties {
+            &mut self.properties
+        }
+
+        fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_> {
+            *self.saved_ray.lock().unwrap() = Some(*local_ray);
+            Intersections::default()
+        }
+
+        fn local_normal_at(&self, local_point: &Tuple) -> Tuple {
+            Tuple::new_vector(local_point.x, local_point.y, local_point.z)
+        }
+
+        fn bounds(&self) -> BoundingBox {
+            BoundingBox::new(
+                Tuple::new_point(-1.0, -1.0, -1.0),
+                Tuple::new_point(1.0, 1.0, 1.0),
+            )
+        }
+    }
+
+    #[test]
+    fn shape_transform() {
+        let mut shape = TestShape::default();
+        assert!(shape.transform().is_equal_to(&Matrix4::IDENTITY));
+
+        let transform = translation(2.0, 3.0, 4.0);
+        shape.set_transform(transform).unwrap();
+        assert!(shape.transform().is_equal_to(&transform));
+    }
+
+    #[test]
+    fn shape_casts_shadow() {
+        let mut shape = TestShape::default();
+        assert!(shape.casts_shadow());
+        shape.set_casts_shadow(false);
+        assert!(!shape.casts_shadow());
+    }
+
+    #[test]
+    fn shape_set_transform_not_invertible() {
+        let mut shape = TestShape::default();
+        assert_eq!(
+            shape.set_transform(scaling(0.0, 1.0, 1.0)),
+            Err(MatrixError::NotInvertible)
+        );
+        assert!(shape.transform().is_equal_to(&Matrix4::IDENTITY));
+    }
+
+    #[test]
+    fn shape_material() {
+        let mut shape = TestShape::default();
+        assert_approx_eq!(shape.material().ambient, Material::default().ambient);
+
+        let material = Material {
+            ambient: 1.0,
+            ..Material::default()
+        };
+        shape.set_material(material);
+        assert_approx_eq!(shape.material().ambient, 1.0);
+    }
+
+    #[test]
+    fn shape_intersect_scaled() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let mut shape = TestShape::default();
+        shape.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        shape.intersect(&ray);
+        let saved_ray = shape.saved_ray.lock().unwrap();
+        let saved_ray = saved_ray.as_ref().unwrap();
+        assert!(saved_ray
+            .origin
+            .is_equal_to(&Tuple::new_point(0.0, 0.0, -2.5)));
+        assert!(saved_ray
+            .direction
+            .is_equal_to(&Tuple::new_vector(0.0, 0.0, 0.5)));
+    }
+
+    #[test]
+    fn shape_intersect_translated() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let mut shape = TestShape::default();
+        shape.set_transform(translation(5.0, 0.0, 0.0)).unwrap();
+        shape.intersect(&ray);
+        let saved_ray = shape.saved_ray.lock().unwrap();
+        let saved_ray = saved_ray.as_ref().unwrap();
+        assert!(saved_ray
+            .origin
+            .is_equal_to(&Tuple::new_point(-5.0, 0.0, -5.0)));
+        assert!(saved_ray
+            .direction
+            .is_equal_to(&Tuple::new_vector(0.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn shape_normal_at_translated() {
+        let mut shape = TestShape::default();
+        shape.set_transform(translation(0.0, 1.0, 0.0)).unwrap();
+        let normal = shape.normal_at(&Tuple::new_point(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+        assert!(normal.is_equal_to(&Tuple::new_vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2)));
+    }
+
+    #[test]
+    fn shape_normal_at_transformed() {
+        let mut shape = TestShape::default();
+        shape
+            .set_transform(scaling(1.0, 0.5, 1.0) * rotation_z(PI / 5.0))
+            .unwrap();
+        let normal = shape.normal_at(&Tuple::new_point(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+        // The book's values are rounded to five decimal places.
+        assert_approx_eq!(
+            normal,
+            Tuple::new_vector(0.0, 0.97014, -0.24254),
+            epsilon = 1e-5
+        );
+    }
+}
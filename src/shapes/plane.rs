@@ -0,0 +1,136 @@
+use super::{BoundingBox, Primitive, Shape, ShapeProperties};
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use crate::EPSILON;
+
+/// Infinite plane spanning the x and z axes of its object space.
+#[derive(Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Plane {
+    properties: ShapeProperties,
+}
+
+impl Plane {
+    /// Create a plane spanning the x and z axes with an identity transform.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Shape for Plane {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    /// A ray parallel to the plane never intersects it; otherwise it intersects it exactly once.
+    fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_> {
+        if local_ray.direction.y.abs() < EPSILON {
+            return Intersections::default();
+        }
+        let t = -local_ray.origin.y / local_ray.direction.y;
+        Intersections::new(vec![Intersection::new(t, self)])
+    }
+
+    /// The normal of a plane is the same everywhere.
+    fn local_normal_at(&self, _local_point: &Tuple) -> Tuple {
+        Tuple::new_vector(0.0, 1.0, 0.0)
+    }
+
+    fn primitive(&self) -> Option<Primitive> {
+        Some(Primitive::Plane)
+    }
+
+    /// The plane is infinite along x and z but has no thickness along y.
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Tuple::new_point(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Tuple::new_point(f64::INFINITY, 0.0, f64::INFINITY),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn plane_local_normal_at() {
+        let plane = Plane::new();
+        for point in [
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_point(10.0, 0.0, -10.0),
+            Tuple::new_point(-5.0, 0.0, 150.0),
+        ] {
+            assert!(plane
+                .local_normal_at(&point)
+                .is_equal_to(&Tuple::new_vector(0.0, 1.0, 0.0)));
+        }
+    }
+
+    #[test]
+    fn plane_local_intersect_parallel() {
+        let plane = Plane::new();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 10.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        assert!(plane.local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn plane_local_intersect_coplanar() {
+        let plane = Plane::new();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        assert!(plane.local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn plane_local_intersect_from_above() {
+        let plane = Plane::new();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 1.0, 0.0),
+            Tuple::new_vector(0.0, -1.0, 0.0),
+        );
+        let xs = plane.local_intersect(&ray);
+        assert_eq!(xs.len(), 1);
+        assert_approx_eq!(xs[0].t, 1.0);
+        assert!(std::ptr::addr_eq(xs[0].object, &plane));
+    }
+
+    #[test]
+    fn plane_local_intersect_from_below() {
+        let plane = Plane::new();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, -1.0, 0.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        let xs = plane.local_intersect(&ray);
+        assert_eq!(xs.len(), 1);
+        assert_approx_eq!(xs[0].t, 1.0);
+        assert!(std::ptr::addr_eq(xs[0].object, &plane));
+    }
+
+    #[test]
+    fn plane_bounds() {
+        let bounds = Plane::new().bounds();
+        assert_eq!(bounds.min().x, f64::NEG_INFINITY);
+        assert_approx_eq!(bounds.min().y, 0.0);
+        assert_eq!(bounds.min().z, f64::NEG_INFINITY);
+        assert_eq!(bounds.max().x, f64::INFINITY);
+        assert_approx_eq!(bounds.max().y, 0.0);
+        assert_eq!(bounds.max().z, f64::INFINITY);
+    }
+}
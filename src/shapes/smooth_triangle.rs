@@ -0,0 +1,130 @@
+use super::{BoundingBox, Shape, ShapeProperties, Triangle};
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+
+/// Triangle with a normal at each corner, blended across its surface so that meshes of triangles look
+/// smoothly curved rather than faceted.
+#[derive(Debug)]
+pub struct SmoothTriangle {
+    properties: ShapeProperties,
+    triangle: Triangle,
+    n1: Tuple,
+    n2: Tuple,
+    n3: Tuple,
+}
+
+impl SmoothTriangle {
+    /// Create a triangle from three points and the normals at each of them, with an identity transform.
+    pub fn new(p1: Tuple, p2: Tuple, p3: Tuple, n1: Tuple, n2: Tuple, n3: Tuple) -> Self {
+        Self {
+            properties: ShapeProperties::default(),
+            triangle: Triangle::new(p1, p2, p3),
+            n1,
+            n2,
+            n3,
+        }
+    }
+
+    /// Get the flat triangle spanned by the corners.
+    pub fn triangle(&self) -> &Triangle {
+        &self.triangle
+    }
+
+    /// Get the normal at the first corner.
+    pub fn n1(&self) -> &Tuple {
+        &self.n1
+    }
+
+    /// Get the normal at the second corner.
+    pub fn n2(&self) -> &Tuple {
+        &self.n2
+    }
+
+    /// Get the normal at the third corner.
+    pub fn n3(&self) -> &Tuple {
+        &self.n3
+    }
+}
+
+impl Shape for SmoothTriangle {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_> {
+        match self.triangle.intersection_distance(local_ray) {
+            Some(t) => Intersections::new(vec![Intersection::new(t, self)]),
+            None => Intersections::default(),
+        }
+    }
+
+    /// The corner normals are weighted by the barycentric coordinates of the point.
+    fn local_normal_at(&self, local_point: &Tuple) -> Tuple {
+        let (u, v) = self.triangle.barycentric(local_point);
+        ((self.n2 * u) + (self.n3 * v)) + (self.n1 * (1.0 - u - v))
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.triangle.bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    fn test_smooth_triangle() -> SmoothTriangle {
+        SmoothTriangle::new(
+            Tuple::new_point(0.0, 1.0, 0.0),
+            Tuple::new_point(-1.0, 0.0, 0.0),
+            Tuple::new_point(1.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+            Tuple::new_vector(-1.0, 0.0, 0.0),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn smooth_triangle_new() {
+        let triangle = test_smooth_triangle();
+        assert!(triangle
+            .triangle()
+            .p1()
+            .is_equal_to(&Tuple::new_point(0.0, 1.0, 0.0)));
+        assert!(triangle.n1().is_equal_to(&Tuple::new_vector(0.0, 1.0, 0.0)));
+        assert!(triangle
+            .n2()
+            .is_equal_to(&Tuple::new_vector(-1.0, 0.0, 0.0)));
+        assert!(triangle.n3().is_equal_to(&Tuple::new_vector(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn smooth_triangle_local_intersect() {
+        let triangle = test_smooth_triangle();
+        let ray = Ray::new(
+            Tuple::new_point(-0.2, 0.3, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = triangle.local_intersect(&ray);
+        assert_eq!(xs.len(), 1);
+        assert_approx_eq!(xs[0].t, 2.0);
+        assert!(std::ptr::addr_eq(xs[0].object, &triangle));
+    }
+
+    #[test]
+    fn smooth_triangle_normal_at() {
+        // The book's hit at u = 0.45, v = 0.25 lies at (-0.2, 0.3, 0)
+        let triangle = test_smooth_triangle();
+        let normal = triangle.normal_at(&Tuple::new_point(-0.2, 0.3, 0.0));
+        // The book's values are rounded to four and five decimal places.
+        assert_approx_eq!(normal.x, -0.5547, epsilon = 1e-4);
+        assert_approx_eq!(normal.y, 0.83205, epsilon = 1e-5);
+        assert_approx_eq!(normal.z, 0.0);
+    }
+}
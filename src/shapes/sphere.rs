@@ -0,0 +1,240 @@
+use super::{BoundingBox, Primitive, Shape, ShapeProperties};
+use crate::intersection::{Intersection, Intersections};
+use crate::math::roots::solve_quadratic;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+
+/// Unit sphere centered at the origin of its object space.
+#[derive(Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Sphere {
+    properties: ShapeProperties,
+}
+
+impl Sphere {
+    /// Create a unit sphere centered at the origin with an identity transform.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a unit sphere made of `Material::glass`, as used throughout the book's refraction tests.
+    pub fn glass() -> Self {
+        let mut sphere = Self::new();
+        sphere.set_material(crate::light::Material::glass());
+        sphere
+    }
+}
+
+impl Shape for Sphere {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    /// Intersect a ray with the sphere, returning the distances along the ray at which it
+    /// enters and exits the sphere. A ray tangent to the sphere produces two equal intersections.
+    fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_> {
+        let sphere_to_ray = local_ray.origin - Tuple::new_point(0.0, 0.0, 0.0);
+        let a = local_ray.direction.dot_product(&local_ray.direction);
+        let b = 2.0 * local_ray.direction.dot_product(&sphere_to_ray);
+        let c = sphere_to_ray.dot_product(&sphere_to_ray) - 1.0;
+        Intersections::new(
+            solve_quadratic(a, b, c)
+                .into_iter()
+                .map(|t| Intersection::new(t, self))
+                .collect(),
+        )
+    }
+
+    /// The normal of a unit sphere points from its center to the point on its surface.
+    fn local_normal_at(&self, local_point: &Tuple) -> Tuple {
+        local_point - Tuple::new_point(0.0, 0.0, 0.0)
+    }
+
+    fn primitive(&self) -> Option<Primitive> {
+        Some(Primitive::Sphere)
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Tuple::new_point(-1.0, -1.0, -1.0),
+            Tuple::new_point(1.0, 1.0, 1.0),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::{rotation_z, scaling, translation};
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+    #[test]
+    fn sphere_intersect_two_points() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let sphere = Sphere::new();
+        let xs = sphere.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 4.0);
+        assert_approx_eq!(xs[1].t, 6.0);
+    }
+
+    #[test]
+    fn sphere_intersect_tangent() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 1.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let sphere = Sphere::new();
+        let xs = sphere.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 5.0);
+        assert_approx_eq!(xs[1].t, 5.0);
+    }
+
+    #[test]
+    fn sphere_intersect_miss() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 2.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let sphere = Sphere::new();
+        assert_eq!(sphere.intersect(&ray).len(), 0);
+    }
+
+    #[test]
+    fn sphere_intersect_from_inside() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let sphere = Sphere::new();
+        let xs = sphere.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, -1.0);
+        assert_approx_eq!(xs[1].t, 1.0);
+    }
+
+    #[test]
+    fn sphere_intersect_behind_ray() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let sphere = Sphere::new();
+        let xs = sphere.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, -6.0);
+        assert_approx_eq!(xs[1].t, -4.0);
+    }
+
+    #[test]
+    fn sphere_intersect_sets_object() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let sphere = Sphere::new();
+        let xs = sphere.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert!(std::ptr::addr_eq(xs[0].object, &sphere));
+        assert!(std::ptr::addr_eq(xs[1].object, &sphere));
+    }
+
+    #[test]
+    fn sphere_intersect_scaled() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let mut sphere = Sphere::new();
+        sphere.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let xs = sphere.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 3.0);
+        assert_approx_eq!(xs[1].t, 7.0);
+    }
+
+    #[test]
+    fn sphere_intersect_translated() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let mut sphere = Sphere::new();
+        sphere.set_transform(translation(5.0, 0.0, 0.0)).unwrap();
+        assert_eq!(sphere.intersect(&ray).len(), 0);
+    }
+
+    #[test]
+    fn sphere_normal_at() {
+        let sphere = Sphere::new();
+        assert!(sphere
+            .normal_at(&Tuple::new_point(1.0, 0.0, 0.0))
+            .is_equal_to(&Tuple::new_vector(1.0, 0.0, 0.0)));
+        assert!(sphere
+            .normal_at(&Tuple::new_point(0.0, 1.0, 0.0))
+            .is_equal_to(&Tuple::new_vector(0.0, 1.0, 0.0)));
+        assert!(sphere
+            .normal_at(&Tuple::new_point(0.0, 0.0, 1.0))
+            .is_equal_to(&Tuple::new_vector(0.0, 0.0, 1.0)));
+
+        let value = 3.0_f64.sqrt() / 3.0;
+        let normal = sphere.normal_at(&Tuple::new_point(value, value, value));
+        assert!(normal.is_equal_to(&Tuple::new_vector(value, value, value)));
+        assert!(normal.is_vector());
+        assert!(normal.is_equal_to(&normal.normalize()));
+    }
+
+    #[test]
+    fn sphere_normal_at_translated() {
+        let mut sphere = Sphere::new();
+        sphere.set_transform(translation(0.0, 1.0, 0.0)).unwrap();
+        let normal = sphere.normal_at(&Tuple::new_point(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+        assert!(normal.is_equal_to(&Tuple::new_vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2)));
+        assert!(normal.is_vector());
+    }
+
+    #[test]
+    fn sphere_normal_at_transformed() {
+        let mut sphere = Sphere::new();
+        sphere
+            .set_transform(scaling(1.0, 0.5, 1.0) * rotation_z(PI / 5.0))
+            .unwrap();
+        let normal = sphere.normal_at(&Tuple::new_point(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+        // The book's values are rounded to five decimal places.
+        assert_approx_eq!(normal.x, 0.0);
+        assert_approx_eq!(normal.y, 0.97014, epsilon = 1e-5);
+        assert_approx_eq!(normal.z, -0.24254, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn sphere_glass() {
+        let sphere = Sphere::glass();
+        assert!(sphere
+            .transform()
+            .is_equal_to(&crate::matrix::Matrix4::IDENTITY));
+        assert_approx_eq!(sphere.material().transparency, 1.0);
+        assert_approx_eq!(sphere.material().refractive_index, 1.5);
+    }
+
+    #[test]
+    fn sphere_bounds() {
+        let bounds = Sphere::new().bounds();
+        assert!(bounds
+            .min()
+            .is_equal_to(&Tuple::new_point(-1.0, -1.0, -1.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(1.0, 1.0, 1.0)));
+    }
+}
@@ -0,0 +1,177 @@
+use super::{BoundingBox, Shape, ShapeProperties};
+use crate::intersection::{Intersection, Intersections};
+use crate::math::roots::solve_quartic;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+
+/// Torus lying in the xz plane of its object space, centered at the origin.
+/// `major_radius` is the distance from the origin to the center of the tube, and `minor_radius` is the
+/// radius of the tube.
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Torus {
+    properties: ShapeProperties,
+    pub major_radius: f64,
+    pub minor_radius: f64,
+}
+
+impl Default for Torus {
+    /// Create a torus with a major radius of 1 and a minor radius of 0.25.
+    fn default() -> Self {
+        Self::new(1.0, 0.25)
+    }
+}
+
+impl Torus {
+    /// Create a torus with the given radii and an identity transform.
+    pub fn new(major_radius: f64, minor_radius: f64) -> Self {
+        Self {
+            properties: ShapeProperties::default(),
+            major_radius,
+            minor_radius,
+        }
+    }
+}
+
+impl Shape for Torus {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    /// Intersect a ray with the torus by solving the quartic equation of its surface,
+    /// `(x² + y² + z² + R² - r²)² = 4R²(x² + z²)`, along the ray.
+    fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_> {
+        // Solve from the point on the ray closest to the torus' center, keeping the coefficients small
+        // for rays starting far away
+        let direction = &local_ray.direction;
+        let length_squared = direction.dot_product(direction);
+        let shift = -(local_ray.origin - Tuple::new_point(0.0, 0.0, 0.0)).dot_product(direction)
+            / length_squared;
+        let origin = local_ray.position(shift);
+
+        let major_squared = self.major_radius * self.major_radius;
+        let from_center = origin - Tuple::new_point(0.0, 0.0, 0.0);
+        let e = from_center.dot_product(&from_center) + major_squared
+            - self.minor_radius * self.minor_radius;
+        let f = from_center.dot_product(direction);
+        let four_major_squared = 4.0 * major_squared;
+        let roots = solve_quartic(
+            length_squared * length_squared,
+            4.0 * length_squared * f,
+            2.0 * length_squared * e + 4.0 * f * f
+                - four_major_squared * (direction.x * direction.x + direction.z * direction.z),
+            4.0 * f * e
+                - 2.0 * four_major_squared * (origin.x * direction.x + origin.z * direction.z),
+            e * e - four_major_squared * (origin.x * origin.x + origin.z * origin.z),
+        );
+        Intersections::new(
+            roots
+                .into_iter()
+                .map(|t| Intersection::new(t + shift, self))
+                .collect(),
+        )
+    }
+
+    /// The normal is the gradient of the torus' equation at the point.
+    fn local_normal_at(&self, local_point: &Tuple) -> Tuple {
+        let radii = self.major_radius * self.major_radius + self.minor_radius * self.minor_radius;
+        let distance = local_point.x * local_point.x
+            + local_point.y * local_point.y
+            + local_point.z * local_point.z;
+        let across = distance - radii;
+        let along = distance + self.major_radius * self.major_radius
+            - self.minor_radius * self.minor_radius;
+        Tuple::new_vector(
+            local_point.x * across,
+            local_point.y * along,
+            local_point.z * across,
+        )
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let outer = self.major_radius + self.minor_radius;
+        BoundingBox::new(
+            Tuple::new_point(-outer, -self.minor_radius, -outer),
+            Tuple::new_point(outer, self.minor_radius, outer),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn torus_local_intersect() {
+        let torus = Torus::new(1.0, 0.25);
+        let cases = [
+            // Through the hole, crossing the tube on both sides
+            (
+                (-5.0, 0.0, 0.0),
+                (1.0, 0.0, 0.0),
+                vec![3.75, 4.25, 5.75, 6.25],
+            ),
+            // Straight down through the tube
+            ((1.0, 5.0, 0.0), (0.0, -1.0, 0.0), vec![4.75, 5.25]),
+            // Down through the hole
+            ((0.0, 5.0, 0.0), (0.0, -1.0, 0.0), vec![]),
+            // Along the top of the tube, touching it twice
+            ((-5.0, 0.25, 0.0), (1.0, 0.0, 0.0), vec![4.0, 4.0, 6.0, 6.0]),
+        ];
+        for ((ox, oy, oz), (dx, dy, dz), expected) in cases {
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), Tuple::new_vector(dx, dy, dz));
+            let xs = torus.local_intersect(&ray);
+            assert_eq!(xs.len(), expected.len(), "({ox}, {oy}, {oz})");
+            for (intersection, t) in xs.iter().zip(expected) {
+                assert_approx_eq!(intersection.t, t, epsilon = 1e-4, "({ox}, {oy}, {oz})");
+            }
+        }
+    }
+
+    #[test]
+    fn torus_local_intersect_far_away() {
+        let torus = Torus::new(1.0, 0.25);
+        let ray = Ray::new(
+            Tuple::new_point(-1e4, 0.0, 0.0),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+        );
+        let xs = torus.local_intersect(&ray);
+        assert_eq!(xs.len(), 4);
+        assert_approx_eq!(xs[0].t, 1e4 - 1.25, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn torus_local_normal_at() {
+        let torus = Torus::new(1.0, 0.25);
+        let cases = [
+            ((1.25, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            ((0.75, 0.0, 0.0), (-1.0, 0.0, 0.0)),
+            ((0.0, 0.25, 1.0), (0.0, 1.0, 0.0)),
+            ((0.0, -0.25, -1.0), (0.0, -1.0, 0.0)),
+        ];
+        for ((px, py, pz), (nx, ny, nz)) in cases {
+            let normal = torus.local_normal_at(&Tuple::new_point(px, py, pz));
+            assert!(normal
+                .normalize()
+                .is_equal_to(&Tuple::new_vector(nx, ny, nz)));
+        }
+    }
+
+    #[test]
+    fn torus_bounds() {
+        let bounds = Torus::new(2.0, 0.5).bounds();
+        assert!(bounds
+            .min()
+            .is_equal_to(&Tuple::new_point(-2.5, -0.5, -2.5)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(2.5, 0.5, 2.5)));
+    }
+}
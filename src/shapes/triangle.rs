@@ -0,0 +1,285 @@
+use super::{BoundingBox, Primitive, Shape, ShapeProperties};
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use crate::EPSILON;
+
+/// Flat triangle defined by three points in its object space, the building block of meshes.
+/// The edge vectors and normal are computed once when the triangle is created.
+#[derive(Debug)]
+pub struct Triangle {
+    properties: ShapeProperties,
+    p1: Tuple,
+    p2: Tuple,
+    p3: Tuple,
+    e1: Tuple,
+    e2: Tuple,
+    normal: Tuple,
+}
+
+impl Triangle {
+    /// Create a triangle from three points with an identity transform.
+    pub fn new(p1: Tuple, p2: Tuple, p3: Tuple) -> Self {
+        let e1 = p2 - p1;
+        let e2 = p3 - p1;
+        let normal = e2.cross_product(&e1).normalize();
+        Self {
+            properties: ShapeProperties::default(),
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal,
+        }
+    }
+
+    /// Get the first corner of the triangle.
+    pub fn p1(&self) -> &Tuple {
+        &self.p1
+    }
+
+    /// Get the second corner of the triangle.
+    pub fn p2(&self) -> &Tuple {
+        &self.p2
+    }
+
+    /// Get the third corner of the triangle.
+    pub fn p3(&self) -> &Tuple {
+        &self.p3
+    }
+
+    /// Get the edge from the first to the second corner.
+    pub fn e1(&self) -> &Tuple {
+        &self.e1
+    }
+
+    /// Get the edge from the first to the third corner.
+    pub fn e2(&self) -> &Tuple {
+        &self.e2
+    }
+
+    /// Get the normal of the triangle, which is the same everywhere on it.
+    pub fn normal(&self) -> &Tuple {
+        &self.normal
+    }
+
+    /// Find the distance along a ray in the triangle's object space at which it hits the triangle.
+    pub(crate) fn intersection_distance(&self, local_ray: &Ray) -> Option<f64> {
+        Self::intersection_distance_with_edges(local_ray, &self.p1, &self.e1, &self.e2)
+    }
+
+    /// Find the distance along a ray at which it hits the triangle with corner `p1` and edges `e1` and `e2`
+    /// leading from it to the other corners, for shapes made of triangles they do not store.
+    ///
+    /// Möller–Trumbore intersection: the ray misses if it is parallel to the triangle or if the
+    /// barycentric coordinates of the hit fall outside the triangle.
+    pub(crate) fn intersection_distance_with_edges(
+        ray: &Ray,
+        p1: &Tuple,
+        e1: &Tuple,
+        e2: &Tuple,
+    ) -> Option<f64> {
+        let dir_cross_e2 = ray.direction.cross_product(e2);
+        let det = e1.dot_product(&dir_cross_e2);
+        if det.abs() < EPSILON {
+            return None;
+        }
+
+        let f = 1.0 / det;
+        let p1_to_origin = ray.origin - p1;
+        let u = f * p1_to_origin.dot_product(&dir_cross_e2);
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let origin_cross_e1 = p1_to_origin.cross_product(e1);
+        let v = f * ray.direction.dot_product(&origin_cross_e1);
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        Some(f * e2.dot_product(&origin_cross_e1))
+    }
+
+    /// Compute the barycentric coordinates `(u, v)` of a point on the triangle, such that the point is
+    /// `p1 + u * e1 + v * e2`.
+    pub fn barycentric(&self, point: &Tuple) -> (f64, f64) {
+        let p1_to_point = point - self.p1;
+        let e1_e1 = self.e1.dot_product(&self.e1);
+        let e1_e2 = self.e1.dot_product(&self.e2);
+        let e2_e2 = self.e2.dot_product(&self.e2);
+        let point_e1 = p1_to_point.dot_product(&self.e1);
+        let point_e2 = p1_to_point.dot_product(&self.e2);
+        let denominator = e1_e1 * e2_e2 - e1_e2 * e1_e2;
+        (
+            (e2_e2 * point_e1 - e1_e2 * point_e2) / denominator,
+            (e1_e1 * point_e2 - e1_e2 * point_e1) / denominator,
+        )
+    }
+}
+
+impl Shape for Triangle {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, local_ray: &Ray) -> Intersections<'_> {
+        match self.intersection_distance(local_ray) {
+            Some(t) => Intersections::new(vec![Intersection::new(t, self)]),
+            None => Intersections::default(),
+        }
+    }
+
+    /// The normal of a triangle is the same everywhere.
+    fn local_normal_at(&self, _local_point: &Tuple) -> Tuple {
+        self.normal
+    }
+
+    fn primitive(&self) -> Option<Primitive> {
+        Some(Primitive::Triangle {
+            p1: self.p1,
+            p2: self.p2,
+            p3: self.p3,
+        })
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = BoundingBox::default();
+        bounds.add_point(&self.p1);
+        bounds.add_point(&self.p2);
+        bounds.add_point(&self.p3);
+        bounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    fn test_triangle() -> Triangle {
+        Triangle::new(
+            Tuple::new_point(0.0, 1.0, 0.0),
+            Tuple::new_point(-1.0, 0.0, 0.0),
+            Tuple::new_point(1.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn triangle_new() {
+        let triangle = test_triangle();
+        assert!(triangle.p1().is_equal_to(&Tuple::new_point(0.0, 1.0, 0.0)));
+        assert!(triangle.p2().is_equal_to(&Tuple::new_point(-1.0, 0.0, 0.0)));
+        assert!(triangle.p3().is_equal_to(&Tuple::new_point(1.0, 0.0, 0.0)));
+        assert!(triangle
+            .e1()
+            .is_equal_to(&Tuple::new_vector(-1.0, -1.0, 0.0)));
+        assert!(triangle
+            .e2()
+            .is_equal_to(&Tuple::new_vector(1.0, -1.0, 0.0)));
+        assert!(triangle
+            .normal()
+            .is_equal_to(&Tuple::new_vector(0.0, 0.0, -1.0)));
+    }
+
+    #[test]
+    fn triangle_local_normal_at() {
+        let triangle = test_triangle();
+        for point in [
+            Tuple::new_point(0.0, 0.5, 0.0),
+            Tuple::new_point(-0.5, 0.75, 0.0),
+            Tuple::new_point(0.5, 0.25, 0.0),
+        ] {
+            assert!(triangle
+                .local_normal_at(&point)
+                .is_equal_to(triangle.normal()));
+        }
+    }
+
+    #[test]
+    fn triangle_local_intersect_parallel() {
+        let triangle = test_triangle();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, -1.0, -2.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        assert!(triangle.local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn triangle_local_intersect_misses_p1_p3_edge() {
+        let triangle = test_triangle();
+        let ray = Ray::new(
+            Tuple::new_point(1.0, 1.0, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        assert!(triangle.local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn triangle_local_intersect_misses_p1_p2_edge() {
+        let triangle = test_triangle();
+        let ray = Ray::new(
+            Tuple::new_point(-1.0, 1.0, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        assert!(triangle.local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn triangle_local_intersect_misses_p2_p3_edge() {
+        let triangle = test_triangle();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, -1.0, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        assert!(triangle.local_intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn triangle_local_intersect() {
+        let triangle = test_triangle();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.5, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = triangle.local_intersect(&ray);
+        assert_eq!(xs.len(), 1);
+        assert_approx_eq!(xs[0].t, 2.0);
+    }
+
+    #[test]
+    fn triangle_barycentric() {
+        let triangle = test_triangle();
+        let cases = [
+            (Tuple::new_point(0.0, 1.0, 0.0), (0.0, 0.0)),
+            (Tuple::new_point(-1.0, 0.0, 0.0), (1.0, 0.0)),
+            (Tuple::new_point(1.0, 0.0, 0.0), (0.0, 1.0)),
+            (Tuple::new_point(-0.2, 0.3, 0.0), (0.45, 0.25)),
+        ];
+        for (point, (u, v)) in cases {
+            let barycentric = triangle.barycentric(&point);
+            assert_approx_eq!(barycentric.0, u);
+            assert_approx_eq!(barycentric.1, v);
+        }
+    }
+
+    #[test]
+    fn triangle_bounds() {
+        let triangle = Triangle::new(
+            Tuple::new_point(-3.0, 7.0, 2.0),
+            Tuple::new_point(6.0, 2.0, -4.0),
+            Tuple::new_point(2.0, -1.0, -1.0),
+        );
+        let bounds = triangle.bounds();
+        assert!(bounds
+            .min()
+            .is_equal_to(&Tuple::new_point(-3.0, -1.0, -4.0)));
+        assert!(bounds.max().is_equal_to(&Tuple::new_point(6.0, 7.0, 2.0)));
+    }
+}
@@ -0,0 +1,106 @@
+use super::{rotation_x, rotation_y, rotation_z, scaling, shearing, translation};
+use crate::matrix::Matrix4;
+
+/// Builder that composes transformation matrices in the order they are read.
+/// For example, `Transform::identity().rotate_x(r).scale(5.0, 5.0, 5.0)` rotates and then scales,
+/// without having to reverse the order of matrix multiplication by hand.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Transform {
+    matrix: Matrix4,
+}
+
+impl Transform {
+    /// Start a transformation that does nothing.
+    pub fn identity() -> Self {
+        Self {
+            matrix: Matrix4::IDENTITY,
+        }
+    }
+
+    /// Apply a translation after the transformations so far.
+    pub fn translate(self, x: f64, y: f64, z: f64) -> Self {
+        self.then(translation(x, y, z))
+    }
+
+    /// Apply a scaling after the transformations so far.
+    pub fn scale(self, x: f64, y: f64, z: f64) -> Self {
+        self.then(scaling(x, y, z))
+    }
+
+    /// Apply a rotation around the x axis after the transformations so far.
+    pub fn rotate_x(self, radians: f64) -> Self {
+        self.then(rotation_x(radians))
+    }
+
+    /// Apply a rotation around the y axis after the transformations so far.
+    pub fn rotate_y(self, radians: f64) -> Self {
+        self.then(rotation_y(radians))
+    }
+
+    /// Apply a rotation around the z axis after the transformations so far.
+    pub fn rotate_z(self, radians: f64) -> Self {
+        self.then(rotation_z(radians))
+    }
+
+    /// Apply a shearing after the transformations so far.
+    pub fn shear(self, x_y: f64, x_z: f64, y_x: f64, y_z: f64, z_x: f64, z_y: f64) -> Self {
+        self.then(shearing(x_y, x_z, y_x, y_z, z_x, z_y))
+    }
+
+    /// Apply an arbitrary transformation matrix after the transformations so far.
+    pub fn then(self, matrix: Matrix4) -> Self {
+        Self {
+            matrix: matrix * self.matrix,
+        }
+    }
+
+    /// Get the composed transformation matrix.
+    pub fn matrix(&self) -> Matrix4 {
+        self.matrix
+    }
+}
+
+impl From<Transform> for Matrix4 {
+    fn from(transform: Transform) -> Self {
+        transform.matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tuple::Tuple;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn transform_identity() {
+        assert!(Transform::identity()
+            .matrix()
+            .is_equal_to(&Matrix4::IDENTITY));
+    }
+
+    #[test]
+    fn transform_fluent_chaining() {
+        let transform = Transform::identity()
+            .rotate_x(FRAC_PI_2)
+            .scale(5.0, 5.0, 5.0)
+            .translate(10.0, 5.0, 7.0);
+        let expected = translation(10.0, 5.0, 7.0) * scaling(5.0, 5.0, 5.0) * rotation_x(FRAC_PI_2);
+        assert!(transform.matrix().is_equal_to(&expected));
+
+        let point = Tuple::new_point(1.0, 0.0, 1.0);
+        let matrix: Matrix4 = transform.into();
+        assert!((matrix * point).is_equal_to(&Tuple::new_point(15.0, 0.0, 7.0)));
+    }
+
+    #[test]
+    fn transform_fluent_rotations_and_shearing() {
+        let transform = Transform::identity()
+            .rotate_y(FRAC_PI_2)
+            .rotate_z(FRAC_PI_2)
+            .shear(1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        let expected =
+            shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0) * rotation_z(FRAC_PI_2) * rotation_y(FRAC_PI_2);
+        assert!(transform.matrix().is_equal_to(&expected));
+    }
+}
@@ -0,0 +1,246 @@
+use crate::matrix::Matrix4;
+use crate::tuple::Tuple;
+
+pub mod builder;
+
+pub use builder::Transform;
+
+/// Create a matrix that translates points by `(x, y, z)`. Vectors are unaffected.
+pub fn translation(x: f64, y: f64, z: f64) -> Matrix4 {
+    Matrix4::new([
+        [1.0, 0.0, 0.0, x],
+        [0.0, 1.0, 0.0, y],
+        [0.0, 0.0, 1.0, z],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Create a matrix that scales tuples by `(x, y, z)`.
+pub fn scaling(x: f64, y: f64, z: f64) -> Matrix4 {
+    Matrix4::new([
+        [x, 0.0, 0.0, 0.0],
+        [0.0, y, 0.0, 0.0],
+        [0.0, 0.0, z, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Create a matrix that rotates tuples around the x axis by `radians`.
+pub fn rotation_x(radians: f64) -> Matrix4 {
+    let (sin, cos) = radians.sin_cos();
+    Matrix4::new([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, cos, -sin, 0.0],
+        [0.0, sin, cos, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Create a matrix that rotates tuples around the y axis by `radians`.
+pub fn rotation_y(radians: f64) -> Matrix4 {
+    let (sin, cos) = radians.sin_cos();
+    Matrix4::new([
+        [cos, 0.0, sin, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [-sin, 0.0, cos, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Create a matrix that rotates tuples around the z axis by `radians`.
+pub fn rotation_z(radians: f64) -> Matrix4 {
+    let (sin, cos) = radians.sin_cos();
+    Matrix4::new([
+        [cos, -sin, 0.0, 0.0],
+        [sin, cos, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Create a matrix that shears tuples, moving each component in proportion to the other two.
+/// For example, `x_y` moves x in proportion to y.
+pub fn shearing(x_y: f64, x_z: f64, y_x: f64, y_z: f64, z_x: f64, z_y: f64) -> Matrix4 {
+    Matrix4::new([
+        [1.0, x_y, x_z, 0.0],
+        [y_x, 1.0, y_z, 0.0],
+        [z_x, z_y, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Create a matrix that orients the world relative to an eye at the point `from` looking at the point `to`.
+/// `up` is a vector that roughly points upward, which does not need to be perpendicular to the line of sight.
+pub fn view_transform(from: &Tuple, to: &Tuple, up: &Tuple) -> Matrix4 {
+    let forward = (to - from).normalize();
+    let left = forward.cross_product(&up.normalize());
+    let true_up = left.cross_product(&forward);
+    let orientation = Matrix4::new([
+        [left.x, left.y, left.z, 0.0],
+        [true_up.x, true_up.y, true_up.z, 0.0],
+        [-forward.x, -forward.y, -forward.z, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+    orientation * translation(-from.x, -from.y, -from.z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, SQRT_2};
+
+    #[test]
+    fn transform_translation() {
+        let transform = translation(5.0, -3.0, 2.0);
+        let point = Tuple::new_point(-3.0, 4.0, 5.0);
+        assert!((transform * point).is_equal_to(&Tuple::new_point(2.0, 1.0, 7.0)));
+
+        let inverse = transform.inverse().unwrap();
+        assert!((inverse * point).is_equal_to(&Tuple::new_point(-8.0, 7.0, 3.0)));
+
+        let vector = Tuple::new_vector(-3.0, 4.0, 5.0);
+        assert!((transform * vector).is_equal_to(&vector));
+    }
+
+    #[test]
+    fn transform_scaling() {
+        let transform = scaling(2.0, 3.0, 4.0);
+        let point = Tuple::new_point(-4.0, 6.0, 8.0);
+        assert!((transform * point).is_equal_to(&Tuple::new_point(-8.0, 18.0, 32.0)));
+
+        let vector = Tuple::new_vector(-4.0, 6.0, 8.0);
+        assert!((transform * vector).is_equal_to(&Tuple::new_vector(-8.0, 18.0, 32.0)));
+
+        let inverse = transform.inverse().unwrap();
+        assert!((inverse * vector).is_equal_to(&Tuple::new_vector(-2.0, 2.0, 2.0)));
+
+        // Reflection is scaling by a negative value
+        let reflection = scaling(-1.0, 1.0, 1.0);
+        let point = Tuple::new_point(2.0, 3.0, 4.0);
+        assert!((reflection * point).is_equal_to(&Tuple::new_point(-2.0, 3.0, 4.0)));
+    }
+
+    #[test]
+    fn transform_rotation_x() {
+        let point = Tuple::new_point(0.0, 1.0, 0.0);
+        let half_quarter = rotation_x(FRAC_PI_4);
+        let full_quarter = rotation_x(FRAC_PI_2);
+        assert!((half_quarter * point).is_equal_to(&Tuple::new_point(
+            0.0,
+            SQRT_2 / 2.0,
+            SQRT_2 / 2.0
+        )));
+        assert!((full_quarter * point).is_equal_to(&Tuple::new_point(0.0, 0.0, 1.0)));
+
+        // The inverse rotates in the opposite direction
+        let inverse = half_quarter.inverse().unwrap();
+        assert!((inverse * point).is_equal_to(&Tuple::new_point(0.0, SQRT_2 / 2.0, -SQRT_2 / 2.0)));
+    }
+
+    #[test]
+    fn transform_rotation_y() {
+        let point = Tuple::new_point(0.0, 0.0, 1.0);
+        let half_quarter = rotation_y(FRAC_PI_4);
+        let full_quarter = rotation_y(FRAC_PI_2);
+        assert!((half_quarter * point).is_equal_to(&Tuple::new_point(
+            SQRT_2 / 2.0,
+            0.0,
+            SQRT_2 / 2.0
+        )));
+        assert!((full_quarter * point).is_equal_to(&Tuple::new_point(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn transform_rotation_z() {
+        let point = Tuple::new_point(0.0, 1.0, 0.0);
+        let half_quarter = rotation_z(FRAC_PI_4);
+        let full_quarter = rotation_z(FRAC_PI_2);
+        assert!((half_quarter * point).is_equal_to(&Tuple::new_point(
+            -SQRT_2 / 2.0,
+            SQRT_2 / 2.0,
+            0.0
+        )));
+        assert!((full_quarter * point).is_equal_to(&Tuple::new_point(-1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn transform_shearing() {
+        let point = Tuple::new_point(2.0, 3.0, 4.0);
+        let cases = [
+            (shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0), (5.0, 3.0, 4.0)),
+            (shearing(0.0, 1.0, 0.0, 0.0, 0.0, 0.0), (6.0, 3.0, 4.0)),
+            (shearing(0.0, 0.0, 1.0, 0.0, 0.0, 0.0), (2.0, 5.0, 4.0)),
+            (shearing(0.0, 0.0, 0.0, 1.0, 0.0, 0.0), (2.0, 7.0, 4.0)),
+            (shearing(0.0, 0.0, 0.0, 0.0, 1.0, 0.0), (2.0, 3.0, 6.0)),
+            (shearing(0.0, 0.0, 0.0, 0.0, 0.0, 1.0), (2.0, 3.0, 7.0)),
+        ];
+        for (transform, (x, y, z)) in cases {
+            assert!((transform * point).is_equal_to(&Tuple::new_point(x, y, z)));
+        }
+    }
+
+    #[test]
+    fn transform_chained() {
+        let point = Tuple::new_point(1.0, 0.0, 1.0);
+        let a = rotation_x(FRAC_PI_2);
+        let b = scaling(5.0, 5.0, 5.0);
+        let c = translation(10.0, 5.0, 7.0);
+
+        // Individual transformations are applied in sequence
+        let point2 = a * point;
+        assert!(point2.is_equal_to(&Tuple::new_point(1.0, -1.0, 0.0)));
+        let point3 = b * point2;
+        assert!(point3.is_equal_to(&Tuple::new_point(5.0, -5.0, 0.0)));
+        let point4 = c * point3;
+        assert!(point4.is_equal_to(&Tuple::new_point(15.0, 0.0, 7.0)));
+
+        // Chained transformations must be applied in reverse order
+        let transform = c * b * a;
+        assert!((transform * point).is_equal_to(&Tuple::new_point(15.0, 0.0, 7.0)));
+    }
+
+    #[test]
+    fn transform_view_transform_default_orientation() {
+        let from = Tuple::new_point(0.0, 0.0, 0.0);
+        let to = Tuple::new_point(0.0, 0.0, -1.0);
+        let up = Tuple::new_vector(0.0, 1.0, 0.0);
+        assert!(view_transform(&from, &to, &up).is_equal_to(&Matrix4::IDENTITY));
+    }
+
+    #[test]
+    fn transform_view_transform_positive_z() {
+        let from = Tuple::new_point(0.0, 0.0, 0.0);
+        let to = Tuple::new_point(0.0, 0.0, 1.0);
+        let up = Tuple::new_vector(0.0, 1.0, 0.0);
+        assert!(view_transform(&from, &to, &up).is_equal_to(&scaling(-1.0, 1.0, -1.0)));
+    }
+
+    #[test]
+    fn transform_view_transform_moves_world() {
+        let from = Tuple::new_point(0.0, 0.0, 8.0);
+        let to = Tuple::new_point(0.0, 0.0, 0.0);
+        let up = Tuple::new_vector(0.0, 1.0, 0.0);
+        assert!(view_transform(&from, &to, &up).is_equal_to(&translation(0.0, 0.0, -8.0)));
+    }
+
+    #[test]
+    fn transform_view_transform_arbitrary() {
+        let from = Tuple::new_point(1.0, 3.0, 2.0);
+        let to = Tuple::new_point(4.0, -2.0, 8.0);
+        let up = Tuple::new_vector(1.0, 1.0, 0.0);
+        let transform = view_transform(&from, &to, &up);
+        let expected = [
+            [-0.50709, 0.50709, 0.67612, -2.36643],
+            [0.76772, 0.60609, 0.12122, -2.82843],
+            [-0.35857, 0.59761, -0.71714, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        for (row, expected_row) in expected.iter().enumerate() {
+            for (column, expected) in expected_row.iter().enumerate() {
+                // The book's values are rounded to five decimal places.
+                assert_approx_eq!(transform[(row, column)], expected, epsilon = 1e-5);
+            }
+        }
+    }
+}
@@ -1,9 +1,37 @@
-/// Epsilon used for floating-point comparisons
-const EPSILON: f64 = 1e-6;
+use crate::math::{format_number, ApproxEq};
+use std::error::Error;
+use std::fmt;
+use std::ops::{Index, IndexMut};
 
 pub mod ops;
+pub mod point;
+pub mod vector;
 
-#[derive(Debug, Default)]
+pub use point::Point;
+pub use vector::Vector;
+
+/// Error produced when converting a tuple to a `Point` or a `Vector`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TupleError {
+    /// The tuple's `w` is not 1, so it is not a point.
+    NotAPoint,
+    /// The tuple's `w` is not 0, so it is not a vector.
+    NotAVector,
+}
+
+impl fmt::Display for TupleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TupleError::NotAPoint => write!(f, "tuple is not a point"),
+            TupleError::NotAVector => write!(f, "tuple is not a vector"),
+        }
+    }
+}
+
+impl Error for TupleError {}
+
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tuple {
     pub x: f64,
     pub y: f64,
@@ -22,22 +50,46 @@ pub fn new_vector(x: f64, y: f64, z: f64) -> Self {
         Self { x, y, z, w: 0.0 }
     }
 
+    /// Create the point at spherical coordinates around the origin, with the y axis pointing to the north pole.
+    /// `radius` is the distance from the origin, `theta` is the azimuthal angle around the y axis, measured
+    /// from the z axis toward the x axis, and `phi` is the polar angle measured from the north pole.
+    pub fn from_spherical(radius: f64, theta: f64, phi: f64) -> Self {
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        Tuple::new_point(
+            radius * sin_phi * sin_theta,
+            radius * cos_phi,
+            radius * sin_phi * cos_theta,
+        )
+    }
+
+    /// Convert a point or a vector to spherical coordinates `(radius, theta, phi)` as taken by
+    /// `from_spherical`, with `theta` from -π to π and `phi` from 0 to π.
+    /// The angles of the origin are both 0.
+    pub fn to_spherical(&self) -> (f64, f64, f64) {
+        let radius = (self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+        if radius == 0.0 {
+            return (0.0, 0.0, 0.0);
+        }
+        let theta = self.x.atan2(self.z);
+        let phi = (self.y / radius).clamp(-1.0, 1.0).acos();
+        (radius, theta, phi)
+    }
+
     /// Test if the tuple is a point.
     pub fn is_point(&self) -> bool {
-        (self.w - 1.0).abs() < EPSILON
+        self.w.approx_eq(&1.0)
     }
 
     /// Test if the tuple is a vector.
     pub fn is_vector(&self) -> bool {
-        self.w.abs() < EPSILON
+        self.w.approx_eq(&0.0)
     }
 
-    /// Test if this tuple is equal to another.
-    /// Note that this only considers the cartesian coordinates of the two tuples.
+    /// Test if this tuple is equal to another, comparing all four components like `==`,
+    /// so a point is never equal to a vector.
     pub fn is_equal_to(&self, other: &Tuple) -> bool {
-        (self.x - other.x).abs() < EPSILON
-            && (self.y - other.y).abs() < EPSILON
-            && (self.z - other.z).abs() < EPSILON
+        self == other
     }
 
     /// Compute the magnitude of the tuple.
@@ -63,4 +115,120 @@ pub fn cross_product(&self, other: &Tuple) -> Tuple {
             self.x * other.y - self.y * other.x,
         )
     }
+
+    /// Reflect this vector around a surface normal.
+    pub fn reflect(&self, normal: &Tuple) -> Tuple {
+        self - normal * (2.0 * self.dot_product(normal))
+    }
+
+    /// Refract this vector through a surface with the given normal using Snell's law, where `eta_ratio` is
+    /// the refractive index of the material the vector leaves divided by that of the material it enters.
+    /// Both vectors must be normalized, and the normal must face against this vector.
+    /// Returns `None` when the vector undergoes total internal reflection instead.
+    pub fn refract(&self, normal: &Tuple, eta_ratio: f64) -> Option<Tuple> {
+        let cos_i = -self.dot_product(normal);
+        let sin2_t = eta_ratio * eta_ratio * (1.0 - cos_i * cos_i);
+        if sin2_t > 1.0 {
+            return None;
+        }
+
+        let cos_t = (1.0 - sin2_t).sqrt();
+        Some(self * eta_ratio + normal * (eta_ratio * cos_i - cos_t))
+    }
+
+    /// Interpolate linearly between two tuples, giving `a` when `t` is 0 and `b` when `t` is 1.
+    /// Interpolating between two points gives a point, and between two vectors a vector.
+    pub fn lerp(a: &Tuple, b: &Tuple, t: f64) -> Tuple {
+        a + (b - a) * t
+    }
+
+    /// Project this vector onto another, giving the part of it that points along the other vector.
+    pub fn project_onto(&self, other: &Tuple) -> Tuple {
+        other * (self.dot_product(other) / other.dot_product(other))
+    }
+
+    /// Compute the angle between this vector and another, in radians from 0 to π.
+    pub fn angle_between(&self, other: &Tuple) -> f64 {
+        let cos = self.dot_product(other) / (self.magnitude() * other.magnitude());
+        // Rounding can push the cosine of nearly parallel vectors just past ±1
+        cos.clamp(-1.0, 1.0).acos()
+    }
+}
+
+impl Index<usize> for Tuple {
+    type Output = f64;
+
+    /// Get a component by its position: 0 for x, 1 for y, 2 for z and 3 for w.
+    fn index(&self, index: usize) -> &f64 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            3 => &self.w,
+            _ => panic!("tuples have 4 components but the index is {index}"),
+        }
+    }
+}
+
+impl IndexMut<usize> for Tuple {
+    /// Get a mutable reference to a component by its position: 0 for x, 1 for y, 2 for z and 3 for w.
+    fn index_mut(&mut self, index: usize) -> &mut f64 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            3 => &mut self.w,
+            _ => panic!("tuples have 4 components but the index is {index}"),
+        }
+    }
+}
+
+impl PartialEq for Tuple {
+    /// Compare all four components with the default tolerance of `ApproxEq`.
+    /// Since nearby tuples compare equal, equality is not transitive.
+    fn eq(&self, other: &Tuple) -> bool {
+        self.approx_eq(other)
+    }
+}
+
+impl ApproxEq for Tuple {
+    /// Compare all four components, so a point is never approximately equal to a vector.
+    fn approx_eq_with(&self, other: &Tuple, epsilon: f64) -> bool {
+        self.x.approx_eq_with(&other.x, epsilon)
+            && self.y.approx_eq_with(&other.y, epsilon)
+            && self.z.approx_eq_with(&other.z, epsilon)
+            && self.w.approx_eq_with(&other.w, epsilon)
+    }
+}
+
+impl fmt::Display for Tuple {
+    /// Show points as `point(x, y, z)`, vectors as `vector(x, y, z)` and any other tuple as
+    /// `tuple(x, y, z, w)`, using the formatter's precision if one is given.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision();
+        let [x, y, z, w] = [self.x, self.y, self.z, self.w].map(|c| format_number(c, precision));
+        if self.is_point() {
+            write!(f, "point({x}, {y}, {z})")
+        } else if self.is_vector() {
+            write!(f, "vector({x}, {y}, {z})")
+        } else {
+            write!(f, "tuple({x}, {y}, {z}, {w})")
+        }
+    }
+}
+
+/// Create a point tuple from three numbers, which may be integer literals, such as `point!(1, 2, 3)`.
+#[macro_export]
+macro_rules! point {
+    ($x:expr, $y:expr, $z:expr $(,)?) => {
+        $crate::tuple::Tuple::new_point($x as f64, $y as f64, $z as f64)
+    };
+}
+
+/// Create a vector tuple from three numbers, which may be integer literals, such as `vector!(0, 1, 0)`.
+#[macro_export]
+macro_rules! vector {
+    ($x:expr, $y:expr, $z:expr $(,)?) => {
+        $crate::tuple::Tuple::new_vector($x as f64, $y as f64, $z as f64)
+    };
 }
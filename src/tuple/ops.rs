@@ -54,6 +54,7 @@ fn add(self, rhs: Tuple) -> Tuple {
 // For any tuple reference with lifetime `a`, implement `Add` for it such that it can be added with another tuple reference with a different lifetime `b`.
 // We want to implement this trait for reference tuples because we want to be able to use the operands afterwards
 // (i.e., we do not want the `add` function to own the operands).
+#[allow(clippy::needless_lifetimes)]
 impl<'a, 'b> Add<&'b Tuple> for &'a Tuple {
     type Output = Tuple;
 
@@ -121,6 +122,7 @@ fn sub(self, rhs: Tuple) -> Tuple {
 // For any tuple reference with lifetime `a`, implement `Sub` for it such that it can be added with another tuple reference with a different lifetime `b`.
 // We want to implement this trait for reference tuples because we want to be able to use the operands afterwards
 // (i.e., we do not want the `sub` function to own the operands).
+#[allow(clippy::needless_lifetimes)]
 impl<'a, 'b> Sub<&'b Tuple> for &'a Tuple {
     type Output = Tuple;
 
@@ -207,7 +209,7 @@ fn mul(self, rhs: f64) -> Tuple {
     }
 }
 
-///
+//
 // Implement the `Div` trait for a tuple for it to be divided by an f64.
 //
 
@@ -241,16 +243,21 @@ fn div(self, rhs: f64) -> Tuple {
 
 #[cfg(test)]
 mod tests {
-    use super::super::EPSILON;
     use super::*;
+    use crate::assert_approx_eq;
+    use crate::math::ApproxEq;
+    use crate::tuple::{Point, Vector};
+    use crate::EPSILON;
+    use crate::{point, vector};
+    use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2, FRAC_PI_4, PI};
 
     #[test]
     fn tuple_new_point() {
         let tuple = Tuple::new_point(4.3, -4.2, 3.1);
-        assert!((tuple.x - 4.3).abs() < EPSILON);
-        assert!((tuple.y - -4.2).abs() < EPSILON);
-        assert!((tuple.z - 3.1).abs() < EPSILON);
-        assert!((tuple.w - 1.0).abs() < EPSILON);
+        assert_approx_eq!(tuple.x, 4.3);
+        assert_approx_eq!(tuple.y, -4.2);
+        assert_approx_eq!(tuple.z, 3.1);
+        assert_approx_eq!(tuple.w, 1.0);
         assert!(tuple.is_point());
         assert!(!tuple.is_vector());
     }
@@ -258,20 +265,74 @@ fn tuple_new_point() {
     #[test]
     fn tuple_new_vector() {
         let tuple = Tuple::new_vector(4.3, -4.2, 3.1);
-        assert!((tuple.x - 4.3).abs() < EPSILON);
-        assert!((tuple.y - -4.2).abs() < EPSILON);
-        assert!((tuple.z - 3.1).abs() < EPSILON);
-        assert!(tuple.w.abs() < EPSILON);
+        assert_approx_eq!(tuple.x, 4.3);
+        assert_approx_eq!(tuple.y, -4.2);
+        assert_approx_eq!(tuple.z, 3.1);
+        assert_approx_eq!(tuple.w, 0.0);
         assert!(!tuple.is_point());
         assert!(tuple.is_vector());
     }
 
+    #[test]
+    fn tuple_from_spherical() {
+        assert!(Tuple::from_spherical(2.0, 0.0, 0.0).is_equal_to(&Tuple::new_point(0.0, 2.0, 0.0)));
+        assert!(Tuple::from_spherical(2.0, 0.0, PI).is_equal_to(&Tuple::new_point(0.0, -2.0, 0.0)));
+        assert!(Tuple::from_spherical(1.0, 0.0, FRAC_PI_2)
+            .is_equal_to(&Tuple::new_point(0.0, 0.0, 1.0)));
+        assert!(Tuple::from_spherical(1.0, FRAC_PI_2, FRAC_PI_2)
+            .is_equal_to(&Tuple::new_point(1.0, 0.0, 0.0)));
+        assert!(
+            Tuple::from_spherical(2.0, -FRAC_PI_2, FRAC_PI_4).is_equal_to(&Tuple::new_point(
+                -2.0 * FRAC_1_SQRT_2,
+                2.0 * FRAC_1_SQRT_2,
+                0.0
+            ))
+        );
+    }
+
+    #[test]
+    fn tuple_to_spherical() {
+        let (radius, theta, phi) = Tuple::new_vector(0.0, 0.0, -3.0).to_spherical();
+        assert_approx_eq!(radius, 3.0);
+        assert_approx_eq!(theta, PI);
+        assert_approx_eq!(phi, FRAC_PI_2);
+
+        let (radius, theta, phi) = Tuple::new_point(0.0, 0.0, 0.0).to_spherical();
+        assert_eq!((radius, theta, phi), (0.0, 0.0, 0.0));
+
+        // Converting back gives the same point
+        let point = Tuple::new_point(1.0, -2.0, 3.0);
+        let (radius, theta, phi) = point.to_spherical();
+        assert!(Tuple::from_spherical(radius, theta, phi).is_equal_to(&point));
+    }
+
+    #[test]
+    fn tuple_index() {
+        let mut tuple = Tuple {
+            x: 1.0,
+            y: -2.0,
+            z: 3.0,
+            w: -4.0,
+        };
+        let components: Vec<f64> = (0..4).map(|i| tuple[i]).collect();
+        assert_eq!(components, vec![1.0, -2.0, 3.0, -4.0]);
+
+        tuple[2] = 5.0;
+        assert_approx_eq!(tuple.z, 5.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "tuples have 4 components")]
+    fn tuple_index_out_of_bounds() {
+        let _ = Tuple::default()[4];
+    }
+
     #[test]
     fn tuple_is_equal_to() {
         let point = Tuple::new_point(4.3, -4.2, 3.1);
         let vector = Tuple::new_vector(4.3, -4.2, 3.1);
-        assert!(point.is_equal_to(&vector));
-        assert!(vector.is_equal_to(&point));
+        assert!(!point.is_equal_to(&vector));
+        assert!(!vector.is_equal_to(&point));
 
         let not_quite_different = Tuple::new_point(4.3 + 1e-7, -4.2, 3.1);
         assert!(point.is_equal_to(&not_quite_different));
@@ -281,70 +342,88 @@ fn tuple_is_equal_to() {
     }
 
     #[test]
+    fn tuple_eq() {
+        let point = Tuple::new_point(4.3, -4.2, 3.1);
+        assert_eq!(point, Tuple::new_point(4.3 + 1e-7, -4.2, 3.1));
+        assert_ne!(point, Tuple::new_point(4.3 + EPSILON, -4.2, 3.1));
+        assert_ne!(point, Tuple::new_vector(4.3, -4.2, 3.1));
+    }
+
+    #[test]
+    fn tuple_approx_eq() {
+        let point = Tuple::new_point(4.3, -4.2, 3.1);
+        assert_approx_eq!(point, Tuple::new_point(4.3 + 1e-7, -4.2, 3.1));
+        // The comparison includes w
+        assert!(!point.approx_eq(&Tuple::new_vector(4.3, -4.2, 3.1)));
+
+        let nearby = Tuple::new_point(4.3, -4.2, 3.1001);
+        assert!(!point.approx_eq(&nearby));
+        assert_approx_eq!(point, nearby, epsilon = 1e-3);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)] // The reference impls are kept for backward compatibility
     fn tuple_add() {
         let point1 = Tuple::new_point(3.0, -2.0, 5.0);
         let vector1 = Tuple::new_vector(-2.0, 3.0, 1.0);
 
         let point1_plus_vector1 = point1 + vector1;
-        assert!(point1_plus_vector1.is_equal_to(&Tuple::new_point(1.0, 1.0, 6.0)));
+        assert_eq!(point1_plus_vector1, Tuple::new_point(1.0, 1.0, 6.0));
         assert!(point1_plus_vector1.is_point());
 
-        // Add tuples
-        let point1 = Tuple::new_point(3.0, -2.0, 5.0);
-        let point2 = Tuple::new_point(3.0, -2.0, 5.0);
-        let point1_plus_point2 = point1 + point2;
-        assert!(point1_plus_point2.is_equal_to(&Tuple::new_point(6.0, -4.0, 10.0)));
-        assert!((point1_plus_point2.w - 2.0).abs() < EPSILON); // a weird reality
+        // Add two vectors
+        let vector2 = Tuple::new_vector(1.0, 1.0, -1.0);
+        assert_eq!(vector1 + vector2, Tuple::new_vector(-1.0, 4.0, 0.0));
 
         // Add a reference tuple to a tuple
-        let point1 = Tuple::new_point(3.0, -2.0, 5.0);
-        let point2 = Tuple::new_point(3.0, -2.0, 5.0);
-        let point1_plus_point2 = point1 + &point2;
-        assert!(point1_plus_point2.is_equal_to(&Tuple::new_point(6.0, -4.0, 10.0)));
-        assert!((point1_plus_point2.w - 2.0).abs() < EPSILON);
+        let sum = Tuple::new_point(1.0, 1.0, 6.0);
+        assert_eq!(point1 + &vector1, sum);
 
         // Add a tuple to a reference tuple
-        let point1 = Tuple::new_point(3.0, -2.0, 5.0);
-        let point1_plus_point2 = &point1 + point2;
-        assert!(point1_plus_point2.is_equal_to(&Tuple::new_point(6.0, -4.0, 10.0)));
-        assert!((point1_plus_point2.w - 2.0).abs() < EPSILON);
+        assert_eq!(&point1 + vector1, sum);
 
         // Add two reference tuples
-        let point2 = Tuple::new_point(3.0, -2.0, 5.0);
-        let point1_plus_point2 = &point1 + &point2;
-        assert!(point1_plus_point2.is_equal_to(&Tuple::new_point(6.0, -4.0, 10.0)));
-        assert!((point1_plus_point2.w - 2.0).abs() < EPSILON);
+        assert_eq!(&point1 + &vector1, sum);
+
+        // `Point` only accepts a `Vector` here, so adding two points does not compile (see the examples on `Point`)
+        let point = Point::new(3.0, -2.0, 5.0) + Vector::new(-2.0, 3.0, 1.0);
+        assert_eq!(Tuple::from(point), sum);
     }
 
     #[test]
+    #[allow(clippy::op_ref)] // The reference impls are kept for backward compatibility
     fn tuple_sub() {
         let point1 = Tuple::new_point(3.0, 2.0, 1.0);
         let point2 = Tuple::new_point(5.0, 6.0, 7.0);
 
         // Subtract tuples
         let point1_minus_point2 = point1 - point2;
-        assert!(point1_minus_point2.is_equal_to(&Tuple::new_vector(-2.0, -4.0, -6.0)));
+        assert_eq!(point1_minus_point2, Tuple::new_vector(-2.0, -4.0, -6.0));
         assert!(point1_minus_point2.is_vector());
 
         // Subtract a reference tuple from a tuple
         let point1 = Tuple::new_point(3.0, 2.0, 1.0);
         let vector1 = Tuple::new_vector(5.0, 6.0, 7.0);
         let point1_minus_vector1 = point1 - &vector1;
-        assert!(point1_minus_vector1.is_equal_to(&Tuple::new_vector(-2.0, -4.0, -6.0)));
+        assert_eq!(point1_minus_vector1, Tuple::new_point(-2.0, -4.0, -6.0));
         assert!(point1_minus_vector1.is_point());
 
         // Subtract a tuple from a reference tuple
         let vector2 = Tuple::new_vector(3.0, 2.0, 1.0);
         let vector2_minus_vector1 = &vector2 - vector1;
-        assert!(vector2_minus_vector1.is_equal_to(&Tuple::new_vector(-2.0, -4.0, -6.0)));
+        assert_eq!(vector2_minus_vector1, Tuple::new_vector(-2.0, -4.0, -6.0));
         assert!(vector2_minus_vector1.is_vector());
 
         // Subtract two reference tuples
         let point1 = Tuple::new_point(3.0, 2.0, 1.0);
-        let vector1 = Tuple::new_vector(5.0, 6.0, 7.0);
-        let vector1_minus_point1 = &vector1 - &point1;
-        assert!(vector1_minus_point1.is_equal_to(&Tuple::new_point(2.0, 4.0, 6.0)));
-        assert!((vector1_minus_point1.w - -1.0).abs() < EPSILON); // a weird reality
+        let point2 = Tuple::new_point(5.0, 6.0, 7.0);
+        let point2_minus_point1 = &point2 - &point1;
+        assert_eq!(point2_minus_point1, Tuple::new_vector(2.0, 4.0, 6.0));
+
+        // Subtracting two points gives a `Vector`, while subtracting a point from a vector does not compile
+        // (see the examples on `Point`)
+        let difference = Point::new(5.0, 6.0, 7.0) - Point::new(3.0, 2.0, 1.0);
+        assert_eq!(Tuple::from(difference), point2_minus_point1);
     }
 
     #[test]
@@ -357,10 +436,10 @@ fn tuple_neg() {
         };
 
         let neg_tuple = -tuple;
-        assert!((neg_tuple.x - -1.0).abs() < EPSILON);
-        assert!((neg_tuple.y - 2.0).abs() < EPSILON);
-        assert!((neg_tuple.z - -3.0).abs() < EPSILON);
-        assert!((neg_tuple.w - 4.0).abs() < EPSILON);
+        assert_approx_eq!(neg_tuple.x, -1.0);
+        assert_approx_eq!(neg_tuple.y, 2.0);
+        assert_approx_eq!(neg_tuple.z, -3.0);
+        assert_approx_eq!(neg_tuple.w, 4.0);
 
         let tuple = Tuple {
             x: 1.0,
@@ -370,16 +449,16 @@ fn tuple_neg() {
         };
 
         let neg_tuple = -&tuple;
-        assert!((neg_tuple.x - -1.0).abs() < EPSILON);
-        assert!((neg_tuple.y - 2.0).abs() < EPSILON);
-        assert!((neg_tuple.z - -3.0).abs() < EPSILON);
-        assert!((neg_tuple.w - 4.0).abs() < EPSILON);
+        assert_approx_eq!(neg_tuple.x, -1.0);
+        assert_approx_eq!(neg_tuple.y, 2.0);
+        assert_approx_eq!(neg_tuple.z, -3.0);
+        assert_approx_eq!(neg_tuple.w, 4.0);
 
         let neg_neg_tuple = -&-&tuple; // reference types are fun :)
-        assert!((neg_neg_tuple.x - 1.0).abs() < EPSILON);
-        assert!((neg_neg_tuple.y - -2.0).abs() < EPSILON);
-        assert!((neg_neg_tuple.z - 3.0).abs() < EPSILON);
-        assert!((neg_neg_tuple.w - -4.0).abs() < EPSILON);
+        assert_approx_eq!(neg_neg_tuple.x, 1.0);
+        assert_approx_eq!(neg_neg_tuple.y, -2.0);
+        assert_approx_eq!(neg_neg_tuple.z, 3.0);
+        assert_approx_eq!(neg_neg_tuple.w, -4.0);
     }
 
     #[test]
@@ -392,10 +471,10 @@ fn tuple_mul() {
         };
 
         let tuple_mul = tuple * 3.5;
-        assert!((tuple_mul.x - 3.5).abs() < EPSILON);
-        assert!((tuple_mul.y - -7.0).abs() < EPSILON);
-        assert!((tuple_mul.z - 10.5).abs() < EPSILON);
-        assert!((tuple_mul.w - -14.0).abs() < EPSILON);
+        assert_approx_eq!(tuple_mul.x, 3.5);
+        assert_approx_eq!(tuple_mul.y, -7.0);
+        assert_approx_eq!(tuple_mul.z, 10.5);
+        assert_approx_eq!(tuple_mul.w, -14.0);
 
         let tuple = Tuple {
             x: 1.0,
@@ -405,10 +484,10 @@ fn tuple_mul() {
         };
 
         let tuple_mul = &tuple * 0.5;
-        assert!((tuple_mul.x - 0.5).abs() < EPSILON);
-        assert!((tuple_mul.y - -1.0).abs() < EPSILON);
-        assert!((tuple_mul.z - 1.5).abs() < EPSILON);
-        assert!((tuple_mul.w - -2.0).abs() < EPSILON);
+        assert_approx_eq!(tuple_mul.x, 0.5);
+        assert_approx_eq!(tuple_mul.y, -1.0);
+        assert_approx_eq!(tuple_mul.z, 1.5);
+        assert_approx_eq!(tuple_mul.w, -2.0);
     }
 
     #[test]
@@ -421,34 +500,34 @@ fn tuple_div() {
         };
 
         let tuple_div = &tuple / 2.0;
-        assert!((tuple_div.x - 0.5).abs() < EPSILON);
-        assert!((tuple_div.y - -1.0).abs() < EPSILON);
-        assert!((tuple_div.z - 1.5).abs() < EPSILON);
-        assert!((tuple_div.w - -2.0).abs() < EPSILON);
+        assert_approx_eq!(tuple_div.x, 0.5);
+        assert_approx_eq!(tuple_div.y, -1.0);
+        assert_approx_eq!(tuple_div.z, 1.5);
+        assert_approx_eq!(tuple_div.w, -2.0);
 
         let tuple_div = tuple / 2.0;
-        assert!((tuple_div.x - 0.5).abs() < EPSILON);
-        assert!((tuple_div.y - -1.0).abs() < EPSILON);
-        assert!((tuple_div.z - 1.5).abs() < EPSILON);
-        assert!((tuple_div.w - -2.0).abs() < EPSILON);
+        assert_approx_eq!(tuple_div.x, 0.5);
+        assert_approx_eq!(tuple_div.y, -1.0);
+        assert_approx_eq!(tuple_div.z, 1.5);
+        assert_approx_eq!(tuple_div.w, -2.0);
     }
 
     #[test]
     fn tuple_magnitude() {
         let mut tuple = Tuple::new_vector(1.0, 0.0, 0.0);
-        assert!((tuple.magnitude() - 1.0).abs() < EPSILON);
+        assert_approx_eq!(tuple.magnitude(), 1.0);
 
         tuple = Tuple::new_vector(0.0, 1.0, 0.0);
-        assert!((tuple.magnitude() - 1.0).abs() < EPSILON);
+        assert_approx_eq!(tuple.magnitude(), 1.0);
 
         tuple = Tuple::new_vector(0.0, 0.0, 1.0);
-        assert!((tuple.magnitude() - 1.0).abs() < EPSILON);
+        assert_approx_eq!(tuple.magnitude(), 1.0);
 
         tuple = Tuple::new_vector(1.0, 2.0, 3.0);
-        assert!((tuple.magnitude() - 14.0_f64.sqrt()).abs() < EPSILON);
+        assert_approx_eq!(tuple.magnitude(), 14.0_f64.sqrt());
 
         tuple = Tuple::new_vector(-1.0, -2.0, -3.0);
-        assert!((tuple.magnitude() - 14.0_f64.sqrt()).abs() < EPSILON);
+        assert_approx_eq!(tuple.magnitude(), 14.0_f64.sqrt());
     }
 
     #[test]
@@ -465,15 +544,15 @@ fn tuple_normalize() {
             3.0 / 14.0_f64.sqrt()
         )));
 
-        assert!((tuple.normalize().magnitude() - 1.0).abs() < EPSILON);
+        assert_approx_eq!(tuple.normalize().magnitude(), 1.0);
     }
 
     #[test]
     fn tuple_dot_product() {
         let a = Tuple::new_vector(1.0, 2.0, 3.0);
         let b = Tuple::new_vector(2.0, 3.0, 4.0);
-        assert!((a.dot_product(&b) - 20.0).abs() < EPSILON);
-        assert!((b.dot_product(&a) - 20.0).abs() < EPSILON);
+        assert_approx_eq!(a.dot_product(&b), 20.0);
+        assert_approx_eq!(b.dot_product(&a), 20.0);
     }
 
     #[test]
@@ -487,4 +566,127 @@ fn tuple_cross_product() {
             .cross_product(&a)
             .is_equal_to(&Tuple::new_vector(1.0, -2.0, 1.0)));
     }
+
+    #[test]
+    fn tuple_reflect() {
+        // Reflect a vector approaching at 45 degrees
+        let vector = Tuple::new_vector(1.0, -1.0, 0.0);
+        let normal = Tuple::new_vector(0.0, 1.0, 0.0);
+        assert!(vector
+            .reflect(&normal)
+            .is_equal_to(&Tuple::new_vector(1.0, 1.0, 0.0)));
+
+        // Reflect a vector off a slanted surface
+        let vector = Tuple::new_vector(0.0, -1.0, 0.0);
+        let normal = Tuple::new_vector(2.0_f64.sqrt() / 2.0, 2.0_f64.sqrt() / 2.0, 0.0);
+        let reflected = vector.reflect(&normal);
+        assert!(reflected.is_equal_to(&Tuple::new_vector(1.0, 0.0, 0.0)));
+        assert!(reflected.is_vector());
+    }
+
+    #[test]
+    fn tuple_refract() {
+        let normal = Tuple::new_vector(0.0, 1.0, 0.0);
+
+        // Passing straight through, the vector does not bend
+        let vector = Tuple::new_vector(0.0, -1.0, 0.0);
+        assert!(vector
+            .refract(&normal, 1.0 / 1.5)
+            .unwrap()
+            .is_equal_to(&vector));
+
+        // Entering a denser material, the vector bends toward the normal
+        let vector = Tuple::new_vector(FRAC_1_SQRT_2, -FRAC_1_SQRT_2, 0.0);
+        let refracted = vector.refract(&normal, 1.0 / 1.5).unwrap();
+        let sin_t = FRAC_1_SQRT_2 / 1.5;
+        let expected = Tuple::new_vector(sin_t, -(1.0 - sin_t * sin_t).sqrt(), 0.0);
+        assert!(refracted.is_equal_to(&expected));
+        assert_approx_eq!(refracted.magnitude(), 1.0);
+
+        // Leaving a denser material at a shallow angle, the vector is reflected instead
+        assert!(vector.refract(&normal, 1.5).is_none());
+    }
+
+    #[test]
+    fn tuple_lerp() {
+        let a = Tuple::new_point(0.0, 2.0, -4.0);
+        let b = Tuple::new_point(4.0, 2.0, 0.0);
+        assert!(Tuple::lerp(&a, &b, 0.0).is_equal_to(&a));
+        assert!(Tuple::lerp(&a, &b, 1.0).is_equal_to(&b));
+        assert!(Tuple::lerp(&a, &b, 0.25).is_equal_to(&Tuple::new_point(1.0, 2.0, -3.0)));
+
+        let a = Tuple::new_vector(1.0, 0.0, 0.0);
+        let b = Tuple::new_vector(0.0, 1.0, 0.0);
+        assert!(Tuple::lerp(&a, &b, 0.5).is_equal_to(&Tuple::new_vector(0.5, 0.5, 0.0)));
+    }
+
+    #[test]
+    fn tuple_project_onto() {
+        let vector = Tuple::new_vector(3.0, 4.0, 0.0);
+        assert!(vector
+            .project_onto(&Tuple::new_vector(2.0, 0.0, 0.0))
+            .is_equal_to(&Tuple::new_vector(3.0, 0.0, 0.0)));
+        assert!(vector
+            .project_onto(&Tuple::new_vector(1.0, 1.0, 0.0))
+            .is_equal_to(&Tuple::new_vector(3.5, 3.5, 0.0)));
+        // Perpendicular vectors have nothing in common
+        assert!(vector
+            .project_onto(&Tuple::new_vector(0.0, 0.0, 5.0))
+            .is_equal_to(&Tuple::new_vector(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn tuple_angle_between() {
+        let x = Tuple::new_vector(2.0, 0.0, 0.0);
+        assert_approx_eq!(
+            x.angle_between(&Tuple::new_vector(0.0, 3.0, 0.0)),
+            FRAC_PI_2
+        );
+        assert_approx_eq!(
+            x.angle_between(&Tuple::new_vector(1.0, 1.0, 0.0)),
+            FRAC_PI_4
+        );
+        assert_approx_eq!(x.angle_between(&Tuple::new_vector(-1.0, 0.0, 0.0)), PI);
+        assert_approx_eq!(x.angle_between(&(x * 3.0)), 0.0);
+    }
+
+    #[test]
+    fn tuple_display() {
+        assert_eq!(
+            Tuple::new_point(1.0, -2.5, 3.0).to_string(),
+            "point(1.0, -2.5, 3.0)"
+        );
+        assert_eq!(
+            Tuple::new_vector(0.0, 1.0, 0.0).to_string(),
+            "vector(0.0, 1.0, 0.0)"
+        );
+        let tuple = Tuple {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            w: 0.5,
+        };
+        assert_eq!(tuple.to_string(), "tuple(1.0, 2.0, 3.0, 0.5)");
+        assert_eq!(
+            format!("{:.2}", Tuple::new_point(1.0, 1.0 / 3.0, 0.125)),
+            "point(1.00, 0.33, 0.12)"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn tuple_serde() {
+        let tuple = Tuple::new_point(1.0, -2.5, 3.0);
+        let json = serde_json::to_string(&tuple).unwrap();
+        assert_eq!(json, r#"{"x":1.0,"y":-2.5,"z":3.0,"w":1.0}"#);
+        assert_eq!(serde_json::from_str::<Tuple>(&json).unwrap(), tuple);
+    }
+
+    #[test]
+    fn tuple_macros() {
+        assert_eq!(point!(1, -2, 3.5), Tuple::new_point(1.0, -2.0, 3.5));
+        assert_eq!(vector!(0, 1, 0), Tuple::new_vector(0.0, 1.0, 0.0));
+        let x = 2.5;
+        assert_eq!(point!(x, x * 2.0, -x,), Tuple::new_point(2.5, 5.0, -2.5));
+    }
 }
@@ -0,0 +1,199 @@
+use super::{Tuple, TupleError, Vector};
+use crate::math::{format_number, ApproxEq};
+use crate::matrix::Matrix4;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+/// Position in space, whose type rules out operations that make no sense for positions.
+///
+/// Subtracting two points gives the `Vector` between them, and moving a point by a vector gives another
+/// point, but points cannot be added to each other or scaled, so a tuple with a `w` of 2 cannot arise.
+///
+/// ```
+/// use the_ray_tracer_challenge::tuple::{Point, Vector};
+///
+/// let moved = Point::new(1.0, 2.0, 3.0) + Vector::new(1.0, 0.0, 0.0);
+/// assert_eq!(moved - Point::ORIGIN, Vector::new(2.0, 2.0, 3.0));
+/// ```
+///
+/// Adding two points does not compile:
+///
+/// ```compile_fail
+/// use the_ray_tracer_challenge::tuple::Point;
+///
+/// let sum = Point::new(1.0, 2.0, 3.0) + Point::new(4.0, 5.0, 6.0);
+/// ```
+///
+/// Nor does scaling a point:
+///
+/// ```compile_fail
+/// use the_ray_tracer_challenge::tuple::Point;
+///
+/// let scaled = Point::new(1.0, 2.0, 3.0) * 2.0;
+/// ```
+///
+/// Nor does subtracting a point from a vector:
+///
+/// ```compile_fail
+/// use the_ray_tracer_challenge::tuple::{Point, Vector};
+///
+/// let difference = Vector::new(1.0, 2.0, 3.0) - Point::new(4.0, 5.0, 6.0);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Point {
+    /// Point at the origin.
+    pub const ORIGIN: Point = Point::new(0.0, 0.0, 0.0);
+
+    /// Create a point.
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl From<Point> for Tuple {
+    fn from(point: Point) -> Tuple {
+        Tuple::new_point(point.x, point.y, point.z)
+    }
+}
+
+impl TryFrom<Tuple> for Point {
+    type Error = TupleError;
+
+    /// Convert a tuple to a point, failing unless its `w` is 1.
+    fn try_from(tuple: Tuple) -> Result<Point, TupleError> {
+        if tuple.is_point() {
+            Ok(Point::new(tuple.x, tuple.y, tuple.z))
+        } else {
+            Err(TupleError::NotAPoint)
+        }
+    }
+}
+
+impl PartialEq for Point {
+    /// Compare the coordinates with the default tolerance of `ApproxEq`.
+    fn eq(&self, other: &Point) -> bool {
+        self.approx_eq(other)
+    }
+}
+
+impl ApproxEq for Point {
+    fn approx_eq_with(&self, other: &Point, epsilon: f64) -> bool {
+        self.x.approx_eq_with(&other.x, epsilon)
+            && self.y.approx_eq_with(&other.y, epsilon)
+            && self.z.approx_eq_with(&other.z, epsilon)
+    }
+}
+
+impl fmt::Display for Point {
+    /// Show the point as `point(x, y, z)`, using the formatter's precision if one is given.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision();
+        let [x, y, z] = [self.x, self.y, self.z].map(|c| format_number(c, precision));
+        write!(f, "point({x}, {y}, {z})")
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    /// Move the point by a vector.
+    fn add(self, rhs: Vector) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    /// Move the point backward by a vector.
+    fn sub(self, rhs: Vector) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    /// Find the vector from another point to this one.
+    fn sub(self, rhs: Point) -> Vector {
+        Vector::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<Point> for Matrix4 {
+    type Output = Point;
+
+    /// Transform a point, applying the matrix's translation.
+    fn mul(self, rhs: Point) -> Point {
+        let transformed = self * Tuple::from(rhs);
+        Point::new(transformed.x, transformed.y, transformed.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::{scaling, translation};
+
+    #[test]
+    fn point_new() {
+        let point = Point::new(4.3, -4.2, 3.1);
+        assert_approx_eq!(point.x, 4.3);
+        assert_approx_eq!(point.y, -4.2);
+        assert_approx_eq!(point.z, 3.1);
+        assert_eq!(Point::ORIGIN, Point::default());
+    }
+
+    #[test]
+    fn point_tuple_conversions() {
+        let point = Point::new(4.3, -4.2, 3.1);
+        assert_eq!(Tuple::from(point), Tuple::new_point(4.3, -4.2, 3.1));
+        assert_eq!(Point::try_from(Tuple::from(point)), Ok(point));
+        assert_eq!(
+            Point::try_from(Tuple::new_vector(4.3, -4.2, 3.1)),
+            Err(TupleError::NotAPoint)
+        );
+    }
+
+    #[test]
+    fn point_add_sub() {
+        let point = Point::new(3.0, -2.0, 5.0);
+        let vector = Vector::new(-2.0, 3.0, 1.0);
+        assert_eq!(point + vector, Point::new(1.0, 1.0, 6.0));
+        assert_eq!(point - vector, Point::new(5.0, -5.0, 4.0));
+        assert_eq!(
+            Point::new(3.0, 2.0, 1.0) - Point::new(5.0, 6.0, 7.0),
+            Vector::new(-2.0, -4.0, -6.0)
+        );
+    }
+
+    #[test]
+    fn point_transform() {
+        let point = Point::new(-3.0, 4.0, 5.0);
+        assert_eq!(
+            translation(5.0, -3.0, 2.0) * point,
+            Point::new(2.0, 1.0, 7.0)
+        );
+        assert_eq!(scaling(2.0, 3.0, 4.0) * point, Point::new(-6.0, 12.0, 20.0));
+    }
+
+    #[test]
+    fn point_display() {
+        assert_eq!(
+            Point::new(1.0, 2.0, 3.0).to_string(),
+            "point(1.0, 2.0, 3.0)"
+        );
+        assert_eq!(
+            format!("{:.1}", Point::new(0.25, 2.0, 3.0)),
+            "point(0.2, 2.0, 3.0)"
+        );
+    }
+}
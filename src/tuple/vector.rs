@@ -0,0 +1,260 @@
+use super::{Tuple, TupleError};
+use crate::math::{format_number, ApproxEq};
+use crate::matrix::Matrix4;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Direction and length in space, such as the direction of a ray or a surface normal.
+///
+/// Vectors can be added, subtracted and scaled, and moving a `Point` by a vector gives another point.
+/// Transforming a vector ignores translation.
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vector {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vector {
+    /// Create a vector.
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Compute the length of the vector.
+    pub fn magnitude(&self) -> f64 {
+        self.dot_product(self).sqrt()
+    }
+
+    /// Return a new vector in the same direction with a length of 1.
+    pub fn normalize(&self) -> Vector {
+        *self / self.magnitude()
+    }
+
+    /// Compute the dot product of this vector and another.
+    pub fn dot_product(&self, other: &Vector) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Compute the cross product of this vector and another.
+    pub fn cross_product(&self, other: &Vector) -> Vector {
+        Vector::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Reflect this vector around a surface normal.
+    pub fn reflect(&self, normal: &Vector) -> Vector {
+        *self - *normal * (2.0 * self.dot_product(normal))
+    }
+
+    /// Refract this vector through a surface with the given normal using Snell's law, as `Tuple::refract`.
+    /// Returns `None` when the vector undergoes total internal reflection instead.
+    pub fn refract(&self, normal: &Vector, eta_ratio: f64) -> Option<Vector> {
+        Tuple::from(*self)
+            .refract(&Tuple::from(*normal), eta_ratio)
+            .map(|refracted| Vector::new(refracted.x, refracted.y, refracted.z))
+    }
+
+    /// Interpolate linearly between two vectors, giving `a` when `t` is 0 and `b` when `t` is 1.
+    pub fn lerp(a: &Vector, b: &Vector, t: f64) -> Vector {
+        *a + (*b - *a) * t
+    }
+
+    /// Project this vector onto another, giving the part of it that points along the other vector.
+    pub fn project_onto(&self, other: &Vector) -> Vector {
+        *other * (self.dot_product(other) / other.dot_product(other))
+    }
+
+    /// Compute the angle between this vector and another, in radians from 0 to π.
+    pub fn angle_between(&self, other: &Vector) -> f64 {
+        Tuple::from(*self).angle_between(&Tuple::from(*other))
+    }
+}
+
+impl From<Vector> for Tuple {
+    fn from(vector: Vector) -> Tuple {
+        Tuple::new_vector(vector.x, vector.y, vector.z)
+    }
+}
+
+impl TryFrom<Tuple> for Vector {
+    type Error = TupleError;
+
+    /// Convert a tuple to a vector, failing unless its `w` is 0.
+    fn try_from(tuple: Tuple) -> Result<Vector, TupleError> {
+        if tuple.is_vector() {
+            Ok(Vector::new(tuple.x, tuple.y, tuple.z))
+        } else {
+            Err(TupleError::NotAVector)
+        }
+    }
+}
+
+impl PartialEq for Vector {
+    /// Compare the components with the default tolerance of `ApproxEq`.
+    fn eq(&self, other: &Vector) -> bool {
+        self.approx_eq(other)
+    }
+}
+
+impl ApproxEq for Vector {
+    fn approx_eq_with(&self, other: &Vector, epsilon: f64) -> bool {
+        self.x.approx_eq_with(&other.x, epsilon)
+            && self.y.approx_eq_with(&other.y, epsilon)
+            && self.z.approx_eq_with(&other.z, epsilon)
+    }
+}
+
+impl fmt::Display for Vector {
+    /// Show the vector as `vector(x, y, z)`, using the formatter's precision if one is given.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision();
+        let [x, y, z] = [self.x, self.y, self.z].map(|c| format_number(c, precision));
+        write!(f, "vector({x}, {y}, {z})")
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Vector {
+        Vector::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Vector {
+        Vector::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<f64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: f64) -> Vector {
+        Vector::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Div<f64> for Vector {
+    type Output = Vector;
+
+    fn div(self, rhs: f64) -> Vector {
+        Vector::new(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
+impl Mul<Vector> for Matrix4 {
+    type Output = Vector;
+
+    /// Transform a vector, ignoring the matrix's translation.
+    fn mul(self, rhs: Vector) -> Vector {
+        let transformed = self * Tuple::from(rhs);
+        Vector::new(transformed.x, transformed.y, transformed.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::{scaling, translation};
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    #[test]
+    fn vector_tuple_conversions() {
+        let vector = Vector::new(4.3, -4.2, 3.1);
+        assert_eq!(Tuple::from(vector), Tuple::new_vector(4.3, -4.2, 3.1));
+        assert_eq!(Vector::try_from(Tuple::from(vector)), Ok(vector));
+        assert_eq!(
+            Vector::try_from(Tuple::new_point(4.3, -4.2, 3.1)),
+            Err(TupleError::NotAVector)
+        );
+    }
+
+    #[test]
+    fn vector_arithmetic() {
+        let a = Vector::new(3.0, 2.0, 1.0);
+        let b = Vector::new(5.0, 6.0, 7.0);
+        assert_eq!(a + b, Vector::new(8.0, 8.0, 8.0));
+        assert_eq!(a - b, Vector::new(-2.0, -4.0, -6.0));
+        assert_eq!(-a, Vector::new(-3.0, -2.0, -1.0));
+        assert_eq!(a * 2.0, Vector::new(6.0, 4.0, 2.0));
+        assert_eq!(a / 2.0, Vector::new(1.5, 1.0, 0.5));
+    }
+
+    #[test]
+    fn vector_magnitude_and_normalize() {
+        let vector = Vector::new(1.0, 2.0, 3.0);
+        assert_approx_eq!(vector.magnitude(), 14.0f64.sqrt());
+        let normalized = vector.normalize();
+        assert_approx_eq!(normalized.magnitude(), 1.0);
+        assert_eq!(normalized, Vector::new(1.0, 2.0, 3.0) / 14.0f64.sqrt());
+    }
+
+    #[test]
+    fn vector_dot_and_cross_product() {
+        let a = Vector::new(1.0, 2.0, 3.0);
+        let b = Vector::new(2.0, 3.0, 4.0);
+        assert_approx_eq!(a.dot_product(&b), 20.0);
+        assert_eq!(a.cross_product(&b), Vector::new(-1.0, 2.0, -1.0));
+        assert_eq!(b.cross_product(&a), Vector::new(1.0, -2.0, 1.0));
+    }
+
+    #[test]
+    fn vector_reflect() {
+        let vector = Vector::new(0.0, -1.0, 0.0);
+        let normal = Vector::new(FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0);
+        assert_eq!(vector.reflect(&normal), Vector::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn vector_refract() {
+        let normal = Vector::new(0.0, 1.0, 0.0);
+        let vector = Vector::new(0.0, -1.0, 0.0);
+        assert_eq!(vector.refract(&normal, 1.5), Some(vector));
+        let vector = Vector::new(FRAC_1_SQRT_2, -FRAC_1_SQRT_2, 0.0);
+        assert_eq!(vector.refract(&normal, 1.5), None);
+    }
+
+    #[test]
+    fn vector_lerp_project_angle() {
+        let a = Vector::new(3.0, 4.0, 0.0);
+        let b = Vector::new(1.0, 0.0, 0.0);
+        assert_eq!(Vector::lerp(&a, &b, 0.5), Vector::new(2.0, 2.0, 0.0));
+        assert_eq!(a.project_onto(&b), Vector::new(3.0, 0.0, 0.0));
+        assert_approx_eq!(a.angle_between(&b), (3.0f64 / 5.0).acos());
+    }
+
+    #[test]
+    fn vector_transform() {
+        let vector = Vector::new(-3.0, 4.0, 5.0);
+        assert_eq!(translation(5.0, -3.0, 2.0) * vector, vector);
+        assert_eq!(
+            scaling(2.0, 3.0, 4.0) * vector,
+            Vector::new(-6.0, 12.0, 20.0)
+        );
+    }
+
+    #[test]
+    fn vector_display() {
+        assert_eq!(
+            Vector::new(1.0, -2.0, 0.5).to_string(),
+            "vector(1.0, -2.0, 0.5)"
+        );
+    }
+}
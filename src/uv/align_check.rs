@@ -0,0 +1,77 @@
+use super::UvPattern;
+use crate::color::Color;
+
+/// Texture with a distinct color in each corner, used to check the orientation of a mapping.
+#[derive(Debug)]
+pub struct UvAlignCheck {
+    pub main: Color,
+    pub upper_left: Color,
+    pub upper_right: Color,
+    pub bottom_left: Color,
+    pub bottom_right: Color,
+}
+
+impl UvAlignCheck {
+    /// Create a texture that is `main` everywhere except for a small square of color in each corner.
+    pub fn new(
+        main: Color,
+        upper_left: Color,
+        upper_right: Color,
+        bottom_left: Color,
+        bottom_right: Color,
+    ) -> Self {
+        Self {
+            main,
+            upper_left,
+            upper_right,
+            bottom_left,
+            bottom_right,
+        }
+    }
+}
+
+impl UvPattern for UvAlignCheck {
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        if v > 0.8 {
+            if u < 0.2 {
+                return self.upper_left;
+            }
+            if u > 0.8 {
+                return self.upper_right;
+            }
+        } else if v < 0.2 {
+            if u < 0.2 {
+                return self.bottom_left;
+            }
+            if u > 0.8 {
+                return self.bottom_right;
+            }
+        }
+        self.main
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uv_align_check_uv_pattern_at() {
+        let main = Color::new(1.0, 1.0, 1.0);
+        let upper_left = Color::new(1.0, 0.0, 0.0);
+        let upper_right = Color::new(1.0, 1.0, 0.0);
+        let bottom_left = Color::new(0.0, 1.0, 0.0);
+        let bottom_right = Color::new(0.0, 1.0, 1.0);
+        let pattern = UvAlignCheck::new(main, upper_left, upper_right, bottom_left, bottom_right);
+        let cases = [
+            ((0.5, 0.5), main),
+            ((0.1, 0.9), upper_left),
+            ((0.9, 0.9), upper_right),
+            ((0.1, 0.1), bottom_left),
+            ((0.9, 0.1), bottom_right),
+        ];
+        for ((u, v), expected) in cases {
+            assert!(pattern.uv_pattern_at(u, v).is_equal_to(&expected));
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::UvPattern;
+use crate::color::Color;
+
+/// Checkerboard in texture space, with `width` squares along u and `height` squares along v.
+#[derive(Debug)]
+pub struct UvCheckers {
+    pub width: f64,
+    pub height: f64,
+    pub a: Color,
+    pub b: Color,
+}
+
+impl UvCheckers {
+    /// Create a checkerboard of `width` by `height` squares that is `a` at the origin and alternates with `b`.
+    pub fn new(width: f64, height: f64, a: Color, b: Color) -> Self {
+        Self {
+            width,
+            height,
+            a,
+            b,
+        }
+    }
+}
+
+impl UvPattern for UvCheckers {
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        let u2 = (u * self.width).floor() as i64;
+        let v2 = (v * self.height).floor() as i64;
+        if (u2 + v2).rem_euclid(2) == 0 {
+            self.a
+        } else {
+            self.b
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uv_checkers_uv_pattern_at() {
+        let black = Color::new(0.0, 0.0, 0.0);
+        let white = Color::new(1.0, 1.0, 1.0);
+        let checkers = UvCheckers::new(2.0, 2.0, black, white);
+        let cases = [
+            ((0.0, 0.0), black),
+            ((0.5, 0.0), white),
+            ((0.0, 0.5), white),
+            ((0.5, 0.5), black),
+            ((1.0, 1.0), black),
+        ];
+        for ((u, v), expected) in cases {
+            assert!(checkers.uv_pattern_at(u, v).is_equal_to(&expected));
+        }
+    }
+}
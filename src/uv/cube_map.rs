@@ -0,0 +1,245 @@
+use super::UvPattern;
+use crate::color::Color;
+use crate::patterns::{Pattern, PatternProperties};
+use crate::tuple::Tuple;
+
+/// Face of an axis-aligned cube centered at the origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CubeFace {
+    /// Face at x = -1.
+    Left,
+    /// Face at x = 1.
+    Right,
+    /// Face at z = 1.
+    Front,
+    /// Face at z = -1.
+    Back,
+    /// Face at y = 1.
+    Up,
+    /// Face at y = -1.
+    Down,
+}
+
+impl CubeFace {
+    /// Find the face of the cube that a point lies on, or that a direction from the center points toward.
+    pub fn from_point(point: &Tuple) -> Self {
+        let coord = point.x.abs().max(point.y.abs()).max(point.z.abs());
+        if coord == point.x {
+            Self::Right
+        } else if coord == -point.x {
+            Self::Left
+        } else if coord == point.y {
+            Self::Up
+        } else if coord == -point.y {
+            Self::Down
+        } else if coord == point.z {
+            Self::Front
+        } else {
+            Self::Back
+        }
+    }
+
+    /// Map a point on this face of a cube spanning -1 to 1 to texture coordinates.
+    /// Each face is seen from outside the cube, with up toward y = 1 for the side faces and toward z = -1 for the top.
+    pub fn uv(self, point: &Tuple) -> (f64, f64) {
+        let (u, v) = match self {
+            Self::Front => (point.x + 1.0, point.y + 1.0),
+            Self::Back => (1.0 - point.x, point.y + 1.0),
+            Self::Left => (point.z + 1.0, point.y + 1.0),
+            Self::Right => (1.0 - point.z, point.y + 1.0),
+            Self::Up => (point.x + 1.0, 1.0 - point.z),
+            Self::Down => (point.x + 1.0, point.z + 1.0),
+        };
+        (u.rem_euclid(2.0) / 2.0, v.rem_euclid(2.0) / 2.0)
+    }
+}
+
+/// Pattern that wraps a cube with a separate texture on each of its six faces.
+///
+/// Points are projected from the origin onto the cube, so a cube map can also texture spheres or be
+/// looked up by direction, as for the environment surrounding a world.
+#[derive(Debug)]
+pub struct CubeMap {
+    properties: PatternProperties,
+    left: Box<dyn UvPattern>,
+    front: Box<dyn UvPattern>,
+    right: Box<dyn UvPattern>,
+    back: Box<dyn UvPattern>,
+    up: Box<dyn UvPattern>,
+    down: Box<dyn UvPattern>,
+}
+
+impl CubeMap {
+    /// Create a cube map from the textures of its six faces.
+    pub fn new(
+        left: impl Into<Box<dyn UvPattern>>,
+        front: impl Into<Box<dyn UvPattern>>,
+        right: impl Into<Box<dyn UvPattern>>,
+        back: impl Into<Box<dyn UvPattern>>,
+        up: impl Into<Box<dyn UvPattern>>,
+        down: impl Into<Box<dyn UvPattern>>,
+    ) -> Self {
+        Self {
+            properties: PatternProperties::default(),
+            left: left.into(),
+            front: front.into(),
+            right: right.into(),
+            back: back.into(),
+            up: up.into(),
+            down: down.into(),
+        }
+    }
+
+    /// Get the texture of a face.
+    pub fn face(&self, face: CubeFace) -> &dyn UvPattern {
+        match face {
+            CubeFace::Left => self.left.as_ref(),
+            CubeFace::Front => self.front.as_ref(),
+            CubeFace::Right => self.right.as_ref(),
+            CubeFace::Back => self.back.as_ref(),
+            CubeFace::Up => self.up.as_ref(),
+            CubeFace::Down => self.down.as_ref(),
+        }
+    }
+}
+
+impl Pattern for CubeMap {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn local_pattern_at(&self, pattern_point: &Tuple) -> Color {
+        // Project the point from the origin onto the surface of the cube
+        let coord = pattern_point
+            .x
+            .abs()
+            .max(pattern_point.y.abs())
+            .max(pattern_point.z.abs());
+        let scale = if coord > 0.0 { 1.0 / coord } else { 1.0 };
+        let point = Tuple::new_point(
+            pattern_point.x * scale,
+            pattern_point.y * scale,
+            pattern_point.z * scale,
+        );
+        let face = CubeFace::from_point(&point);
+        let (u, v) = face.uv(&point);
+        self.face(face).uv_pattern_at(u, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::uv::UvAlignCheck;
+
+    #[test]
+    fn cube_face_from_point() {
+        let cases = [
+            ((-1.0, 0.5, -0.25), CubeFace::Left),
+            ((1.1, -0.75, 0.8), CubeFace::Right),
+            ((0.1, 0.6, 0.9), CubeFace::Front),
+            ((-0.7, 0.0, -2.0), CubeFace::Back),
+            ((0.5, 1.0, 0.9), CubeFace::Up),
+            ((-0.2, -1.3, 1.1), CubeFace::Down),
+        ];
+        for ((x, y, z), expected) in cases {
+            assert_eq!(CubeFace::from_point(&Tuple::new_point(x, y, z)), expected);
+        }
+    }
+
+    #[test]
+    fn cube_face_uv() {
+        let cases = [
+            (CubeFace::Front, (-0.5, 0.5, 1.0), (0.25, 0.75)),
+            (CubeFace::Front, (0.5, -0.5, 1.0), (0.75, 0.25)),
+            (CubeFace::Back, (0.5, 0.5, -1.0), (0.25, 0.75)),
+            (CubeFace::Back, (-0.5, -0.5, -1.0), (0.75, 0.25)),
+            (CubeFace::Left, (-1.0, 0.5, -0.5), (0.25, 0.75)),
+            (CubeFace::Left, (-1.0, -0.5, 0.5), (0.75, 0.25)),
+            (CubeFace::Right, (1.0, 0.5, 0.5), (0.25, 0.75)),
+            (CubeFace::Right, (1.0, -0.5, -0.5), (0.75, 0.25)),
+            (CubeFace::Up, (-0.5, 1.0, -0.5), (0.25, 0.75)),
+            (CubeFace::Up, (0.5, 1.0, 0.5), (0.75, 0.25)),
+            (CubeFace::Down, (-0.5, -1.0, 0.5), (0.25, 0.75)),
+            (CubeFace::Down, (0.5, -1.0, -0.5), (0.75, 0.25)),
+        ];
+        for (face, (x, y, z), (expected_u, expected_v)) in cases {
+            let (u, v) = face.uv(&Tuple::new_point(x, y, z));
+            assert_approx_eq!(u, expected_u, "{face:?}");
+            assert_approx_eq!(v, expected_v, "{face:?}");
+        }
+    }
+
+    #[test]
+    fn cube_map_pattern_at() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let yellow = Color::new(1.0, 1.0, 0.0);
+        let brown = Color::new(1.0, 0.5, 0.0);
+        let green = Color::new(0.0, 1.0, 0.0);
+        let cyan = Color::new(0.0, 1.0, 1.0);
+        let blue = Color::new(0.0, 0.0, 1.0);
+        let purple = Color::new(1.0, 0.0, 1.0);
+        let white = Color::new(1.0, 1.0, 1.0);
+        let pattern = CubeMap::new(
+            UvAlignCheck::new(yellow, cyan, red, blue, brown),
+            UvAlignCheck::new(cyan, red, yellow, brown, green),
+            UvAlignCheck::new(red, yellow, purple, green, white),
+            UvAlignCheck::new(green, purple, cyan, white, blue),
+            UvAlignCheck::new(brown, cyan, purple, red, yellow),
+            UvAlignCheck::new(purple, brown, green, blue, white),
+        );
+        let cases = [
+            ((-1.0, 0.0, 0.0), yellow),
+            ((-1.0, 0.9, -0.9), cyan),
+            ((-1.0, 0.9, 0.9), red),
+            ((-1.0, -0.9, -0.9), blue),
+            ((-1.0, -0.9, 0.9), brown),
+            ((0.0, 0.0, 1.0), cyan),
+            ((-0.9, 0.9, 1.0), red),
+            ((0.9, 0.9, 1.0), yellow),
+            ((-0.9, -0.9, 1.0), brown),
+            ((0.9, -0.9, 1.0), green),
+            ((1.0, 0.0, 0.0), red),
+            ((1.0, 0.9, 0.9), yellow),
+            ((1.0, 0.9, -0.9), purple),
+            ((1.0, -0.9, 0.9), green),
+            ((1.0, -0.9, -0.9), white),
+            ((0.0, 0.0, -1.0), green),
+            ((0.9, 0.9, -1.0), purple),
+            ((-0.9, 0.9, -1.0), cyan),
+            ((0.9, -0.9, -1.0), white),
+            ((-0.9, -0.9, -1.0), blue),
+            ((0.0, 1.0, 0.0), brown),
+            ((-0.9, 1.0, -0.9), cyan),
+            ((0.9, 1.0, -0.9), purple),
+            ((-0.9, 1.0, 0.9), red),
+            ((0.9, 1.0, 0.9), yellow),
+            ((0.0, -1.0, 0.0), purple),
+            ((-0.9, -1.0, 0.9), brown),
+            ((0.9, -1.0, 0.9), green),
+            ((-0.9, -1.0, -0.9), blue),
+            ((0.9, -1.0, -0.9), white),
+        ];
+        for ((x, y, z), expected) in cases {
+            assert!(
+                pattern
+                    .pattern_at(&Tuple::new_point(x, y, z))
+                    .is_equal_to(&expected),
+                "({x}, {y}, {z})"
+            );
+        }
+
+        // Points inside or outside the cube are projected onto it
+        assert!(pattern
+            .pattern_at(&Tuple::new_point(0.0, 0.0, 10.0))
+            .is_equal_to(&cyan));
+        assert!(pattern
+            .pattern_at(&Tuple::new_point(-0.09, 0.09, 0.1))
+            .is_equal_to(&red));
+    }
+}
@@ -0,0 +1,92 @@
+use super::UvPattern;
+use crate::canvas::Canvas;
+use crate::color::Color;
+
+/// Texture backed by an image, such as a photograph or a map of the earth, loaded into a canvas.
+///
+/// Texture coordinates run from the bottom left corner of the image at (0, 0) to its top right corner
+/// at (1, 1), and each coordinate is looked up in the nearest pixel.
+#[derive(Debug)]
+pub struct ImageTexture {
+    canvas: Canvas,
+}
+
+impl ImageTexture {
+    /// Create a texture from the pixels of a canvas.
+    /// Panics if the canvas has no pixels, since there would be no color to look up.
+    pub fn new(canvas: Canvas) -> Self {
+        assert!(
+            canvas.width() > 0 && canvas.height() > 0,
+            "a texture needs at least one pixel"
+        );
+        Self { canvas }
+    }
+
+    /// Get the canvas holding the texture's pixels.
+    pub fn canvas(&self) -> &Canvas {
+        &self.canvas
+    }
+}
+
+impl UvPattern for ImageTexture {
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        // Flip v, since the first row of the canvas is the top of the image
+        let v = 1.0 - v;
+        let x = u.clamp(0.0, 1.0) * (self.canvas.width() - 1) as f64;
+        let y = v.clamp(0.0, 1.0) * (self.canvas.height() - 1) as f64;
+        self.canvas.pixel_at(x.round() as usize, y.round() as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::Pattern;
+    use crate::tuple::Tuple;
+    use crate::uv::{TextureMap, UvMapping};
+
+    #[test]
+    fn image_texture_uv_pattern_at() {
+        // The book's 10x10 image whose gray at column x and row y is ((x + y) % 10) / 10
+        let mut ppm = String::from("P3\n10 10\n10\n");
+        for y in 0..10 {
+            for x in 0..10 {
+                let value = (x + y) % 10;
+                ppm.push_str(&format!("{value} {value} {value}\n"));
+            }
+        }
+        let texture = ImageTexture::new(Canvas::read_ppm(&mut ppm.as_bytes()).unwrap());
+        let cases = [
+            ((0.0, 0.0), 0.9),
+            ((0.3, 0.0), 0.2),
+            ((0.6, 0.3), 0.1),
+            ((1.0, 1.0), 0.9),
+        ];
+        for ((u, v), expected) in cases {
+            assert!(texture
+                .uv_pattern_at(u, v)
+                .is_equal_to(&Color::new(expected, expected, expected)));
+        }
+    }
+
+    #[test]
+    fn image_texture_through_texture_map() {
+        let mut canvas = Canvas::new(2, 2);
+        let red = Color::new(1.0, 0.0, 0.0);
+        canvas.write_pixel(0, 1, red);
+        let pattern = TextureMap::new(ImageTexture::new(canvas), UvMapping::Planar);
+        // The bottom left corner of the image is mapped to the origin of the plane
+        assert!(pattern
+            .pattern_at(&Tuple::new_point(0.1, 0.0, 0.1))
+            .is_equal_to(&red));
+        assert!(pattern
+            .pattern_at(&Tuple::new_point(0.9, 0.0, 0.9))
+            .is_equal_to(&Color::new(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one pixel")]
+    fn image_texture_empty() {
+        ImageTexture::new(Canvas::new(0, 3));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::color::Color;
+use crate::tuple::Tuple;
+use std::f64::consts::PI;
+use std::fmt::Debug;
+
+pub mod align_check;
+pub mod checkers;
+pub mod cube_map;
+pub mod image_texture;
+pub mod texture_map;
+
+pub use align_check::UvAlignCheck;
+pub use checkers::UvCheckers;
+pub use cube_map::{CubeFace, CubeMap};
+pub use image_texture::ImageTexture;
+pub use texture_map::TextureMap;
+
+/// Function from two-dimensional texture coordinates to colors.
+///
+/// The coordinates `u` and `v` both range from 0 to 1. A `TextureMap` wraps a `UvPattern` around a shape
+/// using a `UvMapping`, so the pattern follows the surface instead of cutting through it.
+pub trait UvPattern: Debug + Send + Sync {
+    /// Compute the color of the pattern at the texture coordinates `u` and `v`.
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color;
+}
+
+impl<P: UvPattern + 'static> From<P> for Box<dyn UvPattern> {
+    /// Box a UV pattern so it can be used by a texture map.
+    fn from(pattern: P) -> Self {
+        Box::new(pattern)
+    }
+}
+
+/// Projection from points in pattern space to texture coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UvMapping {
+    /// Wrap the texture around a unit sphere, like a map of the earth. See `spherical_map`.
+    Spherical,
+    /// Tile the texture across the xz plane. See `planar_map`.
+    Planar,
+    /// Wrap the texture around the y axis and tile it along y. See `cylindrical_map`.
+    Cylindrical,
+}
+
+impl UvMapping {
+    /// Compute the texture coordinates of a point in pattern space.
+    pub fn map(self, point: &Tuple) -> (f64, f64) {
+        match self {
+            Self::Spherical => spherical_map(point),
+            Self::Planar => planar_map(point),
+            Self::Cylindrical => cylindrical_map(point),
+        }
+    }
+}
+
+/// Map a point on a sphere centered at the origin to texture coordinates.
+/// `u` increases counterclockwise around the y axis as seen from above, and `v` increases from the south pole to the north pole.
+pub fn spherical_map(point: &Tuple) -> (f64, f64) {
+    // The azimuthal angle is measured around the y axis, from -π to π, and the polar angle from the
+    // north pole, from 0 to π
+    let (_, theta, phi) = point.to_spherical();
+    let raw_u = theta / (2.0 * PI);
+    // Flip u so that it increases counterclockwise when viewed from above
+    let u = 1.0 - (raw_u + 0.5);
+    let v = 1.0 - phi / PI;
+    (u, v)
+}
+
+/// Map a point to texture coordinates by tiling the texture across the xz plane in unit squares.
+pub fn planar_map(point: &Tuple) -> (f64, f64) {
+    (point.x.rem_euclid(1.0), point.z.rem_euclid(1.0))
+}
+
+/// Map a point to texture coordinates by wrapping the texture once around the y axis and tiling it every unit of y.
+pub fn cylindrical_map(point: &Tuple) -> (f64, f64) {
+    let theta = point.x.atan2(point.z);
+    let raw_u = theta / (2.0 * PI);
+    let u = 1.0 - (raw_u + 0.5);
+    let v = point.y.rem_euclid(1.0);
+    (u, v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    fn assert_uv(mapping: UvMapping, point: [f64; 3], expected: [f64; 2]) {
+        let [x, y, z] = point;
+        let (u, v) = mapping.map(&Tuple::new_point(x, y, z));
+        assert_approx_eq!(u, expected[0], "u at ({x}, {y}, {z})");
+        assert_approx_eq!(v, expected[1], "v at ({x}, {y}, {z})");
+    }
+
+    #[test]
+    fn spherical_map_sphere() {
+        let cases = [
+            ([0.0, 0.0, -1.0], [0.0, 0.5]),
+            ([1.0, 0.0, 0.0], [0.25, 0.5]),
+            ([0.0, 0.0, 1.0], [0.5, 0.5]),
+            ([-1.0, 0.0, 0.0], [0.75, 0.5]),
+            ([0.0, 1.0, 0.0], [0.5, 1.0]),
+            ([0.0, -1.0, 0.0], [0.5, 0.0]),
+            ([FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0], [0.25, 0.75]),
+        ];
+        for (point, expected) in cases {
+            assert_uv(UvMapping::Spherical, point, expected);
+        }
+    }
+
+    #[test]
+    fn planar_map_plane() {
+        let cases = [
+            ([0.25, 0.0, 0.5], [0.25, 0.5]),
+            ([0.25, 0.0, -0.25], [0.25, 0.75]),
+            ([0.25, 0.5, -0.25], [0.25, 0.75]),
+            ([1.25, 0.0, 0.5], [0.25, 0.5]),
+            ([0.25, 0.0, -1.75], [0.25, 0.25]),
+            ([1.0, 0.0, -1.0], [0.0, 0.0]),
+            ([0.0, 0.0, 0.0], [0.0, 0.0]),
+        ];
+        for (point, expected) in cases {
+            assert_uv(UvMapping::Planar, point, expected);
+        }
+    }
+
+    #[test]
+    fn cylindrical_map_cylinder() {
+        let cases = [
+            ([0.0, 0.0, -1.0], [0.0, 0.0]),
+            ([0.0, 0.5, -1.0], [0.0, 0.5]),
+            ([0.0, 1.0, -1.0], [0.0, 0.0]),
+            ([FRAC_1_SQRT_2, 0.5, -FRAC_1_SQRT_2], [0.125, 0.5]),
+            ([1.0, 0.5, 0.0], [0.25, 0.5]),
+            ([FRAC_1_SQRT_2, 0.5, FRAC_1_SQRT_2], [0.375, 0.5]),
+            ([0.0, -0.25, 1.0], [0.5, 0.75]),
+            ([-FRAC_1_SQRT_2, 0.5, FRAC_1_SQRT_2], [0.625, 0.5]),
+            ([-1.0, 1.25, 0.0], [0.75, 0.25]),
+            ([-FRAC_1_SQRT_2, 0.5, -FRAC_1_SQRT_2], [0.875, 0.5]),
+        ];
+        for (point, expected) in cases {
+            assert_uv(UvMapping::Cylindrical, point, expected);
+        }
+    }
+}
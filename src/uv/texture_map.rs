@@ -0,0 +1,76 @@
+use super::{UvMapping, UvPattern};
+use crate::color::Color;
+use crate::patterns::{Pattern, PatternProperties};
+use crate::tuple::Tuple;
+
+/// Pattern that wraps a two-dimensional `UvPattern` around a shape using a `UvMapping`.
+#[derive(Debug)]
+pub struct TextureMap {
+    properties: PatternProperties,
+    uv_pattern: Box<dyn UvPattern>,
+    mapping: UvMapping,
+}
+
+impl TextureMap {
+    /// Create a pattern that maps points to texture coordinates with `mapping` and colors them with `uv_pattern`.
+    pub fn new(uv_pattern: impl Into<Box<dyn UvPattern>>, mapping: UvMapping) -> Self {
+        Self {
+            properties: PatternProperties::default(),
+            uv_pattern: uv_pattern.into(),
+            mapping,
+        }
+    }
+
+    /// Get the projection from pattern space to texture coordinates.
+    pub fn mapping(&self) -> UvMapping {
+        self.mapping
+    }
+}
+
+impl Pattern for TextureMap {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn local_pattern_at(&self, pattern_point: &Tuple) -> Color {
+        let (u, v) = self.mapping.map(pattern_point);
+        self.uv_pattern.uv_pattern_at(u, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uv::UvCheckers;
+
+    #[test]
+    fn texture_map_spherical() {
+        let black = Color::new(0.0, 0.0, 0.0);
+        let white = Color::new(1.0, 1.0, 1.0);
+        let pattern = TextureMap::new(
+            UvCheckers::new(16.0, 8.0, black, white),
+            UvMapping::Spherical,
+        );
+        let cases = [
+            ((0.4315, 0.4670, 0.7719), white),
+            ((-0.9654, 0.2552, -0.0534), black),
+            ((0.1039, 0.7090, 0.6975), white),
+            ((-0.4986, -0.7856, -0.3663), black),
+            ((-0.0317, -0.9395, 0.3411), black),
+            ((0.4809, -0.7721, 0.4154), black),
+            ((0.0285, -0.9612, -0.2745), black),
+            ((-0.5734, -0.2162, -0.7903), white),
+            ((0.7688, -0.1470, 0.6223), black),
+            ((-0.7652, 0.2175, 0.6060), black),
+        ];
+        for ((x, y, z), expected) in cases {
+            assert!(pattern
+                .pattern_at(&Tuple::new_point(x, y, z))
+                .is_equal_to(&expected));
+        }
+    }
+}
@@ -0,0 +1,1466 @@
+use crate::color::Color;
+use crate::intersection::{Computations, Intersections, DEFAULT_SHADOW_BIAS};
+use crate::light::{lighting_with_visibility, Falloff, Light, Material, Medium, PointLight};
+use crate::patterns::Pattern;
+use crate::random::{position_hash, Rng};
+use crate::ray::Ray;
+use crate::shapes::{BoundingBox, Shape};
+use crate::tuple::Tuple;
+
+/// Default number of times a ray may bounce between reflective surfaces before it is no longer followed.
+pub const MAX_RECURSION_DEPTH: usize = 5;
+
+/// Number of jittered rays averaged into the reflections of rough surfaces seen directly from the camera.
+/// Reflections seen in other reflections take a single ray, so the work does not multiply at every bounce.
+pub const GLOSSY_SAMPLES: usize = 8;
+
+/// Default number of rays cast toward each glowing object to light the surfaces around it.
+pub const EMISSIVE_SAMPLES: usize = 16;
+
+/// Collection of objects and the light illuminating them.
+///
+/// Rays that miss every object see the `environment`, a pattern looked up by the ray's direction as if it
+/// were a point on a unit sphere around the world. A `CubeMap` or a spherical `TextureMap` makes a skybox.
+/// With `fog`, the space between the objects is filled with a medium that fades everything with distance.
+#[derive(Debug)]
+pub struct World {
+    pub objects: Vec<Box<dyn Shape>>,
+    pub light: Option<Box<dyn Light>>,
+    pub environment: Option<Box<dyn Pattern>>,
+    pub fog: Option<Medium>,
+    /// Distance by which rays cast from a surface start above or below it. Scenes built at a large scale
+    /// need a larger bias to keep surfaces from shadowing themselves, and scenes at a small scale a smaller
+    /// one to keep shadows attached to the objects casting them.
+    pub shadow_bias: f64,
+    /// Whether light passes through transparent objects, tinted by their color, so they cast colored
+    /// shadows. When off, every object casts a black shadow as in the book.
+    pub transparent_shadows: bool,
+    /// Number of times a ray may be reflected or refracted before it is no longer followed, unless the
+    /// material of the surface it reaches sets its own `max_depth`.
+    pub max_depth: usize,
+    /// Number of rays cast from every shaded point toward each object with an emissive material, which
+    /// light the world like area lights. More rays give smoother soft shadows, and 0 makes glowing
+    /// objects only glow without lighting anything.
+    pub emissive_samples: usize,
+}
+
+impl Default for World {
+    /// Create an empty world with no light, the default shadow bias and maximum depth, and transparent
+    /// shadows.
+    fn default() -> Self {
+        Self {
+            objects: Vec::new(),
+            light: None,
+            environment: None,
+            fog: None,
+            shadow_bias: DEFAULT_SHADOW_BIAS,
+            transparent_shadows: true,
+            max_depth: MAX_RECURSION_DEPTH,
+            emissive_samples: EMISSIVE_SAMPLES,
+        }
+    }
+}
+
+impl World {
+    /// Create a world with no objects and no light.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Intersect a ray with every object in the world.
+    pub fn intersect(&self, ray: &Ray) -> Intersections<'_> {
+        let mut intersections = Intersections::default();
+        for object in &self.objects {
+            intersections.extend(object.intersect(ray));
+        }
+        intersections
+    }
+
+    /// Compute the color at a precomputed intersection, following at most `remaining` reflections and refractions.
+    /// Without a light or glowing objects, the surface itself is not illuminated and only its reflections,
+    /// refractions and the light it emits are seen.
+    pub fn shade_hit(&self, comps: &Computations, remaining: usize) -> Color {
+        self.shade_hit_within(comps, Depth::new(remaining, 0))
+    }
+
+    fn shade_hit_within(&self, comps: &Computations, depth: Depth) -> Color {
+        let surface = self.surface_color(comps, depth.seed);
+        let reflected = self.reflected_color_within(comps, depth);
+        let refracted = self.refracted_color_within(comps, depth);
+
+        // Surfaces that are both reflective and transparent reflect more light at grazing angles
+        let material = comps.object.material();
+        if material.reflective > 0.0 && material.transparency > 0.0 {
+            let reflectance = comps.schlick();
+            surface + reflected * reflectance + refracted * (1.0 - reflectance)
+        } else {
+            surface + reflected + refracted
+        }
+    }
+
+    /// Compute the color of the surface itself at a precomputed intersection, lit by the world's light and
+    /// its glowing objects and with the light it emits, leaving out reflections and refractions. The points
+    /// sampled on lights with a size are drawn from the streams of `seed`.
+    pub fn surface_color(&self, comps: &Computations, seed: u64) -> Color {
+        let lit = match &self.light {
+            Some(light) => lighting_with_visibility(
+                comps.object.material(),
+                comps.object,
+                light.as_ref(),
+                &comps.over_point,
+                &comps.eyev,
+                &comps.normalv,
+                self.light_visibility(&comps.over_point, seed),
+            ),
+            None => Color::default(),
+        };
+        lit + self.emitted_light(comps, seed) + comps.object.material().emissive
+    }
+
+    /// Compute the light shed on the surface at a precomputed intersection by the objects of the world
+    /// with an emissive material, other than the surface's own object.
+    ///
+    /// Each glowing object is sampled like an area light: rays are cast toward random points within its
+    /// bounds, and every ray reaching it lights the surface like a point light of its emissive color
+    /// where it lands, seen through whatever lies in between. The light fades with the inverse square of
+    /// the distance beyond the object's size. Objects without finite bounds, such as planes, only glow.
+    fn emitted_light(&self, comps: &Computations, seed: u64) -> Color {
+        let mut emitters = self
+            .objects
+            .iter()
+            .filter(|object| {
+                !object.material().emissive.is_equal_to(&Color::default())
+                    && !object.includes(comps.object)
+            })
+            .peekable();
+        if self.emissive_samples == 0 || emitters.peek().is_none() {
+            return Color::default();
+        }
+        // Ambient light is the world's light's business, so the glowing objects add none
+        let material = Material {
+            ambient: 0.0,
+            ..comps.object.material().clone()
+        };
+        let point = comps.over_point;
+        let mut rng = Rng::with_stream(seed, position_hash(&point));
+        let mut total = Color::default();
+        for emitter in emitters {
+            let bounds = BoundingBox::parent_space_bounds_of(emitter.as_ref());
+            if !bounds.is_finite() {
+                continue;
+            }
+            let (min, extent) = (*bounds.min(), *bounds.max() - bounds.min());
+            let falloff = Falloff::InverseSquare {
+                radius: extent.x.max(extent.y).max(extent.z) / 2.0,
+            };
+            let (mut sum, mut reached) = (Color::default(), 0);
+            for _ in 0..self.emissive_samples {
+                let target = Tuple::new_point(
+                    min.x + extent.x * rng.next_f64(),
+                    min.y + extent.y * rng.next_f64(),
+                    min.z + extent.z * rng.next_f64(),
+                );
+                let direction = (target - point).normalize();
+                let ray = Ray::new(point, direction);
+                let intersections = emitter.intersect(&ray);
+                let Some(hit) = intersections.hit() else {
+                    continue;
+                };
+                reached += 1;
+                let mut light =
+                    PointLight::new(ray.position(hit.t), hit.object.material().emissive);
+                light.falloff = falloff;
+                sum = sum
+                    + lighting_with_visibility(
+                        &material,
+                        comps.object,
+                        &light,
+                        &point,
+                        &comps.eyev,
+                        &comps.normalv,
+                        self.transmission(&point, &direction, hit.t),
+                    );
+            }
+            if reached > 0 {
+                total = total + sum * (1.0 / reached as f64);
+            }
+        }
+        total
+    }
+
+    /// Compute the color reflected by the surface at a precomputed intersection.
+    /// Once `remaining` reaches zero, reflections are no longer followed, which stops infinite recursion
+    /// between surfaces that reflect each other.
+    pub fn reflected_color(&self, comps: &Computations, remaining: usize) -> Color {
+        self.reflected_color_within(comps, Depth::new(remaining, 0))
+    }
+
+    fn reflected_color_within(&self, comps: &Computations, depth: Depth) -> Color {
+        let material = comps.object.material();
+        if !depth.follows(material) || material.reflective == 0.0 {
+            return Color::default();
+        }
+        let reflected = if material.roughness > 0.0 {
+            let samples = if depth.bounces == 0 {
+                GLOSSY_SAMPLES
+            } else {
+                1
+            };
+            let mut rng = Rng::with_stream(depth.seed, position_hash(&comps.over_point));
+            let sum = (0..samples).fold(Color::default(), |sum, _| {
+                let ray = comps.glossy_reflect_ray(material.roughness, &mut rng);
+                sum + self.color_at_within(&ray, depth.next())
+            });
+            sum * (1.0 / samples as f64)
+        } else {
+            self.color_at_within(&comps.reflect_ray(), depth.next())
+        };
+        reflected * material.reflection_tint(comps.object, &comps.over_point) * material.reflective
+    }
+
+    /// Compute the color refracted through the surface at a precomputed intersection using Snell's law.
+    /// Light undergoing total internal reflection is not refracted and contributes no color.
+    pub fn refracted_color(&self, comps: &Computations, remaining: usize) -> Color {
+        self.refracted_color_within(comps, Depth::new(remaining, 0))
+    }
+
+    fn refracted_color_within(&self, comps: &Computations, depth: Depth) -> Color {
+        let material = comps.object.material();
+        if !depth.follows(material) || material.transparency == 0.0 {
+            return Color::default();
+        }
+        match comps.refract_ray() {
+            Some(refract_ray) => {
+                self.color_at_within(&refract_ray, depth.next()) * material.transparency
+            }
+            None => Color::default(),
+        }
+    }
+
+    /// Test if a point is in shadow by casting a ray from it toward the light
+    /// and checking for an opaque object that casts shadows between the point and the light.
+    /// Transparent objects only dim the light, so points behind them are not in shadow.
+    pub fn is_shadowed(&self, point: &Tuple) -> bool {
+        let Some(light) = &self.light else {
+            return false;
+        };
+        let transmission = self.transmission(
+            point,
+            &light.direction_from(point),
+            light.distance_from(point),
+        );
+        transmission.is_equal_to(&Color::default())
+    }
+
+    /// Compute the fraction of the light that reaches a point in each channel, casting a ray from it
+    /// toward each of the light's samples. Points lit by lights with a size can be partly in shadow, and
+    /// light passing through transparent objects is tinted by them. The samples are drawn from the stream
+    /// of `seed` picked by the point, so renders with the same seed are identical.
+    pub fn light_visibility(&self, point: &Tuple, seed: u64) -> Color {
+        let unblocked = Color::new(1.0, 1.0, 1.0);
+        let Some(light) = &self.light else {
+            return unblocked;
+        };
+        let samples = light.samples_from(point, &mut Rng::with_stream(seed, position_hash(point)));
+        if samples.is_empty() {
+            return unblocked;
+        }
+        let sum = samples
+            .iter()
+            .fold(Color::default(), |sum, (direction, distance)| {
+                sum + self.transmission(point, direction, *distance)
+            });
+        sum * (1.0 / samples.len() as f64)
+    }
+
+    /// Compute the fraction of light in each channel that reaches a point from `distance` along
+    /// `direction`, past the objects that cast shadows. Each transparent object in the way lets through
+    /// its transparency, tinted by its material's color (or pattern) where the light leaves it, so dark
+    /// glass casts a dark shadow. Opaque objects let nothing through. Without transparent shadows, every
+    /// object lets nothing through.
+    fn transmission(&self, point: &Tuple, direction: &Tuple, distance: f64) -> Color {
+        let ray = Ray::new(*point, *direction);
+        let mut transmission = Color::new(1.0, 1.0, 1.0);
+        let mut crossed: Vec<&dyn Shape> = Vec::new();
+        for i in self.intersect(&ray).iter() {
+            // Volumes let light through, dimmed by their medium only where it is seen
+            let material = i.object.material();
+            if i.t < 0.0 || i.t >= distance || !i.object.casts_shadow() || material.medium.is_some()
+            {
+                continue;
+            }
+            if material.transparency <= 0.0 || !self.transparent_shadows {
+                return Color::default();
+            }
+            // Light crosses the surface of a closed object twice, but is only tinted by it once
+            if crossed.iter().any(|object| object.includes(i.object)) {
+                continue;
+            }
+            crossed.push(i.object);
+            let tint = material.color_at(i.object, &ray.position(i.t));
+            transmission = transmission * tint * material.transparency;
+        }
+        transmission
+    }
+
+    /// Compute the color seen along a ray, following at most the world's `max_depth` reflections and
+    /// refractions, or as many as the material of each surface reached allows. Random numbers, such as
+    /// the points sampled on lights with a size, are drawn from the streams of `seed`.
+    pub fn trace(&self, ray: &Ray, seed: u64) -> Color {
+        self.color_at_within(ray, Depth::new(self.max_depth, seed))
+    }
+
+    /// Compute the color seen along a ray, following at most `remaining` reflections, or as many as the
+    /// material of each surface reached allows.
+    /// If the ray hits nothing, the color is that of the environment in the ray's direction, or black without one.
+    /// Fog between the eye and what the ray sees is applied on top.
+    pub fn color_at(&self, ray: &Ray, remaining: usize) -> Color {
+        self.color_at_within(ray, Depth::new(remaining, 0))
+    }
+
+    /// Compute the color seen along a ray that has already gone through `bounces` reflections and
+    /// refractions from the camera, with the world's `max_depth` as the limit, drawing random numbers from
+    /// the streams of `seed`.
+    pub(crate) fn color_after(&self, ray: &Ray, bounces: usize, seed: u64) -> Color {
+        let depth = Depth {
+            bounces,
+            limit: self.max_depth,
+            seed,
+        };
+        self.color_at_within(ray, depth)
+    }
+
+    fn color_at_within(&self, ray: &Ray, depth: Depth) -> Color {
+        let intersections = self.intersect(ray);
+        let (color, distance) = match intersections.hit() {
+            Some(hit) => {
+                let comps =
+                    hit.prepare_computations_with_bias(ray, &intersections, self.shadow_bias);
+                let color = match &comps.object.material().medium {
+                    Some(medium) => self.volume_color(medium, ray, &comps, &intersections, depth),
+                    None => self.shade_hit_within(&comps, depth),
+                };
+                (color, hit.t * ray.direction.magnitude())
+            }
+            None => (self.environment_color(&ray.direction), f64::INFINITY),
+        };
+        match &self.fog {
+            Some(fog) => self.through_medium(fog, ray, distance, color, depth.seed),
+            None => color,
+        }
+    }
+
+    /// Compute the color seen where a ray enters or leaves a volume, looking through its surface.
+    /// Entering the volume, the medium covers whatever is behind it up to the next intersection, and a ray
+    /// leaving it, such as one cast from a camera within a cloud, has crossed the medium from its origin.
+    fn volume_color(
+        &self,
+        medium: &Medium,
+        ray: &Ray,
+        comps: &Computations,
+        intersections: &Intersections,
+        depth: Depth,
+    ) -> Color {
+        let through = Ray::new(comps.under_point, ray.direction);
+        let behind = self.color_at_within(&through, depth);
+        if comps.inside {
+            let distance = comps.t * ray.direction.magnitude();
+            return self.through_medium(medium, ray, distance, behind, depth.seed);
+        }
+        match intersections.iter().find(|i| i.t > comps.t) {
+            // The ray continuing through the volume crosses the medium when it leaves
+            Some(next) if comps.object.includes(next.object) => behind,
+            next => {
+                let distance = next.map_or(f64::INFINITY, |i| {
+                    (i.t - comps.t) * ray.direction.magnitude()
+                });
+                self.through_medium(medium, &through, distance, behind, depth.seed)
+            }
+        }
+    }
+
+    /// Compute the color seen through `distance` units of a medium along a ray, with `behind` seen at the end.
+    fn through_medium(
+        &self,
+        medium: &Medium,
+        ray: &Ray,
+        distance: f64,
+        behind: Color,
+        seed: u64,
+    ) -> Color {
+        let transmittance = medium.transmittance(distance);
+        let seen = behind * transmittance;
+        let light = match &self.light {
+            Some(light) if medium.samples > 0 && medium.density > 0.0 => light,
+            _ => return seen + medium.color * (1.0 - transmittance),
+        };
+
+        // Sum the light scattered toward the eye by each stretch of the medium that is lit
+        let direction = ray.direction.normalize();
+        let step = distance.min(medium.visibility()) / medium.samples as f64;
+        let mut scattered = Color::default();
+        for sample in 0..medium.samples {
+            let start = sample as f64 * step;
+            let point = ray.origin + direction * (start + step / 2.0);
+            let visibility = self.light_visibility(&point, seed);
+            let absorbed = medium.transmittance(start) - medium.transmittance(start + step);
+            scattered =
+                scattered + medium.color * light.intensity_at(&point) * visibility * absorbed;
+        }
+        seen + scattered
+    }
+
+    /// Compute the color of the environment seen in a direction, which is black if there is no environment.
+    pub fn environment_color(&self, direction: &Tuple) -> Color {
+        match &self.environment {
+            Some(environment) => {
+                let direction = direction.normalize();
+                environment.pattern_at(&Tuple::new_point(direction.x, direction.y, direction.z))
+            }
+            None => Color::default(),
+        }
+    }
+}
+
+/// Number of reflections and refractions a ray has gone through, and the limit on them for surfaces whose
+/// material sets none. Counting up from the camera, rather than down, lets materials raise the limit
+/// for the rays reaching them without letting surfaces that see each other recurse forever.
+/// The seed of the render the ray belongs to is carried along to every point the ray shades.
+#[derive(Debug, Clone, Copy)]
+struct Depth {
+    bounces: usize,
+    limit: usize,
+    seed: u64,
+}
+
+impl Depth {
+    /// Start counting for a ray cast from the camera of a render with the given seed.
+    fn new(limit: usize, seed: u64) -> Self {
+        Self {
+            bounces: 0,
+            limit,
+            seed,
+        }
+    }
+
+    /// Check whether a ray reaching a surface of the given material is reflected and refracted further.
+    fn follows(&self, material: &Material) -> bool {
+        self.bounces < material.max_depth.unwrap_or(self.limit)
+    }
+
+    /// Count one more reflection or refraction.
+    fn next(self) -> Self {
+        Self {
+            bounces: self.bounces + 1,
+            ..self
+        }
+    }
+}
+
+/// Create the world used throughout the book's tests: two concentric spheres lit from the upper left.
+#[cfg(test)]
+pub(crate) fn default_world() -> World {
+    use crate::light::{Material, PointLight};
+    use crate::shapes::Sphere;
+    use crate::transform::scaling;
+
+    let light = PointLight::new(
+        Tuple::new_point(-10.0, 10.0, -10.0),
+        Color::new(1.0, 1.0, 1.0),
+    );
+
+    let mut outer = Sphere::new();
+    outer.set_material(Material {
+        color: Color::new(0.8, 1.0, 0.6),
+        diffuse: 0.7,
+        specular: 0.2,
+        ..Material::default()
+    });
+
+    let mut inner = Sphere::new();
+    inner.set_transform(scaling(0.5, 0.5, 0.5)).unwrap();
+
+    World {
+        objects: vec![Box::new(outer), Box::new(inner)],
+        light: Some(Box::new(light)),
+        ..World::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::intersection::Intersection;
+    use crate::light::{AreaLight, DirectionalLight, Material, Medium, PointLight};
+    use crate::patterns::tests::TestPattern;
+    use crate::random::UniformSampler;
+    use crate::shapes::{Plane, Sphere};
+    use crate::transform::{rotation_x, scaling, translation};
+    use std::f64::consts::{FRAC_PI_2, SQRT_2};
+    use std::sync::Arc;
+
+    /// Prepare the computations for a ray that intersects the world only once, at `t` along the ray.
+    fn prepare_single_hit<'a>(t: f64, object: &'a dyn Shape, ray: &Ray) -> Computations<'a> {
+        let intersection = Intersection::new(t, object);
+        intersection.prepare_computations(ray, &Intersections::new(vec![intersection]))
+    }
+
+    #[test]
+    fn world_new() {
+        let world = World::new();
+        assert!(world.objects.is_empty());
+        assert!(world.light.is_none());
+        assert!(world.environment.is_none());
+    }
+
+    #[test]
+    fn world_intersect() {
+        let world = default_world();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let ts: Vec<f64> = world.intersect(&ray).iter().map(|i| i.t).collect();
+        assert_eq!(ts.len(), 4);
+        for (t, expected) in ts.iter().zip([4.0, 4.5, 5.5, 6.0]) {
+            assert_approx_eq!(t, expected);
+        }
+    }
+
+    #[test]
+    fn world_shade_hit() {
+        let world = default_world();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let shape = world.objects[0].as_ref();
+        let comps = prepare_single_hit(4.0, shape, &ray);
+        let color = world.shade_hit(&comps, MAX_RECURSION_DEPTH);
+        // The book's values are rounded to five decimal places.
+        assert_approx_eq!(color, Color::new(0.38066, 0.47583, 0.2855), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn world_shade_hit_inside() {
+        let mut world = default_world();
+        world.light = Some(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 0.25, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let shape = world.objects[1].as_ref();
+        let comps = prepare_single_hit(0.5, shape, &ray);
+        let color = world.shade_hit(&comps, MAX_RECURSION_DEPTH);
+        // The book's values are rounded to five decimal places.
+        assert_approx_eq!(color, Color::new(0.90498, 0.90498, 0.90498), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn world_shade_hit_without_light() {
+        let mut world = default_world();
+        world.light = None;
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let comps = prepare_single_hit(4.0, world.objects[0].as_ref(), &ray);
+        assert!(world
+            .shade_hit(&comps, MAX_RECURSION_DEPTH)
+            .is_equal_to(&Color::default()));
+    }
+
+    #[test]
+    fn world_color_at_miss() {
+        let world = default_world();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        assert!(world
+            .color_at(&ray, MAX_RECURSION_DEPTH)
+            .is_equal_to(&Color::new(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn world_color_at_miss_environment() {
+        let mut world = default_world();
+        world.environment = Some(Box::new(TestPattern::default()));
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 2.0, 0.0),
+        );
+        // The environment is looked up by the normalized direction, wherever the ray starts
+        assert!(world
+            .color_at(&ray, MAX_RECURSION_DEPTH)
+            .is_equal_to(&Color::new(0.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn world_reflected_color_environment() {
+        let mut world = World::new();
+        world.environment = Some(Box::new(TestPattern::default()));
+        let mut plane = Plane::new();
+        plane.set_material(Material {
+            color: Color::new(0.0, 0.0, 0.0),
+            ambient: 0.0,
+            reflective: 1.0,
+            ..Material::default()
+        });
+        world.objects.push(Box::new(plane));
+        // A mirror floor reflects the sky straight up
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 1.0, 0.0),
+            Tuple::new_vector(0.0, -1.0, 0.0),
+        );
+        assert!(world
+            .color_at(&ray, MAX_RECURSION_DEPTH)
+            .is_equal_to(&Color::new(0.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn world_trace_max_depth() {
+        let mut world = sky_above_floor(Material {
+            color: Color::new(0.0, 0.0, 0.0),
+            ambient: 0.0,
+            reflective: 1.0,
+            ..Material::default()
+        });
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 1.0, 0.0),
+            Tuple::new_vector(0.0, -1.0, 0.0),
+        );
+        let sky = Color::new(0.0, 1.0, 0.0);
+        let black = Color::new(0.0, 0.0, 0.0);
+        let cases = [
+            (MAX_RECURSION_DEPTH, None, sky),
+            (0, None, black),
+            // The material's limit wins over the world's, either way
+            (0, Some(1), sky),
+            (MAX_RECURSION_DEPTH, Some(0), black),
+        ];
+        for (max_depth, material_depth, expected) in cases {
+            world.max_depth = max_depth;
+            let material = Material {
+                max_depth: material_depth,
+                ..world.objects[0].material().clone()
+            };
+            world.objects[0].set_material(material);
+            assert!(
+                world.trace(&ray, 0).is_equal_to(&expected),
+                "{max_depth} {material_depth:?}"
+            );
+        }
+    }
+
+    /// Create a world whose sky is colored by its direction, above a floor with the given material.
+    fn sky_above_floor(material: Material) -> World {
+        let mut world = World::new();
+        world.environment = Some(Box::new(TestPattern::default()));
+        let mut plane = Plane::new();
+        plane.set_material(material);
+        world.objects.push(Box::new(plane));
+        world
+    }
+
+    #[test]
+    fn world_trace_metal() {
+        let world = sky_above_floor(Material {
+            color: Color::new(0.5, 0.25, 1.0),
+            ambient: 0.0,
+            reflective: 1.0,
+            metalness: 1.0,
+            ..Material::default()
+        });
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 1.0, 0.0),
+            Tuple::new_vector(0.0, -1.0, 0.0),
+        );
+        // The metal tints the sky straight above it with its own color
+        assert_approx_eq!(world.trace(&ray, 0), Color::new(0.0, 0.25, 0.0));
+    }
+
+    #[test]
+    fn world_trace_rough() {
+        let world = sky_above_floor(Material {
+            color: Color::new(0.0, 0.0, 0.0),
+            ambient: 0.0,
+            reflective: 1.0,
+            roughness: 0.5,
+            ..Material::default()
+        });
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 1.0, 0.0),
+            Tuple::new_vector(0.0, -1.0, 0.0),
+        );
+        // The reflection blurs the sky straight above with the sky up to 30 degrees away
+        let color = world.trace(&ray, 0);
+        assert!(color.green < 1.0 - crate::EPSILON, "{color}");
+        assert!(color.green > 0.75_f64.sqrt(), "{color}");
+        assert!(color.is_equal_to(&world.trace(&ray, 0)));
+        // The jittered rays are drawn from the render's seed
+        assert!((1..8).any(|seed| !world.trace(&ray, seed).is_equal_to(&color)));
+    }
+
+    #[test]
+    fn world_color_at_hit() {
+        let world = default_world();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let color = world.color_at(&ray, MAX_RECURSION_DEPTH);
+        // The book's values are rounded to five decimal places.
+        assert_approx_eq!(color, Color::new(0.38066, 0.47583, 0.2855), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn world_color_at_intersection_behind_ray() {
+        let mut world = default_world();
+        for object in &mut world.objects {
+            let material = Material {
+                ambient: 1.0,
+                ..object.material().clone()
+            };
+            object.set_material(material);
+        }
+        // The ray starts between the two spheres, so it should see the inner sphere's color
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.75),
+            Tuple::new_vector(0.0, 0.0, -1.0),
+        );
+        let inner_color = world.objects[1].material().color;
+        assert!(world
+            .color_at(&ray, MAX_RECURSION_DEPTH)
+            .is_equal_to(&inner_color));
+    }
+
+    #[test]
+    fn world_is_shadowed() {
+        let world = default_world();
+
+        // Nothing is collinear with the point and the light
+        assert!(!world.is_shadowed(&Tuple::new_point(0.0, 10.0, 0.0)));
+
+        // An object is between the point and the light
+        assert!(world.is_shadowed(&Tuple::new_point(10.0, -10.0, 10.0)));
+
+        // The object is behind the light
+        assert!(!world.is_shadowed(&Tuple::new_point(-20.0, 20.0, -20.0)));
+
+        // The object is behind the point
+        assert!(!world.is_shadowed(&Tuple::new_point(-2.0, 2.0, -2.0)));
+    }
+
+    #[test]
+    fn world_light_visibility() {
+        let mut world = default_world();
+        let gray = |value| Color::new(value, value, value);
+        assert_approx_eq!(
+            world.light_visibility(&Tuple::new_point(0.0, 10.0, 0.0), 0),
+            gray(1.0)
+        );
+        assert_approx_eq!(
+            world.light_visibility(&Tuple::new_point(10.0, -10.0, 10.0), 0),
+            gray(0.0)
+        );
+
+        let mut light = AreaLight::new(
+            Tuple::new_point(-0.5, -0.5, -5.0),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+            2,
+            Tuple::new_vector(0.0, 1.0, 0.0),
+            2,
+            Color::new(1.0, 1.0, 1.0),
+        );
+        light.sampler = Arc::new(UniformSampler);
+        world.light = Some(Box::new(light));
+        let cases = [
+            (Tuple::new_point(0.0, 0.0, 2.0), 0.0),
+            (Tuple::new_point(1.0, -1.0, 2.0), 0.25),
+            (Tuple::new_point(1.5, 0.0, 2.0), 0.5),
+            (Tuple::new_point(1.25, 1.25, 3.0), 0.75),
+            (Tuple::new_point(0.0, 0.0, -2.0), 1.0),
+        ];
+        for (point, expected) in cases {
+            assert_approx_eq!(world.light_visibility(&point, 0), gray(expected));
+        }
+    }
+
+    #[test]
+    fn world_light_visibility_seed() {
+        let mut world = default_world();
+        world.light = Some(Box::new(AreaLight::new(
+            Tuple::new_point(-0.5, -0.5, -5.0),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+            2,
+            Tuple::new_vector(0.0, 1.0, 0.0),
+            2,
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        // Jittered samples at the edge of the shadow depend on the seed, and only on the seed
+        let point = Tuple::new_point(1.0, -1.0, 2.0);
+        let visibilities: Vec<_> = (0..8)
+            .map(|seed| world.light_visibility(&point, seed))
+            .collect();
+        assert_approx_eq!(world.light_visibility(&point, 3), visibilities[3]);
+        assert!(visibilities
+            .iter()
+            .any(|visibility| !visibility.is_equal_to(&visibilities[0])));
+    }
+
+    #[test]
+    fn world_light_visibility_transparent() {
+        let mut glass = Sphere::new();
+        glass.set_material(Material {
+            color: Color::new(1.0, 0.2, 0.2),
+            transparency: 0.5,
+            ..Material::default()
+        });
+        let mut world = World::new();
+        world.objects.push(Box::new(glass));
+        world.light = Some(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 10.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+
+        // The light passes through both sides of the glass, but is only tinted once
+        let point = Tuple::new_point(0.0, -5.0, 0.0);
+        assert_approx_eq!(world.light_visibility(&point, 0), Color::new(0.5, 0.1, 0.1));
+        assert!(!world.is_shadowed(&point));
+
+        // Transparent objects further along tint the light again, and opaque objects block it
+        let mut pane = Sphere::new();
+        pane.set_transform(translation(0.0, 5.0, 0.0)).unwrap();
+        pane.set_material(Material {
+            color: Color::new(0.5, 1.0, 1.0),
+            transparency: 1.0,
+            ..Material::default()
+        });
+        world.objects.push(Box::new(pane));
+        assert_approx_eq!(
+            world.light_visibility(&point, 0),
+            Color::new(0.25, 0.1, 0.1)
+        );
+        world.objects.push(Box::new(Sphere::new()));
+        assert_approx_eq!(world.light_visibility(&point, 0), Color::new(0.0, 0.0, 0.0));
+        assert!(world.is_shadowed(&point));
+    }
+
+    #[test]
+    fn world_light_visibility_glass_sphere() {
+        let mut world = World::new();
+        world.objects.push(Box::new(Sphere::glass()));
+        world.light = Some(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 10.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+
+        // The preset glass is clear, so the point behind it is lit through it
+        let point = Tuple::new_point(0.0, -5.0, 0.0);
+        assert!(!world.is_shadowed(&point));
+        assert_approx_eq!(world.light_visibility(&point, 0), Color::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn world_light_visibility_glass_material() {
+        let mut world = World::new();
+        world.light = Some(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 10.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        let point = Tuple::new_point(0.0, -5.0, 0.0);
+        let mut pane = Sphere::new();
+        pane.set_material(Material::glass());
+        world.objects.push(Box::new(pane));
+        assert_approx_eq!(world.light_visibility(&point, 0), Color::new(1.0, 1.0, 1.0));
+
+        // The color of the glass tints the light passing through it, down to black for black glass
+        for color in [Color::new(0.2, 0.6, 0.2), Color::new(0.0, 0.0, 0.0)] {
+            world.objects[0].set_material(Material {
+                color,
+                ..Material::glass()
+            });
+            assert_approx_eq!(world.light_visibility(&point, 0), color);
+        }
+        assert!(world.is_shadowed(&point));
+    }
+
+    #[test]
+    fn world_color_at_colored_shadow() {
+        let mut floor = Plane::new();
+        floor.set_transform(translation(0.0, -2.0, 0.0)).unwrap();
+        floor.set_material(Material {
+            ambient: 0.0,
+            diffuse: 1.0,
+            specular: 0.0,
+            ..Material::default()
+        });
+        let mut glass = Sphere::new();
+        glass.set_material(Material {
+            color: Color::new(0.2, 0.2, 1.0),
+            transparency: 1.0,
+            ambient: 0.0,
+            diffuse: 0.0,
+            specular: 0.0,
+            ..Material::default()
+        });
+        let mut world = World::new();
+        world.objects = vec![Box::new(floor), Box::new(glass)];
+        world.light = Some(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 10.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+
+        // The floor under the blue glass is lit in blue rather than left in black shadow
+        let ray = Ray::new(
+            Tuple::new_point(3.0, 0.0, 0.0),
+            Tuple::new_vector(-3.0, -2.0, 0.0),
+        );
+        let color = world.color_at(&ray, 0);
+        assert_approx_eq!(color, Color::new(0.2, 0.2, 1.0));
+
+        // The book's shadows are opaque
+        world.transparent_shadows = false;
+        assert_approx_eq!(world.color_at(&ray, 0), Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn world_is_shadowed_directional_light() {
+        let mut world = default_world();
+        world.light = Some(Box::new(DirectionalLight::new(
+            Tuple::new_vector(1.0, -1.0, 1.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+
+        // Objects cast shadows no matter how far the point is from them
+        assert!(world.is_shadowed(&Tuple::new_point(10.0, -10.0, 10.0)));
+        assert!(world.is_shadowed(&Tuple::new_point(1e4, -1e4, 1e4)));
+
+        // The object is on the side away from the light
+        assert!(!world.is_shadowed(&Tuple::new_point(-2.0, 2.0, -2.0)));
+    }
+
+    #[test]
+    fn world_is_shadowed_casts_shadow() {
+        let mut world = default_world();
+        let point = Tuple::new_point(10.0, -10.0, 10.0);
+        world.objects[0].set_casts_shadow(false);
+        // The inner sphere still blocks the light
+        assert!(world.is_shadowed(&point));
+        world.objects[1].set_casts_shadow(false);
+        assert!(!world.is_shadowed(&point));
+    }
+
+    #[test]
+    fn world_shade_hit_in_shadow() {
+        let mut world = World::new();
+        world.light = Some(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 0.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        let shadowing = Sphere::new();
+        let mut shadowed = Sphere::new();
+        shadowed.set_transform(translation(0.0, 0.0, 10.0)).unwrap();
+        world.objects = vec![Box::new(shadowing), Box::new(shadowed)];
+
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let comps = prepare_single_hit(4.0, world.objects[1].as_ref(), &ray);
+        assert!(world
+            .shade_hit(&comps, MAX_RECURSION_DEPTH)
+            .is_equal_to(&Color::new(0.1, 0.1, 0.1)));
+    }
+
+    #[test]
+    fn world_color_at_shadow_bias() {
+        let mut world = World::new();
+        world.light = Some(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 10.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        let mut pebble = Sphere::new();
+        pebble
+            .set_transform(translation(0.0, 0.5, 0.0) * scaling(0.1, 0.1, 0.1))
+            .unwrap();
+        world.objects = vec![Box::new(Plane::new()), Box::new(pebble)];
+
+        // The ray sees the floor right under the pebble, which shadows it
+        let ray = Ray::new(
+            Tuple::new_point(-5.0, 1.0, 0.0),
+            Tuple::new_vector(5.0, -1.0, 0.0).normalize(),
+        );
+        let shadowed = world.color_at(&ray, MAX_RECURSION_DEPTH);
+        assert!(shadowed.is_equal_to(&Color::new(0.1, 0.1, 0.1)));
+
+        // A bias larger than the gap lifts the shadow ray past the pebble
+        world.shadow_bias = 1.0;
+        let lit = world.color_at(&ray, MAX_RECURSION_DEPTH);
+        assert!(lit.red > 0.5);
+    }
+
+    #[test]
+    fn world_reflected_color_nonreflective() {
+        let mut world = default_world();
+        let material = Material {
+            ambient: 1.0,
+            ..world.objects[1].material().clone()
+        };
+        world.objects[1].set_material(material);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let comps = prepare_single_hit(1.0, world.objects[1].as_ref(), &ray);
+        assert!(world
+            .reflected_color(&comps, MAX_RECURSION_DEPTH)
+            .is_equal_to(&Color::new(0.0, 0.0, 0.0)));
+    }
+
+    /// Add a reflective plane below the default world, returning its index in the world's objects.
+    fn add_reflective_plane(world: &mut World) -> usize {
+        let mut plane = Plane::new();
+        plane.set_material(Material {
+            reflective: 0.5,
+            ..Material::default()
+        });
+        plane.set_transform(translation(0.0, -1.0, 0.0)).unwrap();
+        world.objects.push(Box::new(plane));
+        world.objects.len() - 1
+    }
+
+    #[test]
+    fn world_reflected_color_reflective() {
+        let mut world = default_world();
+        let plane = add_reflective_plane(&mut world);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -3.0),
+            Tuple::new_vector(0.0, -SQRT_2 / 2.0, SQRT_2 / 2.0),
+        );
+        let comps = prepare_single_hit(SQRT_2, world.objects[plane].as_ref(), &ray);
+        let color = world.reflected_color(&comps, MAX_RECURSION_DEPTH);
+        // The book's values are rounded and computed with a larger over_point offset.
+        assert_approx_eq!(color, Color::new(0.19032, 0.2379, 0.14274), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn world_shade_hit_reflective() {
+        let mut world = default_world();
+        let plane = add_reflective_plane(&mut world);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -3.0),
+            Tuple::new_vector(0.0, -SQRT_2 / 2.0, SQRT_2 / 2.0),
+        );
+        let comps = prepare_single_hit(SQRT_2, world.objects[plane].as_ref(), &ray);
+        let color = world.shade_hit(&comps, MAX_RECURSION_DEPTH);
+        // The book's values are rounded and computed with a larger over_point offset.
+        assert_approx_eq!(color, Color::new(0.87677, 0.92436, 0.82918), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn world_color_at_mutually_reflective_surfaces() {
+        let mut world = World::new();
+        world.light = Some(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        let mirror = Material {
+            reflective: 1.0,
+            ..Material::default()
+        };
+        let mut lower = Plane::new();
+        lower.set_material(mirror.clone());
+        lower.set_transform(translation(0.0, -1.0, 0.0)).unwrap();
+        let mut upper = Plane::new();
+        upper.set_material(mirror);
+        upper.set_transform(translation(0.0, 1.0, 0.0)).unwrap();
+        world.objects = vec![Box::new(lower), Box::new(upper)];
+
+        // The recursion limit stops the ray from bouncing between the planes forever
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        world.color_at(&ray, MAX_RECURSION_DEPTH);
+    }
+
+    #[test]
+    fn world_reflected_color_at_max_recursive_depth() {
+        let mut world = default_world();
+        let plane = add_reflective_plane(&mut world);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -3.0),
+            Tuple::new_vector(0.0, -SQRT_2 / 2.0, SQRT_2 / 2.0),
+        );
+        let comps = prepare_single_hit(SQRT_2, world.objects[plane].as_ref(), &ray);
+        assert!(world
+            .reflected_color(&comps, 0)
+            .is_equal_to(&Color::new(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn world_refracted_color_opaque() {
+        let world = default_world();
+        let shape = world.objects[0].as_ref();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = Intersections::new(vec![
+            Intersection::new(4.0, shape),
+            Intersection::new(6.0, shape),
+        ]);
+        let comps = xs[0].prepare_computations(&ray, &xs);
+        assert!(world
+            .refracted_color(&comps, MAX_RECURSION_DEPTH)
+            .is_equal_to(&Color::new(0.0, 0.0, 0.0)));
+    }
+
+    /// Make the outer sphere of the default world out of glass.
+    fn make_outer_sphere_glass(world: &mut World) {
+        let material = Material {
+            transparency: 1.0,
+            refractive_index: 1.5,
+            ..world.objects[0].material().clone()
+        };
+        world.objects[0].set_material(material);
+    }
+
+    #[test]
+    fn world_refracted_color_at_max_recursive_depth() {
+        let mut world = default_world();
+        make_outer_sphere_glass(&mut world);
+        let shape = world.objects[0].as_ref();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let xs = Intersections::new(vec![
+            Intersection::new(4.0, shape),
+            Intersection::new(6.0, shape),
+        ]);
+        let comps = xs[0].prepare_computations(&ray, &xs);
+        assert!(world
+            .refracted_color(&comps, 0)
+            .is_equal_to(&Color::new(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn world_refracted_color_total_internal_reflection() {
+        let mut world = default_world();
+        make_outer_sphere_glass(&mut world);
+        let shape = world.objects[0].as_ref();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, SQRT_2 / 2.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        let xs = Intersections::new(vec![
+            Intersection::new(-SQRT_2 / 2.0, shape),
+            Intersection::new(SQRT_2 / 2.0, shape),
+        ]);
+        // The ray is inside the sphere, so the second intersection is the one to shade
+        let comps = xs[1].prepare_computations(&ray, &xs);
+        assert!(world
+            .refracted_color(&comps, MAX_RECURSION_DEPTH)
+            .is_equal_to(&Color::new(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn world_refracted_color_with_refracted_ray() {
+        let mut world = default_world();
+        let material = Material {
+            ambient: 1.0,
+            pattern: Some(Arc::new(TestPattern::default())),
+            ..world.objects[0].material().clone()
+        };
+        world.objects[0].set_material(material);
+        let material = Material {
+            transparency: 1.0,
+            refractive_index: 1.5,
+            ..world.objects[1].material().clone()
+        };
+        world.objects[1].set_material(material);
+
+        let a = world.objects[0].as_ref();
+        let b = world.objects[1].as_ref();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.1),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        let xs = Intersections::new(vec![
+            Intersection::new(-0.9899, a),
+            Intersection::new(-0.4899, b),
+            Intersection::new(0.4899, b),
+            Intersection::new(0.9899, a),
+        ]);
+        let comps = xs[2].prepare_computations(&ray, &xs);
+        let color = world.refracted_color(&comps, MAX_RECURSION_DEPTH);
+        // The book's values are rounded and computed with a larger under_point offset.
+        assert_approx_eq!(color, Color::new(0.0, 0.99888, 0.04725), epsilon = 1e-3);
+    }
+
+    /// Add a transparent floor and a red ball beneath it to the default world,
+    /// returning the index of the floor in the world's objects.
+    fn add_transparent_floor_and_ball(world: &mut World, floor_material: Material) -> usize {
+        let mut floor = Plane::new();
+        floor.set_transform(translation(0.0, -1.0, 0.0)).unwrap();
+        floor.set_material(floor_material);
+        world.objects.push(Box::new(floor));
+
+        let mut ball = Sphere::new();
+        ball.set_material(Material {
+            color: Color::new(1.0, 0.0, 0.0),
+            ambient: 0.5,
+            ..Material::default()
+        });
+        ball.set_transform(translation(0.0, -3.5, -0.5)).unwrap();
+        world.objects.push(Box::new(ball));
+        world.objects.len() - 2
+    }
+
+    #[test]
+    fn world_shade_hit_transparent() {
+        let mut world = default_world();
+        // The book's shadows are opaque, so the floor leaves the ball in shadow
+        world.transparent_shadows = false;
+        let floor = add_transparent_floor_and_ball(
+            &mut world,
+            Material {
+                transparency: 0.5,
+                refractive_index: 1.5,
+                ..Material::default()
+            },
+        );
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -3.0),
+            Tuple::new_vector(0.0, -SQRT_2 / 2.0, SQRT_2 / 2.0),
+        );
+        let comps = prepare_single_hit(SQRT_2, world.objects[floor].as_ref(), &ray);
+        let color = world.shade_hit(&comps, MAX_RECURSION_DEPTH);
+        // The book's values are rounded and computed with a larger under_point offset.
+        assert_approx_eq!(color, Color::new(0.93642, 0.68642, 0.68642), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn world_shade_hit_reflective_transparent() {
+        let mut world = default_world();
+        // The book's shadows are opaque, so the floor leaves the ball in shadow
+        world.transparent_shadows = false;
+        let floor = add_transparent_floor_and_ball(
+            &mut world,
+            Material {
+                reflective: 0.5,
+                transparency: 0.5,
+                refractive_index: 1.5,
+                ..Material::default()
+            },
+        );
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -3.0),
+            Tuple::new_vector(0.0, -SQRT_2 / 2.0, SQRT_2 / 2.0),
+        );
+        let comps = prepare_single_hit(SQRT_2, world.objects[floor].as_ref(), &ray);
+        let color = world.shade_hit(&comps, MAX_RECURSION_DEPTH);
+        // The book's values are rounded and computed with a larger offset for over_point and under_point.
+        assert_approx_eq!(color, Color::new(0.93391, 0.69643, 0.69243), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn world_color_at_fog() {
+        let mut world = default_world();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let clear = world.color_at(&ray, MAX_RECURSION_DEPTH);
+        let fog = Medium::new(Color::new(0.5, 0.5, 0.5), 0.25);
+        world.fog = Some(fog);
+
+        // The ray hits the outer sphere 4 units away, and misses everything when turned around
+        let transmittance = (-1.0f64).exp();
+        let expected = clear * transmittance + fog.color * (1.0 - transmittance);
+        assert!(world
+            .color_at(&ray, MAX_RECURSION_DEPTH)
+            .is_equal_to(&expected));
+        let away = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, -1.0),
+        );
+        assert!(world
+            .color_at(&away, MAX_RECURSION_DEPTH)
+            .is_equal_to(&fog.color));
+    }
+
+    #[test]
+    fn world_color_at_volume() {
+        let mut world = World::new();
+        let medium = Medium::new(Color::new(0.2, 0.4, 0.6), 0.5);
+        let mut volume = Sphere::new();
+        volume.set_material(Material {
+            medium: Some(medium),
+            ..Material::default()
+        });
+        world.objects.push(Box::new(volume));
+        world.light = Some(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 10.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+
+        // Through the middle of the sphere, the ray crosses 2 units of the medium
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let expected = medium.color * (1.0 - (-1.0f64).exp());
+        assert!(world
+            .color_at(&ray, MAX_RECURSION_DEPTH)
+            .is_equal_to(&expected));
+
+        // The volume casts no shadow
+        assert!(!world.is_shadowed(&Tuple::new_point(0.0, -5.0, 0.0)));
+    }
+
+    #[test]
+    fn world_color_at_inside_volume() {
+        let mut world = World::new();
+        let medium = Medium::new(Color::new(0.2, 0.4, 0.6), 0.5);
+        let mut volume = Sphere::new();
+        volume.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        volume.set_material(Material {
+            medium: Some(medium),
+            ..Material::default()
+        });
+        let mut wall = Plane::new();
+        wall.set_transform(translation(0.0, 0.0, 5.0) * rotation_x(FRAC_PI_2))
+            .unwrap();
+        wall.set_material(Material {
+            emissive: Color::new(1.0, 1.0, 1.0),
+            ..Material::default()
+        });
+        world.objects.push(Box::new(volume));
+        world.objects.push(Box::new(wall));
+
+        // From the center of the volume, the ray crosses 2 units of the medium before reaching the wall
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let transmittance = (-1.0f64).exp();
+        let expected =
+            Color::new(1.0, 1.0, 1.0) * transmittance + medium.color * (1.0 - transmittance);
+        assert_approx_eq!(world.color_at(&ray, MAX_RECURSION_DEPTH), expected);
+
+        // From outside, the ray crosses all 4 units of the medium, and only once
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let transmittance = (-2.0f64).exp();
+        let expected =
+            Color::new(1.0, 1.0, 1.0) * transmittance + medium.color * (1.0 - transmittance);
+        assert_approx_eq!(world.color_at(&ray, MAX_RECURSION_DEPTH), expected);
+    }
+
+    #[test]
+    fn world_color_at_lit_fog() {
+        let mut world = World::new();
+        world.light = Some(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 10.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        world.fog = Some(Medium {
+            samples: 64,
+            ..Medium::new(Color::new(1.0, 1.0, 1.0), 1.0)
+        });
+        let ray = Ray::new(
+            Tuple::new_point(-2.0, 0.0, 0.0),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+        );
+
+        // All of the fog is lit, giving back all but a thousandth of its color
+        let lit = world.color_at(&ray, MAX_RECURSION_DEPTH);
+        assert_approx_eq!(lit.red, 0.999);
+
+        // A sphere between the light and the ray leaves a shadow in the fog
+        let mut blocker = Sphere::new();
+        blocker.set_transform(translation(0.0, 5.0, 0.0)).unwrap();
+        world.objects.push(Box::new(blocker));
+        let shadowed = world.color_at(&ray, MAX_RECURSION_DEPTH);
+        assert!(shadowed.red < lit.red - 0.1);
+    }
+
+    #[test]
+    fn world_shade_hit_emitted_light() {
+        let mut world = World::new();
+        world.objects.push(Box::new(Plane::new()));
+        let mut lamp = Sphere::new();
+        lamp.set_transform(translation(0.0, 3.0, 0.0)).unwrap();
+        lamp.set_material(Material {
+            emissive: Color::new(1.0, 1.0, 1.0),
+            ..Material::default()
+        });
+        world.objects.push(Box::new(lamp));
+        let floor_at = |world: &World, x: f64| {
+            let ray = Ray::new(
+                Tuple::new_point(x, 0.5, 0.0),
+                Tuple::new_vector(0.0, -1.0, 0.0),
+            );
+            let comps = prepare_single_hit(0.5, world.objects[0].as_ref(), &ray);
+            world.shade_hit(&comps, MAX_RECURSION_DEPTH)
+        };
+
+        // Without a light, the floor is lit by the glowing sphere above it, less so further away
+        let below = floor_at(&world, 0.0);
+        let aside = floor_at(&world, 6.0);
+        assert!(below.red > 0.15 && below.red < 0.25, "{below}");
+        assert_approx_eq!(below.red, below.blue);
+        assert!(aside.red > 0.0 && aside.red < below.red / 4.0, "{aside}");
+
+        // Objects in between cast shadows
+        let mut blocker = Sphere::new();
+        blocker
+            .set_transform(translation(0.0, 1.5, 0.0) * scaling(0.6, 0.6, 0.6))
+            .unwrap();
+        world.objects.push(Box::new(blocker));
+        assert_approx_eq!(floor_at(&world, 0.0), Color::new(0.0, 0.0, 0.0));
+        assert!(floor_at(&world, 6.0).is_equal_to(&aside));
+
+        world.objects.pop();
+        world.emissive_samples = 0;
+        assert_approx_eq!(floor_at(&world, 0.0), Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn world_shade_hit_emissive() {
+        let mut world = default_world();
+        let emissive = Color::new(0.5, 0.0, 1.0);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let lit = world.shade_hit(
+            &prepare_single_hit(4.0, world.objects[0].as_ref(), &ray),
+            MAX_RECURSION_DEPTH,
+        );
+        let material = Material {
+            emissive,
+            ..world.objects[0].material().clone()
+        };
+        world.objects[0].set_material(material);
+        let comps = prepare_single_hit(4.0, world.objects[0].as_ref(), &ray);
+        let color = world.shade_hit(&comps, MAX_RECURSION_DEPTH);
+        assert!(color.is_equal_to(&(lit + emissive)));
+
+        // The glow is seen even without a light
+        world.light = None;
+        let comps = prepare_single_hit(4.0, world.objects[0].as_ref(), &ray);
+        let color = world.shade_hit(&comps, MAX_RECURSION_DEPTH);
+        assert!(color.is_equal_to(&emissive));
+    }
+}